@@ -61,6 +61,7 @@ pub fn op_webgpu_create_buffer(
         usage: wgpu_types::BufferUsages::from_bits(usage)
             .ok_or_else(|| type_error("usage is not valid"))?,
         mapped_at_creation,
+        memory_hints: wgpu_types::MemoryHints::Default,
     };
 
     gfx_put!(device => instance.device_create_buffer(
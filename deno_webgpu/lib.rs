@@ -406,6 +406,8 @@ pub async fn op_webgpu_request_adapter(
             wgpu_types::InstanceDescriptor {
                 backends,
                 dx12_shader_compiler: wgpu_types::Dx12Compiler::Fxc,
+                gles_device: None,
+                gles_program_cache: wgpu_types::GlesProgramCache::Memory,
             },
         )));
         state.borrow::<Instance>()
@@ -646,6 +648,7 @@ pub async fn op_webgpu_request_device(
         label: label.map(Cow::from),
         features: required_features.into(),
         limits: required_limits.unwrap_or_default(),
+        allocator: wgpu_types::AllocatorConfig::default(),
     };
 
     let (device, maybe_err) = gfx_select!(adapter => instance.adapter_request_device(
@@ -8,6 +8,7 @@ use deno_core::ResourceId;
 use serde::Deserialize;
 use serde::Serialize;
 use std::borrow::Cow;
+use std::collections::HashMap;
 use std::rc::Rc;
 
 use super::error::WebGpuError;
@@ -78,7 +79,8 @@ pub enum GPUPipelineLayoutOrGPUAutoLayoutMode {
 pub struct GpuProgrammableStage {
     module: ResourceId,
     entry_point: String,
-    // constants: HashMap<String, GPUPipelineConstantValue>
+    #[serde(default)]
+    constants: HashMap<String, f64>,
 }
 
 #[op]
@@ -113,7 +115,7 @@ pub fn op_webgpu_create_compute_pipeline(
         stage: wgpu_core::pipeline::ProgrammableStageDescriptor {
             module: compute_shader_module_resource.1,
             entry_point: Cow::from(compute.entry_point),
-            // TODO(lucacasonato): support args.compute.constants
+            constants: Cow::Owned(compute.constants),
         },
     };
     let implicit_pipelines = match layout {
@@ -280,6 +282,8 @@ impl<'a> From<GpuVertexBufferLayout> for wgpu_core::pipeline::VertexBufferLayout
 struct GpuVertexState {
     module: ResourceId,
     entry_point: String,
+    #[serde(default)]
+    constants: HashMap<String, f64>,
     buffers: Vec<Option<GpuVertexBufferLayout>>,
 }
 
@@ -307,7 +311,8 @@ struct GpuFragmentState {
     targets: Vec<Option<wgpu_types::ColorTargetState>>,
     module: u32,
     entry_point: String,
-    // TODO(lucacasonato): constants
+    #[serde(default)]
+    constants: HashMap<String, f64>,
 }
 
 #[derive(Deserialize)]
@@ -356,6 +361,7 @@ pub fn op_webgpu_create_render_pipeline(
             stage: wgpu_core::pipeline::ProgrammableStageDescriptor {
                 module: fragment_shader_module_resource.1,
                 entry_point: Cow::from(fragment.entry_point),
+                constants: Cow::Owned(fragment.constants),
             },
             targets: Cow::from(fragment.targets),
         })
@@ -378,6 +384,7 @@ pub fn op_webgpu_create_render_pipeline(
             stage: wgpu_core::pipeline::ProgrammableStageDescriptor {
                 module: vertex_shader_module_resource.1,
                 entry_point: Cow::Owned(args.vertex.entry_point),
+                constants: Cow::Owned(args.vertex.constants),
             },
             buffers: Cow::Owned(vertex_buffers),
         },
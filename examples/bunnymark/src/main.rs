@@ -108,11 +108,13 @@ impl wgpu_example::framework::Example for Example {
             vertex: wgpu::VertexState {
                 module: &shader,
                 entry_point: "vs_main",
+                constants: &Default::default(),
                 buffers: &[],
             },
             fragment: Some(wgpu::FragmentState {
                 module: &shader,
                 entry_point: "fs_main",
+                constants: &Default::default(),
                 targets: &[Some(wgpu::ColorTargetState {
                     format: config.view_formats[0],
                     blend: Some(wgpu::BlendState::ALPHA_BLENDING),
@@ -200,6 +202,7 @@ impl wgpu_example::framework::Example for Example {
             size: (MAX_BUNNIES as wgpu::BufferAddress) * uniform_alignment,
             usage: wgpu::BufferUsages::COPY_DST | wgpu::BufferUsages::UNIFORM,
             mapped_at_creation: false,
+            memory_hints: wgpu::MemoryHints::Default,
         });
 
         let view = texture.create_view(&wgpu::TextureViewDescriptor::default());
@@ -40,6 +40,8 @@ async fn create_red_image_with_dimensions(
     let instance = wgpu::Instance::new(wgpu::InstanceDescriptor {
         backends,
         dx12_shader_compiler: wgpu::Dx12Compiler::default(),
+        gles_device: None,
+        gles_program_cache: wgpu::GlesProgramCache::default(),
     });
     let adapter = instance
         .request_adapter(&wgpu::RequestAdapterOptions::default())
@@ -52,6 +54,7 @@ async fn create_red_image_with_dimensions(
                 label: None,
                 features: wgpu::Features::empty(),
                 limits: wgpu::Limits::downlevel_defaults(),
+                allocator: wgpu::AllocatorConfig::default(),
             },
             None,
         )
@@ -69,6 +72,7 @@ async fn create_red_image_with_dimensions(
         size: (buffer_dimensions.padded_bytes_per_row * buffer_dimensions.height) as u64,
         usage: wgpu::BufferUsages::MAP_READ | wgpu::BufferUsages::COPY_DST,
         mapped_at_creation: false,
+        memory_hints: wgpu::MemoryHints::Default,
     });
 
     let texture_extent = wgpu::Extent3d {
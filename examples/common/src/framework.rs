@@ -159,6 +159,8 @@ async fn setup<E: Example>(title: &str) -> Setup {
     let instance = wgpu::Instance::new(wgpu::InstanceDescriptor {
         backends,
         dx12_shader_compiler,
+        gles_device: wgpu::util::gles_device_from_env(),
+        gles_program_cache: wgpu::GlesProgramCache::default(),
     });
     let (size, surface) = unsafe {
         let size = window.inner_size();
@@ -224,6 +226,7 @@ async fn setup<E: Example>(title: &str) -> Setup {
                 label: None,
                 features: (optional_features & adapter_features) | required_features,
                 limits: needed_limits,
+                allocator: wgpu::AllocatorConfig::default(),
             },
             trace_dir.ok().as_ref().map(std::path::Path::new),
         )
@@ -548,6 +551,7 @@ pub fn test<E: Example>(mut params: FrameworkRefTest) {
                 size: params.width as u64 * params.height as u64 * 4,
                 usage: wgpu::BufferUsages::COPY_DST | wgpu::BufferUsages::MAP_READ,
                 mapped_at_creation: false,
+                memory_hints: wgpu::MemoryHints::Default,
             });
 
             let mut example = E::init(
@@ -48,6 +48,7 @@ async fn execute_gpu(numbers: &[u32]) -> Option<Vec<u32>> {
                 label: None,
                 features: wgpu::Features::empty(),
                 limits: wgpu::Limits::downlevel_defaults(),
+                allocator: wgpu::AllocatorConfig::default(),
             },
             None,
         )
@@ -86,6 +87,7 @@ async fn execute_gpu_inner(
         size,
         usage: wgpu::BufferUsages::MAP_READ | wgpu::BufferUsages::COPY_DST,
         mapped_at_creation: false,
+        memory_hints: wgpu::MemoryHints::Default,
     });
 
     // Instantiates buffer with data (`numbers`).
@@ -113,6 +115,7 @@ async fn execute_gpu_inner(
         layout: None,
         module: &cs_module,
         entry_point: "main",
+        constants: &Default::default(),
     });
 
     // Instantiates the bind group, once again specifying the binding of buffers.
@@ -28,6 +28,16 @@ impl<I: Clone + Debug + wgc::id::TypedId> wgc::identity::IdentityHandler<I>
 
 pub struct IdentityPassThroughFactory;
 
+/// Returns the stage of a recorded GLSL shader, from the extension of its file.
+fn glsl_stage(file_name: &str) -> Option<naga::ShaderStage> {
+    match Path::new(file_name).extension()?.to_str()? {
+        "vert" => Some(naga::ShaderStage::Vertex),
+        "frag" => Some(naga::ShaderStage::Fragment),
+        "comp" => Some(naga::ShaderStage::Compute),
+        _ => None,
+    }
+}
+
 impl<I: Clone + Debug + wgc::id::TypedId> wgc::identity::IdentityHandlerFactory<I>
     for IdentityPassThroughFactory
 {
@@ -255,12 +265,29 @@ impl GlobalPlay for wgc::global::Global<IdentityPassThroughFactory> {
             }
             Action::CreateShaderModule { id, desc, data } => {
                 log::info!("Creating shader from {}", data);
-                let code = fs::read_to_string(dir.join(&data)).unwrap();
+                let read_code = || fs::read_to_string(dir.join(&data)).unwrap();
                 let source = if data.ends_with(".wgsl") {
-                    wgc::pipeline::ShaderModuleSource::Wgsl(Cow::Owned(code))
+                    wgc::pipeline::ShaderModuleSource::Wgsl(Cow::Owned(read_code()))
                 } else if data.ends_with(".ron") {
-                    let module = ron::de::from_str(&code).unwrap();
+                    let module = ron::de::from_str(&read_code()).unwrap();
                     wgc::pipeline::ShaderModuleSource::Naga(module)
+                } else if let Some(stage) = glsl_stage(&data) {
+                    wgc::pipeline::ShaderModuleSource::Glsl(
+                        Cow::Owned(read_code()),
+                        naga::front::glsl::Options::from(stage),
+                    )
+                } else if data.ends_with(".spv") {
+                    let spv = fs::read(dir.join(&data))
+                        .unwrap()
+                        .chunks_exact(4)
+                        .map(|word| u32::from_le_bytes(word.try_into().unwrap()))
+                        .collect::<Vec<_>>();
+                    let options = naga::front::spv::Options {
+                        adjust_coordinate_space: false,
+                        strict_capabilities: true,
+                        block_ctx_dump_prefix: None,
+                    };
+                    wgc::pipeline::ShaderModuleSource::SpirV(Cow::Owned(spv), options)
                 } else {
                     panic!("Unknown shader {}", data);
                 };
@@ -368,6 +395,10 @@ impl GlobalPlay for wgc::global::Global<IdentityPassThroughFactory> {
                 self.queue_write_texture::<A>(device, &to, &bin, &layout, &size)
                     .unwrap();
             }
+            Action::UpdateBindGroup { id, entries } => {
+                self.queue_update_bind_group::<A>(device, id, &entries)
+                    .unwrap();
+            }
             Action::Submit(_index, ref commands) if commands.is_empty() => {
                 self.queue_submit::<A>(device, &[]).unwrap();
             }
@@ -91,6 +91,7 @@ impl Test<'_> {
                 label: None,
                 features: self.features,
                 limits: wgt::Limits::default(),
+                allocator: wgt::AllocatorConfig::default(),
             },
             None,
             device
@@ -185,6 +186,8 @@ impl Corpus {
             wgt::InstanceDescriptor {
                 backends: corpus.backends,
                 dx12_shader_compiler: wgt::Dx12Compiler::Fxc,
+                gles_device: None,
+                gles_program_cache: wgt::GlesProgramCache::default(),
             },
         );
         for &backend in BACKENDS {
@@ -298,6 +298,7 @@ fn copy_via_compute(
         size: buffer.size(),
         usage: BufferUsages::COPY_SRC | BufferUsages::STORAGE,
         mapped_at_creation: false,
+        memory_hints: wgpu::MemoryHints::Default,
     });
 
     let bg = device.create_bind_group(&BindGroupDescriptor {
@@ -346,6 +347,7 @@ fn copy_via_compute(
         layout: Some(&pll),
         module: &sm,
         entry_point: "copy_texture_to_buffer",
+        constants: &Default::default(),
     });
 
     {
@@ -24,6 +24,7 @@ async fn initialize_device(
                 label: None,
                 features,
                 limits,
+                allocator: wgt::AllocatorConfig::default(),
             },
             None,
         )
@@ -36,6 +37,7 @@ async fn initialize_device(
 }
 
 pub struct TestingContext {
+    pub instance: Instance,
     pub adapter: Adapter,
     pub adapter_info: wgt::AdapterInfo,
     pub adapter_downlevel_capabilities: wgt::DownlevelCapabilities,
@@ -207,13 +209,26 @@ pub fn initialize_test(parameters: TestParameters, test_function: impl FnOnce(Te
 
     let _test_guard = isolation::OneTestPerProcessGuard::new();
 
-    let (adapter, _surface_guard) = initialize_adapter();
+    let (instance, adapter, _surface_guard) = initialize_adapter();
 
     let adapter_info = adapter.get_info();
     let adapter_lowercase_name = adapter_info.name.to_lowercase();
-    let adapter_features = adapter.features();
+    let mut adapter_features = adapter.features();
     let adapter_limits = adapter.limits();
     let adapter_downlevel_capabilities = adapter.get_downlevel_capabilities();
+    // Emulated features aren't exposed, but can be requested.
+    if adapter_downlevel_capabilities
+        .flags
+        .contains(DownlevelFlags::EMULATED_MULTI_DRAW_INDIRECT)
+    {
+        adapter_features |= Features::MULTI_DRAW_INDIRECT;
+    }
+    if adapter_downlevel_capabilities
+        .flags
+        .contains(DownlevelFlags::EMULATED_POLYGON_MODE_LINE)
+    {
+        adapter_features |= Features::POLYGON_MODE_LINE;
+    }
 
     let missing_features = parameters.required_features - adapter_features;
     if !missing_features.is_empty() {
@@ -253,6 +268,7 @@ pub fn initialize_test(parameters: TestParameters, test_function: impl FnOnce(Te
     ));
 
     let context = TestingContext {
+        instance,
         adapter,
         adapter_info: adapter_info.clone(),
         adapter_downlevel_capabilities,
@@ -345,12 +361,14 @@ pub fn initialize_test(parameters: TestParameters, test_function: impl FnOnce(Te
     }
 }
 
-fn initialize_adapter() -> (Adapter, SurfaceGuard) {
+fn initialize_adapter() -> (Instance, Adapter, SurfaceGuard) {
     let backends = wgpu::util::backend_bits_from_env().unwrap_or_else(Backends::all);
     let dx12_shader_compiler = wgpu::util::dx12_shader_compiler_from_env().unwrap_or_default();
     let instance = Instance::new(wgpu::InstanceDescriptor {
         backends,
         dx12_shader_compiler,
+        gles_device: wgpu::util::gles_device_from_env(),
+        gles_program_cache: wgpu::GlesProgramCache::default(),
     });
     let surface_guard;
     let compatible_surface;
@@ -408,7 +426,7 @@ fn initialize_adapter() -> (Adapter, SurfaceGuard) {
     ))
     .expect("could not find suitable adapter on the system");
 
-    (adapter, surface_guard)
+    (instance, adapter, surface_guard)
 }
 
 struct SurfaceGuard {
@@ -0,0 +1,16 @@
+struct VertexOutput {
+    @builtin(position) position: vec4<f32>,
+    @location(0) @interpolate(flat) value: u32,
+}
+
+@vertex
+fn vs_main(@location(0) value: u32) -> VertexOutput {
+    // Each value lands on the center of its own pixel of a 4x1 target.
+    let x = (f32(value) + 0.5) / 2.0 - 1.0;
+    return VertexOutput(vec4<f32>(x, 0.0, 0.0, 1.0), value);
+}
+
+@fragment
+fn fs_main(@location(0) @interpolate(flat) value: u32) -> @location(0) u32 {
+    return value + 1u;
+}
@@ -0,0 +1,158 @@
+use wasm_bindgen_test::*;
+use wgpu::util::DeviceExt;
+
+use wgpu_test::{fail_if, initialize_test, TestParameters, TestingContext};
+
+/// Draws the indices `1, 2, 3` with `base_vertex` from a vertex buffer holding
+/// `0, 1, 2` after four padding vertices. Every value pulled by the vertex shader
+/// marks its own pixel of a 4x1 target, which is read back into the returned buffer.
+fn draw_base_vertex(ctx: &TestingContext, base_vertex: i32) -> wgpu::Buffer {
+    let shader = ctx
+        .device
+        .create_shader_module(wgpu::include_wgsl!("draw.wgsl"));
+
+    let output = ctx.device.create_buffer(&wgpu::BufferDescriptor {
+        label: Some("output"),
+        size: wgpu::COPY_BYTES_PER_ROW_ALIGNMENT as u64,
+        usage: wgpu::BufferUsages::COPY_DST | wgpu::BufferUsages::MAP_READ,
+        mapped_at_creation: false,
+        memory_hints: wgpu::MemoryHints::Default,
+    });
+
+    let vertices = ctx
+        .device
+        .create_buffer_init(&wgpu::util::BufferInitDescriptor {
+            label: Some("vertices"),
+            contents: bytemuck::cast_slice(&[3u32, 3, 3, 3, 0, 1, 2]),
+            usage: wgpu::BufferUsages::VERTEX,
+        });
+    let indices = ctx
+        .device
+        .create_buffer_init(&wgpu::util::BufferInitDescriptor {
+            label: Some("indices"),
+            contents: bytemuck::cast_slice(&[1u16, 2, 3, 0]),
+            usage: wgpu::BufferUsages::INDEX,
+        });
+
+    let pipeline = ctx
+        .device
+        .create_render_pipeline(&wgpu::RenderPipelineDescriptor {
+            label: None,
+            layout: None,
+            vertex: wgpu::VertexState {
+                buffers: &[wgpu::VertexBufferLayout {
+                    array_stride: 4,
+                    step_mode: wgpu::VertexStepMode::Vertex,
+                    attributes: &wgpu::vertex_attr_array![0 => Uint32],
+                }],
+                entry_point: "vs_main",
+                constants: &Default::default(),
+                module: &shader,
+            },
+            primitive: wgpu::PrimitiveState {
+                topology: wgpu::PrimitiveTopology::PointList,
+                ..Default::default()
+            },
+            depth_stencil: None,
+            multisample: wgpu::MultisampleState::default(),
+            fragment: Some(wgpu::FragmentState {
+                entry_point: "fs_main",
+                constants: &Default::default(),
+                module: &shader,
+                targets: &[Some(wgpu::ColorTargetState {
+                    format: wgpu::TextureFormat::R32Uint,
+                    blend: None,
+                    write_mask: wgpu::ColorWrites::ALL,
+                })],
+            }),
+            multiview: None,
+        });
+
+    let size = wgpu::Extent3d {
+        width: 4,
+        height: 1,
+        depth_or_array_layers: 1,
+    };
+    let target = ctx.device.create_texture(&wgpu::TextureDescriptor {
+        label: Some("target"),
+        size,
+        mip_level_count: 1,
+        sample_count: 1,
+        dimension: wgpu::TextureDimension::D2,
+        format: wgpu::TextureFormat::R32Uint,
+        usage: wgpu::TextureUsages::RENDER_ATTACHMENT | wgpu::TextureUsages::COPY_SRC,
+        view_formats: &[],
+    });
+    let view = target.create_view(&wgpu::TextureViewDescriptor::default());
+
+    let mut encoder = ctx
+        .device
+        .create_command_encoder(&wgpu::CommandEncoderDescriptor::default());
+
+    let mut rpass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
+        color_attachments: &[Some(wgpu::RenderPassColorAttachment {
+            ops: wgpu::Operations::default(),
+            resolve_target: None,
+            view: &view,
+        })],
+        depth_stencil_attachment: None,
+        label: None,
+    });
+
+    rpass.set_pipeline(&pipeline);
+    rpass.set_vertex_buffer(0, vertices.slice(..));
+    rpass.set_index_buffer(indices.slice(..), wgpu::IndexFormat::Uint16);
+    rpass.draw_indexed(0..3, base_vertex, 0..1);
+
+    drop(rpass);
+
+    encoder.copy_texture_to_buffer(
+        target.as_image_copy(),
+        wgpu::ImageCopyBuffer {
+            buffer: &output,
+            layout: wgpu::ImageDataLayout {
+                offset: 0,
+                bytes_per_row: Some(wgpu::COPY_BYTES_PER_ROW_ALIGNMENT),
+                rows_per_image: None,
+            },
+        },
+        size,
+    );
+
+    ctx.queue.submit(Some(encoder.finish()));
+
+    output
+}
+
+#[test]
+#[wasm_bindgen_test]
+fn draw_indexed_positive_base_vertex() {
+    initialize_test(
+        TestParameters::default().downlevel_flags(wgpu::DownlevelFlags::NON_NEGATIVE_BASE_VERTEX),
+        |ctx| {
+            let output = draw_base_vertex(&ctx, 3);
+
+            let slice = output.slice(..);
+            slice.map_async(wgpu::MapMode::Read, |_| ());
+            ctx.device.poll(wgpu::Maintain::Wait);
+            let data: Vec<u32> = bytemuck::cast_slice(&slice.get_mapped_range()[..16]).to_vec();
+
+            // The padding vertices would mark the last pixel.
+            assert_eq!(data, [1, 2, 3, 0]);
+        },
+    )
+}
+
+#[test]
+#[wasm_bindgen_test]
+fn draw_indexed_negative_base_vertex() {
+    initialize_test(TestParameters::default(), |ctx| {
+        let supported = ctx
+            .adapter_downlevel_capabilities
+            .flags
+            .contains(wgpu::DownlevelFlags::BASE_VERTEX);
+
+        // Negative base vertices can't be emulated by offsetting the vertex buffers.
+        fail_if(&ctx.device, !supported, || draw_base_vertex(&ctx, -1));
+    })
+}
@@ -0,0 +1,126 @@
+use wasm_bindgen_test::*;
+use wgpu_test::{initialize_test, TestParameters, TestingContext};
+
+fn parameters() -> TestParameters {
+    TestParameters::default()
+        .limits(wgpu::Limits::default())
+        .downlevel_flags(wgpu::DownlevelFlags::COMPUTE_SHADERS)
+}
+
+const SHADER: &str = r#"
+@group(0) @binding(0)
+var<storage, read_write> data: array<u32>;
+
+@compute @workgroup_size(1)
+fn first() { data[0] = 1u; }
+
+@compute @workgroup_size(1)
+fn second() { data[1] = 2u; }
+"#;
+
+fn entry(binding: u32, ty: wgpu::BufferBindingType) -> wgpu::BindGroupLayoutEntry {
+    wgpu::BindGroupLayoutEntry {
+        binding,
+        visibility: wgpu::ShaderStages::COMPUTE,
+        ty: wgpu::BindingType::Buffer {
+            ty,
+            has_dynamic_offset: false,
+            min_binding_size: None,
+        },
+        count: None,
+    }
+}
+
+fn create_layout(
+    ctx: &TestingContext,
+    entries: &[wgpu::BindGroupLayoutEntry],
+) -> wgpu::BindGroupLayout {
+    ctx.device
+        .create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
+            label: None,
+            entries,
+        })
+}
+
+#[test]
+#[wasm_bindgen_test]
+fn bind_group_layout_equality() {
+    initialize_test(parameters(), |ctx| {
+        let uniform = entry(1, wgpu::BufferBindingType::Uniform);
+        let storage = entry(0, wgpu::BufferBindingType::Storage { read_only: true });
+
+        let layout = create_layout(&ctx, &[uniform, storage]);
+        // The order of the entries doesn't matter.
+        assert!(layout == create_layout(&ctx, &[storage, uniform]));
+        assert!(layout != create_layout(&ctx, &[uniform]));
+
+        // Entries are sorted by binding.
+        assert_eq!(layout.entries(), Some(vec![storage, uniform]));
+    });
+}
+
+#[test]
+#[wasm_bindgen_test]
+fn pipeline_layout_bind_group_layouts() {
+    initialize_test(parameters(), |ctx| {
+        let first = create_layout(&ctx, &[entry(0, wgpu::BufferBindingType::Uniform)]);
+        let second = create_layout(
+            &ctx,
+            &[entry(
+                0,
+                wgpu::BufferBindingType::Storage { read_only: true },
+            )],
+        );
+        let pipeline_layout = ctx
+            .device
+            .create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
+                label: None,
+                bind_group_layouts: &[&first, &second],
+                push_constant_ranges: &[],
+            });
+
+        let layouts = pipeline_layout.bind_group_layouts().unwrap();
+        assert_eq!(layouts.len(), 2);
+        assert!(layouts[0] == first);
+        assert!(layouts[1] == second);
+
+        // The layouts stay valid after the pipeline layout is dropped.
+        drop(pipeline_layout);
+        assert_eq!(
+            layouts[1].entries(),
+            Some(vec![entry(
+                0,
+                wgpu::BufferBindingType::Storage { read_only: true }
+            )])
+        );
+    });
+}
+
+#[test]
+#[wasm_bindgen_test]
+fn derived_bind_group_layout_equality() {
+    initialize_test(parameters(), |ctx| {
+        let module = ctx
+            .device
+            .create_shader_module(wgpu::ShaderModuleDescriptor {
+                label: None,
+                source: wgpu::ShaderSource::Wgsl(SHADER.into()),
+            });
+        let pipeline = |entry_point| {
+            ctx.device
+                .create_compute_pipeline(&wgpu::ComputePipelineDescriptor {
+                    label: None,
+                    layout: None,
+                    module: &module,
+                    entry_point,
+                    constants: &Default::default(),
+                })
+        };
+        let first = pipeline("first");
+        let second = pipeline("second");
+
+        // Each call returns a new handle, to the same deduplicated layout.
+        assert!(first.get_bind_group_layout(0) == first.get_bind_group_layout(0));
+        assert!(first.get_bind_group_layout(0) == second.get_bind_group_layout(0));
+    })
+}
@@ -0,0 +1,287 @@
+use std::num::NonZeroU32;
+
+use wasm_bindgen_test::*;
+use wgpu::Features;
+
+use wgpu_test::{fail, initialize_test, TestParameters, TestingContext};
+
+const SHADER: &str = r#"
+@group(0) @binding(0)
+var textures: binding_array<texture_2d<u32>, 4>;
+@group(0) @binding(1)
+var<storage, read_write> output: array<u32, 4>;
+
+@compute @workgroup_size(1)
+fn main() {
+    output[0] = textureLoad(textures[0], vec2<i32>(0, 0), 0).x;
+    output[1] = textureLoad(textures[1], vec2<i32>(0, 0), 0).x;
+    output[2] = textureLoad(textures[2], vec2<i32>(0, 0), 0).x;
+    output[3] = textureLoad(textures[3], vec2<i32>(0, 0), 0).x;
+}
+"#;
+
+fn parameters() -> TestParameters {
+    TestParameters::default()
+        .features(Features::TEXTURE_BINDING_ARRAY | Features::UPDATE_AFTER_BIND_DESCRIPTORS)
+}
+
+fn create_layout(ctx: &TestingContext) -> wgpu::BindGroupLayout {
+    ctx.device
+        .create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
+            label: None,
+            entries: &[
+                wgpu::BindGroupLayoutEntry {
+                    binding: 0,
+                    visibility: wgpu::ShaderStages::COMPUTE,
+                    ty: wgpu::BindingType::Texture {
+                        sample_type: wgpu::TextureSampleType::Uint,
+                        view_dimension: wgpu::TextureViewDimension::D2,
+                        multisampled: false,
+                    },
+                    count: NonZeroU32::new(4),
+                },
+                wgpu::BindGroupLayoutEntry {
+                    binding: 1,
+                    visibility: wgpu::ShaderStages::COMPUTE,
+                    ty: wgpu::BindingType::Buffer {
+                        ty: wgpu::BufferBindingType::Storage { read_only: false },
+                        has_dynamic_offset: false,
+                        min_binding_size: None,
+                    },
+                    count: None,
+                },
+            ],
+        })
+}
+
+/// Creates a 1x1 texture containing `value`.
+fn create_view(ctx: &TestingContext, value: u32) -> wgpu::TextureView {
+    let size = wgpu::Extent3d {
+        width: 1,
+        height: 1,
+        depth_or_array_layers: 1,
+    };
+    let texture = ctx.device.create_texture(&wgpu::TextureDescriptor {
+        label: None,
+        size,
+        mip_level_count: 1,
+        sample_count: 1,
+        dimension: wgpu::TextureDimension::D2,
+        format: wgpu::TextureFormat::R32Uint,
+        usage: wgpu::TextureUsages::TEXTURE_BINDING | wgpu::TextureUsages::COPY_DST,
+        view_formats: &[],
+    });
+    ctx.queue.write_texture(
+        texture.as_image_copy(),
+        bytemuck::bytes_of(&value),
+        wgpu::ImageDataLayout {
+            offset: 0,
+            bytes_per_row: Some(4),
+            rows_per_image: None,
+        },
+        size,
+    );
+    texture.create_view(&wgpu::TextureViewDescriptor::default())
+}
+
+fn create_buffer(ctx: &TestingContext, usage: wgpu::BufferUsages) -> wgpu::Buffer {
+    ctx.device.create_buffer(&wgpu::BufferDescriptor {
+        label: None,
+        size: 16,
+        usage,
+        mapped_at_creation: false,
+        memory_hints: wgpu::MemoryHints::Default,
+    })
+}
+
+#[test]
+#[wasm_bindgen_test]
+fn bind_group_update_between_submissions() {
+    initialize_test(parameters(), |ctx| {
+        let layout = create_layout(&ctx);
+        let module = ctx
+            .device
+            .create_shader_module(wgpu::ShaderModuleDescriptor {
+                label: None,
+                source: wgpu::ShaderSource::Wgsl(SHADER.into()),
+            });
+        let pipeline_layout = ctx
+            .device
+            .create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
+                label: None,
+                bind_group_layouts: &[&layout],
+                push_constant_ranges: &[],
+            });
+        let pipeline = ctx
+            .device
+            .create_compute_pipeline(&wgpu::ComputePipelineDescriptor {
+                label: None,
+                layout: Some(&pipeline_layout),
+                module: &module,
+                entry_point: "main",
+                constants: &Default::default(),
+            });
+
+        let views = [1, 2, 3, 4].map(|value| create_view(&ctx, value));
+        let replacement = create_view(&ctx, 30);
+        let output = create_buffer(
+            &ctx,
+            wgpu::BufferUsages::STORAGE | wgpu::BufferUsages::COPY_SRC,
+        );
+        let readback = create_buffer(
+            &ctx,
+            wgpu::BufferUsages::COPY_DST | wgpu::BufferUsages::MAP_READ,
+        );
+
+        let bind_group = ctx.device.create_bind_group(&wgpu::BindGroupDescriptor {
+            label: None,
+            layout: &layout,
+            entries: &[
+                wgpu::BindGroupEntry {
+                    binding: 0,
+                    resource: wgpu::BindingResource::TextureViewArray(&[
+                        &views[0], &views[1], &views[2], &views[3],
+                    ]),
+                },
+                wgpu::BindGroupEntry {
+                    binding: 1,
+                    resource: output.as_entire_binding(),
+                },
+            ],
+        });
+
+        let dispatch = || {
+            let mut encoder = ctx
+                .device
+                .create_command_encoder(&wgpu::CommandEncoderDescriptor::default());
+            let mut cpass = encoder.begin_compute_pass(&wgpu::ComputePassDescriptor::default());
+            cpass.set_pipeline(&pipeline);
+            cpass.set_bind_group(0, &bind_group, &[]);
+            cpass.dispatch_workgroups(1, 1, 1);
+            drop(cpass);
+            encoder.copy_buffer_to_buffer(&output, 0, &readback, 0, 16);
+            encoder.finish()
+        };
+        let read = || {
+            let slice = readback.slice(..);
+            slice.map_async(wgpu::MapMode::Read, |_| ());
+            ctx.device.poll(wgpu::Maintain::Wait);
+            let data: [u32; 4] = bytemuck::pod_read_unaligned(&slice.get_mapped_range());
+            readback.unmap();
+            data
+        };
+        let update = || {
+            ctx.queue.update_bind_group(
+                &bind_group,
+                &[wgpu::BindGroupUpdateEntry {
+                    binding: 0,
+                    array_offset: 2,
+                    resource: wgpu::BindingResource::TextureViewArray(&[&replacement]),
+                }],
+            )
+        };
+
+        // The command buffer tracked the state of the previous texture.
+        let command_buffer = dispatch();
+        fail(&ctx.device, update);
+        ctx.queue.submit(Some(command_buffer));
+        assert_eq!(read(), [1, 2, 3, 4]);
+
+        // The submission completed, and the next command buffer tracks the
+        // replacement.
+        update();
+        ctx.queue.submit(Some(dispatch()));
+        assert_eq!(read(), [1, 2, 30, 4]);
+    })
+}
+
+#[test]
+#[wasm_bindgen_test]
+fn bind_group_update_out_of_bounds() {
+    initialize_test(parameters(), |ctx| {
+        let layout = create_layout(&ctx);
+        let views = [1, 2, 3, 4].map(|value| create_view(&ctx, value));
+        let output = create_buffer(&ctx, wgpu::BufferUsages::STORAGE);
+        let bind_group = ctx.device.create_bind_group(&wgpu::BindGroupDescriptor {
+            label: None,
+            layout: &layout,
+            entries: &[
+                wgpu::BindGroupEntry {
+                    binding: 0,
+                    resource: wgpu::BindingResource::TextureViewArray(&[
+                        &views[0], &views[1], &views[2], &views[3],
+                    ]),
+                },
+                wgpu::BindGroupEntry {
+                    binding: 1,
+                    resource: output.as_entire_binding(),
+                },
+            ],
+        });
+
+        fail(&ctx.device, || {
+            ctx.queue.update_bind_group(
+                &bind_group,
+                &[wgpu::BindGroupUpdateEntry {
+                    binding: 0,
+                    array_offset: 3,
+                    resource: wgpu::BindingResource::TextureViewArray(&[&views[0], &views[1]]),
+                }],
+            )
+        });
+        // The buffer binding isn't an array.
+        fail(&ctx.device, || {
+            ctx.queue.update_bind_group(
+                &bind_group,
+                &[wgpu::BindGroupUpdateEntry {
+                    binding: 1,
+                    array_offset: 0,
+                    resource: output.as_entire_binding(),
+                }],
+            )
+        });
+    })
+}
+
+#[test]
+#[wasm_bindgen_test]
+fn bind_group_update_without_arrays() {
+    initialize_test(parameters(), |ctx| {
+        let layout = ctx
+            .device
+            .create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
+                label: None,
+                entries: &[wgpu::BindGroupLayoutEntry {
+                    binding: 0,
+                    visibility: wgpu::ShaderStages::COMPUTE,
+                    ty: wgpu::BindingType::Texture {
+                        sample_type: wgpu::TextureSampleType::Uint,
+                        view_dimension: wgpu::TextureViewDimension::D2,
+                        multisampled: false,
+                    },
+                    count: None,
+                }],
+            });
+        let view = create_view(&ctx, 1);
+        let bind_group = ctx.device.create_bind_group(&wgpu::BindGroupDescriptor {
+            label: None,
+            layout: &layout,
+            entries: &[wgpu::BindGroupEntry {
+                binding: 0,
+                resource: wgpu::BindingResource::TextureView(&view),
+            }],
+        });
+
+        // Layouts without binding arrays are immutable.
+        fail(&ctx.device, || {
+            ctx.queue.update_bind_group(
+                &bind_group,
+                &[wgpu::BindGroupUpdateEntry {
+                    binding: 0,
+                    array_offset: 0,
+                    resource: wgpu::BindingResource::TextureView(&view),
+                }],
+            )
+        });
+    })
+}
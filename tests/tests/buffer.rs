@@ -9,6 +9,7 @@ fn test_empty_buffer_range(ctx: &TestingContext, buffer_size: u64, label: &str)
             size: buffer_size,
             usage,
             mapped_at_creation: false,
+            memory_hints: wgpu::MemoryHints::Default,
         });
 
         b0.slice(0..0)
@@ -68,6 +69,7 @@ fn test_empty_buffer_range(ctx: &TestingContext, buffer_size: u64, label: &str)
         size: buffer_size,
         usage: rw,
         mapped_at_creation: true,
+        memory_hints: wgpu::MemoryHints::Default,
     });
 
     {
@@ -106,12 +108,14 @@ fn test_map_offset() {
             size: 256,
             usage: wgpu::BufferUsages::MAP_WRITE | wgpu::BufferUsages::COPY_SRC,
             mapped_at_creation: false,
+            memory_hints: wgpu::MemoryHints::Default,
         });
         let read_buf = ctx.device.create_buffer(&wgpu::BufferDescriptor {
             label: None,
             size: 256,
             usage: wgpu::BufferUsages::MAP_READ | wgpu::BufferUsages::COPY_DST,
             mapped_at_creation: false,
+            memory_hints: wgpu::MemoryHints::Default,
         });
 
         write_buf
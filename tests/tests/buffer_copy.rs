@@ -38,4 +38,5 @@ const BUFFER_DESCRIPTOR: wgpu::BufferDescriptor = wgpu::BufferDescriptor {
     size: BUFFER_SIZE,
     usage: wgpu::BufferUsages::COPY_SRC.union(wgpu::BufferUsages::COPY_DST),
     mapped_at_creation: false,
+    memory_hints: wgpu::MemoryHints::Default,
 };
@@ -27,6 +27,7 @@ fn buffer_usage() {
                         size: BUFFER_SIZE,
                         usage,
                         mapped_at_creation: false,
+                        memory_hints: wgpu::MemoryHints::Default,
                     });
                 });
             }
@@ -0,0 +1,51 @@
+use wasm_bindgen_test::*;
+use wgpu_test::{fail, initialize_test, valid, TestParameters};
+
+const VALID_SHADER: &str = r#"
+@compute @workgroup_size(1)
+fn main() {}
+"#;
+
+// The error is on the third line, at the `let`.
+const INVALID_SHADER: &str = r#"
+@compute @workgroup_size(1)
+fn main() { let x: u32 = 1.0; }
+"#;
+
+fn create_module(device: &wgpu::Device, source: &str) -> wgpu::ShaderModule {
+    device.create_shader_module(wgpu::ShaderModuleDescriptor {
+        label: None,
+        source: wgpu::ShaderSource::Wgsl(source.into()),
+    })
+}
+
+#[test]
+#[wasm_bindgen_test]
+fn compilation_info_valid_shader() {
+    initialize_test(TestParameters::default(), |ctx| {
+        let module = valid(&ctx.device, || create_module(&ctx.device, VALID_SHADER));
+        let info = pollster::block_on(module.get_compilation_info());
+        assert!(info
+            .messages
+            .iter()
+            .all(|message| message.message_type != wgpu::CompilationMessageType::Error));
+    });
+}
+
+#[test]
+#[wasm_bindgen_test]
+fn compilation_info_invalid_shader() {
+    initialize_test(TestParameters::default(), |ctx| {
+        let module = fail(&ctx.device, || create_module(&ctx.device, INVALID_SHADER));
+        let info = pollster::block_on(module.get_compilation_info());
+        let error = info
+            .messages
+            .iter()
+            .find(|message| message.message_type == wgpu::CompilationMessageType::Error)
+            .expect("expected an error message");
+        assert!(!error.message.is_empty());
+        let location = error.location.expect("expected a source location");
+        assert_eq!(location.line_number, 3);
+        assert!(location.length > 0);
+    });
+}
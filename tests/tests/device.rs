@@ -10,6 +10,38 @@ fn device_initialization() {
     })
 }
 
+#[test]
+fn device_memory_report() {
+    // GL can't tell how big its allocations are.
+    let parameters =
+        TestParameters::default().specific_failure(Some(wgpu::Backends::GL), None, None, true);
+    initialize_test(parameters, |ctx| {
+        let before = ctx.device.memory_report();
+
+        let buffer = ctx.device.create_buffer(&wgpu::BufferDescriptor {
+            label: None,
+            size: 1 << 20,
+            usage: wgpu::BufferUsages::COPY_DST,
+            mapped_at_creation: false,
+            memory_hints: wgpu::MemoryHints::Default,
+        });
+        let created = ctx.device.memory_report();
+        assert!(created.buffer_bytes >= before.buffer_bytes + (1 << 20));
+
+        ctx.queue.write_buffer(&buffer, 0, &[0; 256]);
+        let written = ctx.device.memory_report();
+        assert_eq!(written.staging_bytes, before.staging_bytes + 256);
+
+        ctx.queue.submit(None);
+        buffer.destroy();
+        ctx.device.poll(wgpu::Maintain::Wait);
+
+        let after = ctx.device.memory_report();
+        assert_eq!(after.buffer_bytes, before.buffer_bytes);
+        assert_eq!(after.staging_bytes, before.staging_bytes);
+    })
+}
+
 #[test]
 #[ignore]
 fn device_mismatch() {
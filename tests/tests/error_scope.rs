@@ -0,0 +1,44 @@
+use wasm_bindgen_test::*;
+
+use wgpu_test::{initialize_test, TestParameters};
+
+fn create_invalid_buffer(device: &wgpu::Device) -> wgpu::Buffer {
+    device.create_buffer(&wgpu::BufferDescriptor {
+        label: None,
+        size: 4,
+        usage: wgpu::BufferUsages::empty(),
+        mapped_at_creation: false,
+        memory_hints: wgpu::MemoryHints::Default,
+    })
+}
+
+#[test]
+#[wasm_bindgen_test]
+fn internal_scope_ignores_validation_errors() {
+    initialize_test(TestParameters::default(), |ctx| {
+        ctx.device.push_error_scope(wgpu::ErrorFilter::Validation);
+        ctx.device.push_error_scope(wgpu::ErrorFilter::Internal);
+        let _buffer = create_invalid_buffer(&ctx.device);
+        let internal = pollster::block_on(ctx.device.pop_error_scope());
+        let validation = pollster::block_on(ctx.device.pop_error_scope());
+
+        assert!(internal.is_none());
+        assert!(matches!(validation, Some(wgpu::Error::Validation { .. })));
+    });
+}
+
+#[test]
+#[wasm_bindgen_test]
+fn internal_scope_without_errors() {
+    initialize_test(TestParameters::default(), |ctx| {
+        ctx.device.push_error_scope(wgpu::ErrorFilter::Internal);
+        let _buffer = ctx.device.create_buffer(&wgpu::BufferDescriptor {
+            label: None,
+            size: 4,
+            usage: wgpu::BufferUsages::COPY_DST,
+            mapped_at_creation: false,
+            memory_hints: wgpu::MemoryHints::Default,
+        });
+        assert!(pollster::block_on(ctx.device.pop_error_scope()).is_none());
+    });
+}
@@ -289,6 +289,7 @@ async fn image_bitmap_import() {
                     size: 4 * 64 * 3,
                     usage: wgpu::BufferUsages::MAP_READ | wgpu::BufferUsages::COPY_DST,
                     mapped_at_creation: false,
+                    memory_hints: wgpu::MemoryHints::Default,
                 });
 
                 let mut encoder = ctx
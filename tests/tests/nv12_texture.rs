@@ -0,0 +1,127 @@
+//! Tests for `TextureFormat::NV12` textures.
+
+use wgpu_test::{fail, initialize_test, valid, TestParameters};
+
+fn nv12_descriptor(width: u32, height: u32) -> wgpu::TextureDescriptor<'static> {
+    wgpu::TextureDescriptor {
+        label: Some("nv12"),
+        size: wgpu::Extent3d {
+            width,
+            height,
+            depth_or_array_layers: 1,
+        },
+        mip_level_count: 1,
+        sample_count: 1,
+        dimension: wgpu::TextureDimension::D2,
+        format: wgpu::TextureFormat::NV12,
+        usage: wgpu::TextureUsages::TEXTURE_BINDING
+            | wgpu::TextureUsages::COPY_SRC
+            | wgpu::TextureUsages::COPY_DST,
+        view_formats: &[],
+    }
+}
+
+#[test]
+fn nv12_texture_creation() {
+    let parameters = TestParameters::default().features(wgpu::Features::TEXTURE_FORMAT_NV12);
+    initialize_test(parameters, |ctx| {
+        valid(&ctx.device, || {
+            ctx.device.create_texture(&nv12_descriptor(64, 64))
+        });
+
+        // The chroma plane is subsampled, so both dimensions must be even.
+        fail(&ctx.device, || {
+            ctx.device.create_texture(&nv12_descriptor(63, 64))
+        });
+        fail(&ctx.device, || {
+            ctx.device.create_texture(&nv12_descriptor(64, 63))
+        });
+
+        // Multi-planar textures can't have mipmaps.
+        fail(&ctx.device, || {
+            ctx.device.create_texture(&wgpu::TextureDescriptor {
+                mip_level_count: 2,
+                ..nv12_descriptor(64, 64)
+            })
+        });
+    });
+}
+
+#[test]
+fn nv12_texture_view_plane() {
+    let parameters = TestParameters::default().features(wgpu::Features::TEXTURE_FORMAT_NV12);
+    initialize_test(parameters, |ctx| {
+        let texture = ctx.device.create_texture(&nv12_descriptor(64, 64));
+
+        valid(&ctx.device, || {
+            texture.create_view(&wgpu::TextureViewDescriptor {
+                format: Some(wgpu::TextureFormat::R8Unorm),
+                aspect: wgpu::TextureAspect::Plane0,
+                ..Default::default()
+            })
+        });
+        valid(&ctx.device, || {
+            texture.create_view(&wgpu::TextureViewDescriptor {
+                format: Some(wgpu::TextureFormat::Rg8Unorm),
+                aspect: wgpu::TextureAspect::Plane1,
+                ..Default::default()
+            })
+        });
+
+        // Both planes can't be viewed at once.
+        fail(&ctx.device, || {
+            texture.create_view(&wgpu::TextureViewDescriptor::default())
+        });
+        // The view format must match the plane.
+        fail(&ctx.device, || {
+            texture.create_view(&wgpu::TextureViewDescriptor {
+                format: Some(wgpu::TextureFormat::Rg8Unorm),
+                aspect: wgpu::TextureAspect::Plane0,
+                ..Default::default()
+            })
+        });
+    });
+}
+
+#[test]
+fn nv12_texture_plane_copy() {
+    let parameters = TestParameters::default().features(wgpu::Features::TEXTURE_FORMAT_NV12);
+    initialize_test(parameters, |ctx| {
+        let source = ctx.device.create_texture(&nv12_descriptor(64, 64));
+        let destination = ctx.device.create_texture(&nv12_descriptor(64, 64));
+
+        let plane_copy = |aspect, size| {
+            let mut encoder = ctx
+                .device
+                .create_command_encoder(&wgpu::CommandEncoderDescriptor::default());
+            encoder.copy_texture_to_texture(
+                wgpu::ImageCopyTexture {
+                    texture: &source,
+                    mip_level: 0,
+                    origin: wgpu::Origin3d::ZERO,
+                    aspect,
+                },
+                wgpu::ImageCopyTexture {
+                    texture: &destination,
+                    mip_level: 0,
+                    origin: wgpu::Origin3d::ZERO,
+                    aspect,
+                },
+                wgpu::Extent3d {
+                    width: size,
+                    height: size,
+                    depth_or_array_layers: 1,
+                },
+            );
+            encoder.finish()
+        };
+
+        valid(&ctx.device, || plane_copy(wgpu::TextureAspect::Plane0, 64));
+        valid(&ctx.device, || plane_copy(wgpu::TextureAspect::Plane1, 32));
+
+        // The chroma plane is half the size of the luma plane.
+        fail(&ctx.device, || plane_copy(wgpu::TextureAspect::Plane1, 64));
+        // Planes have to be copied one at a time.
+        fail(&ctx.device, || plane_copy(wgpu::TextureAspect::All, 64));
+    });
+}
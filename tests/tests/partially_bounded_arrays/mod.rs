@@ -72,6 +72,7 @@ fn partially_bounded_array() {
                     layout: Some(&pipeline_layout),
                     module: &cs_module,
                     entry_point: "main",
+                    constants: &Default::default(),
                 });
 
             let bind_group = device.create_bind_group(&wgpu::BindGroupDescriptor {
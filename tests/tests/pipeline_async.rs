@@ -0,0 +1,154 @@
+use wasm_bindgen_test::*;
+use wgpu_test::{initialize_test, valid, TestParameters};
+
+const SHADER: &str = r#"
+@compute @workgroup_size(1)
+fn cs_main() {}
+
+@vertex
+fn vs_main() -> @builtin(position) vec4<f32> {
+    return vec4<f32>(0.0, 0.0, 0.0, 1.0);
+}
+
+@fragment
+fn fs_main() -> @location(0) vec4<f32> {
+    return vec4<f32>(1.0);
+}
+"#;
+
+fn create_module(device: &wgpu::Device) -> wgpu::ShaderModule {
+    device.create_shader_module(wgpu::ShaderModuleDescriptor {
+        label: None,
+        source: wgpu::ShaderSource::Wgsl(SHADER.into()),
+    })
+}
+
+fn create_compute_pipeline(
+    device: &wgpu::Device,
+    module: &wgpu::ShaderModule,
+    entry_point: &str,
+) -> Result<wgpu::ComputePipeline, wgpu::Error> {
+    pollster::block_on(
+        device.create_compute_pipeline_async(&wgpu::ComputePipelineDescriptor {
+            label: Some("async compute"),
+            layout: None,
+            module,
+            entry_point,
+            constants: &Default::default(),
+        }),
+    )
+}
+
+#[test]
+#[wasm_bindgen_test]
+fn compute_pipeline_async() {
+    initialize_test(
+        TestParameters::default()
+            .limits(wgpu::Limits::default())
+            .downlevel_flags(wgpu::DownlevelFlags::COMPUTE_SHADERS),
+        |ctx| {
+            let module = create_module(&ctx.device);
+            let pipeline = valid(&ctx.device, || {
+                create_compute_pipeline(&ctx.device, &module, "cs_main")
+            })
+            .expect("pipeline creation failed");
+
+            let mut encoder = ctx
+                .device
+                .create_command_encoder(&wgpu::CommandEncoderDescriptor::default());
+            {
+                let mut pass = encoder.begin_compute_pass(&wgpu::ComputePassDescriptor::default());
+                pass.set_pipeline(&pipeline);
+                pass.dispatch_workgroups(1, 1, 1);
+            }
+            ctx.queue.submit(Some(encoder.finish()));
+            ctx.device.poll(wgpu::Maintain::Wait);
+        },
+    );
+}
+
+#[test]
+#[wasm_bindgen_test]
+fn compute_pipeline_async_error() {
+    initialize_test(TestParameters::default(), |ctx| {
+        let module = create_module(&ctx.device);
+        // The error is returned by the future, and doesn't reach the error scopes.
+        let result = valid(&ctx.device, || {
+            create_compute_pipeline(&ctx.device, &module, "missing")
+        });
+        assert!(matches!(result, Err(wgpu::Error::Validation { .. })));
+    });
+}
+
+#[test]
+#[wasm_bindgen_test]
+fn render_pipeline_async() {
+    initialize_test(TestParameters::default(), |ctx| {
+        let module = create_module(&ctx.device);
+        let result = valid(&ctx.device, || {
+            pollster::block_on(ctx.device.create_render_pipeline_async(
+                &wgpu::RenderPipelineDescriptor {
+                    label: Some("async render"),
+                    layout: None,
+                    vertex: wgpu::VertexState {
+                        module: &module,
+                        entry_point: "vs_main",
+                        constants: &Default::default(),
+                        buffers: &[],
+                    },
+                    primitive: wgpu::PrimitiveState::default(),
+                    depth_stencil: None,
+                    multisample: wgpu::MultisampleState::default(),
+                    fragment: Some(wgpu::FragmentState {
+                        module: &module,
+                        entry_point: "fs_main",
+                        constants: &Default::default(),
+                        targets: &[Some(wgpu::TextureFormat::Rgba8Unorm.into())],
+                    }),
+                    multiview: None,
+                },
+            ))
+        });
+        assert!(result.is_ok());
+    });
+}
+
+#[test]
+#[wasm_bindgen_test]
+fn compute_pipeline_async_device_dropped() {
+    initialize_test(
+        TestParameters::default()
+            .limits(wgpu::Limits::default())
+            .downlevel_flags(wgpu::DownlevelFlags::COMPUTE_SHADERS),
+        |ctx| {
+            let module = create_module(&ctx.device);
+            let future =
+                ctx.device
+                    .create_compute_pipeline_async(&wgpu::ComputePipelineDescriptor {
+                        label: Some("async compute"),
+                        layout: None,
+                        module: &module,
+                        entry_point: "cs_main",
+                        constants: &Default::default(),
+                    });
+            drop(module);
+
+            // Drop the device while the pipeline may still be compiling.
+            let wgpu_test::TestingContext {
+                instance,
+                device,
+                queue,
+                ..
+            } = ctx;
+            drop(queue);
+            drop(device);
+            instance.poll_all(false);
+
+            // The pending pipeline keeps its device alive until it is registered.
+            let pipeline = pollster::block_on(future);
+            assert!(pipeline.is_ok());
+            drop(pipeline);
+            instance.poll_all(true);
+        },
+    );
+}
@@ -0,0 +1,130 @@
+use std::{borrow::Cow, collections::HashMap};
+
+use wasm_bindgen_test::*;
+use wgpu::{DownlevelFlags, Limits};
+
+use wgpu_test::{fail, initialize_test, TestParameters, TestingContext};
+
+const SHADER: &str = r#"
+const scale: f32 = 1.0;
+const count: u32 = 1u;
+
+@group(0) @binding(0)
+var<storage, read_write> output: f32;
+
+@compute @workgroup_size(1)
+fn main() {
+    output = scale * f32(count);
+}
+"#;
+
+/// Builds the shader module, with `scale` overridable by name and `count` by the identifier 1.
+///
+/// The WGSL frontend doesn't parse `override` declarations yet, so the module is patched.
+fn create_module(ctx: &TestingContext) -> wgpu::ShaderModule {
+    let mut module = naga::front::wgsl::parse_str(SHADER).unwrap();
+    for (_, constant) in module.constants.iter_mut() {
+        constant.r#override = match constant.name.as_deref() {
+            Some("scale") => naga::Override::ByName,
+            Some("count") => naga::Override::ByNameOrId(1),
+            _ => naga::Override::None,
+        };
+    }
+    ctx.device
+        .create_shader_module(wgpu::ShaderModuleDescriptor {
+            label: None,
+            source: wgpu::ShaderSource::Naga(Cow::Owned(module)),
+        })
+}
+
+fn create_pipeline(
+    ctx: &TestingContext,
+    module: &wgpu::ShaderModule,
+    constants: &HashMap<String, f64>,
+) -> wgpu::ComputePipeline {
+    ctx.device
+        .create_compute_pipeline(&wgpu::ComputePipelineDescriptor {
+            label: None,
+            layout: None,
+            module,
+            entry_point: "main",
+            constants,
+        })
+}
+
+fn parameters() -> TestParameters {
+    TestParameters::default()
+        .downlevel_flags(DownlevelFlags::COMPUTE_SHADERS)
+        .limits(Limits::downlevel_defaults())
+}
+
+#[test]
+#[wasm_bindgen_test]
+fn pipeline_constants() {
+    initialize_test(parameters(), |ctx| {
+        let module = create_module(&ctx);
+        let constants = HashMap::from([("scale".to_string(), 2.5), ("1".to_string(), 3.0)]);
+        let pipeline = create_pipeline(&ctx, &module, &constants);
+
+        let output = ctx.device.create_buffer(&wgpu::BufferDescriptor {
+            label: None,
+            size: 4,
+            usage: wgpu::BufferUsages::STORAGE | wgpu::BufferUsages::COPY_SRC,
+            mapped_at_creation: false,
+            memory_hints: wgpu::MemoryHints::Default,
+        });
+        let readback = ctx.device.create_buffer(&wgpu::BufferDescriptor {
+            label: None,
+            size: 4,
+            usage: wgpu::BufferUsages::COPY_DST | wgpu::BufferUsages::MAP_READ,
+            mapped_at_creation: false,
+            memory_hints: wgpu::MemoryHints::Default,
+        });
+        let bind_group = ctx.device.create_bind_group(&wgpu::BindGroupDescriptor {
+            label: None,
+            layout: &pipeline.get_bind_group_layout(0),
+            entries: &[wgpu::BindGroupEntry {
+                binding: 0,
+                resource: output.as_entire_binding(),
+            }],
+        });
+
+        let mut encoder = ctx
+            .device
+            .create_command_encoder(&wgpu::CommandEncoderDescriptor::default());
+        let mut cpass = encoder.begin_compute_pass(&wgpu::ComputePassDescriptor::default());
+        cpass.set_pipeline(&pipeline);
+        cpass.set_bind_group(0, &bind_group, &[]);
+        cpass.dispatch_workgroups(1, 1, 1);
+        drop(cpass);
+        encoder.copy_buffer_to_buffer(&output, 0, &readback, 0, 4);
+        ctx.queue.submit(Some(encoder.finish()));
+
+        let slice = readback.slice(..);
+        slice.map_async(wgpu::MapMode::Read, |_| ());
+        ctx.device.poll(wgpu::Maintain::Wait);
+        let data: f32 = bytemuck::pod_read_unaligned(&slice.get_mapped_range());
+        assert_eq!(data, 7.5);
+    })
+}
+
+#[test]
+#[wasm_bindgen_test]
+fn pipeline_constants_unknown_key() {
+    initialize_test(parameters(), |ctx| {
+        let module = create_module(&ctx);
+        let constants = HashMap::from([("missing".to_string(), 1.0)]);
+        fail(&ctx.device, || create_pipeline(&ctx, &module, &constants));
+    })
+}
+
+#[test]
+#[wasm_bindgen_test]
+fn pipeline_constants_unrepresentable_value() {
+    initialize_test(parameters(), |ctx| {
+        let module = create_module(&ctx);
+        // `count` is a `u32`.
+        let constants = HashMap::from([("1".to_string(), -1.0)]);
+        fail(&ctx.device, || create_pipeline(&ctx, &module, &constants));
+    })
+}
@@ -15,6 +15,7 @@ fn generate_dummy_work(ctx: &TestingContext) -> CommandBuffer {
         size: 16,
         usage: BufferUsages::UNIFORM,
         mapped_at_creation: false,
+        memory_hints: wgpu::MemoryHints::Default,
     });
 
     let bind_group_layout = ctx
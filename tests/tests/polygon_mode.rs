@@ -0,0 +1,333 @@
+use std::ops::Range;
+
+use wasm_bindgen_test::*;
+
+use wgpu::util::DeviceExt;
+use wgpu_test::{fail, initialize_test, TestParameters, TestingContext};
+
+const SIZE: u32 = 8;
+
+/// The draw call of [`covered_pixels`].
+enum Draw<'a> {
+    Vertices(Range<u32>),
+    Indexed(&'a wgpu::Buffer, wgpu::IndexFormat, Range<u32>),
+    Indirect(&'a wgpu::Buffer),
+    /// Indexed indirect draw, with `Uint16` indices.
+    IndexedIndirect(&'a wgpu::Buffer, &'a wgpu::Buffer),
+}
+
+/// Draws triangles into an 8x8 texture and returns which pixels they covered.
+///
+/// The vertices are the corners of a square, in the order lower left, lower
+/// right, upper left, upper right.
+fn covered_pixels(
+    ctx: &TestingContext,
+    polygon_mode: wgpu::PolygonMode,
+    topology: wgpu::PrimitiveTopology,
+    draw: &Draw,
+) -> Vec<bool> {
+    let shader = ctx
+        .device
+        .create_shader_module(wgpu::ShaderModuleDescriptor {
+            label: None,
+            source: wgpu::ShaderSource::Wgsl(
+                r#"
+@vertex
+fn vs_main(@builtin(vertex_index) index: u32) -> @builtin(position) vec4<f32> {
+    var positions = array<vec2<f32>, 4>(
+        vec2<f32>(-0.75, -0.75),
+        vec2<f32>(0.75, -0.75),
+        vec2<f32>(-0.75, 0.75),
+        vec2<f32>(0.75, 0.75),
+    );
+    return vec4<f32>(positions[index], 0.0, 1.0);
+}
+
+@fragment
+fn fs_main() -> @location(0) vec4<f32> {
+    return vec4<f32>(1.0);
+}
+"#
+                .into(),
+            ),
+        });
+
+    let pipeline = ctx
+        .device
+        .create_render_pipeline(&wgpu::RenderPipelineDescriptor {
+            label: None,
+            layout: None,
+            vertex: wgpu::VertexState {
+                buffers: &[],
+                entry_point: "vs_main",
+                constants: &Default::default(),
+                module: &shader,
+            },
+            primitive: wgpu::PrimitiveState {
+                topology,
+                polygon_mode,
+                ..Default::default()
+            },
+            depth_stencil: None,
+            multisample: wgpu::MultisampleState::default(),
+            fragment: Some(wgpu::FragmentState {
+                entry_point: "fs_main",
+                constants: &Default::default(),
+                module: &shader,
+                targets: &[Some(wgpu::TextureFormat::Rgba8Unorm.into())],
+            }),
+            multiview: None,
+        });
+
+    let texture = ctx.device.create_texture(&wgpu::TextureDescriptor {
+        label: None,
+        size: wgpu::Extent3d {
+            width: SIZE,
+            height: SIZE,
+            depth_or_array_layers: 1,
+        },
+        mip_level_count: 1,
+        sample_count: 1,
+        dimension: wgpu::TextureDimension::D2,
+        format: wgpu::TextureFormat::Rgba8Unorm,
+        usage: wgpu::TextureUsages::RENDER_ATTACHMENT | wgpu::TextureUsages::COPY_SRC,
+        view_formats: &[],
+    });
+    let view = texture.create_view(&wgpu::TextureViewDescriptor::default());
+
+    let readback = ctx.device.create_buffer(&wgpu::BufferDescriptor {
+        label: None,
+        size: (wgpu::COPY_BYTES_PER_ROW_ALIGNMENT * SIZE) as u64,
+        usage: wgpu::BufferUsages::COPY_DST | wgpu::BufferUsages::MAP_READ,
+        mapped_at_creation: false,
+        memory_hints: wgpu::MemoryHints::Default,
+    });
+
+    let mut encoder = ctx
+        .device
+        .create_command_encoder(&wgpu::CommandEncoderDescriptor::default());
+    let mut rpass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
+        label: None,
+        color_attachments: &[Some(wgpu::RenderPassColorAttachment {
+            view: &view,
+            resolve_target: None,
+            ops: wgpu::Operations {
+                load: wgpu::LoadOp::Clear(wgpu::Color::TRANSPARENT),
+                store: true,
+            },
+        })],
+        depth_stencil_attachment: None,
+    });
+    rpass.set_pipeline(&pipeline);
+    match *draw {
+        Draw::Vertices(ref vertices) => rpass.draw(vertices.clone(), 0..1),
+        Draw::Indexed(buffer, format, ref indices) => {
+            rpass.set_index_buffer(buffer.slice(..), format);
+            rpass.draw_indexed(indices.clone(), 0, 0..1);
+        }
+        Draw::Indirect(buffer) => rpass.draw_indirect(buffer, 0),
+        Draw::IndexedIndirect(indices, buffer) => {
+            rpass.set_index_buffer(indices.slice(..), wgpu::IndexFormat::Uint16);
+            rpass.draw_indexed_indirect(buffer, 0);
+        }
+    }
+    drop(rpass);
+    encoder.copy_texture_to_buffer(
+        texture.as_image_copy(),
+        wgpu::ImageCopyBuffer {
+            buffer: &readback,
+            layout: wgpu::ImageDataLayout {
+                offset: 0,
+                bytes_per_row: Some(wgpu::COPY_BYTES_PER_ROW_ALIGNMENT),
+                rows_per_image: None,
+            },
+        },
+        texture.size(),
+    );
+    ctx.queue.submit(Some(encoder.finish()));
+
+    let slice = readback.slice(..);
+    slice.map_async(wgpu::MapMode::Read, |_| ());
+    ctx.device.poll(wgpu::Maintain::Wait);
+    let data = slice.get_mapped_range();
+
+    data.chunks_exact(wgpu::COPY_BYTES_PER_ROW_ALIGNMENT as usize)
+        .flat_map(|row| row[..SIZE as usize * 4].chunks_exact(4))
+        .map(|pixel| pixel[0] != 0)
+        .collect()
+}
+
+fn count(pixels: &[bool]) -> usize {
+    pixels.iter().filter(|&&covered| covered).count()
+}
+
+/// Checks that drawing as lines covers some, but not all, of the filled pixels.
+fn assert_edges(ctx: &TestingContext, topology: wgpu::PrimitiveTopology, draw: &Draw) -> Vec<bool> {
+    let filled = covered_pixels(ctx, wgpu::PolygonMode::Fill, topology, draw);
+    let lines = covered_pixels(ctx, wgpu::PolygonMode::Line, topology, draw);
+
+    // Only the edges are drawn, which leaves out the inside of the triangles.
+    assert!(count(&lines) > 0, "no edges were drawn");
+    assert!(
+        count(&lines) < count(&filled),
+        "{} pixels were covered by the edges, {} by the filled triangles",
+        count(&lines),
+        count(&filled)
+    );
+    lines
+}
+
+#[test]
+#[wasm_bindgen_test]
+fn polygon_mode_line() {
+    initialize_test(
+        TestParameters::default().features(wgpu::Features::POLYGON_MODE_LINE),
+        |ctx| {
+            assert_edges(
+                &ctx,
+                wgpu::PrimitiveTopology::TriangleList,
+                &Draw::Vertices(0..3),
+            );
+        },
+    )
+}
+
+#[test]
+#[wasm_bindgen_test]
+fn polygon_mode_line_indexed() {
+    initialize_test(
+        TestParameters::default().features(wgpu::Features::POLYGON_MODE_LINE),
+        |ctx| {
+            let list = ctx
+                .device
+                .create_buffer_init(&wgpu::util::BufferInitDescriptor {
+                    label: None,
+                    contents: bytemuck::cast_slice::<u16, _>(&[0, 1, 2, 2, 1, 3]),
+                    usage: wgpu::BufferUsages::INDEX,
+                });
+            assert_edges(
+                &ctx,
+                wgpu::PrimitiveTopology::TriangleList,
+                &Draw::Indexed(&list, wgpu::IndexFormat::Uint16, 0..6),
+            );
+
+            let strip = ctx
+                .device
+                .create_buffer_init(&wgpu::util::BufferInitDescriptor {
+                    label: None,
+                    contents: bytemuck::cast_slice::<u32, _>(&[0, 1, 2, 3]),
+                    usage: wgpu::BufferUsages::INDEX,
+                });
+            assert_edges(
+                &ctx,
+                wgpu::PrimitiveTopology::TriangleStrip,
+                &Draw::Indexed(&strip, wgpu::IndexFormat::Uint32, 0..4),
+            );
+        },
+    )
+}
+
+#[test]
+#[wasm_bindgen_test]
+fn polygon_mode_line_index_buffer_rewritten() {
+    initialize_test(
+        TestParameters::default().features(wgpu::Features::POLYGON_MODE_LINE),
+        |ctx| {
+            let indices = ctx
+                .device
+                .create_buffer_init(&wgpu::util::BufferInitDescriptor {
+                    label: None,
+                    contents: bytemuck::cast_slice::<u16, _>(&[0, 1, 2, 0]),
+                    usage: wgpu::BufferUsages::INDEX | wgpu::BufferUsages::COPY_DST,
+                });
+            let draw = Draw::Indexed(&indices, wgpu::IndexFormat::Uint16, 0..3);
+            let lower_left = assert_edges(&ctx, wgpu::PrimitiveTopology::TriangleList, &draw);
+
+            // The same draw must pick up the new indices.
+            ctx.queue
+                .write_buffer(&indices, 0, bytemuck::cast_slice::<u16, _>(&[3, 2, 1, 0]));
+            let upper_right = assert_edges(&ctx, wgpu::PrimitiveTopology::TriangleList, &draw);
+
+            assert_ne!(lower_left, upper_right);
+        },
+    )
+}
+
+#[test]
+#[wasm_bindgen_test]
+fn polygon_mode_line_indirect() {
+    initialize_test(
+        TestParameters::default()
+            .features(wgpu::Features::POLYGON_MODE_LINE)
+            .downlevel_flags(wgpu::DownlevelFlags::INDIRECT_EXECUTION),
+        |ctx| {
+            let args = ctx
+                .device
+                .create_buffer_init(&wgpu::util::BufferInitDescriptor {
+                    label: None,
+                    contents: wgpu::util::DrawIndirect {
+                        vertex_count: 4,
+                        instance_count: 1,
+                        base_vertex: 0,
+                        base_instance: 0,
+                    }
+                    .as_bytes(),
+                    usage: wgpu::BufferUsages::INDIRECT,
+                });
+            assert_edges(
+                &ctx,
+                wgpu::PrimitiveTopology::TriangleStrip,
+                &Draw::Indirect(&args),
+            );
+        },
+    )
+}
+
+#[test]
+#[wasm_bindgen_test]
+fn polygon_mode_line_indexed_indirect_emulated() {
+    initialize_test(
+        TestParameters::default()
+            .features(wgpu::Features::POLYGON_MODE_LINE)
+            .downlevel_flags(wgpu::DownlevelFlags::INDIRECT_EXECUTION),
+        |ctx| {
+            let flags = ctx.adapter_downlevel_capabilities.flags;
+            if !flags.contains(wgpu::DownlevelFlags::EMULATED_POLYGON_MODE_LINE)
+                || flags.contains(wgpu::DownlevelFlags::BASE_VERTEX)
+            {
+                return;
+            }
+            let indices = ctx
+                .device
+                .create_buffer_init(&wgpu::util::BufferInitDescriptor {
+                    label: None,
+                    contents: bytemuck::cast_slice::<u16, _>(&[0, 1, 2]),
+                    usage: wgpu::BufferUsages::INDEX,
+                });
+            let args = ctx
+                .device
+                .create_buffer_init(&wgpu::util::BufferInitDescriptor {
+                    label: None,
+                    contents: wgpu::util::DrawIndexedIndirect {
+                        vertex_count: 3,
+                        instance_count: 1,
+                        base_index: 0,
+                        vertex_offset: 0,
+                        base_instance: 0,
+                    }
+                    .as_bytes(),
+                    usage: wgpu::BufferUsages::INDIRECT,
+                });
+            // The emulation can't apply the base vertex read back from the
+            // arguments, so these draws are rejected.
+            fail(&ctx.device, || {
+                covered_pixels(
+                    &ctx,
+                    wgpu::PolygonMode::Line,
+                    wgpu::PrimitiveTopology::TriangleList,
+                    &Draw::IndexedIndirect(&indices, &args),
+                )
+            });
+        },
+    )
+}
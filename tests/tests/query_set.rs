@@ -0,0 +1,65 @@
+use wgpu_test::{initialize_test, TestParameters};
+
+#[test]
+fn timestamp_queries_increase() {
+    initialize_test(
+        TestParameters::default().features(wgpu::Features::TIMESTAMP_QUERY),
+        |ctx| {
+            let query_set = ctx.device.create_query_set(&wgpu::QuerySetDescriptor {
+                label: Some("timestamps"),
+                ty: wgpu::QueryType::Timestamp,
+                count: 2,
+            });
+            let resolve_buffer = ctx.device.create_buffer(&wgpu::BufferDescriptor {
+                label: Some("resolve"),
+                size: 16,
+                usage: wgpu::BufferUsages::QUERY_RESOLVE | wgpu::BufferUsages::COPY_SRC,
+                mapped_at_creation: false,
+                memory_hints: wgpu::MemoryHints::Default,
+            });
+            // Clearing a large buffer takes a measurable amount of time.
+            let work_buffer = ctx.device.create_buffer(&wgpu::BufferDescriptor {
+                label: Some("work"),
+                size: 64 << 20,
+                usage: wgpu::BufferUsages::COPY_DST,
+                mapped_at_creation: false,
+                memory_hints: wgpu::MemoryHints::Default,
+            });
+            let read_buffer = ctx.device.create_buffer(&wgpu::BufferDescriptor {
+                label: Some("read"),
+                size: 16,
+                usage: wgpu::BufferUsages::MAP_READ | wgpu::BufferUsages::COPY_DST,
+                mapped_at_creation: false,
+                memory_hints: wgpu::MemoryHints::Default,
+            });
+
+            let mut encoder = ctx
+                .device
+                .create_command_encoder(&wgpu::CommandEncoderDescriptor::default());
+            encoder.write_timestamp(&query_set, 0);
+            encoder.clear_buffer(&work_buffer, 0, None);
+            encoder.write_timestamp(&query_set, 1);
+            encoder.resolve_query_set(&query_set, 0..2, &resolve_buffer, 0);
+            encoder.copy_buffer_to_buffer(&resolve_buffer, 0, &read_buffer, 0, 16);
+            ctx.queue.submit(Some(encoder.finish()));
+
+            read_buffer
+                .slice(..)
+                .map_async(wgpu::MapMode::Read, Result::unwrap);
+            ctx.device.poll(wgpu::Maintain::Wait);
+
+            let data = read_buffer.slice(..).get_mapped_range();
+            let timestamps: &[u64] = bytemuck::cast_slice(&data);
+            assert!(
+                timestamps[0] != 0 && timestamps[0] < timestamps[1],
+                "timestamps aren't increasing: {timestamps:?}"
+            );
+            let elapsed_ns =
+                (timestamps[1] - timestamps[0]) as f64 * ctx.queue.get_timestamp_period() as f64;
+            assert!(
+                elapsed_ns < 1e9,
+                "clearing a buffer took {elapsed_ns} ns: {timestamps:?}"
+            );
+        },
+    )
+}
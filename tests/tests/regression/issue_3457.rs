@@ -29,6 +29,7 @@ fn pass_reset_vertex_buffer() {
             size: 3 * 16,
             usage: BufferUsages::VERTEX,
             mapped_at_creation: false,
+            memory_hints: wgpu::MemoryHints::Default,
         });
 
         let vertex_buffer2 = ctx.device.create_buffer(&BufferDescriptor {
@@ -36,6 +37,7 @@ fn pass_reset_vertex_buffer() {
             size: 3 * 4,
             usage: BufferUsages::VERTEX,
             mapped_at_creation: false,
+            memory_hints: wgpu::MemoryHints::Default,
         });
 
         let pipeline_layout = ctx
@@ -54,6 +56,7 @@ fn pass_reset_vertex_buffer() {
                 vertex: VertexState {
                     module: &module,
                     entry_point: "double_buffer_vert",
+                    constants: &Default::default(),
                     buffers: &[
                         VertexBufferLayout {
                             array_stride: 16,
@@ -73,6 +76,7 @@ fn pass_reset_vertex_buffer() {
                 fragment: Some(FragmentState {
                     module: &module,
                     entry_point: "double_buffer_frag",
+                    constants: &Default::default(),
                     targets: &[Some(ColorTargetState {
                         format: TextureFormat::Rgba8Unorm,
                         blend: None,
@@ -90,6 +94,7 @@ fn pass_reset_vertex_buffer() {
                 vertex: VertexState {
                     module: &module,
                     entry_point: "single_buffer_vert",
+                    constants: &Default::default(),
                     buffers: &[VertexBufferLayout {
                         array_stride: 16,
                         step_mode: VertexStepMode::Vertex,
@@ -102,6 +107,7 @@ fn pass_reset_vertex_buffer() {
                 fragment: Some(FragmentState {
                     module: &module,
                     entry_point: "single_buffer_frag",
+                    constants: &Default::default(),
                     targets: &[Some(ColorTargetState {
                         format: TextureFormat::Rgba8Unorm,
                         blend: None,
@@ -11,6 +11,7 @@ fn buffer_size_and_usage() {
             size: 1234,
             usage: wgpu::BufferUsages::COPY_SRC | wgpu::BufferUsages::COPY_DST,
             mapped_at_creation: false,
+            memory_hints: wgpu::MemoryHints::Default,
         });
 
         assert_eq!(buffer.size(), 1234);
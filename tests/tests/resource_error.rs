@@ -13,6 +13,7 @@ fn bad_buffer() {
                 size: 99999999,
                 usage: wgpu::BufferUsages::MAP_READ | wgpu::BufferUsages::STORAGE,
                 mapped_at_creation: false,
+                memory_hints: wgpu::MemoryHints::Default,
             })
         });
 
@@ -4,27 +4,45 @@ mod regression {
     mod issue_3457;
 }
 
+mod base_vertex;
+mod bind_group_layout_introspection;
+mod bind_group_update;
 mod buffer;
 mod buffer_copy;
 mod buffer_usages;
 mod clear_texture;
+mod compilation_info;
 mod device;
 mod encoder;
+mod error_scope;
 mod example_wgsl;
 mod external_texture;
 mod instance;
+mod multi_draw_indirect;
+mod nv12_texture;
 mod partially_bounded_arrays;
+mod pipeline_async;
+mod pipeline_constants;
 mod poll;
+mod polygon_mode;
+mod query_set;
 mod queue_transfer;
 mod resource_descriptor_accessor;
 mod resource_error;
 mod scissor_tests;
 mod shader;
+mod shader_module_cache;
+mod shader_passthrough;
 mod shader_primitive_index;
+mod shader_reflection;
 mod shader_view_format;
+mod shader_watcher;
 mod texture_bounds;
 mod transfer;
+mod transient_attachment;
+mod vertex_attribute_64bit;
 mod vertex_indices;
+mod wgsl_composer;
 mod write_texture;
 mod zero_init_texture_after_discard;
 
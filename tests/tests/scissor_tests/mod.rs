@@ -39,6 +39,7 @@ fn scissor_test_impl(ctx: &TestingContext, scissor_rect: Rect, expected_data: [u
             layout: None,
             vertex: wgpu::VertexState {
                 entry_point: "vs_main",
+                constants: &Default::default(),
                 module: &shader,
                 buffers: &[],
             },
@@ -47,6 +48,7 @@ fn scissor_test_impl(ctx: &TestingContext, scissor_rect: Rect, expected_data: [u
             multisample: wgpu::MultisampleState::default(),
             fragment: Some(wgpu::FragmentState {
                 entry_point: "fs_main",
+                constants: &Default::default(),
                 module: &shader,
                 targets: &[Some(wgpu::ColorTargetState {
                     format: wgpu::TextureFormat::Rgba8Unorm,
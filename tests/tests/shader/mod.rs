@@ -215,6 +215,7 @@ fn shader_input_output_test(
         size: MAX_BUFFER_SIZE,
         usage: BufferUsages::COPY_DST | BufferUsages::UNIFORM | BufferUsages::STORAGE,
         mapped_at_creation: false,
+        memory_hints: wgpu::MemoryHints::Default,
     });
 
     let output_buffer = ctx.device.create_buffer(&BufferDescriptor {
@@ -222,6 +223,7 @@ fn shader_input_output_test(
         size: MAX_BUFFER_SIZE,
         usage: BufferUsages::COPY_DST | BufferUsages::COPY_SRC | BufferUsages::STORAGE,
         mapped_at_creation: false,
+        memory_hints: wgpu::MemoryHints::Default,
     });
 
     let mapping_buffer = ctx.device.create_buffer(&BufferDescriptor {
@@ -229,6 +231,7 @@ fn shader_input_output_test(
         size: MAX_BUFFER_SIZE,
         usage: BufferUsages::COPY_DST | BufferUsages::MAP_READ,
         mapped_at_creation: false,
+        memory_hints: wgpu::MemoryHints::Default,
     });
 
     let bg = ctx.device.create_bind_group(&BindGroupDescriptor {
@@ -297,6 +300,7 @@ fn shader_input_output_test(
                 layout: Some(&pll),
                 module: &sm,
                 entry_point: "cs_main",
+                constants: &Default::default(),
             });
 
         // -- Initializing data --
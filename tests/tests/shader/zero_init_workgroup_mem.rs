@@ -65,6 +65,7 @@ fn zero_init_workgroup_mem_impl(ctx: TestingContext) {
         size: BUFFER_SIZE,
         usage: BufferUsages::COPY_DST | BufferUsages::COPY_SRC | BufferUsages::STORAGE,
         mapped_at_creation: false,
+        memory_hints: wgpu::MemoryHints::Default,
     });
 
     let mapping_buffer = ctx.device.create_buffer(&BufferDescriptor {
@@ -72,6 +73,7 @@ fn zero_init_workgroup_mem_impl(ctx: TestingContext) {
         size: BUFFER_SIZE,
         usage: BufferUsages::COPY_DST | BufferUsages::MAP_READ,
         mapped_at_creation: false,
+        memory_hints: wgpu::MemoryHints::Default,
     });
 
     let bg = ctx.device.create_bind_group(&BindGroupDescriptor {
@@ -106,6 +108,7 @@ fn zero_init_workgroup_mem_impl(ctx: TestingContext) {
             layout: Some(&pll),
             module: &sm,
             entry_point: "read",
+            constants: &Default::default(),
         });
 
     let pipeline_write = ctx
@@ -115,6 +118,7 @@ fn zero_init_workgroup_mem_impl(ctx: TestingContext) {
             layout: None,
             module: &sm,
             entry_point: "write",
+            constants: &Default::default(),
         });
 
     // -- Initializing data --
@@ -0,0 +1,73 @@
+#![cfg(any(
+    not(target_arch = "wasm32"),
+    target_os = "emscripten",
+    feature = "webgl"
+))]
+
+use wasm_bindgen_test::*;
+use wgpu_test::{initialize_test, valid, TestParameters, TestingContext};
+
+const SHADER: &str = r#"
+@group(0) @binding(0)
+var<storage, read_write> data: array<u32>;
+
+@compute @workgroup_size(1)
+fn main() { data[0] = 1u; }
+"#;
+
+fn create_module(ctx: &TestingContext, source: &str) -> wgpu::ShaderModule {
+    ctx.device
+        .create_shader_module(wgpu::ShaderModuleDescriptor {
+            label: None,
+            source: wgpu::ShaderSource::Wgsl(source.into()),
+        })
+}
+
+/// Returns the hits and misses of the shader module cache.
+fn cache_report(ctx: &TestingContext) -> (usize, usize) {
+    let report = ctx.instance.generate_report();
+    let cache = &report
+        .hub_report(ctx.adapter_info.backend)
+        .expect("expected a report for the backend of the adapter")
+        .shader_module_cache;
+    (cache.hits, cache.misses)
+}
+
+#[test]
+#[wasm_bindgen_test]
+fn shader_module_cache_dedup() {
+    initialize_test(
+        TestParameters::default()
+            .limits(wgpu::Limits::default())
+            .downlevel_flags(wgpu::DownlevelFlags::COMPUTE_SHADERS),
+        |ctx| {
+            let (hits, misses) = cache_report(&ctx);
+
+            let first = create_module(&ctx, SHADER);
+            let second = create_module(&ctx, SHADER);
+            assert_eq!(cache_report(&ctx), (hits + 1, misses + 1));
+
+            // A different source is another entry.
+            let other = create_module(&ctx, &format!("{SHADER}\n"));
+            assert_eq!(cache_report(&ctx), (hits + 1, misses + 2));
+
+            // Duplicates are still separate modules, which can be used and
+            // dropped on their own.
+            drop(first);
+            for module in [&second, &other] {
+                valid(&ctx.device, || {
+                    ctx.device
+                        .create_compute_pipeline(&wgpu::ComputePipelineDescriptor {
+                            label: None,
+                            layout: None,
+                            module,
+                            entry_point: "main",
+                            constants: &Default::default(),
+                        })
+                });
+            }
+            let _third = create_module(&ctx, SHADER);
+            assert_eq!(cache_report(&ctx), (hits + 2, misses + 2));
+        },
+    )
+}
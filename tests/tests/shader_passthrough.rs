@@ -0,0 +1,329 @@
+use wasm_bindgen_test::*;
+use wgpu::{DownlevelFlags, Features, Limits};
+
+use wgpu_test::{fail, initialize_test, TestParameters, TestingContext};
+
+// Both uniform blocks get the binding 0 by default, so the results are only
+// right if they are rebound to their slots through the binding map.
+const GLSL: &str = r#"#version 310 es
+layout(local_size_x = 4) in;
+
+uniform Offset { uint offset; };
+uniform Scale { uint scale; };
+layout(std430, binding = 0) buffer Data { uint data[]; };
+
+void main() {
+    uint index = gl_GlobalInvocationID.x;
+    data[index] = index * scale + offset;
+}
+"#;
+
+const BINDINGS: &[wgpu::PassthroughBinding<'static>] = &[
+    wgpu::PassthroughBinding {
+        group: 0,
+        binding: 0,
+        name: "Offset",
+    },
+    wgpu::PassthroughBinding {
+        group: 1,
+        binding: 0,
+        name: "Scale",
+    },
+];
+
+fn parameters() -> TestParameters {
+    TestParameters::default()
+        .features(Features::SHADER_PASSTHROUGH)
+        .downlevel_flags(DownlevelFlags::COMPUTE_SHADERS)
+        .limits(Limits::downlevel_defaults())
+}
+
+/// Whether the tests running the GLSL shader apply, as the other backends
+/// don't have a shader to run.
+fn is_gl(ctx: &TestingContext) -> bool {
+    ctx.adapter_info.backend == wgpu::Backend::Gl
+}
+
+fn layout_entry(binding: u32, ty: wgpu::BufferBindingType) -> wgpu::BindGroupLayoutEntry {
+    wgpu::BindGroupLayoutEntry {
+        binding,
+        visibility: wgpu::ShaderStages::COMPUTE,
+        ty: wgpu::BindingType::Buffer {
+            ty,
+            has_dynamic_offset: false,
+            min_binding_size: None,
+        },
+        count: None,
+    }
+}
+
+fn create_buffer(ctx: &TestingContext, usage: wgpu::BufferUsages) -> wgpu::Buffer {
+    ctx.device.create_buffer(&wgpu::BufferDescriptor {
+        label: None,
+        size: 16,
+        usage,
+        mapped_at_creation: false,
+        memory_hints: wgpu::MemoryHints::Default,
+    })
+}
+
+#[test]
+#[wasm_bindgen_test]
+fn shader_passthrough_glsl() {
+    initialize_test(parameters(), |ctx| {
+        if !is_gl(&ctx) {
+            return;
+        }
+        let module = unsafe {
+            ctx.device
+                .create_shader_module_passthrough(&wgpu::ShaderModuleDescriptorPassthrough {
+                    label: Some("passthrough"),
+                    stage: wgpu::ShaderStages::COMPUTE,
+                    glsl: Some(GLSL.into()),
+                    bindings: BINDINGS,
+                    ..Default::default()
+                })
+        };
+
+        let bgl_0 = ctx
+            .device
+            .create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
+                label: None,
+                entries: &[
+                    layout_entry(0, wgpu::BufferBindingType::Uniform),
+                    layout_entry(1, wgpu::BufferBindingType::Storage { read_only: false }),
+                ],
+            });
+        let bgl_1 = ctx
+            .device
+            .create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
+                label: None,
+                entries: &[layout_entry(0, wgpu::BufferBindingType::Uniform)],
+            });
+        let layout = ctx
+            .device
+            .create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
+                label: None,
+                bind_group_layouts: &[&bgl_0, &bgl_1],
+                push_constant_ranges: &[],
+            });
+        let pipeline = ctx
+            .device
+            .create_compute_pipeline(&wgpu::ComputePipelineDescriptor {
+                label: None,
+                layout: Some(&layout),
+                module: &module,
+                entry_point: "main",
+                constants: &Default::default(),
+            });
+
+        let offset = create_buffer(
+            &ctx,
+            wgpu::BufferUsages::UNIFORM | wgpu::BufferUsages::COPY_DST,
+        );
+        let scale = create_buffer(
+            &ctx,
+            wgpu::BufferUsages::UNIFORM | wgpu::BufferUsages::COPY_DST,
+        );
+        let output = create_buffer(
+            &ctx,
+            wgpu::BufferUsages::STORAGE | wgpu::BufferUsages::COPY_SRC,
+        );
+        let readback = create_buffer(
+            &ctx,
+            wgpu::BufferUsages::COPY_DST | wgpu::BufferUsages::MAP_READ,
+        );
+        ctx.queue
+            .write_buffer(&offset, 0, bytemuck::cast_slice(&[7u32, 0, 0, 0]));
+        ctx.queue
+            .write_buffer(&scale, 0, bytemuck::cast_slice(&[3u32, 0, 0, 0]));
+
+        let bind_group_0 = ctx.device.create_bind_group(&wgpu::BindGroupDescriptor {
+            label: None,
+            layout: &bgl_0,
+            entries: &[
+                wgpu::BindGroupEntry {
+                    binding: 0,
+                    resource: offset.as_entire_binding(),
+                },
+                wgpu::BindGroupEntry {
+                    binding: 1,
+                    resource: output.as_entire_binding(),
+                },
+            ],
+        });
+        let bind_group_1 = ctx.device.create_bind_group(&wgpu::BindGroupDescriptor {
+            label: None,
+            layout: &bgl_1,
+            entries: &[wgpu::BindGroupEntry {
+                binding: 0,
+                resource: scale.as_entire_binding(),
+            }],
+        });
+
+        let mut encoder = ctx
+            .device
+            .create_command_encoder(&wgpu::CommandEncoderDescriptor::default());
+        let mut cpass = encoder.begin_compute_pass(&wgpu::ComputePassDescriptor::default());
+        cpass.set_pipeline(&pipeline);
+        cpass.set_bind_group(0, &bind_group_0, &[]);
+        cpass.set_bind_group(1, &bind_group_1, &[]);
+        cpass.dispatch_workgroups(1, 1, 1);
+        drop(cpass);
+        encoder.copy_buffer_to_buffer(&output, 0, &readback, 0, 16);
+        ctx.queue.submit(Some(encoder.finish()));
+
+        let slice = readback.slice(..);
+        slice.map_async(wgpu::MapMode::Read, |_| ());
+        ctx.device.poll(wgpu::Maintain::Wait);
+        let data: [u32; 4] = bytemuck::pod_read_unaligned(&slice.get_mapped_range());
+        assert_eq!(data, [7, 10, 13, 16]);
+    })
+}
+
+#[test]
+#[wasm_bindgen_test]
+fn shader_passthrough_missing_source() {
+    initialize_test(parameters(), |ctx| {
+        fail(&ctx.device, || unsafe {
+            ctx.device
+                .create_shader_module_passthrough(&wgpu::ShaderModuleDescriptorPassthrough {
+                    label: None,
+                    stage: wgpu::ShaderStages::COMPUTE,
+                    ..Default::default()
+                })
+        });
+    })
+}
+
+#[test]
+#[wasm_bindgen_test]
+fn shader_passthrough_unknown_binding() {
+    initialize_test(parameters(), |ctx| {
+        if !is_gl(&ctx) {
+            return;
+        }
+        let module = unsafe {
+            ctx.device
+                .create_shader_module_passthrough(&wgpu::ShaderModuleDescriptorPassthrough {
+                    label: None,
+                    stage: wgpu::ShaderStages::COMPUTE,
+                    glsl: Some(GLSL.into()),
+                    bindings: BINDINGS,
+                    ..Default::default()
+                })
+        };
+        // The layout has no group 1 for `Scale`.
+        let bgl = ctx
+            .device
+            .create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
+                label: None,
+                entries: &[
+                    layout_entry(0, wgpu::BufferBindingType::Uniform),
+                    layout_entry(1, wgpu::BufferBindingType::Storage { read_only: false }),
+                ],
+            });
+        let layout = ctx
+            .device
+            .create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
+                label: None,
+                bind_group_layouts: &[&bgl],
+                push_constant_ranges: &[],
+            });
+        // The binding doesn't match the layout, which is a validation error.
+        ctx.device.push_error_scope(wgpu::ErrorFilter::Validation);
+        let _pipeline = ctx
+            .device
+            .create_compute_pipeline(&wgpu::ComputePipelineDescriptor {
+                label: None,
+                layout: Some(&layout),
+                module: &module,
+                entry_point: "main",
+                constants: &Default::default(),
+            });
+        assert!(matches!(
+            pollster::block_on(ctx.device.pop_error_scope()),
+            Some(wgpu::Error::Validation { .. })
+        ));
+    })
+}
+
+#[test]
+#[wasm_bindgen_test]
+fn shader_passthrough_constants() {
+    initialize_test(parameters(), |ctx| {
+        if !is_gl(&ctx) {
+            return;
+        }
+        let module = unsafe {
+            ctx.device
+                .create_shader_module_passthrough(&wgpu::ShaderModuleDescriptorPassthrough {
+                    label: None,
+                    stage: wgpu::ShaderStages::COMPUTE,
+                    glsl: Some(GLSL.into()),
+                    bindings: BINDINGS,
+                    ..Default::default()
+                })
+        };
+        let bgl_0 = ctx
+            .device
+            .create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
+                label: None,
+                entries: &[
+                    layout_entry(0, wgpu::BufferBindingType::Uniform),
+                    layout_entry(1, wgpu::BufferBindingType::Storage { read_only: false }),
+                ],
+            });
+        let bgl_1 = ctx
+            .device
+            .create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
+                label: None,
+                entries: &[layout_entry(0, wgpu::BufferBindingType::Uniform)],
+            });
+        let layout = ctx
+            .device
+            .create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
+                label: None,
+                bind_group_layouts: &[&bgl_0, &bgl_1],
+                push_constant_ranges: &[],
+            });
+        // Constants can't be substituted in GLSL shaders.
+        fail(&ctx.device, || {
+            ctx.device
+                .create_compute_pipeline(&wgpu::ComputePipelineDescriptor {
+                    label: None,
+                    layout: Some(&layout),
+                    module: &module,
+                    entry_point: "main",
+                    constants: &[("scale".to_string(), 2.0)].into_iter().collect(),
+                })
+        });
+    })
+}
+
+#[test]
+#[wasm_bindgen_test]
+fn shader_passthrough_bindings_unsupported() {
+    initialize_test(parameters(), |ctx| {
+        if is_gl(&ctx) {
+            return;
+        }
+        // Only GL applies a binding map.
+        ctx.device.push_error_scope(wgpu::ErrorFilter::Validation);
+        let _module = unsafe {
+            ctx.device
+                .create_shader_module_passthrough(&wgpu::ShaderModuleDescriptorPassthrough {
+                    label: None,
+                    stage: wgpu::ShaderStages::COMPUTE,
+                    bindings: BINDINGS,
+                    ..Default::default()
+                })
+        };
+        match pollster::block_on(ctx.device.pop_error_scope()) {
+            Some(wgpu::Error::Validation { description, .. }) => {
+                assert!(description.contains("binding maps"), "{description}")
+            }
+            other => panic!("expected a validation error, got {other:?}"),
+        }
+    })
+}
@@ -133,6 +133,7 @@ fn pulling_common(
                     }],
                 }],
                 entry_point: "vs_main",
+                constants: &Default::default(),
                 module: &shader,
             },
             primitive: wgpu::PrimitiveState::default(),
@@ -140,6 +141,7 @@ fn pulling_common(
             multisample: wgpu::MultisampleState::default(),
             fragment: Some(wgpu::FragmentState {
                 entry_point: "fs_main",
+                constants: &Default::default(),
                 module: &shader,
                 targets: &[Some(wgpu::ColorTargetState {
                     format: wgpu::TextureFormat::Rgba8Unorm,
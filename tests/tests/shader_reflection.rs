@@ -0,0 +1,135 @@
+use std::num::NonZeroU64;
+
+use wasm_bindgen_test::*;
+use wgpu_test::{initialize_test, TestParameters};
+
+const SHADER: &str = r#"
+struct Globals {
+    transform: mat4x4<f32>,
+}
+
+@group(0) @binding(0)
+var<uniform> globals: Globals;
+@group(1) @binding(1)
+var color_texture: texture_2d<f32>;
+@group(1) @binding(0)
+var color_sampler: sampler;
+@group(0) @binding(1)
+var<storage, read_write> counters: array<u32>;
+
+struct VertexOutput {
+    @builtin(position) position: vec4<f32>,
+    @location(0) uv: vec2<f32>,
+}
+
+@vertex
+fn vs_main(@location(1) uv: vec2<f32>, @location(0) position: vec3<f32>) -> VertexOutput {
+    return VertexOutput(globals.transform * vec4<f32>(position, 1.0), uv);
+}
+
+@fragment
+fn fs_main(@location(0) uv: vec2<f32>) -> @location(0) vec4<f32> {
+    return textureSample(color_texture, color_sampler, uv);
+}
+
+@compute @workgroup_size(8, 4, 1)
+fn cs_main(@builtin(global_invocation_id) id: vec3<u32>) {
+    counters[id.x] += 1u;
+}
+"#;
+
+#[test]
+#[wasm_bindgen_test]
+fn shader_reflection() {
+    initialize_test(
+        TestParameters::default().backend_failure(wgpu::Backends::BROWSER_WEBGPU),
+        |ctx| {
+            let module = ctx
+                .device
+                .create_shader_module(wgpu::ShaderModuleDescriptor {
+                    label: None,
+                    source: wgpu::ShaderSource::Wgsl(SHADER.into()),
+                });
+            let reflection = module.reflect().expect("module has no reflection");
+            let names = reflection
+                .entry_points
+                .iter()
+                .map(|ep| ep.name.as_str())
+                .collect::<Vec<_>>();
+            assert_eq!(names, ["cs_main", "fs_main", "vs_main"]);
+
+            let compute = &reflection.entry_points[0];
+            assert_eq!(compute.stage, wgpu::ShaderStages::COMPUTE);
+            assert_eq!(compute.workgroup_size, [8, 4, 1]);
+            assert_eq!(
+                compute.bindings,
+                [wgpu::BindingReflection {
+                    name: Some("counters".to_string()),
+                    group: 0,
+                    binding: 1,
+                    ty: wgpu::BindingType::Buffer {
+                        ty: wgpu::BufferBindingType::Storage { read_only: false },
+                        has_dynamic_offset: false,
+                        min_binding_size: NonZeroU64::new(4),
+                    },
+                }]
+            );
+
+            let fragment = &reflection.entry_points[1];
+            assert_eq!(fragment.stage, wgpu::ShaderStages::FRAGMENT);
+            let bindings = fragment
+                .bindings
+                .iter()
+                .map(|binding| (binding.group, binding.binding, binding.ty))
+                .collect::<Vec<_>>();
+            assert_eq!(
+                bindings,
+                [
+                    (
+                        1,
+                        0,
+                        wgpu::BindingType::Sampler(wgpu::SamplerBindingType::Filtering)
+                    ),
+                    (
+                        1,
+                        1,
+                        wgpu::BindingType::Texture {
+                            sample_type: wgpu::TextureSampleType::Float { filterable: true },
+                            view_dimension: wgpu::TextureViewDimension::D2,
+                            multisampled: false,
+                        }
+                    ),
+                ]
+            );
+            assert_eq!(
+                fragment.fragment_outputs,
+                [wgpu::FragmentOutputReflection {
+                    location: 0,
+                    sample_type: wgpu::TextureSampleType::Float { filterable: true },
+                    components: 4,
+                }]
+            );
+            assert!(fragment.vertex_inputs.is_empty());
+
+            let vertex = &reflection.entry_points[2];
+            assert_eq!(vertex.stage, wgpu::ShaderStages::VERTEX);
+            assert_eq!(vertex.workgroup_size, [0; 3]);
+            assert_eq!(vertex.bindings.len(), 1);
+            assert_eq!(vertex.bindings[0].name.as_deref(), Some("globals"));
+            assert_eq!(
+                vertex.vertex_inputs,
+                [
+                    wgpu::VertexInputReflection {
+                        location: 0,
+                        format: wgpu::VertexFormat::Float32x3,
+                    },
+                    wgpu::VertexInputReflection {
+                        location: 1,
+                        format: wgpu::VertexFormat::Float32x2,
+                    },
+                ]
+            );
+            assert!(vertex.fragment_outputs.is_empty());
+        },
+    )
+}
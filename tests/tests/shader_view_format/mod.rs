@@ -90,11 +90,13 @@ fn reinterpret(
             vertex: wgpu::VertexState {
                 module: shader,
                 entry_point: "vs_main",
+                constants: &Default::default(),
                 buffers: &[],
             },
             fragment: Some(wgpu::FragmentState {
                 module: shader,
                 entry_point: "fs_main",
+                constants: &Default::default(),
                 targets: &[Some(src_format.into())],
             }),
             primitive: wgpu::PrimitiveState {
@@ -149,6 +151,7 @@ fn reinterpret(
         size: wgpu::COPY_BYTES_PER_ROW_ALIGNMENT as u64 * 2,
         usage: wgpu::BufferUsages::MAP_READ | wgpu::BufferUsages::COPY_DST,
         mapped_at_creation: false,
+        memory_hints: wgpu::MemoryHints::Default,
     });
 
     let mut encoder = ctx
@@ -0,0 +1,105 @@
+#![cfg(not(target_arch = "wasm32"))]
+
+use std::{fs, thread};
+
+use wgpu::util::{ShaderWatcher, ShaderWatcherError};
+use wgpu_test::{initialize_test, TestParameters};
+
+const SHADER: &str = r#"
+@compute @workgroup_size(1)
+fn main() {}
+"#;
+
+const CHANGED_SHADER: &str = r#"
+@compute @workgroup_size(2)
+fn main() {}
+"#;
+
+const INVALID_SHADER: &str = r#"
+@compute @workgroup_size(1)
+fn main() { let x: u32 = 1.0; }
+"#;
+
+// Compiles, but the pipeline can't find its entry point.
+const RENAMED_SHADER: &str = r#"
+@compute @workgroup_size(1)
+fn other() {}
+"#;
+
+#[test]
+fn shader_watcher_reload() {
+    initialize_test(
+        TestParameters::default()
+            .limits(wgpu::Limits::default())
+            .downlevel_flags(wgpu::DownlevelFlags::COMPUTE_SHADERS),
+        |ctx| {
+            let path = std::env::temp_dir().join(format!(
+                "wgpu-shader-watcher-{}-{:?}.wgsl",
+                std::process::id(),
+                thread::current().id(),
+            ));
+            fs::write(&path, SHADER).unwrap();
+
+            let mut watcher = ShaderWatcher::new();
+            let shader = pollster::block_on(watcher.add_shader(&ctx.device, &path)).unwrap();
+            let pipeline = pollster::block_on(watcher.add_compute_pipeline(
+                &ctx.device,
+                &[shader],
+                |device, modules| {
+                    device.create_compute_pipeline(&wgpu::ComputePipelineDescriptor {
+                        label: None,
+                        layout: None,
+                        module: modules[0],
+                        entry_point: "main",
+                        constants: &Default::default(),
+                    })
+                },
+            ))
+            .unwrap();
+
+            // Nothing changed.
+            assert!(pollster::block_on(watcher.poll(&ctx.device)).is_empty());
+
+            // Saving the file without changing it doesn't reload it.
+            fs::write(&path, SHADER).unwrap();
+            assert!(pollster::block_on(watcher.poll(&ctx.device)).is_empty());
+
+            let original = watcher.compute_pipeline(pipeline).global_id();
+
+            // A compilation error keeps the previous pipeline.
+            fs::write(&path, INVALID_SHADER).unwrap();
+            let reloads = pollster::block_on(watcher.poll(&ctx.device));
+            assert_eq!(reloads.len(), 1);
+            assert_eq!(reloads[0].shader, shader);
+            match reloads[0].result {
+                Err(ShaderWatcherError::Compilation(ref info)) => {
+                    assert!(!info.messages.is_empty())
+                }
+                ref other => panic!("expected a compilation error, got {other:?}"),
+            }
+            assert_eq!(watcher.compute_pipeline(pipeline).global_id(), original);
+
+            // The error is only reported once.
+            assert!(pollster::block_on(watcher.poll(&ctx.device)).is_empty());
+
+            // A pipeline error keeps the previous module and pipeline.
+            fs::write(&path, RENAMED_SHADER).unwrap();
+            let reloads = pollster::block_on(watcher.poll(&ctx.device));
+            assert_eq!(reloads.len(), 1);
+            assert!(matches!(
+                reloads[0].result,
+                Err(ShaderWatcherError::Pipeline(_))
+            ));
+            assert_eq!(watcher.compute_pipeline(pipeline).global_id(), original);
+
+            // A valid change rebuilds the pipeline.
+            fs::write(&path, CHANGED_SHADER).unwrap();
+            let reloads = pollster::block_on(watcher.poll(&ctx.device));
+            assert_eq!(reloads.len(), 1);
+            assert!(reloads[0].result.is_ok());
+            assert_ne!(watcher.compute_pipeline(pipeline).global_id(), original);
+
+            fs::remove_file(&path).unwrap();
+        },
+    )
+}
@@ -0,0 +1,110 @@
+//! Tests for `TextureUsages::TRANSIENT_ATTACHMENT` validation.
+
+use wgpu_test::{fail, initialize_test, valid, TestParameters};
+
+fn transient_descriptor(
+    format: wgpu::TextureFormat,
+    usage: wgpu::TextureUsages,
+) -> wgpu::TextureDescriptor<'static> {
+    wgpu::TextureDescriptor {
+        label: Some("transient"),
+        size: wgpu::Extent3d {
+            width: 64,
+            height: 64,
+            depth_or_array_layers: 1,
+        },
+        mip_level_count: 1,
+        sample_count: 1,
+        dimension: wgpu::TextureDimension::D2,
+        format,
+        usage,
+        view_formats: &[],
+    }
+}
+
+#[test]
+fn transient_texture_usages() {
+    initialize_test(TestParameters::default(), |ctx| {
+        valid(&ctx.device, || {
+            ctx.device.create_texture(&transient_descriptor(
+                wgpu::TextureFormat::Depth32Float,
+                wgpu::TextureUsages::RENDER_ATTACHMENT | wgpu::TextureUsages::TRANSIENT_ATTACHMENT,
+            ))
+        });
+
+        // Transient textures can't be anything but render attachments.
+        fail(&ctx.device, || {
+            ctx.device.create_texture(&transient_descriptor(
+                wgpu::TextureFormat::Rgba8Unorm,
+                wgpu::TextureUsages::RENDER_ATTACHMENT
+                    | wgpu::TextureUsages::TRANSIENT_ATTACHMENT
+                    | wgpu::TextureUsages::TEXTURE_BINDING,
+            ))
+        });
+        fail(&ctx.device, || {
+            ctx.device.create_texture(&transient_descriptor(
+                wgpu::TextureFormat::Rgba8Unorm,
+                wgpu::TextureUsages::TRANSIENT_ATTACHMENT,
+            ))
+        });
+    });
+}
+
+#[test]
+fn transient_attachment_ops() {
+    initialize_test(TestParameters::default(), |ctx| {
+        let texture = ctx.device.create_texture(&transient_descriptor(
+            wgpu::TextureFormat::Rgba8Unorm,
+            wgpu::TextureUsages::RENDER_ATTACHMENT | wgpu::TextureUsages::TRANSIENT_ATTACHMENT,
+        ));
+        let view = texture.create_view(&wgpu::TextureViewDescriptor::default());
+
+        let encode_pass = |load: wgpu::LoadOp<wgpu::Color>, store: bool| {
+            let mut encoder = ctx
+                .device
+                .create_command_encoder(&wgpu::CommandEncoderDescriptor::default());
+            encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
+                label: None,
+                color_attachments: &[Some(wgpu::RenderPassColorAttachment {
+                    view: &view,
+                    resolve_target: None,
+                    ops: wgpu::Operations { load, store },
+                })],
+                depth_stencil_attachment: None,
+            });
+            encoder.finish()
+        };
+
+        valid(&ctx.device, || {
+            encode_pass(wgpu::LoadOp::Clear(wgpu::Color::BLACK), false);
+        });
+        fail(&ctx.device, || {
+            encode_pass(wgpu::LoadOp::Clear(wgpu::Color::BLACK), true);
+        });
+        fail(&ctx.device, || {
+            encode_pass(wgpu::LoadOp::Load, false);
+        });
+    });
+}
+
+#[test]
+fn transient_texture_clear() {
+    // Transient textures have no memory to clear outside of a render pass.
+    initialize_test(
+        TestParameters::default().features(wgpu::Features::CLEAR_TEXTURE),
+        |ctx| {
+            let texture = ctx.device.create_texture(&transient_descriptor(
+                wgpu::TextureFormat::Rgba8Unorm,
+                wgpu::TextureUsages::RENDER_ATTACHMENT | wgpu::TextureUsages::TRANSIENT_ATTACHMENT,
+            ));
+
+            let mut encoder = ctx
+                .device
+                .create_command_encoder(&wgpu::CommandEncoderDescriptor::default());
+            fail(&ctx.device, || {
+                encoder.clear_texture(&texture, &wgpu::ImageSubresourceRange::default());
+                encoder.finish()
+            });
+        },
+    );
+}
@@ -0,0 +1,192 @@
+use wasm_bindgen_test::*;
+use wgpu::util::DeviceExt;
+
+use wgpu_test::{fail, initialize_test, TestParameters, TestingContext};
+
+const SIZE: u32 = 4;
+
+fn create_pipeline(ctx: &TestingContext, source: &str) -> wgpu::RenderPipeline {
+    let shader = ctx
+        .device
+        .create_shader_module(wgpu::ShaderModuleDescriptor {
+            label: None,
+            source: wgpu::ShaderSource::Wgsl(source.into()),
+        });
+
+    ctx.device
+        .create_render_pipeline(&wgpu::RenderPipelineDescriptor {
+            label: None,
+            layout: None,
+            vertex: wgpu::VertexState {
+                buffers: &[wgpu::VertexBufferLayout {
+                    array_stride: 16,
+                    step_mode: wgpu::VertexStepMode::Vertex,
+                    attributes: &wgpu::vertex_attr_array![0 => Float64x2],
+                }],
+                entry_point: "vs_main",
+                constants: &Default::default(),
+                module: &shader,
+            },
+            primitive: wgpu::PrimitiveState::default(),
+            depth_stencil: None,
+            multisample: wgpu::MultisampleState::default(),
+            fragment: Some(wgpu::FragmentState {
+                entry_point: "fs_main",
+                constants: &Default::default(),
+                module: &shader,
+                targets: &[Some(wgpu::TextureFormat::Rgba8Unorm.into())],
+            }),
+            multiview: None,
+        })
+}
+
+// Runs on every backend, including GL, which doesn't support the feature.
+#[test]
+#[wasm_bindgen_test]
+fn float64_vertex_attribute_requires_feature() {
+    initialize_test(TestParameters::default(), |ctx| {
+        fail(&ctx.device, || {
+            create_pipeline(
+                &ctx,
+                r#"
+@vertex
+fn vs_main(@location(0) position: vec2<f32>) -> @builtin(position) vec4<f32> {
+    return vec4<f32>(position, 0.0, 1.0);
+}
+
+@fragment
+fn fs_main() -> @location(0) vec4<f32> {
+    return vec4<f32>(1.0);
+}
+"#,
+            )
+        });
+    })
+}
+
+#[test]
+#[wasm_bindgen_test]
+fn float64_vertex_attribute_requires_f64_input() {
+    initialize_test(
+        TestParameters::default().features(wgpu::Features::VERTEX_ATTRIBUTE_64BIT),
+        |ctx| {
+            fail(&ctx.device, || {
+                create_pipeline(
+                    &ctx,
+                    r#"
+@vertex
+fn vs_main(@location(0) position: vec2<f32>) -> @builtin(position) vec4<f32> {
+    return vec4<f32>(position, 0.0, 1.0);
+}
+
+@fragment
+fn fs_main() -> @location(0) vec4<f32> {
+    return vec4<f32>(1.0);
+}
+"#,
+                )
+            });
+        },
+    )
+}
+
+#[test]
+#[wasm_bindgen_test]
+fn float64_vertex_attribute() {
+    initialize_test(
+        TestParameters::default()
+            .features(wgpu::Features::VERTEX_ATTRIBUTE_64BIT | wgpu::Features::SHADER_F64),
+        |ctx| {
+            let pipeline = create_pipeline(
+                &ctx,
+                r#"
+@vertex
+fn vs_main(@location(0) position: vec2<f64>) -> @builtin(position) vec4<f32> {
+    return vec4<f32>(vec2<f32>(position), 0.0, 1.0);
+}
+
+@fragment
+fn fs_main() -> @location(0) vec4<f32> {
+    return vec4<f32>(1.0);
+}
+"#,
+            );
+
+            // A triangle covering the whole target.
+            let positions: [f64; 6] = [-1.0, -1.0, 3.0, -1.0, -1.0, 3.0];
+            let vertex_buffer = ctx
+                .device
+                .create_buffer_init(&wgpu::util::BufferInitDescriptor {
+                    label: None,
+                    contents: bytemuck::cast_slice(&positions),
+                    usage: wgpu::BufferUsages::VERTEX,
+                });
+
+            let texture = ctx.device.create_texture(&wgpu::TextureDescriptor {
+                label: None,
+                size: wgpu::Extent3d {
+                    width: SIZE,
+                    height: SIZE,
+                    depth_or_array_layers: 1,
+                },
+                mip_level_count: 1,
+                sample_count: 1,
+                dimension: wgpu::TextureDimension::D2,
+                format: wgpu::TextureFormat::Rgba8Unorm,
+                usage: wgpu::TextureUsages::RENDER_ATTACHMENT | wgpu::TextureUsages::COPY_SRC,
+                view_formats: &[],
+            });
+            let view = texture.create_view(&wgpu::TextureViewDescriptor::default());
+
+            let readback = ctx.device.create_buffer(&wgpu::BufferDescriptor {
+                label: None,
+                size: (wgpu::COPY_BYTES_PER_ROW_ALIGNMENT * SIZE) as u64,
+                usage: wgpu::BufferUsages::COPY_DST | wgpu::BufferUsages::MAP_READ,
+                mapped_at_creation: false,
+                memory_hints: wgpu::MemoryHints::Default,
+            });
+
+            let mut encoder = ctx
+                .device
+                .create_command_encoder(&wgpu::CommandEncoderDescriptor::default());
+            let mut rpass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
+                label: None,
+                color_attachments: &[Some(wgpu::RenderPassColorAttachment {
+                    view: &view,
+                    resolve_target: None,
+                    ops: wgpu::Operations {
+                        load: wgpu::LoadOp::Clear(wgpu::Color::TRANSPARENT),
+                        store: true,
+                    },
+                })],
+                depth_stencil_attachment: None,
+            });
+            rpass.set_pipeline(&pipeline);
+            rpass.set_vertex_buffer(0, vertex_buffer.slice(..));
+            rpass.draw(0..3, 0..1);
+            drop(rpass);
+            encoder.copy_texture_to_buffer(
+                texture.as_image_copy(),
+                wgpu::ImageCopyBuffer {
+                    buffer: &readback,
+                    layout: wgpu::ImageDataLayout {
+                        offset: 0,
+                        bytes_per_row: Some(wgpu::COPY_BYTES_PER_ROW_ALIGNMENT),
+                        rows_per_image: None,
+                    },
+                },
+                texture.size(),
+            );
+            ctx.queue.submit(Some(encoder.finish()));
+
+            let slice = readback.slice(..);
+            slice.map_async(wgpu::MapMode::Read, |_| ());
+            ctx.device.poll(wgpu::Maintain::Wait);
+            let data = slice.get_mapped_range();
+
+            for row in data.chunks_exact(wgpu::COPY_BYTES_PER_ROW_ALIGNMENT as usize) {
+                assert!(row[..SIZE as usize * 4].iter().all(|&byte| byte == 0xFF));
+            }
+        },
+    )
+}
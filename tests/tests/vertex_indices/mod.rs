@@ -37,6 +37,7 @@ fn pulling_common(
             | wgpu::BufferUsages::STORAGE
             | wgpu::BufferUsages::MAP_READ,
         mapped_at_creation: false,
+        memory_hints: wgpu::MemoryHints::Default,
     });
 
     let bg = ctx.device.create_bind_group(&wgpu::BindGroupDescriptor {
@@ -64,6 +65,7 @@ fn pulling_common(
             vertex: wgpu::VertexState {
                 buffers: &[],
                 entry_point: "vs_main",
+                constants: &Default::default(),
                 module: &shader,
             },
             primitive: wgpu::PrimitiveState::default(),
@@ -71,6 +73,7 @@ fn pulling_common(
             multisample: wgpu::MultisampleState::default(),
             fragment: Some(wgpu::FragmentState {
                 entry_point: "fs_main",
+                constants: &Default::default(),
                 module: &shader,
                 targets: &[Some(wgpu::ColorTargetState {
                     format: wgpu::TextureFormat::Rgba8Unorm,
@@ -0,0 +1,70 @@
+use wasm_bindgen_test::*;
+use wgpu::util::WgslComposer;
+use wgpu_test::{fail, initialize_test, valid, TestParameters};
+
+const COMMON: &str = r#"
+#ifdef BROKEN
+fn value() -> u32 { let x: u32 = 1.0; return x; }
+#else
+fn value() -> u32 { return 1u; }
+#endif
+"#;
+
+const MAIN: &str = r#"#import common
+
+@compute @workgroup_size(1)
+fn main() { let x = value(); }
+"#;
+
+fn composer() -> WgslComposer {
+    let mut composer = WgslComposer::new();
+    composer.add_module("common", COMMON);
+    composer
+}
+
+#[test]
+#[wasm_bindgen_test]
+fn wgsl_composer_valid() {
+    initialize_test(TestParameters::default(), |ctx| {
+        let composed = composer().compose("main", MAIN).unwrap();
+        valid(&ctx.device, || {
+            ctx.device
+                .create_shader_module(wgpu::ShaderModuleDescriptor {
+                    label: None,
+                    source: composed.shader_source(),
+                })
+        });
+    });
+}
+
+#[test]
+#[wasm_bindgen_test]
+fn wgsl_composer_error_location() {
+    initialize_test(TestParameters::default(), |ctx| {
+        let mut composer = composer();
+        composer.define("BROKEN", "1");
+        let composed = composer.compose("main", MAIN).unwrap();
+        let module = fail(&ctx.device, || {
+            ctx.device
+                .create_shader_module(wgpu::ShaderModuleDescriptor {
+                    label: None,
+                    source: composed.shader_source(),
+                })
+        });
+
+        let info = pollster::block_on(module.get_compilation_info());
+        let error = info
+            .messages
+            .iter()
+            .find(|message| message.message_type == wgpu::CompilationMessageType::Error)
+            .expect("expected an error message");
+        let location = composed
+            .locate(error.location.expect("expected a source location"))
+            .expect("expected a location in a module");
+        // The error is on the third line of `common`.
+        assert_eq!(location.file, "common");
+        assert_eq!(location.line_number, 3);
+        // The offset is in `common` too.
+        assert_eq!(COMMON[..location.offset as usize].matches('\n').count(), 2);
+    });
+}
@@ -55,6 +55,7 @@ fn write_texture_subset_2d() {
             size: (size * size) as u64,
             usage: wgpu::BufferUsages::MAP_READ | wgpu::BufferUsages::COPY_DST,
             mapped_at_creation: false,
+            memory_hints: wgpu::MemoryHints::Default,
         });
 
         let mut encoder = ctx
@@ -151,6 +152,7 @@ fn write_texture_subset_3d() {
             size: (size * size * depth) as u64,
             usage: wgpu::BufferUsages::MAP_READ | wgpu::BufferUsages::COPY_DST,
             mapped_at_creation: false,
+            memory_hints: wgpu::MemoryHints::Default,
         });
 
         let mut encoder = ctx
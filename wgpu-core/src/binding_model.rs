@@ -1,8 +1,11 @@
 use crate::{
     device::{DeviceError, MissingDownlevelFlags, MissingFeatures, SHADER_STAGE_COUNT},
-    error::{ErrorFormatter, PrettyError},
+    error::{ErrorFormatter, ErrorType, PrettyError},
     hal_api::HalApi,
-    id::{BindGroupLayoutId, BufferId, DeviceId, SamplerId, TextureId, TextureViewId, Valid},
+    id::{
+        BindGroupId, BindGroupLayoutId, BufferId, DeviceId, SamplerId, TextureId, TextureViewId,
+        Valid,
+    },
     init_tracker::{BufferInitTrackerAction, TextureInitTrackerAction},
     resource::Resource,
     track::{BindGroupStates, UsageConflict},
@@ -17,7 +20,7 @@ use serde::Deserialize;
 #[cfg(feature = "trace")]
 use serde::Serialize;
 
-use std::{borrow::Cow, ops::Range};
+use std::{borrow::Cow, collections::BTreeMap, ops::Range, sync::Arc};
 
 use thiserror::Error;
 
@@ -59,6 +62,20 @@ pub enum CreateBindGroupLayoutError {
     InvalidVisibility(wgt::ShaderStages),
 }
 
+impl CreateBindGroupLayoutError {
+    /// Returns the [`ErrorType`] this error is reported as.
+    pub fn error_type(&self) -> ErrorType {
+        match *self {
+            Self::Device(ref err) => err.error_type(),
+            Self::ConflictBinding(..)
+            | Self::Entry { .. }
+            | Self::TooManyBindings(..)
+            | Self::InvalidBindingIndex { .. }
+            | Self::InvalidVisibility(..) => ErrorType::Validation,
+        }
+    }
+}
+
 //TODO: refactor this to move out `enum BindingError`.
 
 #[derive(Clone, Debug, Error)]
@@ -177,6 +194,110 @@ pub enum CreateBindGroupError {
     ResourceUsageConflict(#[from] UsageConflict),
 }
 
+impl CreateBindGroupError {
+    /// Returns the [`ErrorType`] this error is reported as.
+    pub fn error_type(&self) -> ErrorType {
+        match *self {
+            Self::Device(ref err) => err.error_type(),
+            Self::InvalidLayout
+            | Self::InvalidBuffer(..)
+            | Self::InvalidTextureView(..)
+            | Self::InvalidTexture(..)
+            | Self::InvalidSampler(..)
+            | Self::BindingArrayPartialLengthMismatch { .. }
+            | Self::BindingArrayLengthMismatch { .. }
+            | Self::BindingArrayZeroLength
+            | Self::BindingRangeTooLarge { .. }
+            | Self::BindingSizeTooSmall { .. }
+            | Self::BindingZeroSize(..)
+            | Self::BindingsNumMismatch { .. }
+            | Self::DuplicateBinding(..)
+            | Self::MissingBindingDeclaration(..)
+            | Self::MissingBufferUsage(..)
+            | Self::MissingTextureUsage(..)
+            | Self::SingleBindingExpected
+            | Self::UnalignedBufferOffset(..)
+            | Self::BufferRangeTooLarge { .. }
+            | Self::WrongBindingType { .. }
+            | Self::InvalidTextureMultisample { .. }
+            | Self::InvalidTextureSampleType { .. }
+            | Self::InvalidTextureDimension { .. }
+            | Self::InvalidStorageTextureFormat { .. }
+            | Self::InvalidStorageTextureMipLevelCount { .. }
+            | Self::WrongSamplerComparison { .. }
+            | Self::WrongSamplerFiltering { .. }
+            | Self::DepthStencilAspect
+            | Self::StorageReadNotSupported(..)
+            | Self::ResourceUsageConflict(..) => ErrorType::Validation,
+        }
+    }
+}
+
+#[derive(Clone, Debug, Error)]
+#[non_exhaustive]
+pub enum UpdateBindGroupError {
+    #[error(transparent)]
+    Device(#[from] DeviceError),
+    #[error("Bind group {0:?} is invalid")]
+    InvalidBindGroup(BindGroupId),
+    #[error("Bind group {0:?} belongs to another device")]
+    WrongDevice(BindGroupId),
+    #[error("Bind group {0:?} is used by a submission which hasn't completed yet")]
+    UsedBySubmission(BindGroupId),
+    #[error(
+        "Bind group {0:?} is used by a command buffer or render bundle which wasn't submitted yet"
+    )]
+    UsedByRecording(BindGroupId),
+    #[error("The layout of the bind group doesn't allow updates after bind, which requires the feature UPDATE_AFTER_BIND_DESCRIPTORS")]
+    NotUpdatable,
+    #[error("Unable to find a corresponding declaration for the given binding {0}")]
+    MissingBindingDeclaration(u32),
+    #[error("Binding {0} is not a binding array of textures, samplers or storage buffers, and can't be updated")]
+    BindingNotUpdatable(u32),
+    #[error("Elements {range:?} of binding {binding} are out of bounds of the {count} elements of the array")]
+    ArrayOutOfBounds {
+        binding: u32,
+        range: Range<u32>,
+        count: u32,
+    },
+    #[error(transparent)]
+    InvalidResource(#[from] CreateBindGroupError),
+}
+
+impl UpdateBindGroupError {
+    /// Returns the [`ErrorType`] this error is reported as.
+    pub fn error_type(&self) -> ErrorType {
+        match *self {
+            Self::Device(ref err) => err.error_type(),
+            Self::InvalidBindGroup(..)
+            | Self::WrongDevice(..)
+            | Self::UsedBySubmission(..)
+            | Self::UsedByRecording(..)
+            | Self::NotUpdatable
+            | Self::MissingBindingDeclaration(..)
+            | Self::BindingNotUpdatable(..)
+            | Self::ArrayOutOfBounds { .. }
+            | Self::InvalidResource(..) => ErrorType::Validation,
+        }
+    }
+}
+
+impl PrettyError for UpdateBindGroupError {
+    fn fmt_pretty(&self, fmt: &mut ErrorFormatter) {
+        match *self {
+            Self::InvalidResource(ref error) => error.fmt_pretty(fmt),
+            Self::InvalidBindGroup(id)
+            | Self::WrongDevice(id)
+            | Self::UsedBySubmission(id)
+            | Self::UsedByRecording(id) => {
+                fmt.error(self);
+                fmt.bind_group_label(&id);
+            }
+            _ => fmt.error(self),
+        }
+    }
+}
+
 impl PrettyError for CreateBindGroupError {
     fn fmt_pretty(&self, fmt: &mut ErrorFormatter) {
         fmt.error(self);
@@ -421,6 +542,21 @@ pub struct BindGroupDescriptor<'a> {
     pub entries: Cow<'a, [BindGroupEntry<'a>]>,
 }
 
+/// Elements of a binding array to replace in a bind group, with
+/// [`Global::queue_update_bind_group`](crate::global::Global::queue_update_bind_group).
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "trace", derive(Serialize))]
+#[cfg_attr(feature = "replay", derive(Deserialize))]
+pub struct BindGroupUpdateEntry<'a> {
+    /// Slot of the binding array in the [`BindGroupLayoutDescriptor`].
+    pub binding: u32,
+    /// Index of the first replaced element of the array.
+    pub array_offset: u32,
+    /// Resources of the replaced elements, either a single resource or an
+    /// array of them.
+    pub resource: BindingResource<'a>,
+}
+
 /// Describes a [`BindGroupLayout`].
 #[derive(Clone, Debug)]
 #[cfg_attr(feature = "trace", derive(serde::Serialize))]
@@ -436,6 +572,19 @@ pub struct BindGroupLayoutDescriptor<'a> {
 
 pub(crate) type BindEntryMap = FastHashMap<u32, wgt::BindGroupLayoutEntry>;
 
+/// Returns true if the elements of the binding may be replaced after bind,
+/// when its layout allows it.
+pub(crate) fn is_updatable_after_bind(entry: &wgt::BindGroupLayoutEntry) -> bool {
+    entry.count.is_some()
+        && !matches!(
+            entry.ty,
+            wgt::BindingType::Buffer {
+                ty: wgt::BufferBindingType::Uniform,
+                ..
+            }
+        )
+}
+
 /// Bind group layout.
 ///
 /// The lifetime of BGLs is a bit special. They are only referenced on CPU
@@ -453,6 +602,9 @@ pub struct BindGroupLayout<A: hal::Api> {
     #[allow(unused)]
     pub(crate) dynamic_count: usize,
     pub(crate) count_validator: BindingTypeMaxCountValidator,
+    /// Whether the elements of binding arrays of the bind groups may be
+    /// replaced after bind.
+    pub(crate) update_after_bind: bool,
     #[cfg(debug_assertions)]
     pub(crate) label: String,
 }
@@ -504,6 +656,22 @@ pub enum CreatePipelineLayoutError {
     TooManyGroups { actual: usize, max: usize },
 }
 
+impl CreatePipelineLayoutError {
+    /// Returns the [`ErrorType`] this error is reported as.
+    pub fn error_type(&self) -> ErrorType {
+        match *self {
+            Self::Device(ref err) => err.error_type(),
+            Self::InvalidBindGroupLayout(..)
+            | Self::MisalignedPushConstantRange { .. }
+            | Self::MissingFeatures(..)
+            | Self::MoreThanOnePushConstantRangePerStage { .. }
+            | Self::PushConstantRangeTooLarge { .. }
+            | Self::TooManyBindings(..)
+            | Self::TooManyGroups { .. } => ErrorType::Validation,
+        }
+    }
+}
+
 impl PrettyError for CreatePipelineLayoutError {
     fn fmt_pretty(&self, fmt: &mut ErrorFormatter) {
         fmt.error(self);
@@ -570,7 +738,8 @@ pub struct PipelineLayoutDescriptor<'a> {
 
 #[derive(Debug)]
 pub struct PipelineLayout<A: hal::Api> {
-    pub(crate) raw: A::PipelineLayout,
+    /// Shared with the pipeline compilations using this layout.
+    pub(crate) raw: Arc<A::PipelineLayout>,
     pub(crate) device_id: Stored<DeviceId>,
     pub(crate) life_guard: LifeGuard,
     pub(crate) bind_group_layout_ids: ArrayVec<Valid<BindGroupLayoutId>, { hal::MAX_BIND_GROUPS }>,
@@ -764,6 +933,19 @@ pub(crate) fn buffer_binding_type_alignment(
     }
 }
 
+/// Resource bound to one element of a binding.
+#[derive(Clone, Debug)]
+pub(crate) enum BoundResource {
+    Buffer(BufferBinding),
+    Sampler(SamplerId),
+    TextureView(TextureViewId),
+}
+
+/// Resources of the bindings of a bind group, by binding and array element.
+///
+/// Elements of partially bound arrays are `None`.
+pub(crate) type BoundResources = BTreeMap<u32, Vec<Option<BoundResource>>>;
+
 pub struct BindGroup<A: HalApi> {
     pub(crate) raw: A::BindGroup,
     pub(crate) device_id: Stored<DeviceId>,
@@ -776,6 +958,11 @@ pub struct BindGroup<A: HalApi> {
     /// Actual binding sizes for buffers that don't have `min_binding_size`
     /// specified in BGL. Listed in the order of iteration of `BGL.entries`.
     pub(crate) late_buffer_binding_sizes: Vec<wgt::BufferSize>,
+    /// Resources of bind groups whose layout allows updates after bind, from
+    /// which `used` and the other usage lists are rebuilt on updates.
+    ///
+    /// `None` for the other bind groups, whose resources never change.
+    pub(crate) bound_resources: Option<BoundResources>,
 }
 
 impl<A: HalApi> BindGroup<A> {
@@ -89,7 +89,7 @@ use crate::{
         AttachmentData, Device, DeviceError, MissingDownlevelFlags,
         RenderPassCompatibilityCheckType, RenderPassContext, SHADER_STAGE_COUNT,
     },
-    error::{ErrorFormatter, PrettyError},
+    error::{ErrorFormatter, ErrorType, PrettyError},
     hal_api::HalApi,
     hub::{Hub, Token},
     id,
@@ -510,7 +510,7 @@ impl RenderBundleEncoder {
                     index_count,
                     instance_count,
                     first_index,
-                    base_vertex: _,
+                    base_vertex,
                     first_instance,
                 } => {
                     let scope = PassErrorScope::Draw {
@@ -518,6 +518,15 @@ impl RenderBundleEncoder {
                         indirect: false,
                         pipeline: state.pipeline_id(),
                     };
+                    if base_vertex < 0 {
+                        device
+                            .require_downlevel_flags(wgt::DownlevelFlags::BASE_VERTEX)
+                            .map_pass_err(scope)?;
+                    } else if base_vertex > 0 {
+                        device
+                            .require_downlevel_flags(wgt::DownlevelFlags::NON_NEGATIVE_BASE_VERTEX)
+                            .map_pass_err(scope)?;
+                    }
                     let pipeline = state.pipeline(scope)?;
                     let used_bind_groups = pipeline.used_bind_groups;
                     let index = match state.index {
@@ -604,6 +613,12 @@ impl RenderBundleEncoder {
 
                     let pipeline = state.pipeline(scope)?;
                     let used_bind_groups = pipeline.used_bind_groups;
+                    // The emulation can't apply the base vertex it reads back.
+                    if pipeline.emulated_polygon_mode_line {
+                        device
+                            .require_downlevel_flags(wgt::DownlevelFlags::BASE_VERTEX)
+                            .map_pass_err(scope)?;
+                    }
 
                     let buffer: &resource::Buffer<A> = state
                         .trackers
@@ -1111,6 +1126,9 @@ struct PipelineState {
 
     /// The number of bind groups this pipeline uses.
     used_bind_groups: usize,
+
+    /// Whether triangles are drawn as lines through an emulation.
+    emulated_polygon_mode_line: bool,
 }
 
 impl PipelineState {
@@ -1125,6 +1143,9 @@ impl PipelineState {
             steps: pipeline.vertex_steps.to_vec(),
             push_constant_ranges: layout.push_constant_ranges.iter().cloned().collect(),
             used_bind_groups: layout.bind_group_layout_ids.len(),
+            emulated_polygon_mode_line: pipeline
+                .flags
+                .contains(PipelineFlags::EMULATED_POLYGON_MODE_LINE),
         }
     }
 
@@ -1431,6 +1452,17 @@ impl RenderBundleError {
         scope: PassErrorScope::Bundle,
         inner: RenderBundleErrorInner::Device(DeviceError::Invalid),
     };
+
+    /// Returns the [`ErrorType`] this error is reported as.
+    pub fn error_type(&self) -> ErrorType {
+        match self.inner {
+            RenderBundleErrorInner::Device(ref err) => err.error_type(),
+            RenderBundleErrorInner::NotValidToUse
+            | RenderBundleErrorInner::RenderCommand(..)
+            | RenderBundleErrorInner::Draw(..)
+            | RenderBundleErrorInner::MissingDownlevelFlags(..) => ErrorType::Validation,
+        }
+    }
 }
 impl PrettyError for RenderBundleError {
     fn fmt_pretty(&self, fmt: &mut ErrorFormatter) {
@@ -99,6 +99,12 @@ pub struct PassChannel<V> {
 }
 
 impl<V> PassChannel<V> {
+    /// Whether these operations are allowed on a transient attachment, whose
+    /// contents must neither be loaded nor stored.
+    fn is_transient_compatible(&self) -> bool {
+        self.load_op == LoadOp::Clear && self.store_op == StoreOp::Discard
+    }
+
     fn hal_ops(&self) -> hal::AttachmentOps {
         let mut ops = hal::AttachmentOps::empty();
         match self.load_op {
@@ -529,6 +535,10 @@ pub enum RenderPassErrorInner {
         src: wgt::TextureFormat,
         dst: wgt::TextureFormat,
     },
+    #[error("The {location} is transient and must be cleared on load and discarded on store")]
+    InvalidTransientAttachmentOps { location: AttachmentErrorLocation },
+    #[error("The {location} is transient and can't be used as a resolve target")]
+    TransientResolveTarget { location: AttachmentErrorLocation },
     #[error("Surface texture is dropped before the render pass is finished")]
     SurfaceTextureDropped,
     #[error("Not enough memory left")]
@@ -806,6 +816,17 @@ impl<'a, A: HalApi> RenderPassInfo<'a, A> {
             Ok(())
         };
 
+        let is_transient = |view: &TextureView<A>| {
+            texture_guard
+                .get(view.parent_id.value.0)
+                .map_or(false, |texture| {
+                    texture
+                        .desc
+                        .usage
+                        .contains(TextureUsages::TRANSIENT_ATTACHMENT)
+                })
+        };
+
         let mut colors =
             ArrayVec::<Option<hal::ColorAttachment<A>>, { hal::MAX_COLOR_ATTACHMENTS }>::new();
         let mut depth_stencil = None;
@@ -826,6 +847,17 @@ impl<'a, A: HalApi> RenderPassInfo<'a, A> {
                 ));
             }
 
+            if is_transient(view)
+                && ((ds_aspects.contains(hal::FormatAspects::DEPTH)
+                    && !at.depth.is_transient_compatible())
+                    || (ds_aspects.contains(hal::FormatAspects::STENCIL)
+                        && !at.stencil.is_transient_compatible()))
+            {
+                return Err(RenderPassErrorInner::InvalidTransientAttachmentOps {
+                    location: AttachmentErrorLocation::Depth,
+                });
+            }
+
             if !ds_aspects.contains(hal::FormatAspects::STENCIL)
                 || (at.stencil.load_op == at.depth.load_op
                     && at.stencil.store_op == at.depth.store_op)
@@ -967,6 +999,15 @@ impl<'a, A: HalApi> RenderPassInfo<'a, A> {
                 ));
             }
 
+            if is_transient(color_view) && !at.channel.is_transient_compatible() {
+                return Err(RenderPassErrorInner::InvalidTransientAttachmentOps {
+                    location: AttachmentErrorLocation::Color {
+                        index,
+                        resolve: false,
+                    },
+                });
+            }
+
             Self::add_pass_texture_init_actions(
                 &at.channel,
                 &mut cmd_buf.texture_memory_actions,
@@ -1006,6 +1047,11 @@ impl<'a, A: HalApi> RenderPassInfo<'a, A> {
                         actual_extent: render_extent,
                     });
                 }
+                if is_transient(resolve_view) {
+                    return Err(RenderPassErrorInner::TransientResolveTarget {
+                        location: resolve_location,
+                    });
+                }
                 if color_view.samples == 1 || resolve_view.samples != 1 {
                     return Err(RenderPassErrorInner::InvalidResolveSampleCounts {
                         location: resolve_location,
@@ -1755,6 +1801,18 @@ impl<G: GlobalIdentityHandlerFactory> Global<G> {
                         };
                         state.is_ready(indexed).map_pass_err(scope)?;
 
+                        if base_vertex < 0 {
+                            device
+                                .require_downlevel_flags(wgt::DownlevelFlags::BASE_VERTEX)
+                                .map_pass_err(scope)?;
+                        } else if base_vertex > 0 {
+                            device
+                                .require_downlevel_flags(
+                                    wgt::DownlevelFlags::NON_NEGATIVE_BASE_VERTEX,
+                                )
+                                .map_pass_err(scope)?;
+                        }
+
                         //TODO: validate that base_vertex + max_index() is
                         // within the provided range
                         let last_index = first_index + index_count;
@@ -1810,9 +1868,25 @@ impl<G: GlobalIdentityHandlerFactory> Global<G> {
                                 .require_features(wgt::Features::MULTI_DRAW_INDIRECT)
                                 .map_pass_err(scope)?;
                         }
+                        // Without native support, every draw is issued on its own.
+                        let emulated = count.is_some()
+                            && device
+                                .downlevel
+                                .flags
+                                .contains(wgt::DownlevelFlags::EMULATED_MULTI_DRAW_INDIRECT);
                         device
                             .require_downlevel_flags(wgt::DownlevelFlags::INDIRECT_EXECUTION)
                             .map_pass_err(scope)?;
+                        // The emulation can't apply the base vertex it reads back.
+                        if indexed
+                            && state
+                                .pipeline_flags
+                                .contains(PipelineFlags::EMULATED_POLYGON_MODE_LINE)
+                        {
+                            device
+                                .require_downlevel_flags(wgt::DownlevelFlags::BASE_VERTEX)
+                                .map_pass_err(scope)?;
+                        }
 
                         let indirect_buffer: &Buffer<A> = info
                             .usage_scope
@@ -1848,13 +1922,25 @@ impl<G: GlobalIdentityHandlerFactory> Global<G> {
                             ),
                         );
 
-                        match indexed {
-                            false => unsafe {
-                                raw.draw_indirect(indirect_raw, offset, actual_count);
-                            },
-                            true => unsafe {
-                                raw.draw_indexed_indirect(indirect_raw, offset, actual_count);
-                            },
+                        let (draw_count, draws) = if emulated {
+                            (1, actual_count)
+                        } else {
+                            (actual_count, 1)
+                        };
+                        for draw in 0..draws as u64 {
+                            let draw_offset = offset + draw * stride as u64;
+                            match indexed {
+                                false => unsafe {
+                                    raw.draw_indirect(indirect_raw, draw_offset, draw_count);
+                                },
+                                true => unsafe {
+                                    raw.draw_indexed_indirect(
+                                        indirect_raw,
+                                        draw_offset,
+                                        draw_count,
+                                    );
+                                },
+                            }
                         }
                     }
                     RenderCommand::MultiDrawIndirectCount {
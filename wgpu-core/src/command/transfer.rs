@@ -356,7 +356,14 @@ pub(crate) fn validate_texture_copy_range(
         },
     )?;
     // physical size can be larger than the virtual
-    let extent = extent_virtual.physical_size(desc.format);
+    let mut extent = extent_virtual.physical_size(desc.format);
+
+    // chroma planes of multi-planar formats are subsampled
+    let (width_factor, height_factor) = desc
+        .format
+        .subsampling_factors(Some(texture_copy_view.aspect));
+    extent.width /= width_factor;
+    extent.height /= height_factor;
 
     if desc.format.is_depth_stencil_format() && *copy_size != extent {
         return Err(TransferError::InvalidDepthTextureExtent);
@@ -1066,13 +1073,20 @@ impl<G: GlobalIdentityHandlerFactory> Global<G> {
         let (src_range, src_tex_base) = extract_texture_selector(source, copy_size, src_texture)?;
         let (dst_range, dst_tex_base) =
             extract_texture_selector(destination, copy_size, dst_texture)?;
-        let src_texture_aspects = hal::FormatAspects::from(src_texture.desc.format);
-        let dst_texture_aspects = hal::FormatAspects::from(dst_texture.desc.format);
-        if src_tex_base.aspect != src_texture_aspects {
-            return Err(TransferError::CopySrcMissingAspects.into());
-        }
-        if dst_tex_base.aspect != dst_texture_aspects {
-            return Err(TransferError::CopyDstMissingAspects.into());
+        if src_texture.desc.format.is_multi_planar_format() {
+            // planes have different sizes, so they are copied one at a time
+            if !src_tex_base.aspect.is_one() || src_tex_base.aspect != dst_tex_base.aspect {
+                return Err(TransferError::CopyAspectNotOne.into());
+            }
+        } else {
+            let src_texture_aspects = hal::FormatAspects::from(src_texture.desc.format);
+            let dst_texture_aspects = hal::FormatAspects::from(dst_texture.desc.format);
+            if src_tex_base.aspect != src_texture_aspects {
+                return Err(TransferError::CopySrcMissingAspects.into());
+            }
+            if dst_tex_base.aspect != dst_texture_aspects {
+                return Err(TransferError::CopyDstMissingAspects.into());
+            }
         }
 
         // Handle texture init *before* dealing with barrier transitions so we
@@ -128,6 +128,10 @@ pub fn map_texture_usage(
         hal::TextureUses::DEPTH_STENCIL_READ | hal::TextureUses::DEPTH_STENCIL_WRITE,
         usage.contains(wgt::TextureUsages::RENDER_ATTACHMENT) && !is_color,
     );
+    u.set(
+        hal::TextureUses::TRANSIENT,
+        usage.contains(wgt::TextureUsages::TRANSIENT_ATTACHMENT),
+    );
     u
 }
 
@@ -153,6 +157,10 @@ pub fn map_texture_usage_from_hal(uses: hal::TextureUses) -> wgt::TextureUsages
         wgt::TextureUsages::RENDER_ATTACHMENT,
         uses.contains(hal::TextureUses::COLOR_TARGET),
     );
+    u.set(
+        wgt::TextureUsages::TRANSIENT_ATTACHMENT,
+        uses.contains(hal::TextureUses::TRANSIENT),
+    );
     u
 }
 
@@ -208,11 +216,24 @@ pub fn check_texture_dimension_size(
     Ok(())
 }
 
-pub fn bind_group_layout_flags(features: wgt::Features) -> hal::BindGroupLayoutFlags {
+pub fn bind_group_layout_flags(
+    features: wgt::Features,
+    entries: &crate::binding_model::BindEntryMap,
+) -> hal::BindGroupLayoutFlags {
     let mut flags = hal::BindGroupLayoutFlags::empty();
     flags.set(
         hal::BindGroupLayoutFlags::PARTIALLY_BOUND,
         features.contains(wgt::Features::PARTIALLY_BOUND_BINDING_ARRAY),
     );
+    // Update-after-bind layouts can't have dynamic offsets, so only the
+    // layouts which may benefit from it use it.
+    flags.set(
+        hal::BindGroupLayoutFlags::UPDATE_AFTER_BIND,
+        features.contains(wgt::Features::UPDATE_AFTER_BIND_DESCRIPTORS)
+            && entries
+                .values()
+                .any(crate::binding_model::is_updatable_after_bind)
+            && !entries.values().any(|entry| entry.ty.has_dynamic_offset()),
+    );
     flags
 }
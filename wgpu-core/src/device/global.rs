@@ -23,7 +23,7 @@ use smallvec::SmallVec;
 
 use wgt::{BufferAddress, TextureFormat};
 
-use std::{borrow::Cow, iter, mem, ops::Range, ptr};
+use std::{borrow::Cow, iter, mem, ops::Range, ptr, sync::Arc};
 
 use super::{BufferMapPendingClosure, ImplicitPipelineIds, InvalidDevice, UserClosures};
 
@@ -130,6 +130,31 @@ impl<G: GlobalIdentityHandlerFactory> Global<G> {
         Ok(device.downlevel.clone())
     }
 
+    pub fn device_memory_report<A: HalApi>(
+        &self,
+        device_id: DeviceId,
+    ) -> Result<wgt::MemoryReport, InvalidDevice> {
+        let hub = A::hub(self);
+        let mut token = Token::root();
+        let (device_guard, mut token) = hub.devices.read(&mut token);
+        let device = device_guard.get(device_id).map_err(|_| InvalidDevice)?;
+
+        let hal::MemoryReport {
+            heaps,
+            buffer_bytes,
+            texture_bytes,
+        } = unsafe { device.raw.memory_report() };
+        let staging_bytes = device.pending_writes.staging_bytes
+            + device.lock_life(&mut token).staging_bytes_in_flight();
+
+        Ok(wgt::MemoryReport {
+            heaps,
+            buffer_bytes,
+            texture_bytes,
+            staging_bytes,
+        })
+    }
+
     pub fn device_create_buffer<A: HalApi>(
         &self,
         device_id: DeviceId,
@@ -166,7 +191,7 @@ impl<G: GlobalIdentityHandlerFactory> Global<G> {
                     .add(trace::Action::CreateBuffer(fid.id(), desc));
             }
 
-            let mut buffer = match device.create_buffer(device_id, desc, false) {
+            let mut buffer = match device.create_buffer(device_id, desc) {
                 Ok(buffer) => buffer,
                 Err(e) => break e,
             };
@@ -180,7 +205,7 @@ impl<G: GlobalIdentityHandlerFactory> Global<G> {
                 let ptr = if map_size == 0 {
                     std::ptr::NonNull::dangling()
                 } else {
-                    match map_buffer(&device.raw, &mut buffer, 0, map_size, HostMap::Write) {
+                    match map_buffer(&*device.raw, &mut buffer, 0, map_size, HostMap::Write) {
                         Ok(ptr) => ptr,
                         Err(e) => {
                             let raw = buffer.raw.unwrap();
@@ -207,8 +232,9 @@ impl<G: GlobalIdentityHandlerFactory> Global<G> {
                     size: desc.size,
                     usage: wgt::BufferUsages::MAP_WRITE | wgt::BufferUsages::COPY_SRC,
                     mapped_at_creation: false,
+                    memory_hints: wgt::MemoryHints::Transient,
                 };
-                let mut stage = match device.create_buffer(device_id, &stage_desc, true) {
+                let mut stage = match device.create_buffer(device_id, &stage_desc) {
                     Ok(stage) => stage,
                     Err(e) => {
                         let raw = buffer.raw.unwrap();
@@ -1111,6 +1137,22 @@ impl<G: GlobalIdentityHandlerFactory> Global<G> {
         A::hub(self).bind_group_layouts.label_for_resource(id)
     }
 
+    /// Returns the entries of a bind group layout, sorted by binding.
+    ///
+    /// Returns `None` if the layout is invalid.
+    pub fn bind_group_layout_entries<A: HalApi>(
+        &self,
+        bind_group_layout_id: id::BindGroupLayoutId,
+    ) -> Option<Vec<wgt::BindGroupLayoutEntry>> {
+        let hub = A::hub(self);
+        let mut token = Token::root();
+        let (bind_group_layout_guard, _) = hub.bind_group_layouts.read(&mut token);
+        let layout = bind_group_layout_guard.get(bind_group_layout_id).ok()?;
+        let mut entries = layout.entries.values().copied().collect::<Vec<_>>();
+        entries.sort_by_key(|entry| entry.binding);
+        Some(entries)
+    }
+
     pub fn bind_group_layout_drop<A: HalApi>(&self, bind_group_layout_id: id::BindGroupLayoutId) {
         profiling::scope!("BindGroupLayout::drop");
         log::debug!("bind group layout {:?} is dropped", bind_group_layout_id);
@@ -1185,6 +1227,32 @@ impl<G: GlobalIdentityHandlerFactory> Global<G> {
         A::hub(self).pipeline_layouts.label_for_resource(id)
     }
 
+    /// Get the IDs of the bind group layouts of a pipeline layout. Each ID
+    /// adds a refcount, which needs to be released by calling
+    /// `bind_group_layout_drop`.
+    ///
+    /// Returns `None` if the pipeline layout is invalid.
+    pub fn pipeline_layout_get_bind_group_layouts<A: HalApi>(
+        &self,
+        pipeline_layout_id: id::PipelineLayoutId,
+    ) -> Option<Vec<id::BindGroupLayoutId>> {
+        let hub = A::hub(self);
+        let mut token = Token::root();
+        let (pipeline_layout_guard, mut token) = hub.pipeline_layouts.read(&mut token);
+        let (bind_group_layout_guard, _) = hub.bind_group_layouts.read(&mut token);
+        let layout = pipeline_layout_guard.get(pipeline_layout_id).ok()?;
+        Some(
+            layout
+                .bind_group_layout_ids
+                .iter()
+                .map(|&id| {
+                    bind_group_layout_guard[id].multi_ref_count.inc();
+                    id.0
+                })
+                .collect(),
+        )
+    }
+
     pub fn pipeline_layout_drop<A: HalApi>(&self, pipeline_layout_id: id::PipelineLayoutId) {
         profiling::scope!("PipelineLayout::drop");
         log::debug!("pipeline layout {:?} is dropped", pipeline_layout_id);
@@ -1335,6 +1403,24 @@ impl<G: GlobalIdentityHandlerFactory> Global<G> {
                     pipeline::ShaderModuleSource::Wgsl(ref code) => {
                         trace.make_binary("wgsl", code.as_bytes())
                     }
+                    #[cfg(feature = "glsl")]
+                    pipeline::ShaderModuleSource::Glsl(ref code, ref options) => {
+                        let kind = match options.stage {
+                            naga::ShaderStage::Vertex => "vert",
+                            naga::ShaderStage::Fragment => "frag",
+                            naga::ShaderStage::Compute => "comp",
+                        };
+                        let code = trace::glsl_with_defines(code, &options.defines);
+                        trace.make_binary(kind, code.as_bytes())
+                    }
+                    #[cfg(feature = "spirv")]
+                    pipeline::ShaderModuleSource::SpirV(ref code, _) => {
+                        let bytes = code
+                            .iter()
+                            .flat_map(|word| word.to_le_bytes())
+                            .collect::<Vec<_>>();
+                        trace.make_binary("spv", &bytes)
+                    }
                     pipeline::ShaderModuleSource::Naga(ref module) => {
                         let string =
                             ron::ser::to_string_pretty(module, ron::ser::PrettyConfig::default())
@@ -1418,10 +1504,105 @@ impl<G: GlobalIdentityHandlerFactory> Global<G> {
         (id, Some(error))
     }
 
+    // Unsafe-ness of internal calls has little to do with unsafe-ness of this.
+    #[allow(unused_unsafe)]
+    /// # Safety
+    ///
+    /// This function passes the backend-native shader to the backend as-is and
+    /// can potentially result in a driver crash.
+    pub unsafe fn device_create_shader_module_passthrough<A: HalApi>(
+        &self,
+        device_id: DeviceId,
+        desc: &pipeline::ShaderModuleDescriptorPassthrough,
+        id_in: Input<G, id::ShaderModuleId>,
+    ) -> (
+        id::ShaderModuleId,
+        Option<pipeline::CreateShaderModuleError>,
+    ) {
+        profiling::scope!("Device::create_shader_module_passthrough");
+
+        let hub = A::hub(self);
+        let mut token = Token::root();
+        let fid = hub.shader_modules.prepare(id_in);
+
+        let (device_guard, mut token) = hub.devices.read(&mut token);
+        let error = loop {
+            let device = match device_guard.get(device_id) {
+                Ok(device) => device,
+                Err(_) => break DeviceError::Invalid.into(),
+            };
+            #[cfg(feature = "trace")]
+            if let Some(ref trace) = device.trace {
+                let mut trace = trace.lock();
+                let data = match A::VARIANT {
+                    wgt::Backend::Vulkan => desc.spirv.as_ref().map(|spv| {
+                        trace.make_binary("spv", unsafe {
+                            std::slice::from_raw_parts(spv.as_ptr() as *const u8, spv.len() * 4)
+                        })
+                    }),
+                    wgt::Backend::Metal => desc
+                        .msl
+                        .as_ref()
+                        .map(|msl| trace.make_binary("metal", msl.as_bytes())),
+                    wgt::Backend::Dx12 => match (desc.dxil.as_ref(), desc.hlsl.as_ref()) {
+                        (Some(dxil), _) => Some(trace.make_binary("dxil", dxil)),
+                        (None, Some(hlsl)) => Some(trace.make_binary("hlsl", hlsl.as_bytes())),
+                        (None, None) => None,
+                    },
+                    wgt::Backend::Gl => desc
+                        .glsl
+                        .as_ref()
+                        .map(|glsl| trace.make_binary("glsl", glsl.as_bytes())),
+                    _ => None,
+                };
+                if let Some(data) = data {
+                    trace.add(trace::Action::CreateShaderModule {
+                        id: fid.id(),
+                        desc: pipeline::ShaderModuleDescriptor {
+                            label: desc.label.clone(),
+                            shader_bound_checks: unsafe { wgt::ShaderBoundChecks::unchecked() },
+                        },
+                        data,
+                    });
+                }
+            };
+
+            let shader = match unsafe { device.create_shader_module_passthrough(device_id, desc) } {
+                Ok(shader) => shader,
+                Err(e) => break e,
+            };
+            let id = fid.assign(shader, &mut token);
+            return (id.0, None);
+        };
+
+        let id = fid.assign_error(desc.label.borrow_or_default(), &mut token);
+        (id, Some(error))
+    }
+
     pub fn shader_module_label<A: HalApi>(&self, id: id::ShaderModuleId) -> String {
         A::hub(self).shader_modules.label_for_resource(id)
     }
 
+    /// Describes the entry points of a shader module and the resources and
+    /// inputs/outputs they use.
+    ///
+    /// Returns `None` if the module is invalid, or was passed through to the
+    /// backend without being parsed.
+    pub fn shader_module_reflect<A: HalApi>(
+        &self,
+        shader_module_id: id::ShaderModuleId,
+    ) -> Option<wgt::ShaderModuleReflection> {
+        let hub = A::hub(self);
+        let mut token = Token::root();
+        let (_, mut token) = hub.devices.read(&mut token);
+        let (shader_module_guard, _) = hub.shader_modules.read(&mut token);
+        let module = shader_module_guard.get(shader_module_id).ok()?;
+        module
+            .interface
+            .as_ref()
+            .map(|interface| interface.reflect())
+    }
+
     pub fn shader_module_drop<A: HalApi>(&self, shader_module_id: id::ShaderModuleId) {
         profiling::scope!("ShaderModule::drop");
         log::debug!("shader module {:?} is dropped", shader_module_id);
@@ -1438,9 +1619,9 @@ impl<G: GlobalIdentityHandlerFactory> Global<G> {
                     .lock()
                     .add(trace::Action::DestroyShaderModule(shader_module_id));
             }
-            unsafe {
-                device.raw.destroy_shader_module(module.raw);
-            }
+            device
+                .lock_life(&mut token)
+                .schedule_shader_module_destruction(&device.raw, module.raw);
         }
     }
 
@@ -1765,6 +1946,120 @@ impl<G: GlobalIdentityHandlerFactory> Global<G> {
         (id, Some(error))
     }
 
+    /// Creates a render pipeline, compiling its backend pipeline on a
+    /// worker thread.
+    ///
+    /// The descriptor is validated before returning, but the pipeline is only
+    /// registered right before `callback` is called, with its id and the
+    /// error that made it invalid, if any. The id must not be used before.
+    pub fn device_create_render_pipeline_async<A: HalApi + 'static>(
+        self: &Arc<Self>,
+        device_id: DeviceId,
+        desc: pipeline::RenderPipelineDescriptor<'static>,
+        id_in: Input<G, id::RenderPipelineId>,
+        implicit_pipeline_ids: Option<ImplicitPipelineIds<G>>,
+        callback: pipeline::CreateRenderPipelineCallback,
+    ) where
+        G: 'static,
+        Self: wgt::WasmNotSend + wgt::WasmNotSync,
+    {
+        profiling::scope!("Device::create_render_pipeline_async");
+
+        let hub = A::hub(&**self);
+        let prepared = {
+            let mut token = Token::root();
+
+            let fid = hub.render_pipelines.prepare(id_in);
+            let implicit_context = implicit_pipeline_ids.map(|ipi| ipi.prepare(hub));
+
+            let (adapter_guard, mut token) = hub.adapters.read(&mut token);
+            let (device_guard, mut token) = hub.devices.read(&mut token);
+            let result = match device_guard.get(device_id) {
+                Ok(device) => {
+                    let adapter = &adapter_guard[device.adapter_id.value];
+                    #[cfg(feature = "trace")]
+                    if let Some(ref trace) = device.trace {
+                        trace.lock().add(trace::Action::CreateRenderPipeline {
+                            id: fid.id(),
+                            desc: desc.clone(),
+                            implicit_context: implicit_context.clone(),
+                        });
+                    }
+
+                    device.prepare_render_pipeline(
+                        device_id,
+                        adapter,
+                        &desc,
+                        implicit_context,
+                        hub,
+                        &mut token,
+                    )
+                }
+                Err(_) => Err(DeviceError::Invalid.into()),
+            };
+
+            match result {
+                Ok(pending) => Ok((fid.into_id(), pending)),
+                Err(e) => Err((
+                    fid.assign_error(desc.label.borrow_or_default(), &mut token),
+                    e,
+                )),
+            }
+        };
+
+        let (id, (pending, sources)) = match prepared {
+            Ok(pair) => pair,
+            Err((id, error)) => return callback(id, Some(error)),
+        };
+
+        let global = Arc::clone(self);
+        super::spawn_worker("wgpu render pipeline", move || {
+            let result = Device::compile_render_pipeline(&desc, &sources);
+
+            // The sources, which the device waits for before it is torn
+            // down, are held until the pipeline is registered.
+            let hub = A::hub(&*global);
+            let error = {
+                let mut token = Token::root();
+                let (device_guard, mut token) = hub.devices.read(&mut token);
+                let fid = hub.render_pipelines.future_id(id);
+
+                match (result, device_guard.get(device_id)) {
+                    (Ok(raw), Ok(device)) => {
+                        let pipeline = pending.into_pipeline(raw, &desc);
+                        let ref_count = pipeline.life_guard.add_ref();
+
+                        let id = fid.assign(pipeline, &mut token);
+                        log::info!("Created render pipeline {:?} with {:?}", id, desc);
+
+                        device
+                            .trackers
+                            .lock()
+                            .render_pipelines
+                            .insert_single(id, ref_count);
+                        None
+                    }
+                    (Ok(raw), Err(_)) => {
+                        unsafe {
+                            sources.raw_device.destroy_render_pipeline(raw);
+                        }
+                        drop(pending);
+                        fid.assign_error(desc.label.borrow_or_default(), &mut token);
+                        Some(DeviceError::Invalid.into())
+                    }
+                    (Err(e), _) => {
+                        drop(pending);
+                        fid.assign_error(desc.label.borrow_or_default(), &mut token);
+                        Some(e)
+                    }
+                }
+            };
+            sources.release();
+
+            callback(id, error);
+        });
+    }
+
     /// Get an ID of one of the bind group layouts. The ID adds a refcount,
     /// which needs to be released by calling `bind_group_layout_drop`.
     pub fn render_pipeline_get_bind_group_layout<A: HalApi>(
@@ -1905,6 +2200,115 @@ impl<G: GlobalIdentityHandlerFactory> Global<G> {
         (id, Some(error))
     }
 
+    /// Creates a compute pipeline, compiling its backend pipeline on a
+    /// worker thread.
+    ///
+    /// See [`Global::device_create_render_pipeline_async`].
+    pub fn device_create_compute_pipeline_async<A: HalApi + 'static>(
+        self: &Arc<Self>,
+        device_id: DeviceId,
+        desc: pipeline::ComputePipelineDescriptor<'static>,
+        id_in: Input<G, id::ComputePipelineId>,
+        implicit_pipeline_ids: Option<ImplicitPipelineIds<G>>,
+        callback: pipeline::CreateComputePipelineCallback,
+    ) where
+        G: 'static,
+        Self: wgt::WasmNotSend + wgt::WasmNotSync,
+    {
+        profiling::scope!("Device::create_compute_pipeline_async");
+
+        let hub = A::hub(&**self);
+        let prepared = {
+            let mut token = Token::root();
+
+            let fid = hub.compute_pipelines.prepare(id_in);
+            let implicit_context = implicit_pipeline_ids.map(|ipi| ipi.prepare(hub));
+
+            let (device_guard, mut token) = hub.devices.read(&mut token);
+            let result = match device_guard.get(device_id) {
+                Ok(device) => {
+                    #[cfg(feature = "trace")]
+                    if let Some(ref trace) = device.trace {
+                        trace.lock().add(trace::Action::CreateComputePipeline {
+                            id: fid.id(),
+                            desc: desc.clone(),
+                            implicit_context: implicit_context.clone(),
+                        });
+                    }
+
+                    device.prepare_compute_pipeline(
+                        device_id,
+                        &desc,
+                        implicit_context,
+                        hub,
+                        &mut token,
+                    )
+                }
+                Err(_) => Err(DeviceError::Invalid.into()),
+            };
+
+            match result {
+                Ok(pending) => Ok((fid.into_id(), pending)),
+                Err(e) => Err((
+                    fid.assign_error(desc.label.borrow_or_default(), &mut token),
+                    e,
+                )),
+            }
+        };
+
+        let (id, (pending, sources)) = match prepared {
+            Ok(pair) => pair,
+            Err((id, error)) => return callback(id, Some(error)),
+        };
+
+        let global = Arc::clone(self);
+        super::spawn_worker("wgpu compute pipeline", move || {
+            let result = Device::compile_compute_pipeline(&desc, &sources);
+
+            // The sources, which the device waits for before it is torn
+            // down, are held until the pipeline is registered.
+            let hub = A::hub(&*global);
+            let error = {
+                let mut token = Token::root();
+                let (device_guard, mut token) = hub.devices.read(&mut token);
+                let fid = hub.compute_pipelines.future_id(id);
+
+                match (result, device_guard.get(device_id)) {
+                    (Ok(raw), Ok(device)) => {
+                        let pipeline = pending.into_pipeline(raw, &desc);
+                        let ref_count = pipeline.life_guard.add_ref();
+
+                        let id = fid.assign(pipeline, &mut token);
+                        log::info!("Created compute pipeline {:?} with {:?}", id, desc);
+
+                        device
+                            .trackers
+                            .lock()
+                            .compute_pipelines
+                            .insert_single(id, ref_count);
+                        None
+                    }
+                    (Ok(raw), Err(_)) => {
+                        unsafe {
+                            sources.raw_device.destroy_compute_pipeline(raw);
+                        }
+                        drop(pending);
+                        fid.assign_error(desc.label.borrow_or_default(), &mut token);
+                        Some(DeviceError::Invalid.into())
+                    }
+                    (Err(e), _) => {
+                        drop(pending);
+                        fid.assign_error(desc.label.borrow_or_default(), &mut token);
+                        Some(e)
+                    }
+                }
+            };
+            sources.release();
+
+            callback(id, error);
+        });
+    }
+
     /// Get an ID of one of the bind group layouts. The ID adds a refcount,
     /// which needs to be released by calling `bind_group_layout_drop`.
     pub fn compute_pipeline_get_bind_group_layout<A: HalApi>(
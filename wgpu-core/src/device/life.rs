@@ -19,7 +19,7 @@ use hal::Device as _;
 use parking_lot::Mutex;
 use thiserror::Error;
 
-use std::mem;
+use std::{mem, sync::Arc};
 
 /// A struct that keeps lists of resources that are no longer needed by the user.
 #[derive(Debug, Default)]
@@ -215,6 +215,9 @@ struct ActiveSubmission<A: hal::Api> {
     /// this submission is their last reference.)
     last_resources: NonReferencedResources<A>,
 
+    /// Bytes of the staging buffers in `last_resources`.
+    staging_bytes: wgt::BufferAddress,
+
     /// Buffers to be mapped once this submission has completed.
     mapped: Vec<id::Valid<id::BufferId>>,
 
@@ -304,6 +307,16 @@ pub(super) struct LifetimeTracker<A: hal::Api> {
     /// Buffers the user has asked us to map, and which are not used by any
     /// queue submission still in flight.
     ready_to_map: Vec<id::Valid<id::BufferId>>,
+
+    /// Shader modules the user has dropped while pipeline compilations were
+    /// still using them.
+    ///
+    /// `LifetimeTracker::cleanup` frees them once those compilations are done.
+    orphaned_shader_modules: Vec<Arc<A::ShaderModule>>,
+
+    /// Pipeline layouts destroyed while pipeline compilations were still
+    /// using them, freed like `orphaned_shader_modules`.
+    orphaned_pipeline_layouts: Vec<Arc<A::PipelineLayout>>,
 }
 
 impl<A: hal::Api> LifetimeTracker<A> {
@@ -316,6 +329,8 @@ impl<A: hal::Api> LifetimeTracker<A> {
             active: Vec::new(),
             free_resources: NonReferencedResources::new(),
             ready_to_map: Vec::new(),
+            orphaned_shader_modules: Vec::new(),
+            orphaned_pipeline_layouts: Vec::new(),
         }
     }
 
@@ -329,6 +344,7 @@ impl<A: hal::Api> LifetimeTracker<A> {
         &mut self,
         index: SubmissionIndex,
         temp_resources: impl Iterator<Item = TempResource<A>>,
+        staging_bytes: wgt::BufferAddress,
         encoders: Vec<EncoderInFlight<A>>,
     ) {
         let mut last_resources = NonReferencedResources::new();
@@ -345,6 +361,7 @@ impl<A: hal::Api> LifetimeTracker<A> {
         self.active.push(ActiveSubmission {
             index,
             last_resources,
+            staging_bytes,
             mapped: Vec::new(),
             encoders,
             work_done_closures: SmallVec::new(),
@@ -364,6 +381,11 @@ impl<A: hal::Api> LifetimeTracker<A> {
         );
     }
 
+    /// Bytes of staging buffers held by submissions that haven't completed yet.
+    pub(super) fn staging_bytes_in_flight(&self) -> wgt::BufferAddress {
+        self.active.iter().map(|a| a.staging_bytes).sum()
+    }
+
     pub(crate) fn map(&mut self, value: id::Valid<id::BufferId>, ref_count: RefCount) {
         self.mapped.push(Stored { value, ref_count });
     }
@@ -424,6 +446,31 @@ impl<A: hal::Api> LifetimeTracker<A> {
         unsafe {
             self.free_resources.clean(device);
         }
+        for module in mem::take(&mut self.orphaned_shader_modules) {
+            match Arc::try_unwrap(module) {
+                Ok(raw) => unsafe { device.destroy_shader_module(raw) },
+                Err(module) => self.orphaned_shader_modules.push(module),
+            }
+        }
+        for layout in mem::take(&mut self.orphaned_pipeline_layouts) {
+            match Arc::try_unwrap(layout) {
+                Ok(raw) => unsafe { device.destroy_pipeline_layout(raw) },
+                Err(layout) => self.orphaned_pipeline_layouts.push(layout),
+            }
+        }
+    }
+
+    /// Destroy a shader module dropped by the user, or keep it until the
+    /// pipeline compilations using it are done.
+    pub fn schedule_shader_module_destruction(
+        &mut self,
+        device: &A::Device,
+        module: Arc<A::ShaderModule>,
+    ) {
+        match Arc::try_unwrap(module) {
+            Ok(raw) => unsafe { device.destroy_shader_module(raw) },
+            Err(module) => self.orphaned_shader_modules.push(module),
+        }
     }
 
     pub fn schedule_resource_destruction(
@@ -748,7 +795,10 @@ impl<A: HalApi> LifetimeTracker<A> {
                         self.suspected_resources
                             .bind_group_layouts
                             .extend_from_slice(&lay.bind_group_layout_ids);
-                        self.free_resources.pipeline_layouts.push(lay.raw);
+                        match Arc::try_unwrap(lay.raw) {
+                            Ok(raw) => self.free_resources.pipeline_layouts.push(raw),
+                            Err(raw) => self.orphaned_pipeline_layouts.push(raw),
+                        }
                     }
                 }
             }
@@ -1,6 +1,7 @@
 use crate::{
     binding_model,
     device::life::WaitIdleError,
+    error::ErrorType,
     hal_api::HalApi,
     hub::Hub,
     id,
@@ -12,19 +13,22 @@ use crate::{
 
 use arrayvec::ArrayVec;
 use hal::Device as _;
+use parking_lot::{Condvar, Mutex};
 use smallvec::SmallVec;
 use thiserror::Error;
 use wgt::{BufferAddress, TextureFormat};
 
-use std::{iter, num::NonZeroU32, ptr};
+use std::{iter, num::NonZeroU32, ptr, sync::Arc};
 
 pub mod global;
 mod life;
 pub mod queue;
 pub mod resource;
+mod shader_cache;
 #[cfg(any(feature = "trace", feature = "replay"))]
 pub mod trace;
 pub use resource::Device;
+pub use shader_cache::ShaderModuleCacheReport;
 
 pub const SHADER_STAGE_COUNT: usize = 3;
 // Should be large enough for the largest possible texture row. This
@@ -190,6 +194,85 @@ impl UserClosures {
     }
 }
 
+/// Counts the pipelines of a device being compiled, so the device can wait
+/// for them before it is destroyed.
+#[derive(Debug, Default)]
+pub(crate) struct PipelineCompilations {
+    count: Mutex<usize>,
+    done: Condvar,
+}
+
+impl PipelineCompilations {
+    pub(crate) fn start(self: &Arc<Self>) -> PipelineCompilation {
+        *self.count.lock() += 1;
+        PipelineCompilation(Arc::clone(self))
+    }
+
+    /// Returns `true` if no compilation is in progress.
+    pub(crate) fn is_idle(&self) -> bool {
+        *self.count.lock() == 0
+    }
+
+    /// Blocks until all the compilations started so far are done.
+    pub(crate) fn wait(&self) {
+        let mut count = self.count.lock();
+        while *count != 0 {
+            self.done.wait(&mut count);
+        }
+    }
+}
+
+/// A pipeline compilation in progress, which is done when dropped.
+///
+/// It is held until the compiled pipeline is registered, so that a device
+/// waiting for its compilations isn't torn down under the pipeline.
+#[derive(Debug)]
+pub(crate) struct PipelineCompilation(Arc<PipelineCompilations>);
+
+impl Drop for PipelineCompilation {
+    fn drop(&mut self) {
+        let mut count = self.0.count.lock();
+        *count -= 1;
+        if *count == 0 {
+            self.0.done.notify_all();
+        }
+    }
+}
+
+/// Runs `work` on a new worker thread, or right away on the calling thread
+/// on platforms without threads, or if the thread can't be spawned.
+fn spawn_worker(name: &str, work: impl FnOnce() + wgt::WasmNotSend + 'static) {
+    #[cfg(not(target_arch = "wasm32"))]
+    {
+        // `spawn` drops the closure when it fails, so it only takes `work`
+        // from a slot which is still filled for the fallback.
+        let slot = Arc::new(Mutex::new(Some(work)));
+        let worker_slot = Arc::clone(&slot);
+        let spawned = std::thread::Builder::new()
+            .name(name.to_string())
+            .spawn(move || {
+                let work = worker_slot.lock().take();
+                if let Some(work) = work {
+                    work();
+                }
+            });
+        if let Err(error) = spawned {
+            log::warn!(
+                "Failed to spawn a {name} thread, running it on the calling thread: {error}"
+            );
+            let work = slot.lock().take();
+            if let Some(work) = work {
+                work();
+            }
+        }
+    }
+    #[cfg(target_arch = "wasm32")]
+    {
+        let _ = name;
+        work();
+    }
+}
+
 fn map_buffer<A: hal::Api>(
     raw: &A::Device,
     buffer: &mut Buffer<A>,
@@ -298,6 +381,17 @@ pub enum DeviceError {
     OutOfMemory,
 }
 
+impl DeviceError {
+    /// Returns the [`ErrorType`] this error is reported as.
+    pub fn error_type(&self) -> ErrorType {
+        match *self {
+            Self::Invalid => ErrorType::Validation,
+            Self::Lost => ErrorType::Internal,
+            Self::OutOfMemory => ErrorType::OutOfMemory,
+        }
+    }
+}
+
 impl From<hal::DeviceError> for DeviceError {
     fn from(error: hal::DeviceError) -> Self {
         match error {
@@ -1,12 +1,14 @@
 #[cfg(feature = "trace")]
 use crate::device::trace::Action;
 use crate::{
+    binding_model::{BindGroupUpdateEntry, UpdateBindGroupError},
     command::{
         extract_texture_selector, validate_linear_texture_data, validate_texture_copy_range,
         ClearError, CommandBuffer, CopySide, ImageCopyTexture, TransferError,
     },
     conv,
     device::{DeviceError, WaitIdleError},
+    error::ErrorType,
     get_lowest_common_denom,
     global::Global,
     hal_api::HalApi,
@@ -165,6 +167,8 @@ pub(crate) struct PendingWrites<A: hal::Api> {
     pub command_encoder: A::CommandEncoder,
     pub is_active: bool,
     pub temp_resources: Vec<TempResource<A>>,
+    /// Bytes of the staging buffers in `temp_resources`.
+    pub staging_bytes: wgt::BufferAddress,
     pub dst_buffers: FastHashSet<id::BufferId>,
     pub dst_textures: FastHashSet<id::TextureId>,
     pub executing_command_buffers: Vec<A::CommandBuffer>,
@@ -176,6 +180,7 @@ impl<A: hal::Api> PendingWrites<A> {
             command_encoder,
             is_active: false,
             temp_resources: Vec::new(),
+            staging_bytes: 0,
             dst_buffers: FastHashSet::default(),
             dst_textures: FastHashSet::default(),
             executing_command_buffers: Vec::new(),
@@ -212,6 +217,7 @@ impl<A: hal::Api> PendingWrites<A> {
     }
 
     fn consume(&mut self, buffer: StagingBuffer<A>) {
+        self.staging_bytes += buffer.size;
         self.temp_resources.push(TempResource::Buffer(buffer.raw));
     }
 
@@ -273,7 +279,7 @@ impl<A: hal::Api> PendingWrites<A> {
 }
 
 fn prepare_staging_buffer<A: HalApi>(
-    device: &mut A::Device,
+    device: &A::Device,
     size: wgt::BufferAddress,
 ) -> Result<(StagingBuffer<A>, *mut u8), DeviceError> {
     profiling::scope!("prepare_staging_buffer");
@@ -321,6 +327,16 @@ pub enum QueueWriteError {
     MemoryInitFailure(#[from] ClearError),
 }
 
+impl QueueWriteError {
+    /// Returns the [`ErrorType`] this error is reported as.
+    pub fn error_type(&self) -> ErrorType {
+        match *self {
+            Self::Queue(ref err) => err.error_type(),
+            Self::Transfer(..) | Self::MemoryInitFailure(..) => ErrorType::Validation,
+        }
+    }
+}
+
 #[derive(Clone, Debug, Error)]
 #[non_exhaustive]
 pub enum QueueSubmitError {
@@ -342,6 +358,22 @@ pub enum QueueSubmitError {
     StuckGpu,
 }
 
+impl QueueSubmitError {
+    /// Returns the [`ErrorType`] this error is reported as.
+    pub fn error_type(&self) -> ErrorType {
+        match *self {
+            Self::Queue(ref err) => err.error_type(),
+            Self::Unmap(ref err) => err.error_type(),
+            Self::StuckGpu => ErrorType::Internal,
+            Self::DestroyedBuffer(..)
+            | Self::DestroyedTexture(..)
+            | Self::BufferStillMapped(..)
+            | Self::SurfaceOutputDropped
+            | Self::SurfaceUnconfigured => ErrorType::Validation,
+        }
+    }
+}
+
 //TODO: move out common parts of write_xxx.
 
 impl<G: GlobalIdentityHandlerFactory> Global<G> {
@@ -384,13 +416,12 @@ impl<G: GlobalIdentityHandlerFactory> Global<G> {
         // Platform validation requires that the staging buffer always be
         // freed, even if an error occurs. All paths from here must call
         // `device.pending_writes.consume`.
-        let (staging_buffer, staging_buffer_ptr) =
-            prepare_staging_buffer(&mut device.raw, data_size)?;
+        let (staging_buffer, staging_buffer_ptr) = prepare_staging_buffer(&*device.raw, data_size)?;
 
         if let Err(flush_error) = unsafe {
             profiling::scope!("copy");
             ptr::copy_nonoverlapping(data.as_ptr(), staging_buffer_ptr, data.len());
-            staging_buffer.flush(&device.raw)
+            staging_buffer.flush(&*device.raw)
         } {
             device.pending_writes.consume(staging_buffer);
             return Err(flush_error.into());
@@ -424,7 +455,7 @@ impl<G: GlobalIdentityHandlerFactory> Global<G> {
             .map_err(|_| DeviceError::Invalid)?;
 
         let (staging_buffer, staging_buffer_ptr) =
-            prepare_staging_buffer(&mut device.raw, buffer_size.get())?;
+            prepare_staging_buffer(&*device.raw, buffer_size.get())?;
 
         let fid = hub.staging_buffers.prepare(id_in);
         let id = fid.assign(staging_buffer, device_token);
@@ -768,7 +799,7 @@ impl<G: GlobalIdentityHandlerFactory> Global<G> {
         // freed, even if an error occurs. All paths from here must call
         // `device.pending_writes.consume`.
         let (staging_buffer, staging_buffer_ptr) =
-            prepare_staging_buffer(&mut device.raw, stage_size)?;
+            prepare_staging_buffer(&*device.raw, stage_size)?;
 
         if stage_bytes_per_row == bytes_per_row {
             profiling::scope!("copy aligned");
@@ -803,7 +834,7 @@ impl<G: GlobalIdentityHandlerFactory> Global<G> {
             }
         }
 
-        if let Err(e) = unsafe { staging_buffer.flush(&device.raw) } {
+        if let Err(e) = unsafe { staging_buffer.flush(&*device.raw) } {
             device.pending_writes.consume(staging_buffer);
             return Err(e.into());
         }
@@ -1045,6 +1076,71 @@ impl<G: GlobalIdentityHandlerFactory> Global<G> {
         Ok(())
     }
 
+    /// Replaces elements of binding arrays of a bind group whose layout was
+    /// created with [`wgt::Features::UPDATE_AFTER_BIND_DESCRIPTORS`].
+    ///
+    /// Fails if the bind group is used by a submission which hasn't completed
+    /// yet, or by a command buffer or render bundle which isn't submitted yet,
+    /// as those tracked the state of the previous resources.
+    pub fn queue_update_bind_group<A: HalApi>(
+        &self,
+        queue_id: id::QueueId,
+        bind_group_id: id::BindGroupId,
+        entries: &[BindGroupUpdateEntry],
+    ) -> Result<(), UpdateBindGroupError> {
+        profiling::scope!("Queue::update_bind_group");
+
+        let hub = A::hub(self);
+        let mut token = Token::root();
+        let (mut device_guard, mut token) = hub.devices.write(&mut token);
+        let device = device_guard
+            .get_mut(queue_id)
+            .map_err(|_| DeviceError::Invalid)?;
+
+        #[cfg(feature = "trace")]
+        if let Some(ref trace) = device.trace {
+            trace.lock().add(Action::UpdateBindGroup {
+                id: bind_group_id,
+                entries: entries.to_vec(),
+            });
+        }
+
+        let (command_buffer_guard, mut token) = hub.command_buffers.read(&mut token);
+        let (render_bundle_guard, mut token) = hub.render_bundles.read(&mut token);
+        let (_, mut token) = hub.pipeline_layouts.read(&mut token);
+        let (bind_group_layout_guard, mut token) = hub.bind_group_layouts.read(&mut token);
+        let (mut bind_group_guard, mut token) = hub.bind_groups.write(&mut token);
+        let bind_group = bind_group_guard
+            .get_mut(bind_group_id)
+            .map_err(|_| UpdateBindGroupError::InvalidBindGroup(bind_group_id))?;
+        if bind_group.device_id.value.0 != queue_id {
+            return Err(UpdateBindGroupError::WrongDevice(bind_group_id));
+        }
+
+        let last_done_index = unsafe {
+            device
+                .raw
+                .get_fence_value(&device.fence)
+                .map_err(DeviceError::from)?
+        };
+        if bind_group.life_guard.life_count() > last_done_index {
+            return Err(UpdateBindGroupError::UsedBySubmission(bind_group_id));
+        }
+        let valid_id = id::Valid(bind_group_id);
+        let recorded = command_buffer_guard
+            .iter(A::VARIANT)
+            .any(|(_, cmd_buf)| cmd_buf.trackers.bind_groups.contains(valid_id))
+            || render_bundle_guard
+                .iter(A::VARIANT)
+                .any(|(_, bundle)| bundle.used.bind_groups.contains(valid_id));
+        if recorded {
+            return Err(UpdateBindGroupError::UsedByRecording(bind_group_id));
+        }
+        let layout = &bind_group_layout_guard[bind_group.layout_id];
+
+        device.update_bind_group(bind_group, layout, entries, hub, &mut token)
+    }
+
     pub fn queue_submit<A: HalApi>(
         &self,
         queue_id: id::QueueId,
@@ -1383,9 +1479,11 @@ impl<G: GlobalIdentityHandlerFactory> Global<G> {
 
             // this will register the new submission to the life time tracker
             let mut pending_write_resources = mem::take(&mut device.pending_writes.temp_resources);
+            let staging_bytes = mem::take(&mut device.pending_writes.staging_bytes);
             device.lock_life(&mut token).track_submission(
                 submit_index,
                 pending_write_resources.drain(..),
+                staging_bytes,
                 active_executions,
             );
 
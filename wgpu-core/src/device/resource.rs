@@ -31,11 +31,13 @@ use smallvec::SmallVec;
 use thiserror::Error;
 use wgt::{TextureFormat, TextureSampleType, TextureViewDimension};
 
-use std::{borrow::Cow, iter, num::NonZeroU32};
+use std::{borrow::Cow, iter, num::NonZeroU32, sync::Arc};
 
 use super::{
-    life, queue, DeviceDescriptor, DeviceError, ImplicitPipelineContext, UserClosures, EP_FAILURE,
-    IMPLICIT_FAILURE, ZERO_BUFFER_SIZE,
+    life, queue,
+    shader_cache::{ShaderModuleCache, ShaderModuleCacheKey},
+    DeviceDescriptor, DeviceError, ImplicitPipelineContext, PipelineCompilations, UserClosures,
+    EP_FAILURE, IMPLICIT_FAILURE, ZERO_BUFFER_SIZE,
 };
 
 /// Structure describing a logical device. Some members are internally mutable,
@@ -50,7 +52,9 @@ use super::{
 /// 1. `self.trackers` is locked last (unenforced)
 /// 1. `self.trace` is locked last (unenforced)
 pub struct Device<A: HalApi> {
-    pub(crate) raw: A::Device,
+    /// The backend device, shared with the worker threads compiling
+    /// pipelines asynchronously.
+    pub(crate) raw: Arc<A::Device>,
     pub(crate) adapter_id: Stored<id::AdapterId>,
     pub(crate) queue: A::Queue,
     pub(crate) zero_buffer: A::Buffer,
@@ -77,6 +81,11 @@ pub struct Device<A: HalApi> {
     pub(crate) trackers: Mutex<Tracker<A>>,
     // Life tracker should be locked right after the device and before anything else.
     life_tracker: Mutex<life::LifetimeTracker<A>>,
+    /// Shader modules deduplicated by source, locked briefly.
+    pub(crate) shader_module_cache: Mutex<ShaderModuleCache<A>>,
+    /// Pipelines being compiled on worker threads, which are waited for
+    /// before the device is destroyed.
+    pub(crate) pipeline_compilations: Arc<PipelineCompilations>,
     /// Temporary storage for resource management functions. Cleared at the end
     /// of every call (unless an error occurs).
     pub(super) temp_suspected: life::SuspectedResources,
@@ -179,7 +188,7 @@ impl<A: HalApi> Device<A> {
         let life_guard = LifeGuard::new("<device>");
         let ref_count = life_guard.add_ref();
         Ok(Self {
-            raw: open.device,
+            raw: Arc::new(open.device),
             adapter_id,
             queue: open.queue,
             zero_buffer,
@@ -190,6 +199,8 @@ impl<A: HalApi> Device<A> {
             fence,
             trackers: Mutex::new(Tracker::new()),
             life_tracker: Mutex::new(life::LifetimeTracker::new()),
+            shader_module_cache: Mutex::new(ShaderModuleCache::new()),
+            pipeline_compilations: Arc::default(),
             temp_suspected: life::SuspectedResources::default(),
             #[cfg(feature = "trace")]
             trace: trace_path.and_then(|path| match trace::Trace::new(path) {
@@ -368,7 +379,6 @@ impl<A: HalApi> Device<A> {
         &self,
         self_id: id::DeviceId,
         desc: &resource::BufferDescriptor,
-        transient: bool,
     ) -> Result<Buffer<A>, resource::CreateBufferError> {
         debug_assert_eq!(self_id.backend(), A::VARIANT);
 
@@ -440,8 +450,11 @@ impl<A: HalApi> Device<A> {
             actual_size
         };
 
-        let mut memory_flags = hal::MemoryFlags::empty();
-        memory_flags.set(hal::MemoryFlags::TRANSIENT, transient);
+        let memory_flags = match desc.memory_hints {
+            wgt::MemoryHints::Default => hal::MemoryFlags::empty(),
+            wgt::MemoryHints::Transient => hal::MemoryFlags::TRANSIENT,
+            wgt::MemoryHints::LongLived => hal::MemoryFlags::LONG_LIVED,
+        };
 
         let hal_desc = hal::BufferDescriptor {
             label: desc.label.borrow_option(),
@@ -536,6 +549,17 @@ impl<A: HalApi> Device<A> {
             return Err(CreateTextureError::InvalidUsage(desc.usage));
         }
 
+        if desc
+            .usage
+            .contains(wgt::TextureUsages::TRANSIENT_ATTACHMENT)
+        {
+            let transient_usage =
+                wgt::TextureUsages::RENDER_ATTACHMENT | wgt::TextureUsages::TRANSIENT_ATTACHMENT;
+            if desc.usage != transient_usage {
+                return Err(CreateTextureError::InvalidTransientUsages(desc.usage));
+            }
+        }
+
         conv::check_texture_dimension_size(
             desc.dimension,
             desc.size,
@@ -566,6 +590,14 @@ impl<A: HalApi> Device<A> {
                     desc.format,
                 ));
             }
+
+            // Multi-planar textures can only be 2D
+            if desc.format.is_multi_planar_format() {
+                return Err(CreateTextureError::InvalidMultiPlanarDimension(
+                    desc.dimension,
+                    desc.format,
+                ));
+            }
         }
 
         if desc.format.is_compressed() {
@@ -592,6 +624,36 @@ impl<A: HalApi> Device<A> {
             }
         }
 
+        if desc.format.is_multi_planar_format() {
+            if desc.mip_level_count != 1 {
+                return Err(CreateTextureError::InvalidMipLevelCount {
+                    requested: desc.mip_level_count,
+                    maximum: 1,
+                });
+            }
+
+            // The chroma planes are subsampled by 2 in both directions
+            if desc.size.width % 2 != 0 {
+                return Err(CreateTextureError::InvalidDimension(
+                    TextureDimensionError::NotMultipleOfBlockWidth {
+                        width: desc.size.width,
+                        block_width: 2,
+                        format: desc.format,
+                    },
+                ));
+            }
+
+            if desc.size.height % 2 != 0 {
+                return Err(CreateTextureError::InvalidDimension(
+                    TextureDimensionError::NotMultipleOfBlockHeight {
+                        height: desc.size.height,
+                        block_height: 2,
+                        format: desc.format,
+                    },
+                ));
+            }
+        }
+
         let format_features = self
             .describe_format_features(adapter, desc.format)
             .map_err(|error| CreateTextureError::MissingFeatures(desc.format, error))?;
@@ -717,7 +779,12 @@ impl<A: HalApi> Device<A> {
                 .map_err(DeviceError::from)?
         };
 
-        let clear_mode = if hal_usage
+        let clear_mode = if hal_usage.contains(hal::TextureUses::TRANSIENT) {
+            // Transient textures are always cleared on load and never stored,
+            // so they never need to be initialized, and there may not even be
+            // memory backing them outside of a render pass.
+            resource::TextureClearMode::None
+        } else if hal_usage
             .intersects(hal::TextureUses::DEPTH_STENCIL_WRITE | hal::TextureUses::COLOR_TARGET)
         {
             let (is_color, usage) = if desc.format.is_depth_stencil_format() {
@@ -835,7 +902,9 @@ impl<A: HalApi> Device<A> {
         // validate TextureViewDescriptor
 
         let aspects = hal::FormatAspects::new(texture.desc.format, desc.range.aspect);
-        if aspects.is_empty() {
+        // multi-planar textures can only be viewed one plane at a time
+        if aspects.is_empty() || (texture.desc.format.is_multi_planar_format() && !aspects.is_one())
+        {
             return Err(resource::CreateTextureViewError::InvalidAspect {
                 texture_format: texture.desc.format,
                 requested_aspect: desc.range.aspect,
@@ -1021,8 +1090,10 @@ impl<A: HalApi> Device<A> {
             usage
         );
 
-        // use the combined depth-stencil format for the view
-        let format = if resolved_format.is_depth_stencil_component(texture.desc.format) {
+        // use the combined depth-stencil or multi-planar format for the view
+        let format = if resolved_format.is_depth_stencil_component(texture.desc.format)
+            || texture.desc.format.is_multi_planar_format()
+        {
             texture.desc.format
         } else {
             resolved_format
@@ -1190,6 +1261,22 @@ impl<A: HalApi> Device<A> {
         desc: &pipeline::ShaderModuleDescriptor<'a>,
         source: pipeline::ShaderModuleSource<'a>,
     ) -> Result<pipeline::ShaderModule<A>, pipeline::CreateShaderModuleError> {
+        let cache_key = ShaderModuleCacheKey::new(&source, &desc.shader_bound_checks);
+        if let Some(ref key) = cache_key {
+            if let Some((raw, interface)) = self.shader_module_cache.lock().get(key) {
+                return Ok(pipeline::ShaderModule {
+                    raw,
+                    device_id: Stored {
+                        value: id::Valid(self_id),
+                        ref_count: self.life_guard.add_ref(),
+                    },
+                    interface: Some(interface),
+                    #[cfg(debug_assertions)]
+                    label: desc.label.borrow_or_default().to_string(),
+                });
+            }
+        }
+
         let (module, source) = match source {
             #[cfg(feature = "wgsl")]
             pipeline::ShaderModuleSource::Wgsl(code) => {
@@ -1203,6 +1290,32 @@ impl<A: HalApi> Device<A> {
                 })?;
                 (Cow::Owned(module), code.into_owned())
             }
+            #[cfg(feature = "glsl")]
+            pipeline::ShaderModuleSource::Glsl(code, options) => {
+                profiling::scope!("naga::glsl::parse");
+                let mut parser = naga::front::glsl::Frontend::default();
+                let module = parser.parse(&options, &code).map_err(|errors| {
+                    pipeline::CreateShaderModuleError::ParsingGlsl(pipeline::ShaderError {
+                        source: code.to_string(),
+                        label: desc.label.as_ref().map(|l| l.to_string()),
+                        inner: Box::new(pipeline::GlslErrors(errors)),
+                    })
+                })?;
+                (Cow::Owned(module), code.into_owned())
+            }
+            #[cfg(feature = "spirv")]
+            pipeline::ShaderModuleSource::SpirV(spv, options) => {
+                profiling::scope!("naga::spv::parse");
+                let parser = naga::front::spv::Frontend::new(spv.iter().cloned(), &options);
+                let module = parser.parse().map_err(|inner| {
+                    pipeline::CreateShaderModuleError::ParsingSpirV(pipeline::ShaderError {
+                        source: String::new(),
+                        label: desc.label.as_ref().map(|l| l.to_string()),
+                        inner: Box::new(inner),
+                    })
+                })?;
+                (Cow::Owned(module), String::new())
+            }
             pipeline::ShaderModuleSource::Naga(module) => (module, String::new()),
             pipeline::ShaderModuleSource::Dummy(_) => panic!("found `ShaderModuleSource::Dummy`"),
         };
@@ -1285,8 +1398,12 @@ impl<A: HalApi> Device<A> {
                     inner: Box::new(inner),
                 })
             })?;
-        let interface =
-            validation::Interface::new(&module, &info, self.features, self.limits.clone());
+        let interface = Arc::new(validation::Interface::new(
+            &module,
+            &info,
+            self.features,
+            self.limits.clone(),
+        ));
         let hal_shader = hal::ShaderInput::Naga(hal::NagaShader { module, info });
 
         let hal_desc = hal::ShaderModuleDescriptor {
@@ -1307,6 +1424,12 @@ impl<A: HalApi> Device<A> {
                 })
             }
         };
+        let raw = Arc::new(raw);
+        if let Some(key) = cache_key {
+            self.shader_module_cache
+                .lock()
+                .insert(key, &raw, &interface);
+        }
 
         Ok(pipeline::ShaderModule {
             raw,
@@ -1349,7 +1472,97 @@ impl<A: HalApi> Device<A> {
         };
 
         Ok(pipeline::ShaderModule {
-            raw,
+            raw: Arc::new(raw),
+            device_id: Stored {
+                value: id::Valid(self_id),
+                ref_count: self.life_guard.add_ref(),
+            },
+            interface: None,
+            #[cfg(debug_assertions)]
+            label: desc.label.borrow_or_default().to_string(),
+        })
+    }
+
+    #[allow(unused_unsafe)]
+    pub(super) unsafe fn create_shader_module_passthrough(
+        &self,
+        self_id: id::DeviceId,
+        desc: &pipeline::ShaderModuleDescriptorPassthrough,
+    ) -> Result<pipeline::ShaderModule<A>, pipeline::CreateShaderModuleError> {
+        self.require_features(wgt::Features::SHADER_PASSTHROUGH)?;
+        let missing_source =
+            || pipeline::CreateShaderModuleError::MissingPassthroughSource(A::VARIANT);
+        // Only GL rebinds the resources of passthrough shaders, the other
+        // backends expect them at the slots they assign to the layout.
+        if !desc.bindings.is_empty() && A::VARIANT != wgt::Backend::Gl {
+            return Err(
+                pipeline::CreateShaderModuleError::UnsupportedPassthroughBindings(A::VARIANT),
+            );
+        }
+        // The stage of the GLSL and DXIL shaders, which have a single entry point.
+        let stage = || match desc.stage {
+            wgt::ShaderStages::VERTEX => Ok(naga::ShaderStage::Vertex),
+            wgt::ShaderStages::FRAGMENT => Ok(naga::ShaderStage::Fragment),
+            wgt::ShaderStages::COMPUTE => Ok(naga::ShaderStage::Compute),
+            stage => Err(pipeline::CreateShaderModuleError::InvalidPassthroughStage(
+                stage,
+            )),
+        };
+
+        let bindings = desc
+            .bindings
+            .iter()
+            .map(|binding| wgt::PassthroughBinding {
+                group: binding.group,
+                binding: binding.binding,
+                name: binding.name.as_ref(),
+            })
+            .collect::<Vec<_>>();
+        let hal_shader = match A::VARIANT {
+            wgt::Backend::Vulkan => {
+                hal::ShaderInput::SpirV(desc.spirv.as_deref().ok_or_else(missing_source)?)
+            }
+            wgt::Backend::Metal => hal::ShaderInput::Msl {
+                source: desc.msl.as_deref().ok_or_else(missing_source)?,
+                workgroup_size: desc.workgroup_size,
+            },
+            wgt::Backend::Dx12 => match (desc.dxil.as_deref(), desc.hlsl.as_deref()) {
+                (Some(dxil), _) => hal::ShaderInput::Dxil {
+                    bytecode: dxil,
+                    stage: stage()?,
+                },
+                (None, Some(hlsl)) => hal::ShaderInput::Hlsl(hlsl),
+                (None, None) => return Err(missing_source()),
+            },
+            wgt::Backend::Gl => hal::ShaderInput::Glsl {
+                source: desc.glsl.as_deref().ok_or_else(missing_source)?,
+                stage: stage()?,
+                bindings: &bindings,
+            },
+            _ => return Err(missing_source()),
+        };
+
+        let hal_desc = hal::ShaderModuleDescriptor {
+            label: desc.label.borrow_option(),
+            runtime_checks: false,
+        };
+        let raw = match unsafe { self.raw.create_shader_module(&hal_desc, hal_shader) } {
+            Ok(raw) => raw,
+            Err(error) => {
+                return Err(match error {
+                    hal::ShaderError::Device(error) => {
+                        pipeline::CreateShaderModuleError::Device(error.into())
+                    }
+                    hal::ShaderError::Compilation(ref msg) => {
+                        log::error!("Shader error: {}", msg);
+                        pipeline::CreateShaderModuleError::Generation
+                    }
+                })
+            }
+        };
+
+        Ok(pipeline::ShaderModule {
+            raw: Arc::new(raw),
             device_id: Stored {
                 value: id::Valid(self_id),
                 ref_count: self.life_guard.add_ref(),
@@ -1586,7 +1799,7 @@ impl<A: HalApi> Device<A> {
                 })?;
         }
 
-        let bgl_flags = conv::bind_group_layout_flags(self.features);
+        let bgl_flags = conv::bind_group_layout_flags(self.features, &entry_map);
 
         let mut hal_bindings = entry_map.values().cloned().collect::<Vec<_>>();
         hal_bindings.sort_by_key(|b| b.binding);
@@ -1624,6 +1837,7 @@ impl<A: HalApi> Device<A> {
                 .count(),
             count_validator,
             entries: entry_map,
+            update_after_bind: bgl_flags.contains(hal::BindGroupLayoutFlags::UPDATE_AFTER_BIND),
             #[cfg(debug_assertions)]
             label: label.unwrap_or("").to_string(),
         })
@@ -1882,50 +2096,15 @@ impl<A: HalApi> Device<A> {
                     (res_index, num_bindings)
                 }
                 Br::Sampler(id) => {
-                    match decl.ty {
-                        wgt::BindingType::Sampler(ty) => {
-                            let sampler = used
-                                .samplers
-                                .add_single(&*sampler_guard, id)
-                                .ok_or(Error::InvalidSampler(id))?;
-
-                            // Allowed sampler values for filtering and comparison
-                            let (allowed_filtering, allowed_comparison) = match ty {
-                                wgt::SamplerBindingType::Filtering => (None, false),
-                                wgt::SamplerBindingType::NonFiltering => (Some(false), false),
-                                wgt::SamplerBindingType::Comparison => (None, true),
-                            };
-
-                            if let Some(allowed_filtering) = allowed_filtering {
-                                if allowed_filtering != sampler.filtering {
-                                    return Err(Error::WrongSamplerFiltering {
-                                        binding,
-                                        layout_flt: allowed_filtering,
-                                        sampler_flt: sampler.filtering,
-                                    });
-                                }
-                            }
-
-                            if allowed_comparison != sampler.comparison {
-                                return Err(Error::WrongSamplerComparison {
-                                    binding,
-                                    layout_cmp: allowed_comparison,
-                                    sampler_cmp: sampler.comparison,
-                                });
-                            }
+                    let sampler = used
+                        .samplers
+                        .add_single(&*sampler_guard, id)
+                        .ok_or(Error::InvalidSampler(id))?;
+                    Self::check_sampler_binding(binding, decl, sampler)?;
 
-                            let res_index = hal_samplers.len();
-                            hal_samplers.push(&sampler.raw);
-                            (res_index, 1)
-                        }
-                        _ => {
-                            return Err(Error::WrongBindingType {
-                                binding,
-                                actual: decl.ty,
-                                expected: "Sampler",
-                            })
-                        }
-                    }
+                    let res_index = hal_samplers.len();
+                    hal_samplers.push(&sampler.raw);
+                    (res_index, 1)
                 }
                 Br::SamplerArray(ref bindings_array) => {
                     let num_bindings = bindings_array.len();
@@ -2029,6 +2208,12 @@ impl<A: HalApi> Device<A> {
                 .map_err(DeviceError::from)?
         };
 
+        let bound_resources = if layout.update_after_bind {
+            Some(Self::bound_resources(layout, &desc.entries))
+        } else {
+            None
+        };
+
         // manually add a dependency on BGL
         layout.multi_ref_count.inc();
 
@@ -2050,9 +2235,303 @@ impl<A: HalApi> Device<A> {
                 .keys()
                 .flat_map(|binding| late_buffer_binding_sizes.get(binding).cloned())
                 .collect(),
+            bound_resources,
         })
     }
 
+    fn check_sampler_binding(
+        binding: u32,
+        decl: &wgt::BindGroupLayoutEntry,
+        sampler: &resource::Sampler<A>,
+    ) -> Result<(), binding_model::CreateBindGroupError> {
+        use crate::binding_model::CreateBindGroupError as Error;
+
+        let ty = match decl.ty {
+            wgt::BindingType::Sampler(ty) => ty,
+            _ => {
+                return Err(Error::WrongBindingType {
+                    binding,
+                    actual: decl.ty,
+                    expected: "Sampler",
+                })
+            }
+        };
+
+        // Allowed sampler values for filtering and comparison
+        let (allowed_filtering, allowed_comparison) = match ty {
+            wgt::SamplerBindingType::Filtering => (None, false),
+            wgt::SamplerBindingType::NonFiltering => (Some(false), false),
+            wgt::SamplerBindingType::Comparison => (None, true),
+        };
+
+        if let Some(allowed_filtering) = allowed_filtering {
+            if allowed_filtering != sampler.filtering {
+                return Err(Error::WrongSamplerFiltering {
+                    binding,
+                    layout_flt: allowed_filtering,
+                    sampler_flt: sampler.filtering,
+                });
+            }
+        }
+
+        if allowed_comparison != sampler.comparison {
+            return Err(Error::WrongSamplerComparison {
+                binding,
+                layout_cmp: allowed_comparison,
+                sampler_cmp: sampler.comparison,
+            });
+        }
+
+        Ok(())
+    }
+
+    /// Records the resources of a bind group whose layout allows updates
+    /// after bind. The entries must have been validated against the layout.
+    fn bound_resources(
+        layout: &binding_model::BindGroupLayout<A>,
+        entries: &[binding_model::BindGroupEntry],
+    ) -> binding_model::BoundResources {
+        use crate::binding_model::{BindingResource as Br, BoundResource};
+
+        let mut bound_resources = binding_model::BoundResources::new();
+        for entry in entries.iter() {
+            let decl = &layout.entries[&entry.binding];
+            let count = decl.count.map_or(1, NonZeroU32::get) as usize;
+            let mut elements = match entry.resource {
+                Br::Buffer(ref bb) => vec![Some(BoundResource::Buffer(bb.clone()))],
+                Br::BufferArray(ref bindings_array) => bindings_array
+                    .iter()
+                    .map(|bb| Some(BoundResource::Buffer(bb.clone())))
+                    .collect(),
+                Br::Sampler(id) => vec![Some(BoundResource::Sampler(id))],
+                Br::SamplerArray(ref bindings_array) => bindings_array
+                    .iter()
+                    .map(|&id| Some(BoundResource::Sampler(id)))
+                    .collect(),
+                Br::TextureView(id) => vec![Some(BoundResource::TextureView(id))],
+                Br::TextureViewArray(ref bindings_array) => bindings_array
+                    .iter()
+                    .map(|&id| Some(BoundResource::TextureView(id)))
+                    .collect(),
+            };
+            elements.resize(count, None);
+            bound_resources.insert(entry.binding, elements);
+        }
+        bound_resources
+    }
+
+    /// Replaces elements of binding arrays of a bind group whose layout allows
+    /// updates after bind.
+    ///
+    /// The caller checks that the bind group isn't used by any command buffer,
+    /// render bundle or unfinished submission. The usage of the bind group is
+    /// rebuilt from all of its resources, so that the command buffers recorded
+    /// afterwards transition the new resources.
+    pub(super) fn update_bind_group<G: GlobalIdentityHandlerFactory>(
+        &mut self,
+        bind_group: &mut binding_model::BindGroup<A>,
+        layout: &binding_model::BindGroupLayout<A>,
+        entries: &[binding_model::BindGroupUpdateEntry],
+        hub: &Hub<A, G>,
+        token: &mut Token<binding_model::BindGroup<A>>,
+    ) -> Result<(), binding_model::UpdateBindGroupError> {
+        use crate::binding_model::{
+            BindingResource as Br, BoundResource, UpdateBindGroupError as Error,
+        };
+
+        let mut bound_resources = bind_group
+            .bound_resources
+            .clone()
+            .ok_or(Error::NotUpdatable)?;
+
+        for entry in entries.iter() {
+            let binding = entry.binding;
+            let decl = layout
+                .entries
+                .get(&binding)
+                .ok_or(Error::MissingBindingDeclaration(binding))?;
+            if !binding_model::is_updatable_after_bind(decl) {
+                return Err(Error::BindingNotUpdatable(binding));
+            }
+
+            let resources: Vec<_> = match entry.resource {
+                Br::Buffer(ref bb) => vec![BoundResource::Buffer(bb.clone())],
+                Br::BufferArray(ref bindings_array) => bindings_array
+                    .iter()
+                    .map(|bb| BoundResource::Buffer(bb.clone()))
+                    .collect(),
+                Br::Sampler(id) => vec![BoundResource::Sampler(id)],
+                Br::SamplerArray(ref bindings_array) => bindings_array
+                    .iter()
+                    .map(|&id| BoundResource::Sampler(id))
+                    .collect(),
+                Br::TextureView(id) => vec![BoundResource::TextureView(id)],
+                Br::TextureViewArray(ref bindings_array) => bindings_array
+                    .iter()
+                    .map(|&id| BoundResource::TextureView(id))
+                    .collect(),
+            };
+
+            let elements = bound_resources
+                .get_mut(&binding)
+                .ok_or(Error::MissingBindingDeclaration(binding))?;
+            let start = entry.array_offset as usize;
+            let end = start + resources.len();
+            if end > elements.len() {
+                return Err(Error::ArrayOutOfBounds {
+                    binding,
+                    range: entry.array_offset..end as u32,
+                    count: elements.len() as u32,
+                });
+            }
+            for (element, resource) in elements[start..end].iter_mut().zip(resources) {
+                *element = Some(resource);
+            }
+        }
+
+        let (buffer_guard, mut token) = hub.buffers.read(token);
+        let (texture_guard, mut token) = hub.textures.read(&mut token);
+        let (texture_view_guard, mut token) = hub.texture_views.read(&mut token);
+        let (sampler_guard, _) = hub.samplers.read(&mut token);
+
+        // Validate all the resources, as the bind group is used by the next
+        // submissions as a whole.
+        let mut used = BindGroupStates::new();
+        let mut used_buffer_ranges = Vec::new();
+        let mut used_texture_ranges = Vec::new();
+        let mut late_buffer_binding_sizes = FastHashMap::default();
+        for (&binding, elements) in bound_resources.iter() {
+            let decl = &layout.entries[&binding];
+            for resource in elements.iter().flatten() {
+                match *resource {
+                    BoundResource::Buffer(ref bb) => {
+                        Self::create_buffer_binding(
+                            bb,
+                            binding,
+                            decl,
+                            &mut used_buffer_ranges,
+                            &mut Vec::new(),
+                            &mut late_buffer_binding_sizes,
+                            &mut used,
+                            &*buffer_guard,
+                            &self.limits,
+                        )?;
+                    }
+                    BoundResource::Sampler(id) => {
+                        let sampler = used
+                            .samplers
+                            .add_single(&*sampler_guard, id)
+                            .ok_or(binding_model::CreateBindGroupError::InvalidSampler(id))?;
+                        Self::check_sampler_binding(binding, decl, sampler)?;
+                    }
+                    BoundResource::TextureView(id) => {
+                        let view = used
+                            .views
+                            .add_single(&*texture_view_guard, id)
+                            .ok_or(binding_model::CreateBindGroupError::InvalidTextureView(id))?;
+                        let (pub_usage, internal_use) = Self::texture_use_parameters(
+                            binding,
+                            decl,
+                            view,
+                            "SampledTextureArray, ReadonlyStorageTextureArray or WriteonlyStorageTextureArray",
+                        )?;
+                        Self::create_texture_binding(
+                            view,
+                            &texture_guard,
+                            internal_use,
+                            pub_usage,
+                            &mut used,
+                            &mut used_texture_ranges,
+                        )?;
+                    }
+                }
+            }
+        }
+        used.optimize();
+
+        let mut hal_entries = Vec::with_capacity(entries.len());
+        let mut hal_buffers = Vec::new();
+        let mut hal_samplers = Vec::new();
+        let mut hal_textures = Vec::new();
+        for entry in entries.iter() {
+            let decl = &layout.entries[&entry.binding];
+            let elements = &bound_resources[&entry.binding];
+            let count = match entry.resource {
+                Br::Buffer(_) | Br::Sampler(_) | Br::TextureView(_) => 1,
+                Br::BufferArray(ref array) => array.len(),
+                Br::SamplerArray(ref array) => array.len(),
+                Br::TextureViewArray(ref array) => array.len(),
+            };
+            if count == 0 {
+                continue;
+            }
+            let start = entry.array_offset as usize;
+            let resource_index = match elements[start] {
+                Some(BoundResource::Buffer(_)) => hal_buffers.len(),
+                Some(BoundResource::Sampler(_)) => hal_samplers.len(),
+                Some(BoundResource::TextureView(_)) => hal_textures.len(),
+                None => unreachable!(),
+            };
+            for resource in elements[start..start + count].iter().flatten() {
+                // The resources were validated above.
+                match *resource {
+                    BoundResource::Buffer(ref bb) => {
+                        let buffer = &buffer_guard[id::Valid(bb.buffer_id)];
+                        hal_buffers.push(hal::BufferBinding {
+                            buffer: buffer.raw.as_ref().unwrap(),
+                            offset: bb.offset,
+                            size: bb.size,
+                        });
+                    }
+                    BoundResource::Sampler(id) => {
+                        hal_samplers.push(&sampler_guard[id::Valid(id)].raw);
+                    }
+                    BoundResource::TextureView(id) => {
+                        let view = &texture_view_guard[id::Valid(id)];
+                        let (_, internal_use) = Self::texture_use_parameters(
+                            entry.binding,
+                            decl,
+                            view,
+                            "SampledTextureArray, ReadonlyStorageTextureArray or WriteonlyStorageTextureArray",
+                        )?;
+                        hal_textures.push(hal::TextureBinding {
+                            view: &view.raw,
+                            usage: internal_use,
+                        });
+                    }
+                }
+            }
+            hal_entries.push(hal::BindGroupUpdateEntry {
+                binding: entry.binding,
+                array_offset: entry.array_offset,
+                resource_index: resource_index as u32,
+                count: count as u32,
+            });
+        }
+
+        let hal_desc = hal::BindGroupUpdateDescriptor {
+            layout: &layout.raw,
+            entries: &hal_entries,
+            buffers: &hal_buffers,
+            samplers: &hal_samplers,
+            textures: &hal_textures,
+        };
+        unsafe {
+            self.raw.update_bind_group(&bind_group.raw, &hal_desc);
+        }
+
+        bind_group.used = used;
+        bind_group.used_buffer_ranges = used_buffer_ranges;
+        bind_group.used_texture_ranges = used_texture_ranges;
+        bind_group.late_buffer_binding_sizes = layout
+            .entries
+            .keys()
+            .flat_map(|binding| late_buffer_binding_sizes.get(binding).cloned())
+            .collect();
+        bind_group.bound_resources = Some(bound_resources);
+        Ok(())
+    }
+
     fn check_array_binding(
         features: wgt::Features,
         count: Option<NonZeroU32>,
@@ -2303,7 +2782,7 @@ impl<A: HalApi> Device<A> {
         };
 
         Ok(binding_model::PipelineLayout {
-            raw,
+            raw: Arc::new(raw),
             device_id: Stored {
                 value: id::Valid(self_id),
                 ref_count: self.life_guard.add_ref(),
@@ -2379,6 +2858,29 @@ impl<A: HalApi> Device<A> {
         hub: &Hub<A, G>,
         token: &mut Token<Self>,
     ) -> Result<pipeline::ComputePipeline<A>, pipeline::CreateComputePipelineError> {
+        let (pending, sources) =
+            self.prepare_compute_pipeline(self_id, desc, implicit_context, hub, token)?;
+        let result = Self::compile_compute_pipeline(desc, &sources);
+        sources.release();
+        let raw = result?;
+        Ok(pending.into_pipeline(raw, desc))
+    }
+
+    /// Validates a compute pipeline and derives its implicit layout, if any.
+    pub(super) fn prepare_compute_pipeline<G: GlobalIdentityHandlerFactory>(
+        &self,
+        self_id: id::DeviceId,
+        desc: &pipeline::ComputePipelineDescriptor,
+        implicit_context: Option<ImplicitPipelineContext>,
+        hub: &Hub<A, G>,
+        token: &mut Token<Self>,
+    ) -> Result<
+        (
+            pipeline::PendingComputePipeline,
+            pipeline::ComputePipelineSources<A>,
+        ),
+        pipeline::CreateComputePipelineError,
+    > {
         //TODO: only lock mutable if the layout is derived
         let (mut pipeline_layout_guard, mut token) = hub.pipeline_layouts.write(token);
         let (mut bgl_guard, mut token) = hub.bind_group_layouts.write(&mut token);
@@ -2431,6 +2933,7 @@ impl<A: HalApi> Device<A> {
                     io,
                     None,
                 )?;
+                interface.check_constants(&desc.stage.constants)?;
             }
         }
 
@@ -2451,32 +2954,7 @@ impl<A: HalApi> Device<A> {
         let late_sized_buffer_groups =
             Device::make_late_sized_buffer_groups(&shader_binding_sizes, layout, &*bgl_guard);
 
-        let pipeline_desc = hal::ComputePipelineDescriptor {
-            label: desc.label.borrow_option(),
-            layout: &layout.raw,
-            stage: hal::ProgrammableStage {
-                entry_point: desc.stage.entry_point.as_ref(),
-                module: &shader_module.raw,
-            },
-        };
-
-        let raw =
-            unsafe { self.raw.create_compute_pipeline(&pipeline_desc) }.map_err(
-                |err| match err {
-                    hal::PipelineError::Device(error) => {
-                        pipeline::CreateComputePipelineError::Device(error.into())
-                    }
-                    hal::PipelineError::Linkage(_stages, msg) => {
-                        pipeline::CreateComputePipelineError::Internal(msg)
-                    }
-                    hal::PipelineError::EntryPoint(_stage) => {
-                        pipeline::CreateComputePipelineError::Internal(EP_FAILURE.to_string())
-                    }
-                },
-            )?;
-
-        let pipeline = pipeline::ComputePipeline {
-            raw,
+        let pending = pipeline::PendingComputePipeline {
             layout_id: Stored {
                 value: id::Valid(pipeline_layout_id),
                 ref_count: layout.life_guard.add_ref(),
@@ -2486,9 +2964,50 @@ impl<A: HalApi> Device<A> {
                 ref_count: self.life_guard.add_ref(),
             },
             late_sized_buffer_groups,
-            life_guard: LifeGuard::new(desc.label.borrow_or_default()),
         };
-        Ok(pipeline)
+        let sources = pipeline::ComputePipelineSources {
+            raw_device: Arc::clone(&self.raw),
+            raw_layout: Arc::clone(&layout.raw),
+            module: Arc::clone(&shader_module.raw),
+            compilation: self.pipeline_compilations.start(),
+        };
+        Ok((pending, sources))
+    }
+
+    /// Creates the backend pipeline of a validated compute pipeline.
+    ///
+    /// This doesn't touch the hub, so it can run on a worker thread without
+    /// holding any lock. The caller releases the sources once the pipeline
+    /// is registered.
+    pub(super) fn compile_compute_pipeline(
+        desc: &pipeline::ComputePipelineDescriptor,
+        sources: &pipeline::ComputePipelineSources<A>,
+    ) -> Result<A::ComputePipeline, pipeline::CreateComputePipelineError> {
+        let pipeline_desc = hal::ComputePipelineDescriptor {
+            label: desc.label.borrow_option(),
+            layout: &*sources.raw_layout,
+            stage: hal::ProgrammableStage {
+                entry_point: desc.stage.entry_point.as_ref(),
+                module: &*sources.module,
+                constants: desc.stage.constants.as_ref(),
+            },
+        };
+
+        let result = unsafe { sources.raw_device.create_compute_pipeline(&pipeline_desc) };
+        result.map_err(|err| match err {
+            hal::PipelineError::Device(error) => {
+                pipeline::CreateComputePipelineError::Device(error.into())
+            }
+            hal::PipelineError::Layout(_stages, msg) => {
+                pipeline::CreateComputePipelineError::Layout(msg)
+            }
+            hal::PipelineError::Linkage(_stages, msg) => {
+                pipeline::CreateComputePipelineError::Internal(msg)
+            }
+            hal::PipelineError::EntryPoint(_stage) => {
+                pipeline::CreateComputePipelineError::Internal(EP_FAILURE.to_string())
+            }
+        })
     }
 
     pub(super) fn create_render_pipeline<G: GlobalIdentityHandlerFactory>(
@@ -2500,6 +3019,30 @@ impl<A: HalApi> Device<A> {
         hub: &Hub<A, G>,
         token: &mut Token<Self>,
     ) -> Result<pipeline::RenderPipeline<A>, pipeline::CreateRenderPipelineError> {
+        let (pending, sources) =
+            self.prepare_render_pipeline(self_id, adapter, desc, implicit_context, hub, token)?;
+        let result = Self::compile_render_pipeline(desc, &sources);
+        sources.release();
+        let raw = result?;
+        Ok(pending.into_pipeline(raw, desc))
+    }
+
+    /// Validates a render pipeline and derives its implicit layout, if any.
+    pub(super) fn prepare_render_pipeline<G: GlobalIdentityHandlerFactory>(
+        &self,
+        self_id: id::DeviceId,
+        adapter: &Adapter<A>,
+        desc: &pipeline::RenderPipelineDescriptor,
+        implicit_context: Option<ImplicitPipelineContext>,
+        hub: &Hub<A, G>,
+        token: &mut Token<Self>,
+    ) -> Result<
+        (
+            pipeline::PendingRenderPipeline,
+            pipeline::RenderPipelineSources<A>,
+        ),
+        pipeline::CreateRenderPipelineError,
+    > {
         use wgt::TextureFormatFeatureFlags as Tfff;
 
         //TODO: only lock mutable if the layout is derived
@@ -2551,7 +3094,7 @@ impl<A: HalApi> Device<A> {
         let mut validated_stages = wgt::ShaderStages::empty();
 
         let mut vertex_steps = Vec::with_capacity(desc.vertex.buffers.len());
-        let mut vertex_buffers = Vec::with_capacity(desc.vertex.buffers.len());
+        let mut vertex_buffer_count = 0;
         let mut total_attributes = 0;
         for (i, vb_state) in desc.vertex.buffers.iter().enumerate() {
             vertex_steps.push(pipeline::VertexStep {
@@ -2574,11 +3117,7 @@ impl<A: HalApi> Device<A> {
                     stride: vb_state.array_stride,
                 });
             }
-            vertex_buffers.push(hal::VertexBufferLayout {
-                array_stride: vb_state.array_stride,
-                step_mode: vb_state.step_mode,
-                attributes: vb_state.attributes.as_ref(),
-            });
+            vertex_buffer_count += 1;
 
             for attribute in vb_state.attributes.iter() {
                 if attribute.offset >= 0x10000000 {
@@ -2612,9 +3151,9 @@ impl<A: HalApi> Device<A> {
             total_attributes += vb_state.attributes.len();
         }
 
-        if vertex_buffers.len() > self.limits.max_vertex_buffers as usize {
+        if vertex_buffer_count > self.limits.max_vertex_buffers as usize {
             return Err(pipeline::CreateRenderPipelineError::TooManyVertexBuffers {
-                given: vertex_buffers.len() as u32,
+                given: vertex_buffer_count as u32,
                 limit: self.limits.max_vertex_buffers,
             });
         }
@@ -2760,7 +3299,7 @@ impl<A: HalApi> Device<A> {
 
         let (shader_module_guard, _) = hub.shader_modules.read(&mut token);
 
-        let vertex_stage = {
+        let vertex_module = {
             let stage = &desc.vertex.stage;
             let flag = wgt::ShaderStages::VERTEX;
 
@@ -2800,15 +3339,18 @@ impl<A: HalApi> Device<A> {
                         error,
                     })?;
                 validated_stages |= flag;
+                interface
+                    .check_constants(&stage.constants)
+                    .map_err(|error| pipeline::CreateRenderPipelineError::Stage {
+                        stage: flag,
+                        error,
+                    })?;
             }
 
-            hal::ProgrammableStage {
-                module: &shader_module.raw,
-                entry_point: stage.entry_point.as_ref(),
-            }
+            Arc::clone(&shader_module.raw)
         };
 
-        let fragment_stage = match desc.fragment {
+        let fragment_module = match desc.fragment {
             Some(ref fragment) => {
                 let flag = wgt::ShaderStages::FRAGMENT;
 
@@ -2830,6 +3372,15 @@ impl<A: HalApi> Device<A> {
                     None => None,
                 };
 
+                if let Some(ref interface) = shader_module.interface {
+                    interface
+                        .check_constants(&fragment.stage.constants)
+                        .map_err(|error| pipeline::CreateRenderPipelineError::Stage {
+                            stage: flag,
+                            error,
+                        })?;
+                }
+
                 if validated_stages == wgt::ShaderStages::VERTEX {
                     if let Some(ref interface) = shader_module.interface {
                         io = interface
@@ -2850,10 +3401,7 @@ impl<A: HalApi> Device<A> {
                     }
                 }
 
-                Some(hal::ProgrammableStage {
-                    module: &shader_module.raw,
-                    entry_point: fragment.stage.entry_point.as_ref(),
-                })
+                Some(Arc::clone(&shader_module.raw))
             }
             None => None,
         };
@@ -2877,9 +3425,9 @@ impl<A: HalApi> Device<A> {
                     _ => {
                         log::info!(
                             "The fragment stage {:?} output @location({}) values are ignored",
-                            fragment_stage
+                            desc.fragment
                                 .as_ref()
-                                .map_or("", |stage| stage.entry_point),
+                                .map_or("", |fragment| &fragment.stage.entry_point),
                             i
                         );
                     }
@@ -2932,36 +3480,6 @@ impl<A: HalApi> Device<A> {
         let late_sized_buffer_groups =
             Device::make_late_sized_buffer_groups(&shader_binding_sizes, layout, &*bgl_guard);
 
-        let pipeline_desc = hal::RenderPipelineDescriptor {
-            label: desc.label.borrow_option(),
-            layout: &layout.raw,
-            vertex_buffers: &vertex_buffers,
-            vertex_stage,
-            primitive: desc.primitive,
-            depth_stencil: desc.depth_stencil.clone(),
-            multisample: desc.multisample,
-            fragment_stage,
-            color_targets,
-            multiview: desc.multiview,
-        };
-        let raw =
-            unsafe { self.raw.create_render_pipeline(&pipeline_desc) }.map_err(
-                |err| match err {
-                    hal::PipelineError::Device(error) => {
-                        pipeline::CreateRenderPipelineError::Device(error.into())
-                    }
-                    hal::PipelineError::Linkage(stage, msg) => {
-                        pipeline::CreateRenderPipelineError::Internal { stage, error: msg }
-                    }
-                    hal::PipelineError::EntryPoint(stage) => {
-                        pipeline::CreateRenderPipelineError::Internal {
-                            stage: hal::auxil::map_naga_stage(stage),
-                            error: EP_FAILURE.to_string(),
-                        }
-                    }
-                },
-            )?;
-
         let pass_context = RenderPassContext {
             attachments: AttachmentData {
                 colors: color_targets
@@ -2994,9 +3512,27 @@ impl<A: HalApi> Device<A> {
                 flags |= pipeline::PipelineFlags::WRITES_STENCIL;
             }
         }
+        if desc.primitive.polygon_mode == wgt::PolygonMode::Line
+            && matches!(
+                desc.primitive.topology,
+                wgt::PrimitiveTopology::TriangleList | wgt::PrimitiveTopology::TriangleStrip
+            )
+            && self
+                .downlevel
+                .flags
+                .contains(wgt::DownlevelFlags::EMULATED_POLYGON_MODE_LINE)
+        {
+            flags |= pipeline::PipelineFlags::EMULATED_POLYGON_MODE_LINE;
+        }
 
-        let pipeline = pipeline::RenderPipeline {
-            raw,
+        let sources = pipeline::RenderPipelineSources {
+            raw_device: Arc::clone(&self.raw),
+            raw_layout: Arc::clone(&layout.raw),
+            vertex_module,
+            fragment_module,
+            compilation: self.pipeline_compilations.start(),
+        };
+        let pending = pipeline::PendingRenderPipeline {
             layout_id: Stored {
                 value: id::Valid(pipeline_layout_id),
                 ref_count: layout.life_guard.add_ref(),
@@ -3007,12 +3543,81 @@ impl<A: HalApi> Device<A> {
             },
             pass_context,
             flags,
-            strip_index_format: desc.primitive.strip_index_format,
             vertex_steps,
             late_sized_buffer_groups,
-            life_guard: LifeGuard::new(desc.label.borrow_or_default()),
         };
-        Ok(pipeline)
+        Ok((pending, sources))
+    }
+
+    /// Creates the backend pipeline of a validated render pipeline.
+    ///
+    /// This doesn't touch the hub, so it can run on a worker thread without
+    /// holding any lock. The caller releases the sources once the pipeline
+    /// is registered.
+    pub(super) fn compile_render_pipeline(
+        desc: &pipeline::RenderPipelineDescriptor,
+        sources: &pipeline::RenderPipelineSources<A>,
+    ) -> Result<A::RenderPipeline, pipeline::CreateRenderPipelineError> {
+        let vertex_stage = hal::ProgrammableStage {
+            module: &*sources.vertex_module,
+            entry_point: desc.vertex.stage.entry_point.as_ref(),
+            constants: desc.vertex.stage.constants.as_ref(),
+        };
+        let fragment_stage = desc
+            .fragment
+            .as_ref()
+            .zip(sources.fragment_module.as_ref())
+            .map(|(fragment, module)| hal::ProgrammableStage {
+                module: &**module,
+                entry_point: fragment.stage.entry_point.as_ref(),
+                constants: fragment.stage.constants.as_ref(),
+            });
+
+        let vertex_buffers = desc
+            .vertex
+            .buffers
+            .iter()
+            .filter(|vb_state| !vb_state.attributes.is_empty())
+            .map(|vb_state| hal::VertexBufferLayout {
+                array_stride: vb_state.array_stride,
+                step_mode: vb_state.step_mode,
+                attributes: vb_state.attributes.as_ref(),
+            })
+            .collect::<Vec<_>>();
+
+        let pipeline_desc = hal::RenderPipelineDescriptor {
+            label: desc.label.borrow_option(),
+            layout: &*sources.raw_layout,
+            vertex_buffers: &vertex_buffers,
+            vertex_stage,
+            primitive: desc.primitive,
+            depth_stencil: desc.depth_stencil.clone(),
+            multisample: desc.multisample,
+            fragment_stage,
+            color_targets: desc
+                .fragment
+                .as_ref()
+                .map_or(&[][..], |fragment| &fragment.targets),
+            multiview: desc.multiview,
+        };
+        let result = unsafe { sources.raw_device.create_render_pipeline(&pipeline_desc) };
+        result.map_err(|err| match err {
+            hal::PipelineError::Device(error) => {
+                pipeline::CreateRenderPipelineError::Device(error.into())
+            }
+            hal::PipelineError::Layout(stage, msg) => {
+                pipeline::CreateRenderPipelineError::Layout { stage, error: msg }
+            }
+            hal::PipelineError::Linkage(stage, msg) => {
+                pipeline::CreateRenderPipelineError::Internal { stage, error: msg }
+            }
+            hal::PipelineError::EntryPoint(stage) => {
+                pipeline::CreateRenderPipelineError::Internal {
+                    stage: hal::auxil::map_naga_stage(stage),
+                    error: EP_FAILURE.to_string(),
+                }
+            }
+        })
     }
 
     pub(super) fn describe_format_features(
@@ -3125,6 +3730,10 @@ impl<A: HalApi> Device<A> {
     }
 
     /// Wait for idle and remove resources that we can, before we die.
+    ///
+    /// The caller waits for the pipeline compilations of the device first,
+    /// without holding the lock of the devices, which the compilations take
+    /// to register their pipeline.
     pub(crate) fn prepare_to_die(&mut self) {
         self.pending_writes.deactivate();
         let mut life_tracker = self.life_tracker.lock();
@@ -3141,12 +3750,20 @@ impl<A: HalApi> Device<A> {
     }
 
     pub(crate) fn dispose(self) {
+        // Pipeline compilations hold clones of `raw` and of the objects they
+        // are compiled from until they are done.
+        self.pipeline_compilations.wait();
         self.pending_writes.dispose(&self.raw);
         self.command_allocator.into_inner().dispose(&self.raw);
+        // Frees the objects that were orphaned while pipelines were compiled.
+        self.life_tracker.into_inner().cleanup(&self.raw);
+        let raw = Arc::try_unwrap(self.raw)
+            .ok()
+            .expect("Device is still shared after its pipeline compilations are done");
         unsafe {
-            self.raw.destroy_buffer(self.zero_buffer);
-            self.raw.destroy_fence(self.fence);
-            self.raw.exit(self.queue);
+            raw.destroy_buffer(self.zero_buffer);
+            raw.destroy_fence(self.fence);
+            raw.exit(self.queue);
         }
     }
 }
@@ -0,0 +1,153 @@
+use crate::{pipeline, validation, FastHashMap};
+
+use std::sync::{Arc, Weak};
+
+/// Statistics of the shader module caches of the devices of a hub.
+#[derive(Clone, Debug, Default)]
+pub struct ShaderModuleCacheReport {
+    /// Number of shader modules which reused a module of the cache.
+    pub hits: usize,
+    /// Number of cacheable shader modules which had to be parsed and validated.
+    pub misses: usize,
+    /// Number of live modules in the cache.
+    pub entries: usize,
+}
+
+/// Source of a cacheable shader module, compared in full to detect duplicates.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+enum CacheSource {
+    #[cfg(feature = "wgsl")]
+    Wgsl(String),
+    #[cfg(feature = "glsl")]
+    Glsl {
+        code: String,
+        stage: naga::ShaderStage,
+        /// Sorted by name.
+        defines: Vec<(String, String)>,
+    },
+}
+
+/// Key of a module in the [`ShaderModuleCache`].
+///
+/// The hash of the key covers the whole source, so identical sources land in
+/// the same entry.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub(crate) struct ShaderModuleCacheKey {
+    source: CacheSource,
+    runtime_checks: bool,
+}
+
+impl ShaderModuleCacheKey {
+    /// Returns the key of a module, or `None` if modules from this kind of
+    /// source aren't cached.
+    #[cfg_attr(
+        not(any(feature = "wgsl", feature = "glsl")),
+        allow(unused_variables, unreachable_code)
+    )]
+    pub(crate) fn new(
+        source: &pipeline::ShaderModuleSource,
+        shader_bound_checks: &wgt::ShaderBoundChecks,
+    ) -> Option<Self> {
+        let source = match *source {
+            #[cfg(feature = "wgsl")]
+            pipeline::ShaderModuleSource::Wgsl(ref code) => CacheSource::Wgsl(code.to_string()),
+            #[cfg(feature = "glsl")]
+            pipeline::ShaderModuleSource::Glsl(ref code, ref options) => {
+                let mut defines = options
+                    .defines
+                    .iter()
+                    .map(|(name, value)| (name.clone(), value.clone()))
+                    .collect::<Vec<_>>();
+                defines.sort();
+                CacheSource::Glsl {
+                    code: code.to_string(),
+                    stage: options.stage,
+                    defines,
+                }
+            }
+            _ => return None,
+        };
+        Some(Self {
+            source,
+            runtime_checks: shader_bound_checks.runtime_checks(),
+        })
+    }
+}
+
+#[derive(Debug)]
+struct CachedShaderModule<A: hal::Api> {
+    /// Only kept while a shader module or pipeline compilation uses it.
+    raw: Weak<A::ShaderModule>,
+    interface: Arc<validation::Interface>,
+}
+
+/// Shader modules of a device, deduplicated by source.
+///
+/// Duplicates share the backend module, and with it the validated naga
+/// module, as well as the interface used to validate pipelines.
+#[derive(Debug)]
+pub(crate) struct ShaderModuleCache<A: hal::Api> {
+    modules: FastHashMap<ShaderModuleCacheKey, CachedShaderModule<A>>,
+    hits: usize,
+    misses: usize,
+}
+
+impl<A: hal::Api> ShaderModuleCache<A> {
+    pub(crate) fn new() -> Self {
+        Self {
+            modules: FastHashMap::default(),
+            hits: 0,
+            misses: 0,
+        }
+    }
+
+    /// Returns the live module created from the same source, if any.
+    pub(crate) fn get(
+        &mut self,
+        key: &ShaderModuleCacheKey,
+    ) -> Option<(Arc<A::ShaderModule>, Arc<validation::Interface>)> {
+        let cached = self.modules.get(key)?;
+        match cached.raw.upgrade() {
+            Some(raw) => {
+                let interface = Arc::clone(&cached.interface);
+                self.hits += 1;
+                Some((raw, interface))
+            }
+            None => {
+                self.modules.remove(key);
+                None
+            }
+        }
+    }
+
+    /// Adds a module which wasn't found in the cache.
+    pub(crate) fn insert(
+        &mut self,
+        key: ShaderModuleCacheKey,
+        raw: &Arc<A::ShaderModule>,
+        interface: &Arc<validation::Interface>,
+    ) {
+        self.modules
+            .retain(|_, cached| cached.raw.strong_count() != 0);
+        self.modules.insert(
+            key,
+            CachedShaderModule {
+                raw: Arc::downgrade(raw),
+                interface: Arc::clone(interface),
+            },
+        );
+        self.misses += 1;
+    }
+
+    pub(crate) fn generate_report(&self) -> ShaderModuleCacheReport {
+        ShaderModuleCacheReport {
+            hits: self.hits,
+            misses: self.misses,
+            entries: self
+                .modules
+                .values()
+                .filter(|cached| cached.raw.strong_count() != 0)
+                .count(),
+        }
+    }
+}
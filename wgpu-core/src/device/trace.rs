@@ -9,6 +9,32 @@ type FileName = String;
 
 pub const FILE_NAME: &str = "trace.ron";
 
+/// Inlines the preprocessor definitions given to the GLSL frontend after the
+/// `#version` directive, so that the recorded shader can be parsed on its own.
+#[cfg(all(feature = "trace", feature = "glsl"))]
+pub(crate) fn glsl_with_defines(code: &str, defines: &naga::FastHashMap<String, String>) -> String {
+    let mut lines = defines
+        .iter()
+        .map(|(name, value)| format!("#define {name} {value}\n"))
+        .collect::<Vec<_>>();
+    lines.sort();
+
+    let split = match code.find("#version") {
+        Some(start) => code[start..]
+            .find('\n')
+            .map_or(code.len(), |end| start + end + 1),
+        None => 0,
+    };
+    let (head, tail) = code.split_at(split);
+    let mut result = head.to_string();
+    if !result.is_empty() && !result.ends_with('\n') {
+        result.push('\n');
+    }
+    result.extend(lines);
+    result.push_str(tail);
+    result
+}
+
 #[cfg(feature = "trace")]
 pub(crate) fn new_render_bundle_encoder_descriptor<'a>(
     label: crate::Label<'a>,
@@ -122,6 +148,10 @@ pub enum Action<'a> {
         layout: wgt::ImageDataLayout,
         size: wgt::Extent3d,
     },
+    UpdateBindGroup {
+        id: id::BindGroupId,
+        entries: Vec<crate::binding_model::BindGroupUpdateEntry<'a>>,
+    },
     Submit(crate::SubmissionIndex, Vec<Command>),
 }
 
@@ -159,6 +159,73 @@ pub fn format_pretty_any(
     fmt.error(error)
 }
 
+/// The type of an error, matching the filters of WebGPU error scopes.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum ErrorType {
+    /// The API was used incorrectly.
+    Validation,
+    /// An allocation failed.
+    OutOfMemory,
+    /// The implementation failed for a reason unrelated to the use of the API,
+    /// like a shader failing to translate or the device being lost.
+    Internal,
+}
+
+/// Returns the [`ErrorType`] of an error returned by [`Global`],
+/// looking through the chain of its sources.
+///
+/// Errors which aren't known to be caused by the device or the backend are
+/// classified as [`ErrorType::Validation`].
+pub fn error_type(error: &(dyn Error + 'static)) -> ErrorType {
+    let mut source = Some(error);
+    while let Some(error) = source {
+        if let Some(ty) = direct_error_type(error) {
+            return ty;
+        }
+        source = error.source();
+    }
+    ErrorType::Validation
+}
+
+fn direct_error_type(error: &(dyn Error + 'static)) -> Option<ErrorType> {
+    use crate::{binding_model, command, device, pipeline, present, resource};
+
+    // Most errors wrap `DeviceError` transparently, which hides it from the
+    // chain of sources, so each of them classifies its own variants.
+    macro_rules! error_type {
+        ($($ty:path),* $(,)?) => {
+            $(
+                if let Some(err) = error.downcast_ref::<$ty>() {
+                    return Some(err.error_type());
+                }
+            )*
+        };
+    }
+
+    error_type!(
+        device::DeviceError,
+        resource::BufferAccessError,
+        resource::CreateBufferError,
+        resource::CreateTextureError,
+        resource::CreateSamplerError,
+        resource::CreateQuerySetError,
+        binding_model::CreateBindGroupLayoutError,
+        binding_model::CreateBindGroupError,
+        binding_model::UpdateBindGroupError,
+        binding_model::CreatePipelineLayoutError,
+        pipeline::CreateShaderModuleError,
+        pipeline::CreateComputePipelineError,
+        pipeline::CreateRenderPipelineError,
+        device::queue::QueueWriteError,
+        device::queue::QueueSubmitError,
+        present::SurfaceError,
+        present::ConfigureSurfaceError,
+        command::RenderBundleError,
+    );
+
+    None
+}
+
 #[derive(Debug)]
 pub struct ContextError {
     pub string: &'static str,
@@ -200,3 +267,49 @@ impl Error for ContextError {
         Some(self.cause.as_ref())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::{error_type, ContextError, ErrorType};
+    use crate::{
+        device::DeviceError,
+        pipeline::{CreateRenderPipelineError, CreateShaderModuleError},
+        resource::{BufferAccessError, CreateBufferError},
+    };
+
+    fn in_context(cause: impl std::error::Error + Send + Sync + 'static) -> ContextError {
+        ContextError {
+            string: "Device::create_buffer",
+            cause: Box::new(cause),
+            label_key: "",
+            label: String::new(),
+        }
+    }
+
+    #[test]
+    fn device_errors() {
+        let lost = in_context(CreateBufferError::Device(DeviceError::Lost));
+        assert_eq!(error_type(&lost), ErrorType::Internal);
+        let oom = in_context(CreateBufferError::Device(DeviceError::OutOfMemory));
+        assert_eq!(error_type(&oom), ErrorType::OutOfMemory);
+        let invalid = in_context(CreateBufferError::Device(DeviceError::Invalid));
+        assert_eq!(error_type(&invalid), ErrorType::Validation);
+        let nested = in_context(CreateBufferError::AccessError(BufferAccessError::Device(
+            DeviceError::OutOfMemory,
+        )));
+        assert_eq!(error_type(&nested), ErrorType::OutOfMemory);
+    }
+
+    #[test]
+    fn backend_errors() {
+        let generation = in_context(CreateShaderModuleError::Generation);
+        assert_eq!(error_type(&generation), ErrorType::Internal);
+        let usage = in_context(CreateBufferError::InvalidUsage(wgt::BufferUsages::empty()));
+        assert_eq!(error_type(&usage), ErrorType::Validation);
+        let layout = in_context(CreateRenderPipelineError::Layout {
+            stage: wgt::ShaderStages::VERTEX,
+            error: String::new(),
+        });
+        assert_eq!(error_type(&layout), ErrorType::Validation);
+    }
+}
@@ -23,6 +23,25 @@ pub struct GlobalReport {
     pub gl: Option<HubReport>,
 }
 
+impl GlobalReport {
+    /// Returns the report of the hub of `backend`, if it was initialized.
+    pub fn hub_report(&self, backend: wgt::Backend) -> Option<&HubReport> {
+        match backend {
+            #[cfg(all(feature = "vulkan", not(target_arch = "wasm32")))]
+            wgt::Backend::Vulkan => self.vulkan.as_ref(),
+            #[cfg(all(feature = "metal", any(target_os = "macos", target_os = "ios")))]
+            wgt::Backend::Metal => self.metal.as_ref(),
+            #[cfg(all(feature = "dx12", windows))]
+            wgt::Backend::Dx12 => self.dx12.as_ref(),
+            #[cfg(all(feature = "dx11", windows))]
+            wgt::Backend::Dx11 => self.dx11.as_ref(),
+            #[cfg(feature = "gles")]
+            wgt::Backend::Gl => self.gl.as_ref(),
+            _ => None,
+        }
+    }
+}
+
 pub struct Global<G: GlobalIdentityHandlerFactory> {
     pub instance: Instance,
     pub surfaces: Registry<Surface, id::SurfaceId, G>,
@@ -158,7 +158,7 @@ flagged as errors as well.
 use crate::{
     binding_model::{BindGroup, BindGroupLayout, PipelineLayout},
     command::{CommandBuffer, RenderBundle},
-    device::Device,
+    device::{Device, ShaderModuleCacheReport},
     hal_api::HalApi,
     id,
     identity::GlobalIdentityHandlerFactory,
@@ -171,7 +171,7 @@ use crate::{
 
 #[cfg(debug_assertions)]
 use std::cell::Cell;
-use std::{fmt::Debug, marker::PhantomData};
+use std::{fmt::Debug, marker::PhantomData, sync::Arc};
 
 /// Type system for enforcing the lock order on [`Hub`] fields.
 ///
@@ -371,6 +371,8 @@ pub struct HubReport {
     pub textures: StorageReport,
     pub texture_views: StorageReport,
     pub samplers: StorageReport,
+    /// Summed over the devices of the hub.
+    pub shader_module_cache: ShaderModuleCacheReport,
 }
 
 impl HubReport {
@@ -497,7 +499,35 @@ impl<A: HalApi, F: GlobalIdentityHandlerFactory> Hub<A, F> {
         use crate::resource::TextureInner;
         use hal::{Device as _, Surface as _};
 
-        let mut devices = self.devices.data.write();
+        // Pipeline compilations register their pipeline under the lock of the
+        // devices, so they are waited for without holding it. New ones start
+        // under the lock, so none is left once it is taken with all idle.
+        let mut devices = loop {
+            let compilations = self
+                .devices
+                .data
+                .read()
+                .map
+                .iter()
+                .filter_map(|element| match *element {
+                    Element::Occupied(ref device, _) => {
+                        Some(Arc::clone(&device.pipeline_compilations))
+                    }
+                    _ => None,
+                })
+                .collect::<Vec<_>>();
+            for compilations in compilations.iter() {
+                compilations.wait();
+            }
+            let devices = self.devices.data.write();
+            let idle = devices.map.iter().all(|element| match *element {
+                Element::Occupied(ref device, _) => device.pipeline_compilations.is_idle(),
+                _ => true,
+            });
+            if idle {
+                break devices;
+            }
+        };
         for element in devices.map.iter_mut() {
             if let Element::Occupied(ref mut device, _) = *element {
                 device.prepare_to_die();
@@ -566,8 +596,11 @@ impl<A: HalApi, F: GlobalIdentityHandlerFactory> Hub<A, F> {
         for element in self.shader_modules.data.write().map.drain(..) {
             if let Element::Occupied(module, _) = element {
                 let device = &devices[module.device_id.value];
-                unsafe {
-                    device.raw.destroy_shader_module(module.raw);
+                // The pipeline compilations were waited for above.
+                if let Ok(raw) = Arc::try_unwrap(module.raw) {
+                    unsafe {
+                        device.raw.destroy_shader_module(raw);
+                    }
                 }
             }
         }
@@ -582,8 +615,10 @@ impl<A: HalApi, F: GlobalIdentityHandlerFactory> Hub<A, F> {
         for element in self.pipeline_layouts.data.write().map.drain(..) {
             if let Element::Occupied(pipeline_layout, _) = element {
                 let device = &devices[pipeline_layout.device_id.value];
-                unsafe {
-                    device.raw.destroy_pipeline_layout(pipeline_layout.raw);
+                if let Ok(raw) = Arc::try_unwrap(pipeline_layout.raw) {
+                    unsafe {
+                        device.raw.destroy_pipeline_layout(raw);
+                    }
                 }
             }
         }
@@ -661,6 +696,13 @@ impl<A: HalApi, F: GlobalIdentityHandlerFactory> Hub<A, F> {
     }
 
     pub fn generate_report(&self) -> HubReport {
+        let mut shader_module_cache = ShaderModuleCacheReport::default();
+        for (_, device) in self.devices.data.read().iter(A::VARIANT) {
+            let report = device.shader_module_cache.lock().generate_report();
+            shader_module_cache.hits += report.hits;
+            shader_module_cache.misses += report.misses;
+            shader_module_cache.entries += report.entries;
+        }
         HubReport {
             adapters: self.adapters.data.read().generate_report(),
             devices: self.devices.data.read().generate_report(),
@@ -677,6 +719,7 @@ impl<A: HalApi, F: GlobalIdentityHandlerFactory> Hub<A, F> {
             textures: self.textures.data.read().generate_report(),
             texture_views: self.texture_views.data.read().generate_report(),
             samplers: self.samplers.data.read().generate_report(),
+            shader_module_cache,
         }
     }
 }
@@ -82,6 +82,8 @@ impl Instance {
                     name: "wgpu",
                     flags,
                     dx12_shader_compiler: instance_desc.dx12_shader_compiler.clone(),
+                    gles_device: instance_desc.gles_device.clone(),
+                    gles_program_cache: instance_desc.gles_program_cache.clone(),
                 };
                 unsafe { hal::Instance::init(&hal_desc).ok() }
             } else {
@@ -193,6 +195,18 @@ impl<A: HalApi> Adapter<A> {
             .min_storage_buffer_offset_alignment
             .max(MIN_BUFFER_OFFSET_ALIGNMENT_LOWER_BOUND);
 
+        // Multi-draw-indirect can be lowered to a loop of single indirect draws
+        // when the backend can't do it natively. The feature isn't exposed, users
+        // opt in by requesting it from an adapter reporting the flag.
+        let downlevel = &mut raw.capabilities.downlevel;
+        if downlevel
+            .flags
+            .contains(wgt::DownlevelFlags::INDIRECT_EXECUTION)
+            && !raw.features.contains(wgt::Features::MULTI_DRAW_INDIRECT)
+        {
+            downlevel.flags |= wgt::DownlevelFlags::EMULATED_MULTI_DRAW_INDIRECT;
+        }
+
         Self {
             raw,
             life_guard: LifeGuard::new("<Adapter>"),
@@ -232,7 +246,7 @@ impl<A: HalApi> Adapter<A> {
             caps.contains(Tfc::STORAGE),
         );
         allowed_usages.set(
-            wgt::TextureUsages::RENDER_ATTACHMENT,
+            wgt::TextureUsages::RENDER_ATTACHMENT | wgt::TextureUsages::TRANSIENT_ATTACHMENT,
             caps.intersects(Tfc::COLOR_ATTACHMENT | Tfc::DEPTH_STENCIL_ATTACHMENT),
         );
 
@@ -308,14 +322,30 @@ impl<A: HalApi> Adapter<A> {
         desc: &DeviceDescriptor,
         trace_path: Option<&std::path::Path>,
     ) -> Result<Device<A>, RequestDeviceError> {
-        // Verify all features were exposed by the adapter
-        if !self.raw.features.contains(desc.features) {
+        let caps = &self.raw.capabilities;
+
+        // Verify all features were exposed by the adapter, or can be emulated
+        let mut supported_features = self.raw.features;
+        if caps
+            .downlevel
+            .flags
+            .contains(wgt::DownlevelFlags::EMULATED_MULTI_DRAW_INDIRECT)
+        {
+            supported_features |= wgt::Features::MULTI_DRAW_INDIRECT;
+        }
+        if caps
+            .downlevel
+            .flags
+            .contains(wgt::DownlevelFlags::EMULATED_POLYGON_MODE_LINE)
+        {
+            supported_features |= wgt::Features::POLYGON_MODE_LINE;
+        }
+        if !supported_features.contains(desc.features) {
             return Err(RequestDeviceError::UnsupportedFeature(
-                desc.features - self.raw.features,
+                desc.features - supported_features,
             ));
         }
 
-        let caps = &self.raw.capabilities;
         if wgt::Backends::PRIMARY.contains(wgt::Backends::from(A::VARIANT))
             && !caps.downlevel.is_webgpu_compliant()
         {
@@ -348,12 +378,25 @@ impl<A: HalApi> Adapter<A> {
             return Err(RequestDeviceError::LimitsExceeded(failed));
         }
 
-        let open = unsafe { self.raw.adapter.open(desc.features, &desc.limits) }.map_err(
-            |err| match err {
-                hal::DeviceError::Lost => RequestDeviceError::DeviceLost,
-                hal::DeviceError::OutOfMemory => RequestDeviceError::OutOfMemory,
-            },
-        )?;
+        // Emulated features are implemented by `wgpu-core`, the backend doesn't know about them.
+        let mut hal_features = desc.features;
+        if caps
+            .downlevel
+            .flags
+            .contains(wgt::DownlevelFlags::EMULATED_MULTI_DRAW_INDIRECT)
+        {
+            hal_features.remove(wgt::Features::MULTI_DRAW_INDIRECT);
+        }
+
+        let open = unsafe {
+            self.raw
+                .adapter
+                .open(hal_features, &desc.limits, &desc.allocator)
+        }
+        .map_err(|err| match err {
+            hal::DeviceError::Lost => RequestDeviceError::DeviceLost,
+            hal::DeviceError::OutOfMemory => RequestDeviceError::OutOfMemory,
+        })?;
 
         self.create_device_from_hal(self_id, open, desc, trace_path)
     }
@@ -1,13 +1,16 @@
 use crate::{
     binding_model::{CreateBindGroupLayoutError, CreatePipelineLayoutError},
     command::ColorAttachmentError,
-    device::{DeviceError, MissingDownlevelFlags, MissingFeatures, RenderPassContext},
-    id::{DeviceId, PipelineLayoutId, ShaderModuleId},
+    device::{
+        DeviceError, MissingDownlevelFlags, MissingFeatures, PipelineCompilation, RenderPassContext,
+    },
+    error::ErrorType,
+    id::{ComputePipelineId, DeviceId, PipelineLayoutId, RenderPipelineId, ShaderModuleId},
     resource::Resource,
-    validation, Label, LifeGuard, Stored,
+    validation, Label, LabelHelpers as _, LifeGuard, Stored,
 };
 use arrayvec::ArrayVec;
-use std::{borrow::Cow, error::Error, fmt, marker::PhantomData, num::NonZeroU32};
+use std::{borrow::Cow, error::Error, fmt, marker::PhantomData, num::NonZeroU32, sync::Arc};
 use thiserror::Error;
 
 /// Information about buffer bindings, which
@@ -23,6 +26,10 @@ pub(crate) struct LateSizedBufferGroup {
 pub enum ShaderModuleSource<'a> {
     #[cfg(feature = "wgsl")]
     Wgsl(Cow<'a, str>),
+    #[cfg(feature = "glsl")]
+    Glsl(Cow<'a, str>, naga::front::glsl::Options),
+    #[cfg(feature = "spirv")]
+    SpirV(Cow<'a, [u32]>, naga::front::spv::Options),
     Naga(Cow<'static, naga::Module>),
     /// Dummy variant because `Naga` doesn't have a lifetime and without enough active features it
     /// could be the last one active.
@@ -39,11 +46,41 @@ pub struct ShaderModuleDescriptor<'a> {
     pub shader_bound_checks: wgt::ShaderBoundChecks,
 }
 
+/// Describes a shader module given by backend-native shaders, which are passed
+/// to the backend as-is.
+///
+/// Only the source for the backend of the device is used.
+#[derive(Clone, Debug)]
+pub struct ShaderModuleDescriptorPassthrough<'a> {
+    pub label: Label<'a>,
+    /// Stage of the GLSL source or DXIL bytecode, which has a single `main`
+    /// entry point.
+    pub stage: wgt::ShaderStages,
+    /// Workgroup size of the MSL compute entry points.
+    pub workgroup_size: [u32; 3],
+    /// SPIR-V binary used on Vulkan.
+    pub spirv: Option<Cow<'a, [u32]>>,
+    /// Metal Shading Language source used on Metal.
+    pub msl: Option<Cow<'a, str>>,
+    /// DXIL bytecode of a single entry point used on DX12, in place of `hlsl`.
+    pub dxil: Option<Cow<'a, [u8]>>,
+    /// HLSL source used on DX12.
+    pub hlsl: Option<Cow<'a, str>>,
+    /// GLSL ES source used on GL.
+    pub glsl: Option<Cow<'a, str>>,
+    /// Maps the resources of the GLSL shader to the bindings of the pipeline
+    /// layout. Must be empty on the other backends.
+    pub bindings: Cow<'a, [wgt::PassthroughBinding<Cow<'a, str>>]>,
+}
+
 #[derive(Debug)]
 pub struct ShaderModule<A: hal::Api> {
-    pub(crate) raw: A::ShaderModule,
+    /// Shared with the pipeline compilations using this module, which may
+    /// outlive it.
+    pub(crate) raw: Arc<A::ShaderModule>,
     pub(crate) device_id: Stored<DeviceId>,
-    pub(crate) interface: Option<validation::Interface>,
+    /// Shared with the duplicates of this module.
+    pub(crate) interface: Option<Arc<validation::Interface>>,
     #[cfg(debug_assertions)]
     pub(crate) label: String,
 }
@@ -77,6 +114,42 @@ impl fmt::Display for ShaderError<naga::front::wgsl::ParseError> {
         write!(f, "\nShader '{label}' parsing {string}")
     }
 }
+#[cfg(feature = "glsl")]
+impl fmt::Display for ShaderError<GlslErrors> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        use codespan_reporting::{
+            diagnostic::{Diagnostic, Label},
+            files::SimpleFile,
+            term,
+        };
+
+        let label = self.label.as_deref().unwrap_or_default();
+        let files = SimpleFile::new(label, &self.source);
+        let config = term::Config::default();
+        let mut writer = term::termcolor::NoColor::new(Vec::new());
+
+        for error in self.inner.0.iter() {
+            let mut diagnostic = Diagnostic::error().with_message(error.kind.to_string());
+            if let Some(range) = error.meta.to_range() {
+                diagnostic = diagnostic.with_labels(vec![Label::primary((), range)]);
+            }
+            term::emit(&mut writer, &config, &files, &diagnostic).expect("cannot write error");
+        }
+
+        write!(
+            f,
+            "\nShader '{label}' parsing {}",
+            String::from_utf8_lossy(&writer.into_inner())
+        )
+    }
+}
+#[cfg(feature = "spirv")]
+impl fmt::Display for ShaderError<naga::front::spv::Error> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let label = self.label.as_deref().unwrap_or_default();
+        write!(f, "\nShader '{label}' parsing error: {}", self.inner)
+    }
+}
 impl fmt::Display for ShaderError<naga::WithSpan<naga::valid::ValidationError>> {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         use codespan_reporting::{
@@ -118,6 +191,27 @@ where
     }
 }
 
+/// All the errors reported by the GLSL frontend, which doesn't stop at the first one.
+#[cfg(feature = "glsl")]
+#[derive(Debug)]
+pub struct GlslErrors(pub Vec<naga::front::glsl::Error>);
+
+#[cfg(feature = "glsl")]
+impl fmt::Display for GlslErrors {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (i, error) in self.0.iter().enumerate() {
+            if i != 0 {
+                writeln!(f)?;
+            }
+            write!(f, "{error}")?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(feature = "glsl")]
+impl Error for GlslErrors {}
+
 //Note: `Clone` would require `WithSpan: Clone`.
 #[derive(Debug, Error)]
 #[non_exhaustive]
@@ -125,6 +219,12 @@ pub enum CreateShaderModuleError {
     #[cfg(feature = "wgsl")]
     #[error(transparent)]
     Parsing(#[from] ShaderError<naga::front::wgsl::ParseError>),
+    #[cfg(feature = "glsl")]
+    #[error(transparent)]
+    ParsingGlsl(#[from] ShaderError<GlslErrors>),
+    #[cfg(feature = "spirv")]
+    #[error(transparent)]
+    ParsingSpirV(#[from] ShaderError<naga::front::spv::Error>),
     #[error("Failed to generate the backend-specific code")]
     Generation,
     #[error(transparent)]
@@ -141,17 +241,91 @@ pub enum CreateShaderModuleError {
         group: u32,
         limit: u32,
     },
+    #[error("No passthrough shader was provided for the {0:?} backend")]
+    MissingPassthroughSource(wgt::Backend),
+    #[error("Passthrough GLSL and DXIL shaders must have a single stage, not {0:?}")]
+    InvalidPassthroughStage(wgt::ShaderStages),
+    #[error("The {0:?} backend doesn't support binding maps, passthrough shaders must declare their resources at the slots of the pipeline layout")]
+    UnsupportedPassthroughBindings(wgt::Backend),
 }
 
 impl CreateShaderModuleError {
+    /// Returns the [`ErrorType`] this error is reported as.
+    pub fn error_type(&self) -> ErrorType {
+        match *self {
+            Self::Device(ref err) => err.error_type(),
+            Self::Generation => ErrorType::Internal,
+            Self::Parsing(..)
+            | Self::ParsingGlsl(..)
+            | Self::ParsingSpirV(..)
+            | Self::Validation(..)
+            | Self::MissingFeatures(..)
+            | Self::InvalidGroupIndex { .. }
+            | Self::MissingPassthroughSource(..)
+            | Self::InvalidPassthroughStage(..)
+            | Self::UnsupportedPassthroughBindings(..) => ErrorType::Validation,
+        }
+    }
+
     pub fn location(&self, source: &str) -> Option<naga::SourceLocation> {
         match *self {
             #[cfg(feature = "wgsl")]
             CreateShaderModuleError::Parsing(ref err) => err.inner.location(source),
+            #[cfg(feature = "glsl")]
+            CreateShaderModuleError::ParsingGlsl(ref err) => err
+                .inner
+                .0
+                .first()
+                .filter(|error| error.meta.is_defined())
+                .map(|error| error.meta.location(source)),
             CreateShaderModuleError::Validation(ref err) => err.inner.location(source),
             _ => None,
         }
     }
+
+    /// Lists the problems found in the shader source, each with its location
+    /// in that source when known.
+    pub fn diagnostics(&self) -> Vec<(String, Option<naga::SourceLocation>)> {
+        fn span_location(span: naga::Span, source: &str) -> Option<naga::SourceLocation> {
+            let range = span.to_range()?;
+            (range.end <= source.len()).then(|| span.location(source))
+        }
+
+        match *self {
+            #[cfg(feature = "wgsl")]
+            CreateShaderModuleError::Parsing(ref err) => vec![(
+                err.inner.message().to_string(),
+                err.inner.location(&err.source),
+            )],
+            #[cfg(feature = "glsl")]
+            CreateShaderModuleError::ParsingGlsl(ref err) => err
+                .inner
+                .0
+                .iter()
+                .map(|error| {
+                    (
+                        error.kind.to_string(),
+                        span_location(error.meta, &err.source),
+                    )
+                })
+                .collect(),
+            CreateShaderModuleError::Validation(ref err) => {
+                let mut message = err.inner.as_inner().to_string();
+                let mut source = Error::source(err.inner.as_inner());
+                while let Some(cause) = source {
+                    message = format!("{message}: {cause}");
+                    source = cause.source();
+                }
+                let location = err
+                    .inner
+                    .spans()
+                    .next()
+                    .and_then(|&(span, _)| span_location(span, &err.source));
+                vec![(message, location)]
+            }
+            ref other => vec![(other.to_string(), None)],
+        }
+    }
 }
 
 /// Describes a programmable pipeline stage.
@@ -164,6 +338,10 @@ pub struct ProgrammableStageDescriptor<'a> {
     /// The name of the entry point in the compiled shader. There must be a function with this name
     /// in the shader.
     pub entry_point: Cow<'a, str>,
+    /// Values of the pipeline-overridable constants of the module, keyed by
+    /// their name or their numeric identifier.
+    #[cfg_attr(any(feature = "replay", feature = "trace"), serde(default))]
+    pub constants: Cow<'a, hal::PipelineConstants>,
 }
 
 /// Number of implicit bind groups derived at pipeline creation.
@@ -205,12 +383,29 @@ pub enum CreateComputePipelineError {
     Implicit(#[from] ImplicitLayoutError),
     #[error("Error matching shader requirements against the pipeline")]
     Stage(#[from] validation::StageError),
+    #[error("Shader doesn't match the pipeline: {0}")]
+    Layout(String),
     #[error("Internal error: {0}")]
     Internal(String),
     #[error(transparent)]
     MissingDownlevelFlags(#[from] MissingDownlevelFlags),
 }
 
+impl CreateComputePipelineError {
+    /// Returns the [`ErrorType`] this error is reported as.
+    pub fn error_type(&self) -> ErrorType {
+        match *self {
+            Self::Device(ref err) => err.error_type(),
+            Self::Internal(..) => ErrorType::Internal,
+            Self::InvalidLayout
+            | Self::Implicit(..)
+            | Self::Stage(..)
+            | Self::Layout(..)
+            | Self::MissingDownlevelFlags(..) => ErrorType::Validation,
+        }
+    }
+}
+
 #[derive(Debug)]
 pub struct ComputePipeline<A: hal::Api> {
     pub(crate) raw: A::ComputePipeline,
@@ -228,6 +423,77 @@ impl<A: hal::Api> Resource for ComputePipeline<A> {
     }
 }
 
+/// The backend objects a compute pipeline is compiled from, so the
+/// compilation can run without any lock held.
+pub(crate) struct ComputePipelineSources<A: hal::Api> {
+    pub(crate) raw_device: Arc<A::Device>,
+    pub(crate) raw_layout: Arc<A::PipelineLayout>,
+    pub(crate) module: Arc<A::ShaderModule>,
+    pub(crate) compilation: PipelineCompilation,
+}
+
+impl<A: hal::Api> ComputePipelineSources<A> {
+    /// Releases the backend objects, then marks the compilation as done.
+    pub(crate) fn release(self) {
+        let Self {
+            raw_device,
+            raw_layout,
+            module,
+            compilation,
+        } = self;
+        drop((raw_device, raw_layout, module));
+        drop(compilation);
+    }
+}
+
+/// A compute pipeline that passed validation, waiting for its backend
+/// pipeline to be compiled from its [`ComputePipelineSources`].
+pub(crate) struct PendingComputePipeline {
+    pub(crate) layout_id: Stored<PipelineLayoutId>,
+    pub(crate) device_id: Stored<DeviceId>,
+    pub(crate) late_sized_buffer_groups: ArrayVec<LateSizedBufferGroup, { hal::MAX_BIND_GROUPS }>,
+}
+
+impl PendingComputePipeline {
+    pub(crate) fn into_pipeline<A: hal::Api>(
+        self,
+        raw: A::ComputePipeline,
+        desc: &ComputePipelineDescriptor,
+    ) -> ComputePipeline<A> {
+        ComputePipeline {
+            raw,
+            layout_id: self.layout_id,
+            device_id: self.device_id,
+            late_sized_buffer_groups: self.late_sized_buffer_groups,
+            life_guard: LifeGuard::new(desc.label.borrow_or_default()),
+        }
+    }
+}
+
+/// Called from a worker thread once a compute pipeline created by
+/// [`Global::device_create_compute_pipeline_async`] is registered, with the
+/// error that made it invalid, if any.
+///
+/// [`Global::device_create_compute_pipeline_async`]: crate::global::Global::device_create_compute_pipeline_async
+#[cfg(any(
+    not(target_arch = "wasm32"),
+    all(
+        feature = "fragile-send-sync-non-atomic-wasm",
+        not(target_feature = "atomics")
+    )
+))]
+pub type CreateComputePipelineCallback =
+    Box<dyn FnOnce(ComputePipelineId, Option<CreateComputePipelineError>) + Send + 'static>;
+#[cfg(not(any(
+    not(target_arch = "wasm32"),
+    all(
+        feature = "fragile-send-sync-non-atomic-wasm",
+        not(target_feature = "atomics")
+    )
+)))]
+pub type CreateComputePipelineCallback =
+    Box<dyn FnOnce(ComputePipelineId, Option<CreateComputePipelineError>) + 'static>;
+
 /// Describes how the vertex buffer is interpreted.
 #[derive(Clone, Debug)]
 #[cfg_attr(feature = "trace", derive(serde::Serialize))]
@@ -377,6 +643,11 @@ pub enum CreateRenderPipelineError {
         #[source]
         error: validation::StageError,
     },
+    #[error("{stage:?} shader doesn't match the pipeline: {error}")]
+    Layout {
+        stage: wgt::ShaderStages,
+        error: String,
+    },
     #[error("Internal error in {stage:?} shader: {error}")]
     Internal {
         stage: wgt::ShaderStages,
@@ -386,6 +657,35 @@ pub enum CreateRenderPipelineError {
     UnalignedShader { group: u32, binding: u32, size: u64 },
 }
 
+impl CreateRenderPipelineError {
+    /// Returns the [`ErrorType`] this error is reported as.
+    pub fn error_type(&self) -> ErrorType {
+        match *self {
+            Self::Device(ref err) => err.error_type(),
+            Self::Internal { .. } => ErrorType::Internal,
+            Self::ColorAttachment(..)
+            | Self::InvalidLayout
+            | Self::Implicit(..)
+            | Self::ColorState(..)
+            | Self::DepthStencilState(..)
+            | Self::InvalidSampleCount(..)
+            | Self::TooManyVertexBuffers { .. }
+            | Self::TooManyVertexAttributes { .. }
+            | Self::VertexStrideTooLarge { .. }
+            | Self::UnalignedVertexStride { .. }
+            | Self::InvalidVertexAttributeOffset { .. }
+            | Self::ShaderLocationClash(..)
+            | Self::StripIndexFormatForNonStripTopology { .. }
+            | Self::ConservativeRasterizationNonFillPolygonMode
+            | Self::MissingFeatures(..)
+            | Self::MissingDownlevelFlags(..)
+            | Self::Stage { .. }
+            | Self::Layout { .. }
+            | Self::UnalignedShader { .. } => ErrorType::Validation,
+        }
+    }
+}
+
 bitflags::bitflags! {
     #[repr(transparent)]
     #[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
@@ -394,6 +694,8 @@ bitflags::bitflags! {
         const STENCIL_REFERENCE = 1 << 1;
         const WRITES_DEPTH = 1 << 2;
         const WRITES_STENCIL = 1 << 3;
+        /// Triangles are drawn as lines through [`wgt::DownlevelFlags::EMULATED_POLYGON_MODE_LINE`].
+        const EMULATED_POLYGON_MODE_LINE = 1 << 4;
     }
 }
 
@@ -436,3 +738,83 @@ impl<A: hal::Api> Resource for RenderPipeline<A> {
         &self.life_guard
     }
 }
+
+/// The backend objects a render pipeline is compiled from, so the
+/// compilation can run without any lock held.
+pub(crate) struct RenderPipelineSources<A: hal::Api> {
+    pub(crate) raw_device: Arc<A::Device>,
+    pub(crate) raw_layout: Arc<A::PipelineLayout>,
+    pub(crate) vertex_module: Arc<A::ShaderModule>,
+    pub(crate) fragment_module: Option<Arc<A::ShaderModule>>,
+    pub(crate) compilation: PipelineCompilation,
+}
+
+impl<A: hal::Api> RenderPipelineSources<A> {
+    /// Releases the backend objects, then marks the compilation as done.
+    pub(crate) fn release(self) {
+        let Self {
+            raw_device,
+            raw_layout,
+            vertex_module,
+            fragment_module,
+            compilation,
+        } = self;
+        drop((raw_device, raw_layout, vertex_module, fragment_module));
+        drop(compilation);
+    }
+}
+
+/// A render pipeline that passed validation, waiting for its backend
+/// pipeline to be compiled from its [`RenderPipelineSources`].
+pub(crate) struct PendingRenderPipeline {
+    pub(crate) layout_id: Stored<PipelineLayoutId>,
+    pub(crate) device_id: Stored<DeviceId>,
+    pub(crate) pass_context: RenderPassContext,
+    pub(crate) flags: PipelineFlags,
+    pub(crate) vertex_steps: Vec<VertexStep>,
+    pub(crate) late_sized_buffer_groups: ArrayVec<LateSizedBufferGroup, { hal::MAX_BIND_GROUPS }>,
+}
+
+impl PendingRenderPipeline {
+    pub(crate) fn into_pipeline<A: hal::Api>(
+        self,
+        raw: A::RenderPipeline,
+        desc: &RenderPipelineDescriptor,
+    ) -> RenderPipeline<A> {
+        RenderPipeline {
+            raw,
+            layout_id: self.layout_id,
+            device_id: self.device_id,
+            pass_context: self.pass_context,
+            flags: self.flags,
+            strip_index_format: desc.primitive.strip_index_format,
+            vertex_steps: self.vertex_steps,
+            late_sized_buffer_groups: self.late_sized_buffer_groups,
+            life_guard: LifeGuard::new(desc.label.borrow_or_default()),
+        }
+    }
+}
+
+/// Called from a worker thread once a render pipeline created by
+/// [`Global::device_create_render_pipeline_async`] is registered, with the
+/// error that made it invalid, if any.
+///
+/// [`Global::device_create_render_pipeline_async`]: crate::global::Global::device_create_render_pipeline_async
+#[cfg(any(
+    not(target_arch = "wasm32"),
+    all(
+        feature = "fragile-send-sync-non-atomic-wasm",
+        not(target_feature = "atomics")
+    )
+))]
+pub type CreateRenderPipelineCallback =
+    Box<dyn FnOnce(RenderPipelineId, Option<CreateRenderPipelineError>) + Send + 'static>;
+#[cfg(not(any(
+    not(target_arch = "wasm32"),
+    all(
+        feature = "fragile-send-sync-non-atomic-wasm",
+        not(target_feature = "atomics")
+    )
+)))]
+pub type CreateRenderPipelineCallback =
+    Box<dyn FnOnce(RenderPipelineId, Option<CreateRenderPipelineError>) + 'static>;
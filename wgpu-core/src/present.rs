@@ -16,6 +16,7 @@ use crate::device::trace::Action;
 use crate::{
     conv,
     device::{DeviceError, MissingDownlevelFlags},
+    error::ErrorType,
     global::Global,
     hal_api::HalApi,
     hub::Token,
@@ -62,6 +63,18 @@ pub enum SurfaceError {
     StillReferenced,
 }
 
+impl SurfaceError {
+    /// Returns the [`ErrorType`] this error is reported as.
+    pub fn error_type(&self) -> ErrorType {
+        match *self {
+            Self::Device(ref err) => err.error_type(),
+            Self::Invalid | Self::NotConfigured | Self::AlreadyAcquired | Self::StillReferenced => {
+                ErrorType::Validation
+            }
+        }
+    }
+}
+
 #[derive(Clone, Debug, Error)]
 #[non_exhaustive]
 pub enum ConfigureSurfaceError {
@@ -98,6 +111,25 @@ pub enum ConfigureSurfaceError {
     UnsupportedUsage,
 }
 
+impl ConfigureSurfaceError {
+    /// Returns the [`ErrorType`] this error is reported as.
+    pub fn error_type(&self) -> ErrorType {
+        match *self {
+            Self::Device(ref err) => err.error_type(),
+            Self::InvalidSurface
+            | Self::InvalidViewFormat(..)
+            | Self::MissingDownlevelFlags(..)
+            | Self::PreviousOutputExists
+            | Self::ZeroArea
+            | Self::UnsupportedQueueFamily
+            | Self::UnsupportedFormat { .. }
+            | Self::UnsupportedPresentMode { .. }
+            | Self::UnsupportedAlphaMode { .. }
+            | Self::UnsupportedUsage => ErrorType::Validation,
+        }
+    }
+}
+
 #[repr(C)]
 #[derive(Debug)]
 pub struct SurfaceOutput {
@@ -161,7 +193,7 @@ impl<G: GlobalIdentityHandlerFactory> Global<G> {
                 clear_views.push(
                     unsafe {
                         hal::Device::create_texture_view(
-                            &device.raw,
+                            &*device.raw,
                             ast.texture.borrow(),
                             &clear_view_desc,
                         )
@@ -305,7 +337,7 @@ impl<G: GlobalIdentityHandlerFactory> Global<G> {
                 {
                     for clear_view in clear_views {
                         unsafe {
-                            hal::Device::destroy_texture_view(&device.raw, clear_view);
+                            hal::Device::destroy_texture_view(&*device.raw, clear_view);
                         }
                     }
                 }
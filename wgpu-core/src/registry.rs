@@ -82,6 +82,17 @@ impl<T: Resource, I: id::TypedId + Copy, F: IdentityHandlerFactory<I>> Registry<
         }
     }
 
+    /// Resume the assignment of an id obtained from [`Registry::prepare`].
+    ///
+    /// This is used when the resource is only created later, possibly on
+    /// another thread, after the original `FutureId` has been released.
+    pub(crate) fn future_id(&self, id: I) -> FutureId<I, T> {
+        FutureId {
+            id,
+            data: &self.data,
+        }
+    }
+
     /// Acquire read access to this `Registry`'s contents.
     ///
     /// The caller must present a mutable reference to a `Token<A>`,
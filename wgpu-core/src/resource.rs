@@ -1,5 +1,6 @@
 use crate::{
     device::{DeviceError, HostMap, MissingDownlevelFlags, MissingFeatures},
+    error::ErrorType,
     global::Global,
     hal_api::HalApi,
     hub::Token,
@@ -262,6 +263,29 @@ pub enum BufferAccessError {
     MapAborted,
 }
 
+impl BufferAccessError {
+    /// Returns the [`ErrorType`] this error is reported as.
+    pub fn error_type(&self) -> ErrorType {
+        match *self {
+            Self::Device(ref err) => err.error_type(),
+            Self::Failed
+            | Self::Invalid
+            | Self::Destroyed
+            | Self::AlreadyMapped
+            | Self::MapAlreadyPending
+            | Self::MissingBufferUsage(..)
+            | Self::NotMapped
+            | Self::UnalignedRange
+            | Self::UnalignedOffset { .. }
+            | Self::UnalignedRangeSize { .. }
+            | Self::OutOfBoundsUnderrun { .. }
+            | Self::OutOfBoundsOverrun { .. }
+            | Self::NegativeRange { .. }
+            | Self::MapAborted => ErrorType::Validation,
+        }
+    }
+}
+
 pub type BufferAccessResult = Result<(), BufferAccessError>;
 pub(crate) struct BufferPendingMapping {
     pub range: Range<wgt::BufferAddress>,
@@ -302,6 +326,21 @@ pub enum CreateBufferError {
     MissingDownlevelFlags(#[from] MissingDownlevelFlags),
 }
 
+impl CreateBufferError {
+    /// Returns the [`ErrorType`] this error is reported as.
+    pub fn error_type(&self) -> ErrorType {
+        match *self {
+            Self::Device(ref err) => err.error_type(),
+            Self::AccessError(ref err) => err.error_type(),
+            Self::UnalignedSize
+            | Self::InvalidUsage(..)
+            | Self::UsageMismatch(..)
+            | Self::MaxBufferSize { .. }
+            | Self::MissingDownlevelFlags(..) => ErrorType::Validation,
+        }
+    }
+}
+
 impl<A: hal::Api> Resource for Buffer<A> {
     const TYPE: &'static str = "Buffer";
 
@@ -476,7 +515,7 @@ impl<G: GlobalIdentityHandlerFactory> Global<G> {
         let mut token = Token::root();
         let (guard, _) = hub.devices.read(&mut token);
         let device = guard.try_get(id).ok().flatten();
-        let hal_device = device.map(|device| &device.raw);
+        let hal_device = device.map(|device| &*device.raw);
 
         hal_device_callback(hal_device)
     }
@@ -550,6 +589,8 @@ pub enum CreateTextureError {
     InvalidDepthDimension(wgt::TextureDimension, wgt::TextureFormat),
     #[error("Compressed texture ({1:?}) can't be created as {0:?}")]
     InvalidCompressedDimension(wgt::TextureDimension, wgt::TextureFormat),
+    #[error("Multi-planar texture ({1:?}) can't be created as {0:?}")]
+    InvalidMultiPlanarDimension(wgt::TextureDimension, wgt::TextureFormat),
     #[error(
         "Texture descriptor mip level count {requested} is invalid, maximum allowed is {maximum}"
     )]
@@ -571,12 +612,39 @@ pub enum CreateTextureError {
     InvalidSampleCount(u32, wgt::TextureFormat),
     #[error("Multisampled textures must have RENDER_ATTACHMENT usage")]
     MultisampledNotRenderAttachment,
+    #[error("Transient textures must have exactly the RENDER_ATTACHMENT and TRANSIENT_ATTACHMENT usages, but {0:?} were given")]
+    InvalidTransientUsages(wgt::TextureUsages),
     #[error("Texture format {0:?} can't be used due to missing features")]
     MissingFeatures(wgt::TextureFormat, #[source] MissingFeatures),
     #[error(transparent)]
     MissingDownlevelFlags(#[from] MissingDownlevelFlags),
 }
 
+impl CreateTextureError {
+    /// Returns the [`ErrorType`] this error is reported as.
+    pub fn error_type(&self) -> ErrorType {
+        match *self {
+            Self::Device(ref err) => err.error_type(),
+            Self::InvalidUsage(..)
+            | Self::InvalidDimension(..)
+            | Self::InvalidDepthDimension(..)
+            | Self::InvalidCompressedDimension(..)
+            | Self::InvalidMultiPlanarDimension(..)
+            | Self::InvalidMipLevelCount { .. }
+            | Self::InvalidFormatUsages(..)
+            | Self::InvalidViewFormat(..)
+            | Self::InvalidDimensionUsages(..)
+            | Self::InvalidMultisampledStorageBinding
+            | Self::InvalidMultisampledFormat(..)
+            | Self::InvalidSampleCount(..)
+            | Self::MultisampledNotRenderAttachment
+            | Self::InvalidTransientUsages(..)
+            | Self::MissingFeatures(..)
+            | Self::MissingDownlevelFlags(..) => ErrorType::Validation,
+        }
+    }
+}
+
 impl<A: hal::Api> Resource for Texture<A> {
     const TYPE: &'static str = "Texture";
 
@@ -805,6 +873,21 @@ pub enum CreateSamplerError {
     MissingFeatures(#[from] MissingFeatures),
 }
 
+impl CreateSamplerError {
+    /// Returns the [`ErrorType`] this error is reported as.
+    pub fn error_type(&self) -> ErrorType {
+        match *self {
+            Self::Device(ref err) => err.error_type(),
+            Self::InvalidLodMinClamp(..)
+            | Self::InvalidLodMaxClamp { .. }
+            | Self::InvalidAnisotropy(..)
+            | Self::InvalidFilterModeWithAnisotropy { .. }
+            | Self::TooManyObjects
+            | Self::MissingFeatures(..) => ErrorType::Validation,
+        }
+    }
+}
+
 impl<A: hal::Api> Resource for Sampler<A> {
     const TYPE: &'static str = "Sampler";
 
@@ -826,6 +909,18 @@ pub enum CreateQuerySetError {
     MissingFeatures(#[from] MissingFeatures),
 }
 
+impl CreateQuerySetError {
+    /// Returns the [`ErrorType`] this error is reported as.
+    pub fn error_type(&self) -> ErrorType {
+        match *self {
+            Self::Device(ref err) => err.error_type(),
+            Self::ZeroCount | Self::TooManyQueries { .. } | Self::MissingFeatures(..) => {
+                ErrorType::Validation
+            }
+        }
+    }
+}
+
 pub type QuerySetDescriptor<'a> = wgt::QuerySetDescriptor<Label<'a>>;
 
 #[derive(Debug)]
@@ -99,6 +99,13 @@ impl<A: HalApi, T: resource::Resource, Id: TypedId> StatelessTracker<A, T, Id> {
         self.metadata.owned_ids()
     }
 
+    /// Returns true if the tracker owns the given resource.
+    pub fn contains(&self, id: Valid<Id>) -> bool {
+        let (index32, _, _) = id.0.unzip();
+        let index = index32 as usize;
+        index < self.metadata.size() && self.metadata.contains(index)
+    }
+
     /// Inserts a single resource into the resource tracker.
     ///
     /// If the resource already exists in the tracker, it will be overwritten.
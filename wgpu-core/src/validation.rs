@@ -21,7 +21,6 @@ enum ResourceType {
 
 #[derive(Debug)]
 struct Resource {
-    #[allow(unused)]
     name: Option<String>,
     bind: naga::ResourceBinding,
     ty: ResourceType,
@@ -101,11 +100,36 @@ enum Varying {
     BuiltIn(naga::BuiltIn),
 }
 
-#[allow(unused)]
+/// A pipeline-overridable constant of a module.
 #[derive(Debug)]
 struct SpecializationConstant {
-    id: u32,
-    ty: NumericType,
+    name: Option<String>,
+    id: Option<u32>,
+    kind: naga::ScalarKind,
+    width: naga::Bytes,
+}
+
+impl SpecializationConstant {
+    fn is_named(&self, key: &str) -> bool {
+        self.name.as_deref() == Some(key) || matches!(self.id, Some(id) if id.to_string() == key)
+    }
+
+    fn can_represent(&self, value: f64) -> bool {
+        if !value.is_finite() {
+            return false;
+        }
+        match (self.kind, self.width) {
+            (naga::ScalarKind::Bool, _) => true,
+            (naga::ScalarKind::Sint, _) => {
+                value.fract() == 0.0 && value >= i32::MIN as f64 && value <= i32::MAX as f64
+            }
+            (naga::ScalarKind::Uint, _) => {
+                value.fract() == 0.0 && value >= 0.0 && value <= u32::MAX as f64
+            }
+            (naga::ScalarKind::Float, 8) => true,
+            (naga::ScalarKind::Float, _) => value.abs() <= f32::MAX as f64,
+        }
+    }
 }
 
 #[derive(Debug, Default)]
@@ -113,8 +137,6 @@ struct EntryPoint {
     inputs: Vec<Varying>,
     outputs: Vec<Varying>,
     resources: Vec<(naga::Handle<Resource>, GlobalUse)>,
-    #[allow(unused)]
-    spec_constants: Vec<SpecializationConstant>,
     sampling_pairs: FastHashSet<(naga::Handle<Resource>, naga::Handle<Resource>)>,
     workgroup_size: [u32; 3],
 }
@@ -125,6 +147,7 @@ pub struct Interface {
     limits: wgt::Limits,
     resources: naga::Arena<Resource>,
     entry_points: FastHashMap<(naga::ShaderStage, String), EntryPoint>,
+    spec_constants: Vec<SpecializationConstant>,
 }
 
 #[derive(Clone, Debug, Error)]
@@ -270,6 +293,14 @@ pub enum StageError {
     },
     #[error("Location[{location}] is provided by the previous stage output but is not consumed as input by this stage.")]
     InputNotConsumed { location: wgt::ShaderLocation },
+    #[error("Shader has no overridable constant named '{0}'")]
+    MissingConstant(String),
+    #[error("Value {value} of the overridable constant '{name}' is not representable as {kind:?}")]
+    InvalidConstantValue {
+        name: String,
+        value: f64,
+        kind: naga::ScalarKind,
+    },
 }
 
 fn map_storage_format_to_naga(format: wgt::TextureFormat) -> Option<naga::StorageFormat> {
@@ -731,6 +762,7 @@ impl NumericType {
             | Tf::Depth24PlusStencil8 => {
                 panic!("Unexpected depth format")
             }
+            Tf::NV12 | Tf::P010 => panic!("Unexpected multi-planar format"),
             Tf::Rgb9e5Ufloat => (NumericDimension::Vector(Vs::Tri), Sk::Float),
             Tf::Bc1RgbaUnorm
             | Tf::Bc1RgbaUnormSrgb
@@ -786,8 +818,36 @@ impl NumericType {
         }
     }
 
+    /// Returns the 32 or 64-bit vertex format the shader sees as this type.
+    fn to_vertex_format(self) -> Option<wgt::VertexFormat> {
+        use naga::{ScalarKind as Sk, VectorSize as Vs};
+        use wgt::VertexFormat as Vf;
+
+        Some(match (self.kind, self.width, self.dim) {
+            (Sk::Uint, 4, NumericDimension::Scalar) => Vf::Uint32,
+            (Sk::Uint, 4, NumericDimension::Vector(Vs::Bi)) => Vf::Uint32x2,
+            (Sk::Uint, 4, NumericDimension::Vector(Vs::Tri)) => Vf::Uint32x3,
+            (Sk::Uint, 4, NumericDimension::Vector(Vs::Quad)) => Vf::Uint32x4,
+            (Sk::Sint, 4, NumericDimension::Scalar) => Vf::Sint32,
+            (Sk::Sint, 4, NumericDimension::Vector(Vs::Bi)) => Vf::Sint32x2,
+            (Sk::Sint, 4, NumericDimension::Vector(Vs::Tri)) => Vf::Sint32x3,
+            (Sk::Sint, 4, NumericDimension::Vector(Vs::Quad)) => Vf::Sint32x4,
+            (Sk::Float, 4, NumericDimension::Scalar) => Vf::Float32,
+            (Sk::Float, 4, NumericDimension::Vector(Vs::Bi)) => Vf::Float32x2,
+            (Sk::Float, 4, NumericDimension::Vector(Vs::Tri)) => Vf::Float32x3,
+            (Sk::Float, 4, NumericDimension::Vector(Vs::Quad)) => Vf::Float32x4,
+            (Sk::Float, 8, NumericDimension::Scalar) => Vf::Float64,
+            (Sk::Float, 8, NumericDimension::Vector(Vs::Bi)) => Vf::Float64x2,
+            (Sk::Float, 8, NumericDimension::Vector(Vs::Tri)) => Vf::Float64x3,
+            (Sk::Float, 8, NumericDimension::Vector(Vs::Quad)) => Vf::Float64x4,
+            _ => return None,
+        })
+    }
+
     fn is_compatible_with(&self, other: &NumericType) -> bool {
-        if self.kind != other.kind {
+        // 64-bit attributes are only read by 64-bit inputs, and the other way around.
+        // The narrower widths are converted to the width of the input.
+        if self.kind != other.kind || (self.width == 8) != (other.width == 8) {
             return false;
         }
         match (self.dim, other.dim) {
@@ -967,12 +1027,142 @@ impl Interface {
             entry_points.insert((entry_point.stage, entry_point.name.clone()), ep);
         }
 
+        let spec_constants = module
+            .constants
+            .iter()
+            .filter_map(|(_, constant)| {
+                let id = match constant.r#override {
+                    naga::Override::None => return None,
+                    naga::Override::ByName => None,
+                    naga::Override::ByNameOrId(id) => Some(id),
+                };
+                match module.types[constant.ty].inner {
+                    naga::TypeInner::Scalar { kind, width } => Some(SpecializationConstant {
+                        name: constant.name.clone(),
+                        id,
+                        kind,
+                        width,
+                    }),
+                    _ => None,
+                }
+            })
+            .collect();
+
         Self {
             features,
             limits,
             resources,
             entry_points,
+            spec_constants,
+        }
+    }
+
+    /// Checks that the values of pipeline-overridable constants given at pipeline
+    /// creation match overrides of the module, and fit their types.
+    pub fn check_constants(&self, constants: &hal::PipelineConstants) -> Result<(), StageError> {
+        for (key, &value) in constants.iter() {
+            let constant = self
+                .spec_constants
+                .iter()
+                .find(|constant| constant.is_named(key))
+                .ok_or_else(|| StageError::MissingConstant(key.clone()))?;
+            if !constant.can_represent(value) {
+                return Err(StageError::InvalidConstantValue {
+                    name: key.clone(),
+                    value,
+                    kind: constant.kind,
+                });
+            }
         }
+        Ok(())
+    }
+
+    /// Describes the entry points of the module, with the bindings a derived
+    /// layout would have.
+    pub fn reflect(&self) -> wgt::ShaderModuleReflection {
+        fn locals(list: &[Varying]) -> impl Iterator<Item = (u32, &InterfaceVar)> {
+            list.iter().filter_map(|varying| match *varying {
+                Varying::Local { location, ref iv } => Some((location, iv)),
+                Varying::BuiltIn(_) => None,
+            })
+        }
+
+        let mut entry_points = self
+            .entry_points
+            .iter()
+            .map(|(&(stage, ref name), entry_point)| {
+                let mut bindings = entry_point
+                    .resources
+                    .iter()
+                    .filter_map(|&(handle, usage)| {
+                        let res = &self.resources[handle];
+                        // Bindings that can't be derived fail pipeline creation anyway.
+                        let ty = res.derive_binding_type(usage, self.features).ok()?;
+                        Some(wgt::BindingReflection {
+                            name: res.name.clone(),
+                            group: res.bind.group,
+                            binding: res.bind.binding,
+                            ty,
+                        })
+                    })
+                    .collect::<Vec<_>>();
+                bindings.sort_by_key(|binding| (binding.group, binding.binding));
+
+                let mut vertex_inputs = Vec::new();
+                let mut fragment_outputs = Vec::new();
+                match stage {
+                    naga::ShaderStage::Vertex => {
+                        vertex_inputs.extend(locals(&entry_point.inputs).filter_map(
+                            |(location, iv)| {
+                                Some(wgt::VertexInputReflection {
+                                    location,
+                                    format: iv.ty.to_vertex_format()?,
+                                })
+                            },
+                        ));
+                        vertex_inputs.sort_by_key(|input| input.location);
+                    }
+                    naga::ShaderStage::Fragment => {
+                        fragment_outputs.extend(locals(&entry_point.outputs).filter_map(
+                            |(location, iv)| {
+                                let sample_type = match iv.ty.kind {
+                                    naga::ScalarKind::Float => {
+                                        wgt::TextureSampleType::Float { filterable: true }
+                                    }
+                                    naga::ScalarKind::Sint => wgt::TextureSampleType::Sint,
+                                    naga::ScalarKind::Uint => wgt::TextureSampleType::Uint,
+                                    naga::ScalarKind::Bool => return None,
+                                };
+                                Some(wgt::FragmentOutputReflection {
+                                    location,
+                                    sample_type,
+                                    components: iv.ty.dim.num_components(),
+                                })
+                            },
+                        ));
+                        fragment_outputs.sort_by_key(|output| output.location);
+                    }
+                    naga::ShaderStage::Compute => {}
+                }
+
+                wgt::EntryPointReflection {
+                    name: name.clone(),
+                    stage: match stage {
+                        naga::ShaderStage::Vertex => wgt::ShaderStages::VERTEX,
+                        naga::ShaderStage::Fragment => wgt::ShaderStages::FRAGMENT,
+                        naga::ShaderStage::Compute => wgt::ShaderStages::COMPUTE,
+                    },
+                    workgroup_size: entry_point.workgroup_size,
+                    bindings,
+                    vertex_inputs,
+                    fragment_outputs,
+                }
+            })
+            .collect::<Vec<_>>();
+        // The entry points are stored in a map, so give them a stable order.
+        entry_points.sort_by(|a, b| (&a.name, a.stage.bits()).cmp(&(&b.name, b.stage.bits())));
+
+        wgt::ShaderModuleReflection { entry_points }
     }
 
     pub fn check_stage(
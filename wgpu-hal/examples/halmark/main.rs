@@ -1,5 +1,8 @@
 //! This example shows basic usage of wgpu-hal by rendering
 //! a ton of moving sprites, each with a separate texture and draw call.
+//!
+//! Press space to spawn more sprites, and `B` to measure how fast
+//! command buffers recording their draw calls are encoded.
 extern crate wgpu_hal as hal;
 
 use hal::{
@@ -19,6 +22,8 @@ const GRAVITY: f32 = -9.8 * 100.0;
 const MAX_VELOCITY: f32 = 750.0;
 const COMMAND_BUFFER_PER_CONTEXT: usize = 100;
 const DESIRED_FRAMES: u32 = 3;
+const BENCHMARK_ROUNDS: usize = 10;
+const BENCHMARK_MIN_DRAWS: usize = 1000;
 
 #[repr(C)]
 #[derive(Clone, Copy)]
@@ -96,6 +101,8 @@ impl<A: hal::Api> Example<A> {
             },
             // Can't rely on having DXC available, so use FXC instead
             dx12_shader_compiler: wgt::Dx12Compiler::Fxc,
+            gles_device: None,
+            gles_program_cache: wgt::GlesProgramCache::default(),
         };
         let instance = unsafe { A::Instance::init(&instance_desc)? };
         let mut surface = unsafe {
@@ -118,7 +125,11 @@ impl<A: hal::Api> Example<A> {
 
         let hal::OpenDevice { device, mut queue } = unsafe {
             adapter
-                .open(wgt::Features::empty(), &wgt::Limits::default())
+                .open(
+                    wgt::Features::empty(),
+                    &wgt::Limits::default(),
+                    &wgt::AllocatorConfig::default(),
+                )
                 .unwrap()
         };
 
@@ -236,17 +247,20 @@ impl<A: hal::Api> Example<A> {
                 .unwrap()
         };
 
+        let constants = hal::PipelineConstants::default();
         let pipeline_desc = hal::RenderPipelineDescriptor {
             label: None,
             layout: &pipeline_layout,
             vertex_stage: hal::ProgrammableStage {
                 module: &shader,
                 entry_point: "vs_main",
+                constants: &constants,
             },
             vertex_buffers: &[],
             fragment_stage: Some(hal::ProgrammableStage {
                 module: &shader,
                 entry_point: "fs_main",
+                constants: &constants,
             }),
             primitive: wgt::PrimitiveState {
                 topology: wgt::PrimitiveTopology::TriangleStrip,
@@ -564,6 +578,18 @@ impl<A: hal::Api> Example<A> {
     }
 
     fn update(&mut self, event: winit::event::WindowEvent) {
+        if let winit::event::WindowEvent::KeyboardInput {
+            input:
+                winit::event::KeyboardInput {
+                    virtual_keycode: Some(winit::event::VirtualKeyCode::B),
+                    state: winit::event::ElementState::Pressed,
+                    ..
+                },
+            ..
+        } = event
+        {
+            self.benchmark_encoding();
+        }
         if let winit::event::WindowEvent::KeyboardInput {
             input:
                 winit::event::KeyboardInput {
@@ -595,6 +621,116 @@ impl<A: hal::Api> Example<A> {
         }
     }
 
+    /// Encodes the draw calls of a frame into many command buffers, without submitting them,
+    /// and reports the encoding throughput.
+    ///
+    /// Command buffers are reset after each round, so later rounds reuse their storage.
+    fn benchmark_encoding(&mut self) {
+        let draw_count = self.bunnies.len().max(BENCHMARK_MIN_DRAWS);
+        let texture_desc = hal::TextureDescriptor {
+            label: Some("benchmark target"),
+            size: wgt::Extent3d {
+                width: self.extent[0],
+                height: self.extent[1],
+                depth_or_array_layers: 1,
+            },
+            mip_level_count: 1,
+            sample_count: 1,
+            dimension: wgt::TextureDimension::D2,
+            format: self.surface_format,
+            usage: hal::TextureUses::COLOR_TARGET,
+            memory_flags: hal::MemoryFlags::empty(),
+            view_formats: vec![],
+        };
+        let view_desc = hal::TextureViewDescriptor {
+            label: None,
+            format: self.surface_format,
+            dimension: wgt::TextureViewDimension::D2,
+            usage: hal::TextureUses::COLOR_TARGET,
+            range: wgt::ImageSubresourceRange::default(),
+        };
+        let encoder_desc = hal::CommandEncoderDescriptor {
+            label: Some("benchmark"),
+            queue: &self.queue,
+        };
+        let (texture, view, mut encoder) = unsafe {
+            let texture = self.device.create_texture(&texture_desc).unwrap();
+            let view = self
+                .device
+                .create_texture_view(&texture, &view_desc)
+                .unwrap();
+            let encoder = self.device.create_command_encoder(&encoder_desc).unwrap();
+            (texture, view, encoder)
+        };
+        let pass_desc = hal::RenderPassDescriptor {
+            label: None,
+            extent: texture_desc.size,
+            sample_count: 1,
+            color_attachments: &[Some(hal::ColorAttachment {
+                target: hal::Attachment {
+                    view: &view,
+                    usage: hal::TextureUses::COLOR_TARGET,
+                },
+                resolve_target: None,
+                ops: hal::AttachmentOps::STORE,
+                clear_value: wgt::Color::BLACK,
+            })],
+            depth_stencil_attachment: None,
+            multiview: None,
+        };
+
+        let mut round_times = Vec::with_capacity(BENCHMARK_ROUNDS);
+        for _ in 0..BENCHMARK_ROUNDS {
+            let start = Instant::now();
+            let mut cmd_bufs = Vec::with_capacity(COMMAND_BUFFER_PER_CONTEXT);
+            for _ in 0..COMMAND_BUFFER_PER_CONTEXT {
+                unsafe {
+                    encoder.begin_encoding(Some("benchmark")).unwrap();
+                    encoder.begin_render_pass(&pass_desc);
+                    encoder.set_render_pipeline(&self.pipeline);
+                    encoder.set_bind_group(&self.pipeline_layout, 0, &self.global_group, &[]);
+                    for i in 0..draw_count {
+                        let offset = (i as wgt::DynamicOffset)
+                            * (self.local_alignment as wgt::DynamicOffset);
+                        encoder.set_bind_group(
+                            &self.pipeline_layout,
+                            1,
+                            &self.local_group,
+                            &[offset],
+                        );
+                        encoder.draw(0, 4, 0, 1);
+                    }
+                    encoder.end_render_pass();
+                    cmd_bufs.push(encoder.end_encoding().unwrap());
+                }
+            }
+            unsafe { encoder.reset_all(cmd_bufs.into_iter()) };
+            round_times.push(start.elapsed());
+        }
+
+        unsafe {
+            self.device.destroy_command_encoder(encoder);
+            self.device.destroy_texture_view(view);
+            self.device.destroy_texture(texture);
+        }
+
+        let encoded = COMMAND_BUFFER_PER_CONTEXT as f64;
+        let first = round_times[0].as_secs_f64();
+        let rest = round_times[1..]
+            .iter()
+            .map(|t| t.as_secs_f64())
+            .sum::<f64>()
+            / (BENCHMARK_ROUNDS - 1) as f64;
+        println!(
+            "Encoded {} command buffers of {} draws: {:.1} per second in the first round, \
+            {:.1} per second on average once reused",
+            encoded,
+            draw_count,
+            encoded / first,
+            encoded / rest,
+        );
+    }
+
     fn render(&mut self) {
         let delta = 0.01;
         for bunny in self.bunnies.iter_mut() {
@@ -67,6 +67,8 @@ pub fn map_texture_format_failable(format: wgt::TextureFormat) -> Option<dxgifor
         Tf::Bc6hRgbFloat => DXGI_FORMAT_BC6H_SF16,
         Tf::Bc7RgbaUnorm => DXGI_FORMAT_BC7_UNORM,
         Tf::Bc7RgbaUnormSrgb => DXGI_FORMAT_BC7_UNORM_SRGB,
+        Tf::NV12 => DXGI_FORMAT_NV12,
+        Tf::P010 => DXGI_FORMAT_P010,
         Tf::Etc2Rgb8Unorm
         | Tf::Etc2Rgb8UnormSrgb
         | Tf::Etc2Rgb8A1Unorm
@@ -130,6 +132,19 @@ pub fn map_texture_format_for_srv_uav(
             crate::FormatAspects::STENCIL,
         ) => dxgiformat::DXGI_FORMAT_X24_TYPELESS_G8_UINT,
 
+        (wgt::TextureFormat::NV12, crate::FormatAspects::PLANE_0) => {
+            dxgiformat::DXGI_FORMAT_R8_UNORM
+        }
+        (wgt::TextureFormat::NV12, crate::FormatAspects::PLANE_1) => {
+            dxgiformat::DXGI_FORMAT_R8G8_UNORM
+        }
+        (wgt::TextureFormat::P010, crate::FormatAspects::PLANE_0) => {
+            dxgiformat::DXGI_FORMAT_R16_UNORM
+        }
+        (wgt::TextureFormat::P010, crate::FormatAspects::PLANE_1) => {
+            dxgiformat::DXGI_FORMAT_R16G16_UNORM
+        }
+
         (format, crate::FormatAspects::COLOR) => map_texture_format(format),
 
         _ => return None,
@@ -157,6 +172,19 @@ pub fn map_texture_format_for_copy(
             crate::FormatAspects::STENCIL,
         ) => dxgiformat::DXGI_FORMAT_R8_UINT,
 
+        (wgt::TextureFormat::NV12, crate::FormatAspects::PLANE_0) => {
+            dxgiformat::DXGI_FORMAT_R8_UNORM
+        }
+        (wgt::TextureFormat::NV12, crate::FormatAspects::PLANE_1) => {
+            dxgiformat::DXGI_FORMAT_R8G8_UNORM
+        }
+        (wgt::TextureFormat::P010, crate::FormatAspects::PLANE_0) => {
+            dxgiformat::DXGI_FORMAT_R16_UNORM
+        }
+        (wgt::TextureFormat::P010, crate::FormatAspects::PLANE_1) => {
+            dxgiformat::DXGI_FORMAT_R16G16_UNORM
+        }
+
         (format, crate::FormatAspects::COLOR) => map_texture_format(format),
 
         _ => return None,
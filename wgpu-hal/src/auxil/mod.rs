@@ -116,6 +116,72 @@ impl crate::TextureCopy {
     }
 }
 
+/// Returns the value given to the overridable `constant`, looked up by
+/// identifier and then by name.
+pub fn override_value(
+    constant: &naga::Constant,
+    constants: &crate::PipelineConstants,
+) -> Option<f64> {
+    let by_name = || constants.get(constant.name.as_ref()?).copied();
+    match constant.r#override {
+        naga::Override::None => None,
+        naga::Override::ByName => by_name(),
+        naga::Override::ByNameOrId(id) => constants.get(&id.to_string()).copied().or_else(by_name),
+    }
+}
+
+/// Substitute the values of `constants` for the initializers of the
+/// overridable constants of `module`, which backends then emit as plain constants.
+///
+/// The values are expected to have been validated against the module, so that
+/// each one is representable in the type of its constant.
+pub fn apply_pipeline_constants<'a>(
+    module: &'a naga::Module,
+    constants: &crate::PipelineConstants,
+) -> std::borrow::Cow<'a, naga::Module> {
+    if constants.is_empty() {
+        return std::borrow::Cow::Borrowed(module);
+    }
+    let mut module = module.clone();
+    for (_, constant) in module.constants.iter_mut() {
+        let value = match override_value(constant, constants) {
+            Some(value) => value,
+            None => continue,
+        };
+        let literal = match module.types[constant.ty].inner {
+            naga::TypeInner::Scalar { kind, width } => match (kind, width) {
+                (naga::ScalarKind::Bool, _) => naga::Literal::Bool(value != 0.0),
+                (naga::ScalarKind::Sint, _) => naga::Literal::I32(value as i32),
+                (naga::ScalarKind::Uint, _) => naga::Literal::U32(value as u32),
+                (naga::ScalarKind::Float, 8) => naga::Literal::F64(value),
+                (naga::ScalarKind::Float, _) => naga::Literal::F32(value as f32),
+            },
+            _ => continue,
+        };
+        // Replacing the expression in place keeps the type information of the
+        // module's analysis valid, as the constant keeps its scalar type.
+        module.const_expressions[constant.init] = naga::Expression::Literal(literal);
+        constant.r#override = naga::Override::None;
+    }
+    std::borrow::Cow::Owned(module)
+}
+
+/// Fails if `constants` are given for a passthrough shader, whose overridable
+/// constants can't be substituted.
+pub fn check_passthrough_constants(
+    constants: &crate::PipelineConstants,
+    stage: naga::ShaderStage,
+) -> Result<(), crate::PipelineError> {
+    if constants.is_empty() {
+        Ok(())
+    } else {
+        Err(crate::PipelineError::Layout(
+            map_naga_stage(stage),
+            "pipeline-overridable constants can't be set for passthrough shaders".to_string(),
+        ))
+    }
+}
+
 /// Construct a `CStr` from a byte slice, up to the first zero byte.
 ///
 /// Return a `CStr` extending from the start of `bytes` up to and
@@ -136,3 +202,91 @@ pub(crate) fn cstr_from_bytes_until_nul(bytes: &[std::os::raw::c_char]) -> Optio
         None
     }
 }
+
+/// Running totals of the bytes allocated for buffers and textures, used to
+/// fill the resource part of [`crate::MemoryReport`].
+#[allow(dead_code)]
+#[derive(Debug, Default)]
+pub(crate) struct AllocationCounters {
+    buffer_bytes: std::sync::atomic::AtomicU64,
+    texture_bytes: std::sync::atomic::AtomicU64,
+}
+
+#[allow(dead_code)]
+impl AllocationCounters {
+    pub fn add_buffer(&self, size: u64) {
+        self.buffer_bytes
+            .fetch_add(size, std::sync::atomic::Ordering::Relaxed);
+    }
+
+    pub fn remove_buffer(&self, size: u64) {
+        self.buffer_bytes
+            .fetch_sub(size, std::sync::atomic::Ordering::Relaxed);
+    }
+
+    pub fn add_texture(&self, size: u64) {
+        self.texture_bytes
+            .fetch_add(size, std::sync::atomic::Ordering::Relaxed);
+    }
+
+    pub fn remove_texture(&self, size: u64) {
+        self.texture_bytes
+            .fetch_sub(size, std::sync::atomic::Ordering::Relaxed);
+    }
+
+    pub fn report(&self, heaps: Vec<wgt::MemoryHeapReport>) -> crate::MemoryReport {
+        crate::MemoryReport {
+            heaps,
+            buffer_bytes: self.buffer_bytes.load(std::sync::atomic::Ordering::Relaxed),
+            texture_bytes: self
+                .texture_bytes
+                .load(std::sync::atomic::Ordering::Relaxed),
+        }
+    }
+}
+
+/// Estimate the bytes needed to store every mip level and layer of a texture,
+/// for backends that can't ask the driver how big an allocation is.
+#[allow(dead_code)]
+pub(crate) fn estimate_texture_size(desc: &crate::TextureDescriptor) -> u64 {
+    if desc.format.is_multi_planar_format() {
+        // Multi-planar textures have a single mip level and sample,
+        // with each plane possibly subsampled.
+        let texels = desc.size.width as u64
+            * desc.size.height as u64
+            * desc.size.depth_or_array_layers as u64;
+        return [wgt::TextureAspect::Plane0, wgt::TextureAspect::Plane1]
+            .into_iter()
+            .map(|plane| {
+                let block_size = desc.format.block_size(Some(plane)).unwrap();
+                let (width_factor, height_factor) = desc.format.subsampling_factors(Some(plane));
+                texels / (width_factor * height_factor) as u64 * block_size as u64
+            })
+            .sum();
+    }
+
+    let block_size = desc.format.block_size(None).unwrap_or_else(|| {
+        // Combined depth-stencil formats have no single block size, and the
+        // depth aspect of `Depth24Plus*` has no defined size at all.
+        let depth = desc
+            .format
+            .block_size(Some(wgt::TextureAspect::DepthOnly))
+            .unwrap_or(4);
+        let stencil = desc
+            .format
+            .block_size(Some(wgt::TextureAspect::StencilOnly))
+            .unwrap_or(0);
+        depth + stencil
+    });
+    let (block_width, block_height) = desc.format.block_dimensions();
+
+    let blocks: u64 = (0..desc.mip_level_count)
+        .map(|level| {
+            let size = desc.size.mip_level_size(level, desc.dimension);
+            let width = (size.width + block_width - 1) / block_width;
+            let height = (size.height + block_height - 1) / block_height;
+            width as u64 * height as u64 * size.depth_or_array_layers as u64
+        })
+        .sum();
+    blocks * block_size as u64 * desc.sample_count as u64
+}
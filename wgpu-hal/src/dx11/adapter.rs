@@ -7,6 +7,7 @@ impl crate::Adapter<super::Api> for super::Adapter {
         &self,
         features: wgt::Features,
         limits: &wgt::Limits,
+        allocator: &wgt::AllocatorConfig,
     ) -> Result<crate::OpenDevice<super::Api>, crate::DeviceError> {
         todo!()
     }
@@ -96,6 +97,7 @@ impl super::Adapter {
             | wgt::Features::TEXTURE_FORMAT_16BIT_NORM
             | wgt::Features::ADDRESS_MODE_CLAMP_TO_ZERO;
         let mut downlevel = wgt::DownlevelFlags::BASE_VERTEX
+            | wgt::DownlevelFlags::NON_NEGATIVE_BASE_VERTEX
             | wgt::DownlevelFlags::READ_ONLY_DEPTH_STENCIL
             | wgt::DownlevelFlags::UNRESTRICTED_INDEX_BUFFER
             | wgt::DownlevelFlags::UNRESTRICTED_EXTERNAL_TEXTURE_COPIES;
@@ -257,6 +259,7 @@ impl super::Adapter {
             },
             driver: String::new(),
             driver_info: String::new(),
+            device_node: String::new(),
             backend: wgt::Backend::Dx11,
         };
 
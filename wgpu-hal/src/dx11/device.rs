@@ -124,6 +124,14 @@ impl crate::Device<super::Api> for super::Device {
         todo!()
     }
 
+    unsafe fn update_bind_group(
+        &self,
+        group: &super::BindGroup,
+        desc: &crate::BindGroupUpdateDescriptor<super::Api>,
+    ) {
+        todo!()
+    }
+
     unsafe fn create_shader_module(
         &self,
         desc: &crate::ShaderModuleDescriptor,
@@ -200,6 +208,10 @@ impl crate::Device<super::Api> for super::Device {
     unsafe fn stop_capture(&self) {
         todo!()
     }
+
+    unsafe fn memory_report(&self) -> crate::MemoryReport {
+        todo!()
+    }
 }
 
 impl crate::Queue<super::Api> for super::Queue {
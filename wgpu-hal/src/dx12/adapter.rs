@@ -146,6 +146,7 @@ impl super::Adapter {
             },
             driver: String::new(),
             driver_info: String::new(),
+            device_node: String::new(),
         };
 
         let mut options: d3d12_ty::D3D12_FEATURE_DATA_D3D12_OPTIONS = unsafe { mem::zeroed() };
@@ -252,7 +253,8 @@ impl super::Adapter {
             | wgt::Features::TEXTURE_FORMAT_16BIT_NORM
             | wgt::Features::PUSH_CONSTANTS
             | wgt::Features::SHADER_PRIMITIVE_INDEX
-            | wgt::Features::RG11B10UFLOAT_RENDERABLE;
+            | wgt::Features::RG11B10UFLOAT_RENDERABLE
+            | wgt::Features::SHADER_PASSTHROUGH;
         //TODO: in order to expose this, we need to run a compute shader
         // that extract the necessary statistics out of the D3D12 result.
         // Alternatively, we could allocate a buffer for the query set,
@@ -272,6 +274,32 @@ impl super::Adapter {
             shader_model_support.HighestShaderModel >= d3d12_ty::D3D_SHADER_MODEL_5_1,
         );
 
+        let supports_multi_planar_format = |format| {
+            let mut format_support = d3d12_ty::D3D12_FEATURE_DATA_FORMAT_SUPPORT {
+                Format: format,
+                Support1: d3d12_ty::D3D12_FORMAT_SUPPORT1_NONE,
+                Support2: d3d12_ty::D3D12_FORMAT_SUPPORT2_NONE,
+            };
+            let hr = unsafe {
+                device.CheckFeatureSupport(
+                    d3d12_ty::D3D12_FEATURE_FORMAT_SUPPORT,
+                    &mut format_support as *mut _ as *mut _,
+                    mem::size_of::<d3d12_ty::D3D12_FEATURE_DATA_FORMAT_SUPPORT>() as _,
+                )
+            };
+            let required = d3d12_ty::D3D12_FORMAT_SUPPORT1_TEXTURE2D
+                | d3d12_ty::D3D12_FORMAT_SUPPORT1_SHADER_SAMPLE;
+            hr == winerror::S_OK && format_support.Support1 & required == required
+        };
+        features.set(
+            wgt::Features::TEXTURE_FORMAT_NV12,
+            supports_multi_planar_format(winapi::shared::dxgiformat::DXGI_FORMAT_NV12),
+        );
+        features.set(
+            wgt::Features::TEXTURE_FORMAT_P010,
+            supports_multi_planar_format(winapi::shared::dxgiformat::DXGI_FORMAT_P010),
+        );
+
         // TODO: Determine if IPresentationManager is supported
         let presentation_timer = auxil::dxgi::time::PresentationTimer::new_dxgi();
 
@@ -378,6 +406,7 @@ impl crate::Adapter<super::Api> for super::Adapter {
         &self,
         _features: wgt::Features,
         _limits: &wgt::Limits,
+        allocator: &wgt::AllocatorConfig,
     ) -> Result<crate::OpenDevice<super::Api>, crate::DeviceError> {
         let queue = {
             profiling::scope!("ID3D12Device::CreateCommandQueue");
@@ -393,10 +422,12 @@ impl crate::Adapter<super::Api> for super::Adapter {
 
         let device = super::Device::new(
             self.device.clone(),
+            self.raw.clone(),
             queue.clone(),
             self.private_caps,
             &self.library,
             self.dx12_shader_compiler.clone(),
+            allocator,
         )?;
         Ok(crate::OpenDevice {
             device,
@@ -424,6 +455,9 @@ impl crate::Adapter<super::Api> for super::Adapter {
                 // use the depth aspect here as opposed to stencil since it has more capabilities
                 crate::FormatAspects::DEPTH,
             )
+        } else if format.is_multi_planar_format() {
+            // Support of multi-planar formats is reported for the format itself
+            Some(raw_format)
         } else {
             auxil::dxgi::conv::map_texture_format_for_srv_uav(
                 format,
@@ -394,12 +394,22 @@ impl crate::CommandEncoder<super::Api> for super::CommandEncoder {
                     // Only one barrier if it affects the whole image.
                     self.temp.barriers.push(raw);
                 } else {
-                    // Selected texture aspect is relevant if the texture format has both depth _and_ stencil aspects.
+                    // Selected texture aspect is relevant if the texture format has both depth _and_ stencil aspects,
+                    // or multiple planes.
                     let planes = if barrier.texture.format.is_combined_depth_stencil_format() {
                         match barrier.range.aspect {
                             wgt::TextureAspect::All => 0..2,
                             wgt::TextureAspect::DepthOnly => 0..1,
                             wgt::TextureAspect::StencilOnly => 1..2,
+                            wgt::TextureAspect::Plane0 | wgt::TextureAspect::Plane1 => {
+                                unreachable!()
+                            }
+                        }
+                    } else if let Some(planes) = barrier.texture.format.planes() {
+                        match barrier.range.aspect {
+                            wgt::TextureAspect::Plane0 => 0..1,
+                            wgt::TextureAspect::Plane1 => 1..2,
+                            _ => 0..planes,
                         }
                     } else {
                         match barrier.texture.format {
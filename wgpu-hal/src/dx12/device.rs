@@ -4,7 +4,7 @@ use super::{conv, descriptor, view};
 use parking_lot::Mutex;
 use std::{ffi, mem, num::NonZeroU32, ptr, sync::Arc};
 use winapi::{
-    shared::{dxgiformat, dxgitype, minwindef::BOOL, winerror},
+    shared::{dxgi, dxgi1_2, dxgi1_4, dxgiformat, dxgitype, minwindef::BOOL, winerror},
     um::{d3d12 as d3d12_ty, synchapi, winbase},
     Interface,
 };
@@ -15,10 +15,12 @@ const NAGA_LOCATION_SEMANTIC: &[u8] = b"LOC\0";
 impl super::Device {
     pub(super) fn new(
         raw: d3d12::Device,
+        adapter: d3d12::DxgiAdapter,
         present_queue: d3d12::CommandQueue,
         private_caps: super::PrivateCapabilities,
         library: &Arc<d3d12::D3D12Lib>,
         dx12_shader_compiler: wgt::Dx12Compiler,
+        allocator_config: &wgt::AllocatorConfig,
     ) -> Result<Self, crate::DeviceError> {
         let mem_allocator = if private_caps.suballocation_supported {
             super::suballocation::create_allocator_wrapper(&raw)?
@@ -151,6 +153,7 @@ impl super::Device {
 
         Ok(super::Device {
             raw: raw.clone(),
+            adapter,
             present_queue,
             idler: super::Idler {
                 fence: idle_fence,
@@ -177,6 +180,8 @@ impl super::Device {
             null_rtv_handle,
             mem_allocator,
             dxc_container,
+            counters: Default::default(),
+            allocator_config: allocator_config.clone(),
         })
     }
 
@@ -206,24 +211,39 @@ impl super::Device {
     ) -> Result<super::CompiledShader, crate::PipelineError> {
         use naga::back::hlsl;
 
+        let naga_shader = match stage.module.source {
+            super::ShaderModuleSource::Naga(ref naga_shader) => naga_shader,
+            super::ShaderModuleSource::Dxil {
+                ref bytecode,
+                stage: module_stage,
+            } => {
+                if module_stage != naga_stage || stage.entry_point != "main" {
+                    return Err(crate::PipelineError::EntryPoint(naga_stage));
+                }
+                crate::auxil::check_passthrough_constants(stage.constants, naga_stage)?;
+                return Ok(super::CompiledShader::Dxc(bytecode.clone()));
+            }
+            super::ShaderModuleSource::Hlsl(ref source) => {
+                crate::auxil::check_passthrough_constants(stage.constants, naga_stage)?;
+                let (result, _) =
+                    self.compile_hlsl(source, stage.entry_point, stage, layout, naga_stage);
+                return result;
+            }
+        };
+
         let stage_bit = crate::auxil::map_naga_stage(naga_stage);
-        let module = &stage.module.naga.module;
+        let module = crate::auxil::apply_pipeline_constants(&naga_shader.module, stage.constants);
+        let module = &*module;
         //TODO: reuse the writer
         let mut source = String::new();
         let mut writer = hlsl::Writer::new(&mut source, &layout.naga_options);
         let reflection_info = {
             profiling::scope!("naga::back::hlsl::write");
             writer
-                .write(module, &stage.module.naga.info)
+                .write(module, &naga_shader.info)
                 .map_err(|e| crate::PipelineError::Linkage(stage_bit, format!("HLSL: {e:?}")))?
         };
 
-        let full_stage = format!(
-            "{}_{}\0",
-            naga_stage.to_hlsl_str(),
-            layout.naga_options.shader_model.to_str()
-        );
-
         let ep_index = module
             .entry_points
             .iter()
@@ -234,6 +254,36 @@ impl super::Device {
             .as_ref()
             .map_err(|e| crate::PipelineError::Linkage(stage_bit, format!("{e}")))?;
 
+        let (result, log_level) = self.compile_hlsl(&source, raw_ep, stage, layout, naga_stage);
+
+        log::log!(
+            log_level,
+            "Naga generated shader for {:?} at {:?}:\n{}",
+            raw_ep,
+            naga_stage,
+            source
+        );
+        result
+    }
+
+    fn compile_hlsl(
+        &self,
+        source: &str,
+        raw_ep: &str,
+        stage: &crate::ProgrammableStage<super::Api>,
+        layout: &super::PipelineLayout,
+        naga_stage: naga::ShaderStage,
+    ) -> (
+        Result<super::CompiledShader, crate::PipelineError>,
+        log::Level,
+    ) {
+        let stage_bit = crate::auxil::map_naga_stage(naga_stage);
+        let full_stage = format!(
+            "{}_{}\0",
+            naga_stage.to_hlsl_str(),
+            layout.naga_options.shader_model.to_str()
+        );
+
         let source_name = stage
             .module
             .raw_name
@@ -242,10 +292,10 @@ impl super::Device {
             .unwrap_or_default();
 
         // Compile with DXC if available, otherwise fall back to FXC
-        let (result, log_level) = if let Some(ref dxc_container) = self.dxc_container {
+        if let Some(ref dxc_container) = self.dxc_container {
             super::shader_compilation::compile_dxc(
                 self,
-                &source,
+                source,
                 source_name,
                 raw_ep,
                 stage_bit,
@@ -255,22 +305,13 @@ impl super::Device {
         } else {
             super::shader_compilation::compile_fxc(
                 self,
-                &source,
+                source,
                 source_name,
-                &ffi::CString::new(raw_ep.as_str()).unwrap(),
+                &ffi::CString::new(raw_ep).unwrap(),
                 stage_bit,
                 full_stage,
             )
-        };
-
-        log::log!(
-            log_level,
-            "Naga generated shader for {:?} at {:?}:\n{}",
-            raw_ep,
-            naga_stage,
-            source
-        );
-        result
+        }
     }
 
     pub fn raw_device(&self) -> &d3d12::Device {
@@ -297,6 +338,7 @@ impl super::Device {
             mip_level_count,
             sample_count,
             allocation: None,
+            allocated_size: 0,
         }
     }
 
@@ -308,6 +350,7 @@ impl super::Device {
             resource,
             size,
             allocation: None,
+            allocated_size: 0,
         }
     }
 }
@@ -354,14 +397,18 @@ impl crate::Device<super::Api> for super::Device {
             unsafe { resource.SetName(cwstr.as_ptr()) };
         }
 
+        self.counters.add_buffer(size);
+
         Ok(super::Buffer {
             resource,
             size,
             allocation,
+            allocated_size: size,
         })
     }
 
     unsafe fn destroy_buffer(&self, mut buffer: super::Buffer) {
+        self.counters.remove_buffer(buffer.allocated_size);
         // Only happens when it's using the windows_rs feature and there's an allocation
         if let Some(alloc) = buffer.allocation.take() {
             super::suballocation::free_buffer_allocation(
@@ -435,6 +482,9 @@ impl crate::Device<super::Api> for super::Device {
             unsafe { resource.SetName(cwstr.as_ptr()) };
         }
 
+        let allocated_size = auxil::estimate_texture_size(desc);
+        self.counters.add_texture(allocated_size);
+
         Ok(super::Texture {
             resource,
             format: desc.format,
@@ -443,10 +493,12 @@ impl crate::Device<super::Api> for super::Device {
             mip_level_count: desc.mip_level_count,
             sample_count: desc.sample_count,
             allocation,
+            allocated_size,
         })
     }
 
     unsafe fn destroy_texture(&self, mut texture: super::Texture) {
+        self.counters.remove_texture(texture.allocated_size);
         if let Some(alloc) = texture.allocation.take() {
             super::suballocation::free_texture_allocation(
                 alloc,
@@ -1236,6 +1288,13 @@ impl crate::Device<super::Api> for super::Device {
             self.shared.heap_samplers.free_slice(dual);
         }
     }
+    unsafe fn update_bind_group(
+        &self,
+        _group: &super::BindGroup,
+        _desc: &crate::BindGroupUpdateDescriptor<super::Api>,
+    ) {
+        unreachable!("UPDATE_AFTER_BIND_DESCRIPTORS is not supported by this backend")
+    }
 
     unsafe fn create_shader_module(
         &self,
@@ -1243,12 +1302,21 @@ impl crate::Device<super::Api> for super::Device {
         shader: crate::ShaderInput,
     ) -> Result<super::ShaderModule, crate::ShaderError> {
         let raw_name = desc.label.and_then(|label| ffi::CString::new(label).ok());
-        match shader {
-            crate::ShaderInput::Naga(naga) => Ok(super::ShaderModule { naga, raw_name }),
+        let source = match shader {
+            crate::ShaderInput::Naga(naga) => super::ShaderModuleSource::Naga(naga),
+            crate::ShaderInput::Dxil { bytecode, stage } => super::ShaderModuleSource::Dxil {
+                bytecode: bytecode.to_vec(),
+                stage,
+            },
+            crate::ShaderInput::Hlsl(source) => super::ShaderModuleSource::Hlsl(source.to_string()),
             crate::ShaderInput::SpirV(_) => {
                 panic!("SPIRV_SHADER_PASSTHROUGH is not enabled for this backend")
             }
-        }
+            crate::ShaderInput::Msl { .. } | crate::ShaderInput::Glsl { .. } => {
+                panic!("Only DXIL and HLSL shaders can be passed through to DX12")
+            }
+        };
+        Ok(super::ShaderModule { source, raw_name })
     }
     unsafe fn destroy_shader_module(&self, _module: super::ShaderModule) {
         // just drop
@@ -1569,4 +1637,52 @@ impl crate::Device<super::Api> for super::Device {
                 .end_frame_capture(self.raw.as_mut_ptr() as *mut _, ptr::null_mut())
         }
     }
+
+    unsafe fn memory_report(&self) -> crate::MemoryReport {
+        // `IDXGIAdapter2` isn't available on all systems, but `IDXGIAdapter1` is.
+        let (dedicated_memory, shared_memory) = match self.adapter.as_adapter2() {
+            Some(adapter) => {
+                let mut desc: dxgi1_2::DXGI_ADAPTER_DESC2 = unsafe { mem::zeroed() };
+                unsafe { adapter.GetDesc2(&mut desc) };
+                (desc.DedicatedVideoMemory, desc.SharedSystemMemory)
+            }
+            None => {
+                let mut desc: dxgi::DXGI_ADAPTER_DESC1 = unsafe { mem::zeroed() };
+                unsafe { self.adapter.adapter1().GetDesc1(&mut desc) };
+                (desc.DedicatedVideoMemory, desc.SharedSystemMemory)
+            }
+        };
+
+        let segments = [
+            (
+                dxgi1_4::DXGI_MEMORY_SEGMENT_GROUP_LOCAL,
+                dedicated_memory as u64,
+                true,
+            ),
+            (
+                dxgi1_4::DXGI_MEMORY_SEGMENT_GROUP_NON_LOCAL,
+                shared_memory as u64,
+                false,
+            ),
+        ];
+        let heaps = segments
+            .iter()
+            .map(|&(group, size, device_local)| {
+                // `QueryVideoMemoryInfo` needs `IDXGIAdapter3`, which older systems don't have.
+                let info = self.adapter.as_adapter3().and_then(|adapter| {
+                    let mut info: dxgi1_4::DXGI_QUERY_VIDEO_MEMORY_INFO = unsafe { mem::zeroed() };
+                    let hr = unsafe { adapter.QueryVideoMemoryInfo(0, group, &mut info) };
+                    hr.into_result().ok().map(|()| info)
+                });
+                wgt::MemoryHeapReport {
+                    size,
+                    device_local,
+                    usage: info.map(|info| info.CurrentUsage),
+                    budget: info.map(|info| info.Budget),
+                }
+            })
+            .collect();
+
+        self.counters.report(heaps)
+    }
 }
@@ -222,6 +222,7 @@ struct DeviceShared {
 
 pub struct Device {
     raw: d3d12::Device,
+    adapter: d3d12::DxgiAdapter,
     present_queue: d3d12::CommandQueue,
     idler: Idler,
     private_caps: PrivateCapabilities,
@@ -238,6 +239,9 @@ pub struct Device {
     null_rtv_handle: descriptor::Handle,
     mem_allocator: Option<Mutex<suballocation::GpuAllocatorWrapper>>,
     dxc_container: Option<shader_compilation::DxcContainer>,
+    counters: auxil::AllocationCounters,
+    #[allow(unused)] // Only used when suballocating with the windows_rs feature.
+    allocator_config: wgt::AllocatorConfig,
 }
 
 unsafe impl Send for Device {}
@@ -375,6 +379,8 @@ pub struct Buffer {
     resource: d3d12::Resource,
     size: wgt::BufferAddress,
     allocation: Option<suballocation::AllocationWrapper>,
+    /// Bytes counted in the device's memory report, zero if wgpu didn't allocate the buffer.
+    allocated_size: u64,
 }
 
 unsafe impl Send for Buffer {}
@@ -402,6 +408,8 @@ pub struct Texture {
     mip_level_count: u32,
     sample_count: u32,
     allocation: Option<suballocation::AllocationWrapper>,
+    /// Bytes counted in the device's memory report, zero if wgpu didn't allocate the texture.
+    allocated_size: u64,
 }
 
 unsafe impl Send for Texture {}
@@ -424,6 +432,8 @@ impl Texture {
         let plane = match base.aspect {
             crate::FormatAspects::COLOR | crate::FormatAspects::DEPTH => 0,
             crate::FormatAspects::STENCIL => 1,
+            crate::FormatAspects::PLANE_0 => 0,
+            crate::FormatAspects::PLANE_1 => 1,
             _ => unreachable!(),
         };
         self.calc_subresource(base.mip_level, base.array_layer, plane)
@@ -534,14 +544,26 @@ pub struct PipelineLayout {
     naga_options: naga::back::hlsl::Options,
 }
 
+#[allow(clippy::large_enum_variant)]
+#[derive(Debug)]
+enum ShaderModuleSource {
+    Naga(crate::NagaShader),
+    /// DXIL bytecode passed through as-is.
+    Dxil {
+        bytecode: Vec<u8>,
+        stage: naga::ShaderStage,
+    },
+    /// HLSL source, compiled for each entry point used by a pipeline.
+    Hlsl(String),
+}
+
 #[derive(Debug)]
 pub struct ShaderModule {
-    naga: crate::NagaShader,
+    source: ShaderModuleSource,
     raw_name: Option<ffi::CString>,
 }
 
 pub(super) enum CompiledShader {
-    #[allow(unused)]
     Dxc(Vec<u8>),
     Fxc(d3d12::Blob),
 }
@@ -800,6 +822,7 @@ impl crate::Surface<Api> for Surface {
             mip_level_count: 1,
             sample_count: 1,
             allocation: None,
+            allocated_size: 0,
         };
         Ok(Some(crate::AcquiredSurfaceTexture {
             texture,
@@ -100,6 +100,11 @@ mod placed {
             name,
             location,
         );
+        if wants_dedicated_allocation(device, allocation_desc.size, desc.memory_flags) {
+            drop(allocator);
+            return super::committed::create_buffer_resource(device, desc, raw_desc, resource)
+                .map(|(hr, _)| (hr, None));
+        }
         let allocation = allocator.allocator.allocate(&allocation_desc)?;
 
         let hr = unsafe {
@@ -147,6 +152,11 @@ mod placed {
             name,
             location,
         );
+        if wants_dedicated_allocation(device, allocation_desc.size, desc.memory_flags) {
+            drop(allocator);
+            return super::committed::create_texture_resource(device, desc, raw_desc, resource)
+                .map(|(hr, _)| (hr, None));
+        }
         let allocation = allocator.allocator.allocate(&allocation_desc)?;
 
         let hr = unsafe {
@@ -164,6 +174,19 @@ mod placed {
         Ok((hr, Some(AllocationWrapper { allocation })))
     }
 
+    /// Large resources get a committed resource with its own heap, as configured
+    /// by the device's `AllocatorConfig`, instead of being placed in a shared heap.
+    fn wants_dedicated_allocation(
+        device: &crate::dx12::Device,
+        size: u64,
+        memory_flags: crate::MemoryFlags,
+    ) -> bool {
+        let config = &device.allocator_config;
+        size >= config.dedicated_allocation_threshold
+            || (memory_flags.contains(crate::MemoryFlags::LONG_LIVED)
+                && size >= config.long_lived_dedicated_allocation_threshold)
+    }
+
     pub(crate) fn free_buffer_allocation(
         allocation: AllocationWrapper,
         allocator: &Mutex<GpuAllocatorWrapper>,
@@ -10,6 +10,7 @@ pub(super) struct ViewDescriptor {
     pub rtv_dsv_format: d3d12::Format,
     srv_uav_format: Option<d3d12::Format>,
     multisampled: bool,
+    plane_slice: u32,
     array_layer_base: u32,
     array_layer_count: u32,
     mip_level_base: u32,
@@ -26,6 +27,11 @@ impl crate::TextureViewDescriptor<'_> {
             rtv_dsv_format: auxil::dxgi::conv::map_texture_format(self.format),
             srv_uav_format: auxil::dxgi::conv::map_texture_format_for_srv_uav(self.format, aspects),
             multisampled: texture.sample_count > 1,
+            plane_slice: if aspects == crate::FormatAspects::PLANE_1 {
+                1
+            } else {
+                0
+            },
             mip_level_base: self.range.base_mip_level,
             mip_level_count: self.range.mip_level_count.unwrap_or(!0),
             array_layer_base: self.range.base_array_layer,
@@ -79,7 +85,7 @@ impl ViewDescriptor {
                     *desc.u.Texture2D_mut() = d3d12_ty::D3D12_TEX2D_SRV {
                         MostDetailedMip: self.mip_level_base,
                         MipLevels: self.mip_level_count,
-                        PlaneSlice: 0,
+                        PlaneSlice: self.plane_slice,
                         ResourceMinLODClamp: 0.0,
                     }
                 }
@@ -103,7 +109,7 @@ impl ViewDescriptor {
                         MipLevels: self.mip_level_count,
                         FirstArraySlice: self.array_layer_base,
                         ArraySize: self.array_layer_count,
-                        PlaneSlice: 0,
+                        PlaneSlice: self.plane_slice,
                         ResourceMinLODClamp: 0.0,
                     }
                 }
@@ -80,6 +80,7 @@ impl crate::Adapter<Api> for Context {
         &self,
         features: wgt::Features,
         _limits: &wgt::Limits,
+        _allocator: &wgt::AllocatorConfig,
     ) -> DeviceResult<crate::OpenDevice<Api>> {
         Err(crate::DeviceError::Lost)
     }
@@ -185,6 +186,12 @@ impl crate::Device<Api> for Context {
         Ok(Resource)
     }
     unsafe fn destroy_bind_group(&self, group: Resource) {}
+    unsafe fn update_bind_group(
+        &self,
+        group: &Resource,
+        desc: &crate::BindGroupUpdateDescriptor<Api>,
+    ) {
+    }
 
     unsafe fn create_shader_module(
         &self,
@@ -236,6 +243,9 @@ impl crate::Device<Api> for Context {
         false
     }
     unsafe fn stop_capture(&self) {}
+    unsafe fn memory_report(&self) -> crate::MemoryReport {
+        crate::MemoryReport::default()
+    }
 }
 
 impl crate::CommandEncoder<Api> for Encoder {
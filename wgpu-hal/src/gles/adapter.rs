@@ -175,6 +175,7 @@ impl super::Adapter {
             device_type: inferred_device_type,
             driver: String::new(),
             driver_info: String::new(),
+            device_node: String::new(),
             backend: wgt::Backend::Gl,
         }
     }
@@ -301,9 +302,10 @@ impl super::Adapter {
             max_storage_block_size != 0,
         );
         downlevel_flags.set(wgt::DownlevelFlags::INDIRECT_EXECUTION, ver >= (3, 1));
-        //TODO: we can actually support positive `base_vertex` in the same way
-        // as we emulate the `start_instance`. But we can't deal with negatives...
         downlevel_flags.set(wgt::DownlevelFlags::BASE_VERTEX, ver >= (3, 2));
+        // Positive `base_vertex` is emulated in the same way as `start_instance`,
+        // but we can't deal with negatives.
+        downlevel_flags |= wgt::DownlevelFlags::NON_NEGATIVE_BASE_VERTEX;
         downlevel_flags.set(
             wgt::DownlevelFlags::INDEPENDENT_BLEND,
             ver >= (3, 2) || extensions.contains("GL_EXT_draw_buffers_indexed"),
@@ -345,7 +347,8 @@ impl super::Adapter {
         let mut features = wgt::Features::empty()
             | wgt::Features::TEXTURE_ADAPTER_SPECIFIC_FORMAT_FEATURES
             | wgt::Features::CLEAR_TEXTURE
-            | wgt::Features::PUSH_CONSTANTS;
+            | wgt::Features::PUSH_CONSTANTS
+            | wgt::Features::SHADER_PASSTHROUGH;
         features.set(
             wgt::Features::ADDRESS_MODE_CLAMP_TO_BORDER | wgt::Features::ADDRESS_MODE_CLAMP_TO_ZERO,
             extensions.contains("GL_EXT_texture_border_clamp"),
@@ -368,6 +371,44 @@ impl super::Adapter {
             ver >= (3, 2) || extensions.contains("OES_geometry_shader"),
         );
         features.set(wgt::Features::SHADER_EARLY_DEPTH_TEST, ver >= (3, 1));
+        // glow doesn't implement query counters on WebGL2
+        let timer_query_disjoint = extensions.contains("GL_EXT_disjoint_timer_query");
+        let full_ver = gl.version();
+        // `GL_TIMESTAMP` is core since desktop GL 3.3, which doesn't have to
+        // advertise `GL_ARB_timer_query`.
+        let timer_query = timer_query_disjoint
+            || extensions.contains("GL_ARB_timer_query")
+            || (!full_ver.is_embedded && (full_ver.major, full_ver.minor) >= (3, 3));
+        // Timestamps are read with `glGetQueryObjectui64v`, which glow doesn't wrap for
+        // client memory, so it is loaded separately
+        features.set(
+            wgt::Features::TIMESTAMP_QUERY,
+            timer_query && context.get_query_object_u64().is_some(),
+        );
+        // Pipeline statistics queries are core since desktop GL 4.6, and are read
+        // like timestamps
+        features.set(
+            wgt::Features::PIPELINE_STATISTICS_QUERY,
+            (extensions.contains("GL_ARB_pipeline_statistics_query")
+                || (!full_ver.is_embedded && (full_ver.major, full_ver.minor) >= (4, 6)))
+                && context.get_query_object_u64().is_some(),
+        );
+        // glow doesn't wrap the multi-draw entry points, so they are loaded separately
+        let multi_draw_indirect = downlevel_flags.contains(wgt::DownlevelFlags::INDIRECT_EXECUTION)
+            && (extensions.contains("GL_EXT_multi_draw_indirect")
+                || extensions.contains("GL_ARB_multi_draw_indirect")
+                || (!full_ver.is_embedded && (full_ver.major, full_ver.minor) >= (4, 3)))
+            && context.multi_draw_indirect().is_some();
+        features.set(wgt::Features::MULTI_DRAW_INDIRECT, multi_draw_indirect);
+        let polygon_mode = !gl.version().is_embedded || extensions.contains("GL_NV_polygon_mode");
+        // Without `glPolygonMode`, triangles are drawn as lines by rewriting the indices,
+        // which reads indirect draw arguments back to the CPU, so users have to opt in
+        features.set(wgt::Features::POLYGON_MODE_LINE, polygon_mode);
+        downlevel_flags.set(
+            wgt::DownlevelFlags::EMULATED_POLYGON_MODE_LINE,
+            !polygon_mode,
+        );
+        features.set(wgt::Features::POLYGON_MODE_POINT, polygon_mode);
         let gles_bcn_exts = [
             "GL_EXT_texture_compression_s3tc_srgb",
             "GL_EXT_texture_compression_rgtc",
@@ -474,6 +515,21 @@ impl super::Adapter {
             super::PrivateCapabilities::TEXTURE_FLOAT_LINEAR,
             extensions.contains("OES_texture_float_linear"),
         );
+        private_caps.set(
+            super::PrivateCapabilities::TIMER_QUERY_DISJOINT,
+            timer_query_disjoint,
+        );
+        private_caps.set(super::PrivateCapabilities::BASE_VERTEX, ver >= (3, 2));
+        private_caps.set(
+            super::PrivateCapabilities::MULTI_DRAW_INDIRECT,
+            multi_draw_indirect,
+        );
+        private_caps.set(super::PrivateCapabilities::POLYGON_MODE, polygon_mode);
+        private_caps.set(
+            super::PrivateCapabilities::QUERY_BUFFER,
+            (!full_ver.is_embedded && (full_ver.major, full_ver.minor) >= (4, 4))
+                || extensions.contains("GL_ARB_query_buffer_object"),
+        );
 
         let max_texture_size = unsafe { gl.get_parameter_i32(glow::MAX_TEXTURE_SIZE) } as u32;
         let max_texture_3d_size = unsafe { gl.get_parameter_i32(glow::MAX_3D_TEXTURE_SIZE) } as u32;
@@ -601,6 +657,20 @@ impl super::Adapter {
             workarounds.set(super::Workarounds::MESA_I915_SRGB_SHADER_CLEAR, true);
         }
 
+        // Drivers may support the entry points without any binary format.
+        let program_binaries =
+            if unsafe { gl.get_parameter_i32(glow::NUM_PROGRAM_BINARY_FORMATS) } > 0 {
+                context.program_binary().map(|(fns, storage)| {
+                    super::ProgramBinaryCache::new(
+                        fns,
+                        format!("{vendor}/{renderer}/{version}"),
+                        storage,
+                    )
+                })
+            } else {
+                None
+            };
+
         let downlevel_defaults = wgt::DownlevelLimits {};
 
         // Drop the GL guard so we can move the context into AdapterShared
@@ -620,6 +690,9 @@ impl super::Adapter {
                     max_texture_size,
                     next_shader_id: Default::default(),
                     program_cache: Default::default(),
+                    program_binaries,
+                    wireframe_indices: Default::default(),
+                    reset_queries: Default::default(),
                 }),
             },
             info: Self::make_info(vendor, renderer),
@@ -639,7 +712,7 @@ impl super::Adapter {
         })
     }
 
-    unsafe fn create_shader_clear_program(
+    pub(super) unsafe fn create_shader_clear_program(
         gl: &glow::Context,
     ) -> (glow::Program, glow::UniformLocation) {
         let program = unsafe { gl.create_program() }.expect("Could not create shader program");
@@ -668,6 +741,7 @@ impl crate::Adapter<super::Api> for super::Adapter {
         &self,
         features: wgt::Features,
         _limits: &wgt::Limits,
+        _allocator: &wgt::AllocatorConfig,
     ) -> Result<crate::OpenDevice<super::Api>, crate::DeviceError> {
         let gl = &self.shared.context.lock();
         unsafe { gl.pixel_store_i32(glow::UNPACK_ALIGNMENT, 1) };
@@ -707,6 +781,8 @@ impl crate::Adapter<super::Api> for super::Adapter {
                 temp_query_results: Vec::new(),
                 draw_buffer_count: 1,
                 current_index_buffer: None,
+                wireframe_index_buffer: unsafe { gl.create_buffer() }
+                    .map_err(|_| crate::DeviceError::OutOfMemory)?,
             },
         })
     }
@@ -842,6 +918,7 @@ impl crate::Adapter<super::Api> for super::Adapter {
             | Tf::Depth24Plus
             | Tf::Depth24PlusStencil8 => depth,
             Tf::Rgb9e5Ufloat => filterable,
+            Tf::NV12 | Tf::P010 => empty,
             Tf::Bc1RgbaUnorm
             | Tf::Bc1RgbaUnormSrgb
             | Tf::Bc2RgbaUnorm
@@ -904,8 +981,8 @@ impl crate::Adapter<super::Api> for super::Adapter {
 
             Some(crate::SurfaceCapabilities {
                 formats,
-                present_modes: vec![wgt::PresentMode::Fifo], //TODO
-                composite_alpha_modes: vec![wgt::CompositeAlphaMode::Opaque], //TODO
+                present_modes: surface.present_modes(),
+                composite_alpha_modes: surface.composite_alpha_modes(),
                 swap_chain_sizes: 2..=2,
                 current_extent: None,
                 extents: wgt::Extent3d {
@@ -930,28 +1007,74 @@ impl crate::Adapter<super::Api> for super::Adapter {
 }
 
 impl super::AdapterShared {
+    /// Reads back `dst_data.len()` bytes at `offset` of the buffer bound to
+    /// `target`.
+    ///
+    /// Without `glGetBufferSubData`, the range is copied into a staging buffer
+    /// which is mapped instead, since the storage of the bound buffer may not
+    /// allow `MAP_READ_BIT`.
     pub(super) unsafe fn get_buffer_sub_data(
         &self,
         gl: &glow::Context,
         target: u32,
         offset: i32,
         dst_data: &mut [u8],
-    ) {
+    ) -> Result<(), crate::DeviceError> {
         if self
             .private_caps
             .contains(super::PrivateCapabilities::GET_BUFFER_SUB_DATA)
         {
             unsafe { gl.get_buffer_sub_data(target, offset, dst_data) };
-        } else {
-            log::error!("Fake map");
-            let length = dst_data.len();
-            let buffer_mapping =
-                unsafe { gl.map_buffer_range(target, offset, length as _, glow::MAP_READ_BIT) };
+            return Ok(());
+        }
 
+        log::error!("Fake map");
+        let length = dst_data.len();
+        let staging = unsafe { gl.create_buffer() }.map_err(|_| crate::DeviceError::OutOfMemory)?;
+        unsafe { gl.bind_buffer(glow::COPY_WRITE_BUFFER, Some(staging)) };
+        unsafe { gl.buffer_data_size(glow::COPY_WRITE_BUFFER, length as i32, glow::STREAM_READ) };
+        unsafe {
+            gl.copy_buffer_sub_data(target, glow::COPY_WRITE_BUFFER, offset, 0, length as i32)
+        };
+
+        let buffer_mapping = unsafe {
+            gl.map_buffer_range(
+                glow::COPY_WRITE_BUFFER,
+                0,
+                length as i32,
+                glow::MAP_READ_BIT,
+            )
+        };
+        let result = if buffer_mapping.is_null() {
+            log::error!("Failed to map the staging buffer of a buffer read back");
+            Err(crate::DeviceError::Lost)
+        } else {
             unsafe { std::ptr::copy_nonoverlapping(buffer_mapping, dst_data.as_mut_ptr(), length) };
+            unsafe { gl.unmap_buffer(glow::COPY_WRITE_BUFFER) };
+            Ok(())
+        };
 
-            unsafe { gl.unmap_buffer(target) };
-        }
+        unsafe { gl.bind_buffer(glow::COPY_WRITE_BUFFER, None) };
+        unsafe { gl.delete_buffer(staging) };
+        result
+    }
+
+    /// Drops the line list indices of wireframe draws that were built from
+    /// the contents of `buffer`, before they change.
+    pub(super) unsafe fn invalidate_wireframe_indices(
+        &self,
+        gl: &glow::Context,
+        buffer: glow::Buffer,
+    ) {
+        self.wireframe_indices
+            .lock()
+            .retain(|key, &mut (lines, _)| {
+                let stale = matches!(key.indices, Some((raw, _, _)) if raw == buffer);
+                if stale {
+                    unsafe { gl.delete_buffer(lines) };
+                }
+                !stale
+            });
     }
 }
 
@@ -12,8 +12,11 @@ struct TextureSlotDesc {
 pub(super) struct State {
     topology: u32,
     primitive: super::PrimitiveState,
+    /// Triangles have to be drawn as lines without `glPolygonMode`.
+    emulate_wireframe: bool,
     index_format: wgt::IndexFormat,
     index_offset: wgt::BufferAddress,
+    index_shader_writable: bool,
     vertex_buffers:
         [(super::VertexBufferDesc, Option<super::BufferBinding>); crate::MAX_VERTEX_BUFFERS],
     vertex_attributes: ArrayVec<super::AttributeDesc, { super::MAX_VERTEX_ATTRIBUTES }>,
@@ -28,11 +31,26 @@ pub(super) struct State {
     invalidate_attachments: ArrayVec<u32, { crate::MAX_COLOR_ATTACHMENTS + 2 }>,
     has_pass_label: bool,
     instance_vbuf_mask: usize,
+    vertex_vbuf_mask: usize,
     dirty_vbuf_mask: usize,
     active_first_instance: u32,
+    active_base_vertex: u32,
     push_offset_to_uniform: ArrayVec<super::UniformDesc, { super::MAX_PUSH_CONSTANTS }>,
 }
 
+/// Returns the offset of the first vertex or instance fetched from a vertex buffer.
+fn step_offset(
+    buffer_desc: &super::VertexBufferDesc,
+    first_instance: u32,
+    base_vertex: u32,
+) -> wgt::BufferAddress {
+    let step = match buffer_desc.step {
+        wgt::VertexStepMode::Vertex => base_vertex,
+        wgt::VertexStepMode::Instance => first_instance,
+    };
+    step as wgt::BufferAddress * buffer_desc.stride as wgt::BufferAddress
+}
+
 impl super::CommandBuffer {
     fn clear(&mut self) {
         self.label = None;
@@ -85,7 +103,7 @@ impl super::CommandEncoder {
         }
     }
 
-    fn rebind_vertex_data(&mut self, first_instance: u32) {
+    fn rebind_vertex_data(&mut self, first_instance: u32, base_vertex: u32) {
         if self
             .private_caps
             .contains(super::PrivateCapabilities::VERTEX_BUFFER_LAYOUT)
@@ -99,16 +117,13 @@ impl super::CommandEncoder {
                     (_, None) => continue,
                     (ref vb_desc, Some(ref vb)) => (vb_desc.clone(), vb),
                 };
-                let instance_offset = match buffer_desc.step {
-                    wgt::VertexStepMode::Vertex => 0,
-                    wgt::VertexStepMode::Instance => first_instance * buffer_desc.stride,
-                };
+                let step_offset = step_offset(&buffer_desc, first_instance, base_vertex);
 
                 self.cmd_buffer.commands.push(C::SetVertexBuffer {
                     index: index as u32,
                     buffer: super::BufferBinding {
                         raw: vb.raw,
-                        offset: vb.offset + instance_offset as wgt::BufferAddress,
+                        offset: vb.offset + step_offset,
                     },
                     buffer_desc,
                 });
@@ -127,11 +142,13 @@ impl super::CommandEncoder {
                         (ref vb_desc, Some(ref vb)) => (vb_desc.clone(), vb),
                     };
 
+                let offset = attribute.offset as wgt::BufferAddress
+                    + vb.offset
+                    + step_offset(&buffer_desc, first_instance, base_vertex);
                 let mut attribute_desc = attribute.clone();
-                attribute_desc.offset += vb.offset as u32;
-                if buffer_desc.step == wgt::VertexStepMode::Instance {
-                    attribute_desc.offset += buffer_desc.stride * first_instance;
-                }
+                // Only offsets past the end of the buffer can overflow, and every vertex
+                // fetched from them is out of bounds anyway.
+                attribute_desc.offset = u32::try_from(offset).unwrap_or(u32::MAX);
 
                 self.cmd_buffer.commands.push(C::SetVertexAttribute {
                     buffer: Some(vb.raw),
@@ -161,14 +178,21 @@ impl super::CommandEncoder {
         }
     }
 
-    fn prepare_draw(&mut self, first_instance: u32) {
+    /// Prepares the vertex data for a draw, `base_vertex` is only non-zero
+    /// when it needs to be emulated.
+    fn prepare_draw(&mut self, first_instance: u32, base_vertex: u32) {
         if first_instance != self.state.active_first_instance {
             // rebind all per-instance buffers on first-instance change
             self.state.dirty_vbuf_mask |= self.state.instance_vbuf_mask;
             self.state.active_first_instance = first_instance;
         }
+        if base_vertex != self.state.active_base_vertex {
+            // rebind all per-vertex buffers on base-vertex change
+            self.state.dirty_vbuf_mask |= self.state.vertex_vbuf_mask;
+            self.state.active_base_vertex = base_vertex;
+        }
         if self.state.dirty_vbuf_mask != 0 {
-            self.rebind_vertex_data(first_instance);
+            self.rebind_vertex_data(first_instance, base_vertex);
         }
     }
 
@@ -210,10 +234,17 @@ impl crate::CommandEncoder<super::Api> for super::CommandEncoder {
         self.cmd_buffer.clear();
     }
     unsafe fn end_encoding(&mut self) -> Result<super::CommandBuffer, crate::DeviceError> {
-        Ok(mem::take(&mut self.cmd_buffer))
+        let next = self.free.pop().unwrap_or_default();
+        Ok(mem::replace(&mut self.cmd_buffer, next))
     }
-    unsafe fn reset_all<I>(&mut self, _command_buffers: I) {
-        //TODO: could re-use the allocations in all these command buffers
+    unsafe fn reset_all<I>(&mut self, command_buffers: I)
+    where
+        I: Iterator<Item = super::CommandBuffer>,
+    {
+        self.free.extend(command_buffers.map(|mut cmd_buffer| {
+            cmd_buffer.clear();
+            cmd_buffer
+        }));
     }
 
     unsafe fn transition_buffers<'a, T>(&mut self, barriers: T)
@@ -402,19 +433,31 @@ impl crate::CommandEncoder<super::Api> for super::CommandEncoder {
     }
 
     unsafe fn begin_query(&mut self, set: &super::QuerySet, index: u32) {
-        let query = set.queries[index as usize];
-        self.cmd_buffer
-            .commands
-            .push(C::BeginQuery(query, set.target));
+        let queries = set.raw_queries(index..index + 1);
+        for (&query, &target) in queries.iter().zip(set.targets.iter()) {
+            self.cmd_buffer.commands.push(C::BeginQuery(query, target));
+        }
     }
     unsafe fn end_query(&mut self, set: &super::QuerySet, _index: u32) {
-        self.cmd_buffer.commands.push(C::EndQuery(set.target));
+        for &target in set.targets.iter() {
+            self.cmd_buffer.commands.push(C::EndQuery(target));
+        }
     }
-    unsafe fn write_timestamp(&mut self, _set: &super::QuerySet, _index: u32) {
-        unimplemented!()
+    unsafe fn write_timestamp(&mut self, set: &super::QuerySet, index: u32) {
+        let query = set.queries[index as usize];
+        self.cmd_buffer.commands.push(C::TimestampQuery(query));
     }
-    unsafe fn reset_queries(&mut self, _set: &super::QuerySet, _range: Range<u32>) {
-        //TODO: what do we do here?
+    unsafe fn reset_queries(&mut self, set: &super::QuerySet, range: Range<u32>) {
+        // GL queries are implicitly reset when written to again, so this
+        // only needs to make unwritten queries resolve to zero.
+        let start = self.cmd_buffer.queries.len();
+        self.cmd_buffer
+            .queries
+            .extend_from_slice(set.raw_queries(range));
+        let query_range = start as u32..self.cmd_buffer.queries.len() as u32;
+        self.cmd_buffer
+            .commands
+            .push(C::ResetQueries { query_range });
     }
     unsafe fn copy_query_results(
         &mut self,
@@ -427,10 +470,12 @@ impl crate::CommandEncoder<super::Api> for super::CommandEncoder {
         let start = self.cmd_buffer.queries.len();
         self.cmd_buffer
             .queries
-            .extend_from_slice(&set.queries[range.start as usize..range.end as usize]);
+            .extend_from_slice(set.raw_queries(range));
         let query_range = start as u32..self.cmd_buffer.queries.len() as u32;
+        // pipeline statistics are packed in the same order as their GL queries
         self.cmd_buffer.commands.push(C::CopyQueryResults {
             query_range,
+            query_target: set.targets[0],
             dst: buffer.clone(),
             dst_target: buffer.target,
             dst_offset: offset,
@@ -614,8 +659,10 @@ impl crate::CommandEncoder<super::Api> for super::CommandEncoder {
             self.state.has_pass_label = false;
         }
         self.state.instance_vbuf_mask = 0;
+        self.state.vertex_vbuf_mask = 0;
         self.state.dirty_vbuf_mask = 0;
         self.state.active_first_instance = 0;
+        self.state.active_base_vertex = 0;
         self.state.color_targets.clear();
         for vat in &self.state.vertex_attributes {
             self.cmd_buffer
@@ -743,6 +790,14 @@ impl crate::CommandEncoder<super::Api> for super::CommandEncoder {
 
     unsafe fn set_render_pipeline(&mut self, pipeline: &super::RenderPipeline) {
         self.state.topology = conv::map_primitive_topology(pipeline.primitive.topology);
+        self.state.emulate_wireframe = pipeline.primitive.polygon_mode == wgt::PolygonMode::Line
+            && matches!(
+                pipeline.primitive.topology,
+                wgt::PrimitiveTopology::TriangleList | wgt::PrimitiveTopology::TriangleStrip
+            )
+            && !self
+                .private_caps
+                .contains(super::PrivateCapabilities::POLYGON_MODE);
 
         if self
             .private_caps
@@ -775,6 +830,7 @@ impl crate::CommandEncoder<super::Api> for super::CommandEncoder {
         }
 
         self.state.instance_vbuf_mask = 0;
+        self.state.vertex_vbuf_mask = 0;
         // copy vertex state
         for (index, (&mut (ref mut state_desc, _), pipe_desc)) in self
             .state
@@ -783,8 +839,9 @@ impl crate::CommandEncoder<super::Api> for super::CommandEncoder {
             .zip(pipeline.vertex_buffers.iter())
             .enumerate()
         {
-            if pipe_desc.step == wgt::VertexStepMode::Instance {
-                self.state.instance_vbuf_mask |= 1 << index;
+            match pipe_desc.step {
+                wgt::VertexStepMode::Vertex => self.state.vertex_vbuf_mask |= 1 << index,
+                wgt::VertexStepMode::Instance => self.state.instance_vbuf_mask |= 1 << index,
             }
             if state_desc != pipe_desc {
                 self.state.dirty_vbuf_mask |= 1 << index;
@@ -886,6 +943,7 @@ impl crate::CommandEncoder<super::Api> for super::CommandEncoder {
     ) {
         self.state.index_offset = binding.offset;
         self.state.index_format = format;
+        self.state.index_shader_writable = binding.buffer.shader_writable;
         self.cmd_buffer
             .commands
             .push(C::SetIndexBuffer(binding.buffer.raw.unwrap()));
@@ -937,7 +995,19 @@ impl crate::CommandEncoder<super::Api> for super::CommandEncoder {
         start_instance: u32,
         instance_count: u32,
     ) {
-        self.prepare_draw(start_instance);
+        self.prepare_draw(start_instance, 0);
+        if self.state.emulate_wireframe {
+            self.cmd_buffer.commands.push(C::DrawWireframe {
+                topology: self.state.topology,
+                indices: None,
+                shader_writable: false,
+                start_vertex,
+                count: vertex_count,
+                base_vertex: 0,
+                instance_count,
+            });
+            return;
+        }
         self.cmd_buffer.commands.push(C::Draw {
             topology: self.state.topology,
             start_vertex,
@@ -953,12 +1023,36 @@ impl crate::CommandEncoder<super::Api> for super::CommandEncoder {
         start_instance: u32,
         instance_count: u32,
     ) {
-        self.prepare_draw(start_instance);
+        let base_vertex = if self
+            .private_caps
+            .contains(super::PrivateCapabilities::BASE_VERTEX)
+        {
+            self.prepare_draw(start_instance, 0);
+            base_vertex
+        } else {
+            // emulated by offsetting the per-vertex buffers, which only works
+            // for non-negative values
+            debug_assert!(base_vertex >= 0);
+            self.prepare_draw(start_instance, base_vertex as u32);
+            0
+        };
         let (index_size, index_type) = match self.state.index_format {
             wgt::IndexFormat::Uint16 => (2, glow::UNSIGNED_SHORT),
             wgt::IndexFormat::Uint32 => (4, glow::UNSIGNED_INT),
         };
         let index_offset = self.state.index_offset + index_size * start_index as wgt::BufferAddress;
+        if self.state.emulate_wireframe {
+            self.cmd_buffer.commands.push(C::DrawWireframe {
+                topology: self.state.topology,
+                indices: Some((index_type, index_offset)),
+                shader_writable: self.state.index_shader_writable,
+                start_vertex: 0,
+                count: index_count,
+                base_vertex,
+                instance_count,
+            });
+            return;
+        }
         self.cmd_buffer.commands.push(C::DrawIndexed {
             topology: self.state.topology,
             index_type,
@@ -974,7 +1068,33 @@ impl crate::CommandEncoder<super::Api> for super::CommandEncoder {
         offset: wgt::BufferAddress,
         draw_count: u32,
     ) {
-        self.prepare_draw(0);
+        self.prepare_draw(0, 0);
+        if self.state.emulate_wireframe {
+            for draw in 0..draw_count as wgt::BufferAddress {
+                let indirect_offset =
+                    offset + draw * mem::size_of::<wgt::DrawIndirectArgs>() as wgt::BufferAddress;
+                self.cmd_buffer.commands.push(C::DrawWireframeIndirect {
+                    topology: self.state.topology,
+                    indices: None,
+                    shader_writable: false,
+                    indirect_buf: buffer.raw.unwrap(),
+                    indirect_offset,
+                });
+            }
+            return;
+        }
+        if self
+            .private_caps
+            .contains(super::PrivateCapabilities::MULTI_DRAW_INDIRECT)
+        {
+            self.cmd_buffer.commands.push(C::DrawIndirect {
+                topology: self.state.topology,
+                indirect_buf: buffer.raw.unwrap(),
+                indirect_offset: offset,
+                draw_count,
+            });
+            return;
+        }
         for draw in 0..draw_count as wgt::BufferAddress {
             let indirect_offset =
                 offset + draw * mem::size_of::<wgt::DrawIndirectArgs>() as wgt::BufferAddress;
@@ -982,6 +1102,7 @@ impl crate::CommandEncoder<super::Api> for super::CommandEncoder {
                 topology: self.state.topology,
                 indirect_buf: buffer.raw.unwrap(),
                 indirect_offset,
+                draw_count: 1,
             });
         }
     }
@@ -991,11 +1112,38 @@ impl crate::CommandEncoder<super::Api> for super::CommandEncoder {
         offset: wgt::BufferAddress,
         draw_count: u32,
     ) {
-        self.prepare_draw(0);
+        self.prepare_draw(0, 0);
         let index_type = match self.state.index_format {
             wgt::IndexFormat::Uint16 => glow::UNSIGNED_SHORT,
             wgt::IndexFormat::Uint32 => glow::UNSIGNED_INT,
         };
+        if self.state.emulate_wireframe {
+            for draw in 0..draw_count as wgt::BufferAddress {
+                let indirect_offset = offset
+                    + draw * mem::size_of::<wgt::DrawIndexedIndirectArgs>() as wgt::BufferAddress;
+                self.cmd_buffer.commands.push(C::DrawWireframeIndirect {
+                    topology: self.state.topology,
+                    indices: Some((index_type, self.state.index_offset)),
+                    shader_writable: self.state.index_shader_writable,
+                    indirect_buf: buffer.raw.unwrap(),
+                    indirect_offset,
+                });
+            }
+            return;
+        }
+        if self
+            .private_caps
+            .contains(super::PrivateCapabilities::MULTI_DRAW_INDIRECT)
+        {
+            self.cmd_buffer.commands.push(C::DrawIndexedIndirect {
+                topology: self.state.topology,
+                index_type,
+                indirect_buf: buffer.raw.unwrap(),
+                indirect_offset: offset,
+                draw_count,
+            });
+            return;
+        }
         for draw in 0..draw_count as wgt::BufferAddress {
             let indirect_offset = offset
                 + draw * mem::size_of::<wgt::DrawIndexedIndirectArgs>() as wgt::BufferAddress;
@@ -1004,6 +1152,7 @@ impl crate::CommandEncoder<super::Api> for super::CommandEncoder {
                 index_type,
                 indirect_buf: buffer.raw.unwrap(),
                 indirect_offset,
+                draw_count: 1,
             });
         }
     }
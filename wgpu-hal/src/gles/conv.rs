@@ -83,6 +83,7 @@ impl super::AdapterShared {
                 glow::UNSIGNED_INT_24_8,
             ),
             Tf::Rgb9e5Ufloat => (glow::RGB9_E5, glow::RGB, glow::UNSIGNED_INT_5_9_9_9_REV),
+            Tf::NV12 | Tf::P010 => unreachable!(),
             Tf::Bc1RgbaUnorm => (glow::COMPRESSED_RGBA_S3TC_DXT1_EXT, glow::RGBA, 0),
             Tf::Bc1RgbaUnormSrgb => (glow::COMPRESSED_SRGB_ALPHA_S3TC_DXT1_EXT, glow::RGBA, 0),
             Tf::Bc2RgbaUnorm => (glow::COMPRESSED_RGBA_S3TC_DXT3_EXT, glow::RGBA, 0),
@@ -211,7 +212,10 @@ pub(super) fn describe_vertex_format(vertex_format: wgt::VertexFormat) -> super:
         Vf::Uint32x4 => (4, glow::UNSIGNED_INT, Vak::Integer),
         Vf::Sint32x4 => (4, glow::INT, Vak::Integer),
         Vf::Float32x4 => (4, glow::FLOAT, Vak::Float),
-        Vf::Float64 | Vf::Float64x2 | Vf::Float64x3 | Vf::Float64x4 => unimplemented!(),
+        // GLSL ES has no doubles, so `Features::VERTEX_ATTRIBUTE_64BIT` isn't exposed
+        Vf::Float64 | Vf::Float64x2 | Vf::Float64x3 | Vf::Float64x4 => {
+            unreachable!("64-bit vertex attributes aren't supported on GL")
+        }
     };
 
     super::VertexFormatDesc {
@@ -279,8 +283,6 @@ pub fn map_primitive_topology(topology: wgt::PrimitiveTopology) -> u32 {
 }
 
 pub(super) fn map_primitive_state(state: &wgt::PrimitiveState) -> super::PrimitiveState {
-    //Note: state.polygon_mode is not supported, see `Features::POLYGON_MODE_LINE` and
-    //`Features::POLYGON_MODE_POINT`
     super::PrimitiveState {
         //Note: we are flipping the front face, so that
         // the Y-flip in the generated GLSL keeps the same visibility.
@@ -295,6 +297,11 @@ pub(super) fn map_primitive_state(state: &wgt::PrimitiveState) -> super::Primiti
             None => 0,
         },
         unclipped_depth: state.unclipped_depth,
+        polygon_mode: match state.polygon_mode {
+            wgt::PolygonMode::Fill => glow::FILL,
+            wgt::PolygonMode::Line => glow::LINE,
+            wgt::PolygonMode::Point => glow::POINT,
+        },
     }
 }
 
@@ -309,6 +316,36 @@ pub fn _map_view_dimension(dim: wgt::TextureViewDimension) -> u32 {
     }
 }
 
+/// Returns the GL query targets of the requested statistics, in the order their
+/// results are resolved.
+pub(super) fn map_pipeline_statistics(
+    types: wgt::PipelineStatisticsTypes,
+) -> impl Iterator<Item = u32> {
+    use wgt::PipelineStatisticsTypes as Pst;
+    [
+        (
+            Pst::VERTEX_SHADER_INVOCATIONS,
+            glow::VERTEX_SHADER_INVOCATIONS,
+        ),
+        (Pst::CLIPPER_INVOCATIONS, glow::CLIPPING_INPUT_PRIMITIVES),
+        (
+            Pst::CLIPPER_PRIMITIVES_OUT,
+            glow::CLIPPING_OUTPUT_PRIMITIVES,
+        ),
+        (
+            Pst::FRAGMENT_SHADER_INVOCATIONS,
+            glow::FRAGMENT_SHADER_INVOCATIONS,
+        ),
+        (
+            Pst::COMPUTE_SHADER_INVOCATIONS,
+            glow::COMPUTE_SHADER_INVOCATIONS,
+        ),
+    ]
+    .into_iter()
+    .filter(move |&(statistic, _)| types.contains(statistic))
+    .map(|(_, target)| target)
+}
+
 fn map_stencil_op(operation: wgt::StencilOperation) -> u32 {
     use wgt::StencilOperation as So;
     match operation {
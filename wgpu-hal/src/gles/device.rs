@@ -187,12 +187,14 @@ impl super::Device {
         }
     }
 
+    /// Generates the GLSL source of a shader stage.
     fn create_shader(
         gl: &glow::Context,
         naga_stage: naga::ShaderStage,
         stage: &crate::ProgrammableStage<super::Api>,
+        shader: &crate::NagaShader,
         context: CompilationContext,
-    ) -> Result<glow::Shader, crate::PipelineError> {
+    ) -> Result<String, crate::PipelineError> {
         use naga::back::glsl;
         let pipeline_options = glsl::PipelineOptions {
             shader_stage: naga_stage,
@@ -200,7 +202,6 @@ impl super::Device {
             multiview: context.multiview,
         };
 
-        let shader = &stage.module.naga;
         let entry_point_index = shader
             .module
             .entry_points
@@ -226,10 +227,12 @@ impl super::Device {
             binding_array: BoundsCheckPolicy::Unchecked,
         };
 
+        let module = crate::auxil::apply_pipeline_constants(&shader.module, stage.constants);
+
         let mut output = String::new();
         let mut writer = glsl::Writer::new(
             &mut output,
-            &shader.module,
+            &module,
             &shader.info,
             &context.layout.naga_options,
             &pipeline_options,
@@ -248,12 +251,79 @@ impl super::Device {
         log::debug!("Naga generated shader:\n{}", output);
 
         context.consume_reflection(
-            &shader.module,
+            &module,
             shader.info.get_entry_point(entry_point_index),
             reflection_info,
         );
 
-        unsafe { Self::compile_shader(gl, &output, naga_stage, stage.module.label.as_deref()) }
+        Ok(output)
+    }
+
+    /// Resolves the binding map of a passthrough GLSL shader against the layout.
+    ///
+    /// Uniform blocks and textures are rebound by name once the program is
+    /// linked. Storage blocks and images can't be rebound on GLES, so the shader
+    /// has to declare their binding itself.
+    fn map_passthrough_bindings(
+        layout: &super::PipelineLayout,
+        naga_stage: naga::ShaderStage,
+        bindings: &[wgt::PassthroughBinding<String>],
+        sampler_map: &mut super::SamplerBindMap,
+        name_binding_map: &mut NameBindingMap,
+    ) -> Result<(), crate::PipelineError> {
+        let mut sampler_slots = Vec::new();
+        for binding in bindings {
+            let group_info = layout.group_infos.get(binding.group as usize);
+            let entry = group_info.and_then(|info| {
+                info.entries
+                    .iter()
+                    .find(|entry| entry.binding == binding.binding)
+            });
+            let (group_info, entry) = match (group_info, entry) {
+                (Some(group_info), Some(entry)) => (group_info, entry),
+                _ => {
+                    return Err(crate::PipelineError::Layout(
+                        map_naga_stage(naga_stage),
+                        format!(
+                            "`{}` is bound to {}:{}, which is not in the pipeline layout",
+                            binding.name, binding.group, binding.binding
+                        ),
+                    ))
+                }
+            };
+
+            let slot = group_info.binding_to_slot[binding.binding as usize];
+            let register = match entry.ty {
+                wgt::BindingType::Buffer {
+                    ty: wgt::BufferBindingType::Uniform,
+                    ..
+                } => super::BindingRegister::UniformBuffers,
+                wgt::BindingType::Texture { .. } => super::BindingRegister::Textures,
+                wgt::BindingType::Sampler { .. } => {
+                    sampler_slots.push((&binding.name, slot));
+                    continue;
+                }
+                wgt::BindingType::Buffer { .. } | wgt::BindingType::StorageTexture { .. } => {
+                    continue
+                }
+            };
+            name_binding_map.insert(binding.name.clone(), (register, slot));
+        }
+
+        for (name, sampler_slot) in sampler_slots {
+            match name_binding_map.get(name) {
+                Some(&(super::BindingRegister::Textures, texture_slot)) => {
+                    sampler_map[texture_slot as usize] = Some(sampler_slot);
+                }
+                _ => {
+                    return Err(crate::PipelineError::Layout(
+                        map_naga_stage(naga_stage),
+                        format!("sampler `{name}` is not bound to a texture"),
+                    ))
+                }
+            }
+        }
+        Ok(())
     }
 
     unsafe fn create_pipeline<'a>(
@@ -274,6 +344,7 @@ impl super::Device {
                 naga_stage: naga_stage.to_owned(),
                 shader_id: stage.module.id,
                 entry_point: stage.entry_point.to_owned(),
+                constants: super::pipeline_constants_key(stage.constants),
             });
         }
         let glsl_version = match self.shared.shading_language_version {
@@ -300,7 +371,7 @@ impl super::Device {
                     label,
                     multiview,
                     glsl_version,
-                    self.shared.private_caps,
+                    &self.shared,
                 )
             })
             .to_owned()?;
@@ -316,7 +387,7 @@ impl super::Device {
         #[cfg_attr(target_arch = "wasm32", allow(unused))] label: Option<&str>,
         multiview: Option<std::num::NonZeroU32>,
         glsl_version: u16,
-        private_caps: super::PrivateCapabilities,
+        shared: &super::AdapterShared,
     ) -> Result<Arc<super::PipelineInner>, crate::PipelineError> {
         let program = unsafe { gl.create_program() }.unwrap();
         #[cfg(not(target_arch = "wasm32"))]
@@ -328,59 +399,112 @@ impl super::Device {
         }
 
         let mut name_binding_map = NameBindingMap::default();
+        let mut passthrough_binding_map = NameBindingMap::default();
         let mut sampler_map = [None; super::MAX_TEXTURE_SLOTS];
         let mut has_stages = wgt::ShaderStages::empty();
-        let mut shaders_to_delete = arrayvec::ArrayVec::<_, 3>::new();
+        let mut sources = ArrayVec::<_, 3>::new();
+        let mut labels = ArrayVec::<_, 3>::new();
 
         for (naga_stage, stage) in shaders {
             has_stages |= map_naga_stage(naga_stage);
-            let context = CompilationContext {
-                layout,
-                sampler_map: &mut sampler_map,
-                name_binding_map: &mut name_binding_map,
-                multiview,
+            let source = match stage.module.source {
+                super::ShaderModuleSource::Naga(ref shader) => {
+                    let context = CompilationContext {
+                        layout,
+                        sampler_map: &mut sampler_map,
+                        name_binding_map: &mut name_binding_map,
+                        multiview,
+                    };
+                    Self::create_shader(gl, naga_stage, stage, shader, context)?
+                }
+                super::ShaderModuleSource::Glsl {
+                    ref source,
+                    stage: module_stage,
+                    ref bindings,
+                } => {
+                    if module_stage != naga_stage || stage.entry_point != "main" {
+                        return Err(crate::PipelineError::EntryPoint(naga_stage));
+                    }
+                    crate::auxil::check_passthrough_constants(stage.constants, naga_stage)?;
+                    Self::map_passthrough_bindings(
+                        layout,
+                        naga_stage,
+                        bindings,
+                        &mut sampler_map,
+                        &mut passthrough_binding_map,
+                    )?;
+                    source.clone()
+                }
             };
-
-            let shader = Self::create_shader(gl, naga_stage, stage, context)?;
-            shaders_to_delete.push(shader);
+            sources.push(source);
+            labels.push((naga_stage, stage.module.label.as_deref()));
         }
 
         // Create empty fragment shader if only vertex shader is present
         if has_stages == wgt::ShaderStages::VERTEX {
             let shader_src = format!("#version {glsl_version} es \n void main(void) {{}}",);
             log::info!("Only vertex shader is present. Creating an empty fragment shader",);
-            let shader = unsafe {
-                Self::compile_shader(
-                    gl,
-                    &shader_src,
-                    naga::ShaderStage::Fragment,
-                    Some("(wgpu internal) dummy fragment shader"),
-                )
-            }?;
-            shaders_to_delete.push(shader);
+            sources.push(shader_src);
+            labels.push((
+                naga::ShaderStage::Fragment,
+                Some("(wgpu internal) dummy fragment shader"),
+            ));
         }
 
-        for &shader in shaders_to_delete.iter() {
-            unsafe { gl.attach_shader(program, shader) };
-        }
-        unsafe { gl.link_program(program) };
+        let program_binaries = shared.program_binaries.as_ref();
+        let binary_key = program_binaries.map(|cache| cache.key(&sources));
+        let loaded = match (program_binaries, binary_key.as_ref()) {
+            (Some(cache), Some(key)) => unsafe { cache.load(gl, key, &sources, program) },
+            _ => false,
+        };
 
-        for shader in shaders_to_delete {
-            unsafe { gl.delete_shader(shader) };
-        }
+        if !loaded {
+            let mut shaders_to_delete = ArrayVec::<_, 3>::new();
+            for (source, &(naga_stage, label)) in sources.iter().zip(labels.iter()) {
+                let shader = unsafe { Self::compile_shader(gl, source, naga_stage, label) };
+                match shader {
+                    Ok(shader) => shaders_to_delete.push(shader),
+                    Err(e) => {
+                        for shader in shaders_to_delete {
+                            unsafe { gl.delete_shader(shader) };
+                        }
+                        return Err(e);
+                    }
+                }
+            }
 
-        log::info!("\tLinked program {:?}", program);
+            for &shader in shaders_to_delete.iter() {
+                unsafe { gl.attach_shader(program, shader) };
+            }
+            if let Some(cache) = program_binaries {
+                unsafe { cache.prepare(program) };
+            }
+            unsafe { gl.link_program(program) };
 
-        let linked_ok = unsafe { gl.get_program_link_status(program) };
-        let msg = unsafe { gl.get_program_info_log(program) };
-        if !linked_ok {
-            return Err(crate::PipelineError::Linkage(has_stages, msg));
-        }
-        if !msg.is_empty() {
-            log::warn!("\tLink: {}", msg);
+            for shader in shaders_to_delete {
+                unsafe { gl.delete_shader(shader) };
+            }
+
+            log::info!("\tLinked program {:?}", program);
+
+            let linked_ok = unsafe { gl.get_program_link_status(program) };
+            let msg = unsafe { gl.get_program_info_log(program) };
+            if !linked_ok {
+                return Err(crate::PipelineError::Linkage(has_stages, msg));
+            }
+            if !msg.is_empty() {
+                log::warn!("\tLink: {}", msg);
+            }
+
+            if let (Some(cache), Some(key)) = (program_binaries, binary_key.as_ref()) {
+                unsafe { cache.store(key, &sources, program) };
+            }
         }
 
-        if !private_caps.contains(super::PrivateCapabilities::SHADER_BINDING_LAYOUT) {
+        if !shared
+            .private_caps
+            .contains(super::PrivateCapabilities::SHADER_BINDING_LAYOUT)
+        {
             // This remapping is only needed if we aren't able to put the binding layout
             // in the shader. We can't remap storage buffers this way.
             unsafe { gl.use_program(Some(program)) };
@@ -409,6 +533,34 @@ impl super::Device {
             }
         }
 
+        // Passthrough shaders don't know the slots of their resources, so they
+        // are always rebound by name.
+        if !passthrough_binding_map.is_empty() {
+            unsafe { gl.use_program(Some(program)) };
+            for (ref name, (register, slot)) in passthrough_binding_map {
+                log::trace!(
+                    "Get passthrough binding {:?} from program {:?}",
+                    name,
+                    program
+                );
+                match register {
+                    super::BindingRegister::UniformBuffers => {
+                        match unsafe { gl.get_uniform_block_index(program, name) } {
+                            Some(index) => unsafe {
+                                gl.uniform_block_binding(program, index, slot as _)
+                            },
+                            None => log::warn!("Uniform block {} is not active", name),
+                        }
+                    }
+                    super::BindingRegister::Textures => {
+                        let location = unsafe { gl.get_uniform_location(program, name) };
+                        unsafe { gl.uniform_1_i32(location.as_ref(), slot as _) };
+                    }
+                    super::BindingRegister::StorageBuffers | super::BindingRegister::Images => {}
+                }
+            }
+        }
+
         let mut uniforms: [super::UniformDesc; super::MAX_PUSH_CONSTANTS] =
             [None; super::MAX_PUSH_CONSTANTS].map(|_: Option<()>| Default::default());
         let count = unsafe { gl.get_active_uniforms(program) };
@@ -455,6 +607,10 @@ impl crate::Device<super::Api> for super::Device {
         unsafe { gl.delete_framebuffer(queue.draw_fbo) };
         unsafe { gl.delete_framebuffer(queue.copy_fbo) };
         unsafe { gl.delete_buffer(queue.zero_buffer) };
+        unsafe { gl.delete_buffer(queue.wireframe_index_buffer) };
+        for (_, (lines, _)) in self.shared.wireframe_indices.lock().drain() {
+            unsafe { gl.delete_buffer(lines) };
+        }
     }
 
     unsafe fn create_buffer(
@@ -483,6 +639,7 @@ impl crate::Device<super::Api> for super::Device {
                 size: desc.size,
                 map_flags: 0,
                 data: Some(Arc::new(Mutex::new(vec![0; desc.size as usize]))),
+                shader_writable: false,
             });
         }
 
@@ -527,7 +684,13 @@ impl crate::Device<super::Api> for super::Device {
                     map_flags |= glow::MAP_COHERENT_BIT;
                 }
             }
-            unsafe { gl.buffer_storage(target, raw_size, None, map_flags) };
+            // Query results are resolved with `glBufferSubData`.
+            let storage_flags = if desc.usage.contains(crate::BufferUses::QUERY_RESOLVE) {
+                map_flags | glow::DYNAMIC_STORAGE_BIT
+            } else {
+                map_flags
+            };
+            unsafe { gl.buffer_storage(target, raw_size, None, storage_flags) };
         } else {
             assert!(!is_coherent);
             let usage = if is_host_visible {
@@ -572,11 +735,13 @@ impl crate::Device<super::Api> for super::Device {
             size: desc.size,
             map_flags,
             data,
+            shader_writable: desc.usage.contains(crate::BufferUses::STORAGE_READ_WRITE),
         })
     }
     unsafe fn destroy_buffer(&self, buffer: super::Buffer) {
         if let Some(raw) = buffer.raw {
             let gl = &self.shared.context.lock();
+            unsafe { self.shared.invalidate_wireframe_indices(gl, raw) };
             unsafe { gl.delete_buffer(raw) };
         }
     }
@@ -595,11 +760,14 @@ impl crate::Device<super::Api> for super::Device {
             }
             Some(raw) => {
                 let gl = &self.shared.context.lock();
+                if buffer.map_flags & glow::MAP_WRITE_BIT != 0 {
+                    unsafe { self.shared.invalidate_wireframe_indices(gl, raw) };
+                }
                 unsafe { gl.bind_buffer(buffer.target, Some(raw)) };
                 let ptr = if let Some(ref map_read_allocation) = buffer.data {
                     let mut guard = map_read_allocation.lock().unwrap();
                     let slice = guard.as_mut_slice();
-                    unsafe { self.shared.get_buffer_sub_data(gl, buffer.target, 0, slice) };
+                    unsafe { self.shared.get_buffer_sub_data(gl, buffer.target, 0, slice) }?;
                     slice.as_mut_ptr()
                 } else {
                     unsafe {
@@ -661,7 +829,8 @@ impl crate::Device<super::Api> for super::Device {
 
         let render_usage = crate::TextureUses::COLOR_TARGET
             | crate::TextureUses::DEPTH_STENCIL_WRITE
-            | crate::TextureUses::DEPTH_STENCIL_READ;
+            | crate::TextureUses::DEPTH_STENCIL_READ
+            | crate::TextureUses::TRANSIENT;
         let format_desc = self.shared.describe_texture_format(desc.format);
 
         let (inner, is_cubemap) = if render_usage.contains(desc.usage)
@@ -921,6 +1090,7 @@ impl crate::Device<super::Api> for super::Device {
             cmd_buffer: super::CommandBuffer::default(),
             state: Default::default(),
             private_caps: self.shared.private_caps,
+            free: Vec::new(),
         })
     }
     unsafe fn destroy_command_encoder(&self, _encoder: super::CommandEncoder) {}
@@ -1078,6 +1248,13 @@ impl crate::Device<super::Api> for super::Device {
         })
     }
     unsafe fn destroy_bind_group(&self, _group: super::BindGroup) {}
+    unsafe fn update_bind_group(
+        &self,
+        _group: &super::BindGroup,
+        _desc: &crate::BindGroupUpdateDescriptor<super::Api>,
+    ) {
+        unreachable!("UPDATE_AFTER_BIND_DESCRIPTORS is not supported by this backend")
+    }
 
     unsafe fn create_shader_module(
         &self,
@@ -1085,11 +1262,28 @@ impl crate::Device<super::Api> for super::Device {
         shader: crate::ShaderInput,
     ) -> Result<super::ShaderModule, crate::ShaderError> {
         Ok(super::ShaderModule {
-            naga: match shader {
+            source: match shader {
                 crate::ShaderInput::SpirV(_) => {
                     panic!("`Features::SPIRV_SHADER_PASSTHROUGH` is not enabled")
                 }
-                crate::ShaderInput::Naga(naga) => naga,
+                crate::ShaderInput::Msl { .. }
+                | crate::ShaderInput::Dxil { .. }
+                | crate::ShaderInput::Hlsl(_) => {
+                    panic!("Only GLSL shaders can be passed through to GL")
+                }
+                crate::ShaderInput::Naga(naga) => super::ShaderModuleSource::Naga(naga),
+                crate::ShaderInput::Glsl {
+                    source,
+                    stage,
+                    bindings,
+                } => super::ShaderModuleSource::Glsl {
+                    source: source.to_string(),
+                    stage,
+                    bindings: bindings
+                        .iter()
+                        .map(|binding| binding.map_name(|name| name.to_string()))
+                        .collect(),
+                },
             },
             label: desc.label.map(|str| str.to_string()),
             id: self.shared.next_shader_id.fetch_add(1, Ordering::Relaxed),
@@ -1217,8 +1411,17 @@ impl crate::Device<super::Api> for super::Device {
         let gl = &self.shared.context.lock();
         let mut temp_string = String::new();
 
-        let mut queries = Vec::with_capacity(desc.count as usize);
-        for i in 0..desc.count {
+        let targets: Box<[super::BindTarget]> = match desc.ty {
+            wgt::QueryType::Occlusion => Box::new([glow::ANY_SAMPLES_PASSED]),
+            wgt::QueryType::Timestamp => Box::new([glow::TIMESTAMP]),
+            wgt::QueryType::PipelineStatistics(types) => {
+                conv::map_pipeline_statistics(types).collect()
+            }
+        };
+
+        let count = desc.count as usize * targets.len();
+        let mut queries = Vec::with_capacity(count);
+        for i in 0..count {
             let query =
                 unsafe { gl.create_query() }.map_err(|_| crate::DeviceError::OutOfMemory)?;
             #[cfg(not(target_arch = "wasm32"))]
@@ -1237,15 +1440,15 @@ impl crate::Device<super::Api> for super::Device {
 
         Ok(super::QuerySet {
             queries: queries.into_boxed_slice(),
-            target: match desc.ty {
-                wgt::QueryType::Occlusion => glow::ANY_SAMPLES_PASSED,
-                _ => unimplemented!(),
-            },
+            targets,
         })
     }
     unsafe fn destroy_query_set(&self, set: super::QuerySet) {
         let gl = &self.shared.context.lock();
+        let mut reset_queries = self.shared.reset_queries.lock();
         for &query in set.queries.iter() {
+            // GL may reuse the name for a query that isn't reset.
+            reset_queries.remove(&query);
             unsafe { gl.delete_query(query) };
         }
     }
@@ -1320,6 +1523,11 @@ impl crate::Device<super::Api> for super::Device {
                 .end_frame_capture(ptr::null_mut(), ptr::null_mut())
         }
     }
+
+    unsafe fn memory_report(&self) -> crate::MemoryReport {
+        // GL doesn't expose heaps or the real size of its allocations.
+        crate::MemoryReport::default()
+    }
 }
 
 #[cfg(all(
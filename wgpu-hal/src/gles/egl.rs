@@ -15,8 +15,12 @@ const EGL_PLATFORM_ANGLE_ANGLE: u32 = 0x3202;
 const EGL_PLATFORM_ANGLE_NATIVE_PLATFORM_TYPE_ANGLE: u32 = 0x348F;
 const EGL_PLATFORM_ANGLE_DEBUG_LAYERS_ENABLED: u32 = 0x3451;
 const EGL_PLATFORM_SURFACELESS_MESA: u32 = 0x31DD;
+const EGL_PLATFORM_DEVICE_EXT: u32 = 0x313F;
+const EGL_DRM_DEVICE_FILE_EXT: i32 = 0x3233;
+const EGL_DRM_RENDER_NODE_FILE_EXT: i32 = 0x3377;
 const EGL_GL_COLORSPACE_KHR: u32 = 0x309D;
 const EGL_GL_COLORSPACE_SRGB_KHR: u32 = 0x3089;
+const EGL_PRESENT_OPAQUE_EXT: u32 = 0x31DF;
 
 type XOpenDisplayFun =
     unsafe extern "system" fn(display_name: *const raw::c_char) -> *mut raw::c_void;
@@ -28,6 +32,17 @@ type WlDisplayConnectFun =
 
 type WlDisplayDisconnectFun = unsafe extern "system" fn(display: *const raw::c_void);
 
+type EglDevice = *mut raw::c_void;
+
+type EglQueryDevicesFun = unsafe extern "system" fn(
+    max_devices: khronos_egl::Int,
+    devices: *mut EglDevice,
+    num_devices: *mut khronos_egl::Int,
+) -> khronos_egl::Boolean;
+
+type EglQueryDeviceStringFun =
+    unsafe extern "system" fn(device: EglDevice, name: khronos_egl::Int) -> *const raw::c_char;
+
 #[cfg(not(target_os = "emscripten"))]
 type EglInstance = khronos_egl::DynamicInstance<khronos_egl::EGL1_4>;
 
@@ -202,6 +217,80 @@ fn test_wayland_display() -> Option<DisplayOwner> {
     })
 }
 
+/// Lists the EGL devices (`EGL_EXT_device_enumeration`) along with their DRM device node,
+/// preferring the render node that doesn't need DRM master access.
+fn enumerate_devices(
+    egl: &EglInstance,
+    client_extensions: &str,
+) -> Vec<(EglDevice, Option<String>)> {
+    if !client_extensions.contains("EGL_EXT_device_enumeration")
+        || !client_extensions.contains("EGL_EXT_platform_device")
+    {
+        return Vec::new();
+    }
+    let (query_devices, query_device_string) = match (
+        egl.get_proc_address("eglQueryDevicesEXT"),
+        egl.get_proc_address("eglQueryDeviceStringEXT"),
+    ) {
+        (Some(query_devices), Some(query_device_string)) => unsafe {
+            (
+                std::mem::transmute::<extern "C" fn(), EglQueryDevicesFun>(query_devices),
+                std::mem::transmute::<extern "C" fn(), EglQueryDeviceStringFun>(
+                    query_device_string,
+                ),
+            )
+        },
+        _ => return Vec::new(),
+    };
+
+    let mut count = 0;
+    if unsafe { query_devices(0, ptr::null_mut(), &mut count) } == khronos_egl::FALSE {
+        return Vec::new();
+    }
+    let mut devices = vec![ptr::null_mut(); count as usize];
+    if unsafe { query_devices(count, devices.as_mut_ptr(), &mut count) } == khronos_egl::FALSE {
+        return Vec::new();
+    }
+    devices.truncate(count as usize);
+
+    let query_string = |device, name| {
+        let raw = unsafe { query_device_string(device, name) };
+        if raw.is_null() {
+            // A device without a node (e.g. a software one) fails the query, take the
+            // error so that it isn't reported by the next EGL call that checks for one.
+            let _ = egl.get_error();
+            None
+        } else {
+            Some(
+                unsafe { ffi::CStr::from_ptr(raw) }
+                    .to_string_lossy()
+                    .into_owned(),
+            )
+        }
+    };
+    devices
+        .into_iter()
+        .map(|device| {
+            let extensions = query_string(device, khronos_egl::EXTENSIONS).unwrap_or_default();
+            // Match whole names, "EGL_EXT_device_drm" is a prefix of the render node extension.
+            let has_extension = |name| extensions.split_whitespace().any(|ext| ext == name);
+            let drm_node = if has_extension("EGL_EXT_device_drm_render_node") {
+                query_string(device, EGL_DRM_RENDER_NODE_FILE_EXT)
+            } else {
+                None
+            };
+            let drm_node = match drm_node {
+                None if has_extension("EGL_EXT_device_drm") => {
+                    query_string(device, EGL_DRM_DEVICE_FILE_EXT)
+                }
+                other => other,
+            };
+            log::info!("EGL device {:?}: {:?}", device, drm_node);
+            (device, drm_node)
+        })
+        .collect()
+}
+
 #[derive(Clone, Copy, Debug)]
 enum SrgbFrameBufferKind {
     /// No support for SRGB surface
@@ -286,6 +375,25 @@ fn choose_config(
     Err(crate::InstanceError)
 }
 
+/// Loads a GL entry point, preferring the suffixed names of the functions that
+/// GLES only exposes through extensions: the timer queries of
+/// `GL_EXT_disjoint_timer_query` and the polygon mode of `GL_NV_polygon_mode`.
+fn load_gl_function(
+    name: &str,
+    mut get_proc_address: impl FnMut(&str) -> *const ffi::c_void,
+) -> *const ffi::c_void {
+    let ext_name = match name {
+        "glQueryCounter" => Some("glQueryCounterEXT"),
+        "glGetQueryObjectui64v" => Some("glGetQueryObjectui64vEXT"),
+        "glPolygonMode" => Some("glPolygonModeNV"),
+        _ => None,
+    };
+    match ext_name.map(&mut get_proc_address) {
+        Some(ptr) if !ptr.is_null() => ptr,
+        _ => get_proc_address(name),
+    }
+}
+
 fn gl_debug_message_callback(source: u32, gltype: u32, id: u32, severity: u32, message: &str) {
     let source_str = match source {
         glow::DEBUG_SOURCE_API => "API",
@@ -362,6 +470,10 @@ impl EglContext {
 pub struct AdapterContext {
     glow: Mutex<glow::Context>,
     egl: Option<EglContext>,
+    multi_draw_indirect: Option<super::MultiDrawIndirectFns>,
+    get_query_object_u64: Option<super::GetQueryObjectui64vFn>,
+    program_binary_fns: Option<super::ProgramBinaryFns>,
+    program_binary_storage: wgt::GlesProgramCache,
 }
 
 unsafe impl Sync for AdapterContext {}
@@ -393,6 +505,21 @@ impl AdapterContext {
         self.egl.as_ref().map(|egl| egl.version)
     }
 
+    pub(super) fn multi_draw_indirect(&self) -> Option<&super::MultiDrawIndirectFns> {
+        self.multi_draw_indirect.as_ref()
+    }
+
+    pub(super) fn get_query_object_u64(&self) -> Option<super::GetQueryObjectui64vFn> {
+        self.get_query_object_u64
+    }
+
+    pub(super) fn program_binary(
+        &self,
+    ) -> Option<(super::ProgramBinaryFns, wgt::GlesProgramCache)> {
+        let fns = self.program_binary_fns?;
+        Some((fns, self.program_binary_storage.clone()))
+    }
+
     pub fn raw_context(&self) -> *mut raw::c_void {
         match self.egl {
             Some(ref egl) => egl.raw.as_ptr(),
@@ -484,6 +611,10 @@ struct Inner {
     wl_display: Option<*mut raw::c_void>,
     /// Method by which the framebuffer should support srgb
     srgb_kind: SrgbFrameBufferKind,
+    /// Using EGL_EXT_present_opaque
+    present_opaque: bool,
+    /// DRM device node of the EGL device the display was created from.
+    drm_node: Option<String>,
 }
 
 impl Inner {
@@ -516,6 +647,7 @@ impl Inner {
             log::warn!("\tEGL surface: -srgb");
             SrgbFrameBufferKind::None
         };
+        let present_opaque = display_extensions.contains("EGL_EXT_present_opaque");
 
         if log::max_level() >= log::LevelFilter::Trace {
             log::trace!("Configurations:");
@@ -627,6 +759,8 @@ impl Inner {
             config,
             wl_display: None,
             srgb_kind,
+            present_opaque,
+            drm_node: None,
         })
     }
 }
@@ -664,6 +798,9 @@ pub struct Instance {
     wsi: WindowSystemInterface,
     flags: crate::InstanceFlags,
     inner: Mutex<Inner>,
+    /// Displays of the other EGL devices, when they are enumerated on a headless system.
+    other_devices: Vec<Mutex<Inner>>,
+    program_binary_storage: wgt::GlesProgramCache,
 }
 
 impl Instance {
@@ -757,62 +894,104 @@ impl crate::Instance<super::Api> for Instance {
         #[cfg(target_os = "emscripten")]
         let egl1_5: Option<&Arc<EglInstance>> = Some(&egl);
 
-        let (display, display_owner, wsi_kind) =
-            if let (Some(library), Some(egl)) = (wayland_library, egl1_5) {
-                log::info!("Using Wayland platform");
-                let display_attributes = [khronos_egl::ATTRIB_NONE];
-                let display = egl
-                    .get_platform_display(
-                        EGL_PLATFORM_WAYLAND_KHR,
-                        khronos_egl::DEFAULT_DISPLAY,
-                        &display_attributes,
-                    )
-                    .unwrap();
-                (display, Some(Arc::new(library)), WindowKind::Wayland)
-            } else if let (Some(display_owner), Some(egl)) = (x11_display_library, egl1_5) {
-                log::info!("Using X11 platform");
-                let display_attributes = [khronos_egl::ATTRIB_NONE];
-                let display = egl
-                    .get_platform_display(
-                        EGL_PLATFORM_X11_KHR,
-                        display_owner.display.as_ptr(),
-                        &display_attributes,
-                    )
-                    .unwrap();
-                (display, Some(Arc::new(display_owner)), WindowKind::X11)
-            } else if let (Some(display_owner), Some(egl)) = (angle_x11_display_library, egl1_5) {
-                log::info!("Using Angle platform with X11");
-                let display_attributes = [
-                    EGL_PLATFORM_ANGLE_NATIVE_PLATFORM_TYPE_ANGLE as khronos_egl::Attrib,
-                    EGL_PLATFORM_X11_KHR as khronos_egl::Attrib,
-                    EGL_PLATFORM_ANGLE_DEBUG_LAYERS_ENABLED as khronos_egl::Attrib,
-                    usize::from(desc.flags.contains(crate::InstanceFlags::VALIDATION)),
-                    khronos_egl::ATTRIB_NONE,
-                ];
-                let display = egl
-                    .get_platform_display(
-                        EGL_PLATFORM_ANGLE_ANGLE,
-                        display_owner.display.as_ptr(),
-                        &display_attributes,
-                    )
-                    .unwrap();
-                (display, Some(Arc::new(display_owner)), WindowKind::AngleX11)
-            } else if client_ext_str.contains("EGL_MESA_platform_surfaceless") {
-                log::info!("No windowing system present. Using surfaceless platform");
-                let egl = egl1_5.expect("Failed to get EGL 1.5 for surfaceless");
-                let display = egl
-                    .get_platform_display(
-                        EGL_PLATFORM_SURFACELESS_MESA,
-                        std::ptr::null_mut(),
-                        &[khronos_egl::ATTRIB_NONE],
-                    )
-                    .unwrap();
-                (display, None, WindowKind::Unknown)
-            } else {
-                log::info!("EGL_MESA_platform_surfaceless not available. Using default platform");
-                let display = egl.get_display(khronos_egl::DEFAULT_DISPLAY).unwrap();
-                (display, None, WindowKind::Unknown)
+        let devices = match egl1_5 {
+            Some(_) => enumerate_devices(&egl, &client_ext_str),
+            None => Vec::new(),
+        };
+        let device_display = |device: EglDevice| {
+            egl1_5
+                .unwrap()
+                .get_platform_display(EGL_PLATFORM_DEVICE_EXT, device, &[khronos_egl::ATTRIB_NONE])
+                .map_err(|e| {
+                    log::warn!("Error in get_platform_display(device): {:?}", e);
+                    crate::InstanceError
+                })
+        };
+        // Displays of the other devices, with their DRM device node
+        let mut other_device_displays = Vec::new();
+        let mut drm_node = None;
+
+        let (display, display_owner, wsi_kind) = if let Some(ref path) = desc.gles_device {
+            let device = devices
+                .iter()
+                .find(|&&(_, ref node)| node.as_ref() == Some(path));
+            let &(device, _) = match device {
+                Some(device) => device,
+                None => {
+                    log::warn!("EGL device {} was not found", path);
+                    return Err(crate::InstanceError);
+                }
             };
+            log::info!("Using EGL device {}", path);
+            drm_node = Some(path.clone());
+            (device_display(device)?, None, WindowKind::Unknown)
+        } else if let (Some(library), Some(egl)) = (wayland_library, egl1_5) {
+            log::info!("Using Wayland platform");
+            let display_attributes = [khronos_egl::ATTRIB_NONE];
+            let display = egl
+                .get_platform_display(
+                    EGL_PLATFORM_WAYLAND_KHR,
+                    khronos_egl::DEFAULT_DISPLAY,
+                    &display_attributes,
+                )
+                .unwrap();
+            (display, Some(Arc::new(library)), WindowKind::Wayland)
+        } else if let (Some(display_owner), Some(egl)) = (x11_display_library, egl1_5) {
+            log::info!("Using X11 platform");
+            let display_attributes = [khronos_egl::ATTRIB_NONE];
+            let display = egl
+                .get_platform_display(
+                    EGL_PLATFORM_X11_KHR,
+                    display_owner.display.as_ptr(),
+                    &display_attributes,
+                )
+                .unwrap();
+            (display, Some(Arc::new(display_owner)), WindowKind::X11)
+        } else if let (Some(display_owner), Some(egl)) = (angle_x11_display_library, egl1_5) {
+            log::info!("Using Angle platform with X11");
+            let display_attributes = [
+                EGL_PLATFORM_ANGLE_NATIVE_PLATFORM_TYPE_ANGLE as khronos_egl::Attrib,
+                EGL_PLATFORM_X11_KHR as khronos_egl::Attrib,
+                EGL_PLATFORM_ANGLE_DEBUG_LAYERS_ENABLED as khronos_egl::Attrib,
+                usize::from(desc.flags.contains(crate::InstanceFlags::VALIDATION)),
+                khronos_egl::ATTRIB_NONE,
+            ];
+            let display = egl
+                .get_platform_display(
+                    EGL_PLATFORM_ANGLE_ANGLE,
+                    display_owner.display.as_ptr(),
+                    &display_attributes,
+                )
+                .unwrap();
+            (display, Some(Arc::new(display_owner)), WindowKind::AngleX11)
+        } else if !devices.is_empty() {
+            log::info!(
+                "No windowing system present. Using {} EGL devices",
+                devices.len()
+            );
+            for &(device, ref node) in devices[1..].iter() {
+                if let Ok(display) = device_display(device) {
+                    other_device_displays.push((display, node.clone()));
+                }
+            }
+            drm_node = devices[0].1.clone();
+            (device_display(devices[0].0)?, None, WindowKind::Unknown)
+        } else if client_ext_str.contains("EGL_MESA_platform_surfaceless") {
+            log::info!("No windowing system present. Using surfaceless platform");
+            let egl = egl1_5.expect("Failed to get EGL 1.5 for surfaceless");
+            let display = egl
+                .get_platform_display(
+                    EGL_PLATFORM_SURFACELESS_MESA,
+                    std::ptr::null_mut(),
+                    &[khronos_egl::ATTRIB_NONE],
+                )
+                .unwrap();
+            (display, None, WindowKind::Unknown)
+        } else {
+            log::info!("EGL_MESA_platform_surfaceless not available. Using default platform");
+            let display = egl.get_display(khronos_egl::DEFAULT_DISPLAY).unwrap();
+            (display, None, WindowKind::Unknown)
+        };
 
         if desc.flags.contains(crate::InstanceFlags::VALIDATION)
             && client_ext_str.contains("EGL_KHR_debug")
@@ -836,7 +1015,24 @@ impl crate::Instance<super::Api> for Instance {
             unsafe { (function)(Some(egl_debug_proc), attributes.as_ptr()) };
         }
 
-        let inner = Inner::create(desc.flags, egl, display)?;
+        let mut inner = Inner::create(desc.flags, Arc::clone(&egl), display)?;
+        inner.drm_node = drm_node;
+
+        let other_devices = other_device_displays
+            .into_iter()
+            .filter_map(|(display, drm_node)| {
+                match Inner::create(desc.flags, Arc::clone(&egl), display) {
+                    Ok(mut inner) => {
+                        inner.drm_node = drm_node;
+                        Some(Mutex::new(inner))
+                    }
+                    Err(_) => {
+                        log::warn!("Unable to create a context on EGL device {:?}", drm_node);
+                        None
+                    }
+                }
+            })
+            .collect();
 
         Ok(Instance {
             wsi: WindowSystemInterface {
@@ -845,6 +1041,8 @@ impl crate::Instance<super::Api> for Instance {
             },
             flags: desc.flags,
             inner: Mutex::new(inner),
+            other_devices,
+            program_binary_storage: desc.gles_program_cache.clone(),
         })
     }
 
@@ -946,23 +1144,36 @@ impl crate::Instance<super::Api> for Instance {
             raw_window_handle: window_handle,
             swapchain: None,
             srgb_kind: inner.srgb_kind,
+            present_opaque: inner.present_opaque,
         })
     }
     unsafe fn destroy_surface(&self, _surface: Surface) {}
 
     unsafe fn enumerate_adapters(&self) -> Vec<crate::ExposedAdapter<super::Api>> {
-        let inner = self.inner.lock();
+        std::iter::once(&self.inner)
+            .chain(&self.other_devices)
+            .filter_map(|inner| unsafe { self.expose(&inner.lock()) })
+            .collect()
+    }
+}
+
+impl Instance {
+    unsafe fn expose(&self, inner: &Inner) -> Option<crate::ExposedAdapter<super::Api>> {
         inner.egl.make_current();
 
-        let gl = unsafe {
-            glow::Context::from_loader_function(|name| {
-                inner
-                    .egl
-                    .instance
-                    .get_proc_address(name)
-                    .map_or(ptr::null(), |p| p as *const _)
-            })
+        let get_proc_address = |name: &str| {
+            inner
+                .egl
+                .instance
+                .get_proc_address(name)
+                .map_or(ptr::null(), |p| p as *const _)
         };
+        let mut gl = unsafe {
+            glow::Context::from_loader_function(|name| load_gl_function(name, get_proc_address))
+        };
+        let multi_draw_indirect = super::MultiDrawIndirectFns::load(get_proc_address);
+        let get_query_object_u64 = super::load_get_query_object_u64(get_proc_address);
+        let program_binary_fns = super::ProgramBinaryFns::load(get_proc_address);
 
         if self.flags.contains(crate::InstanceFlags::DEBUG) && gl.supports_debug() {
             log::info!("Max label length: {}", unsafe {
@@ -978,14 +1189,20 @@ impl crate::Instance<super::Api> for Instance {
 
         inner.egl.unmake_current();
 
-        unsafe {
+        let mut exposed = unsafe {
             super::Adapter::expose(AdapterContext {
                 glow: Mutex::new(gl),
                 egl: Some(inner.egl.clone()),
+                multi_draw_indirect,
+                get_query_object_u64,
+                program_binary_fns,
+                program_binary_storage: self.program_binary_storage.clone(),
             })
+        }?;
+        if let Some(ref drm_node) = inner.drm_node {
+            exposed.info.device_node = drm_node.clone();
         }
-        .into_iter()
-        .collect()
+        Some(exposed)
     }
 }
 
@@ -998,13 +1215,21 @@ impl super::Adapter {
     /// - The underlying OpenGL ES context must be current when interfacing with any objects returned by
     ///   wgpu-hal from this adapter.
     pub unsafe fn new_external(
-        fun: impl FnMut(&str) -> *const ffi::c_void,
+        mut fun: impl FnMut(&str) -> *const ffi::c_void,
     ) -> Option<crate::ExposedAdapter<super::Api>> {
-        let context = unsafe { glow::Context::from_loader_function(fun) };
+        let context =
+            unsafe { glow::Context::from_loader_function(|name| load_gl_function(name, &mut fun)) };
+        let multi_draw_indirect = super::MultiDrawIndirectFns::load(&mut fun);
+        let get_query_object_u64 = super::load_get_query_object_u64(&mut fun);
+        let program_binary_fns = super::ProgramBinaryFns::load(&mut fun);
         unsafe {
             Self::expose(AdapterContext {
                 glow: Mutex::new(context),
                 egl: None,
+                multi_draw_indirect,
+                get_query_object_u64,
+                program_binary_fns,
+                program_binary_storage: wgt::GlesProgramCache::Memory,
             })
         }
     }
@@ -1033,6 +1258,40 @@ pub struct Swapchain {
     format_desc: super::TextureFormatDesc,
     #[allow(unused)]
     sample_type: wgt::TextureSampleType,
+    /// `eglSwapInterval` only applies to the current surface, so it's set on the first present.
+    swap_interval: Option<khronos_egl::Int>,
+    alpha: AlphaCompositing,
+    /// Draws a full screen triangle, for [`AlphaCompositing::Premultiply`].
+    premultiply_program: Option<glow::Program>,
+}
+
+/// What presenting does to the alpha channel of the swapchain, which Wayland and
+/// X11 compositors blend the window with as premultiplied colors.
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum AlphaCompositing {
+    /// The colors are presented as they are.
+    Unchanged,
+    /// The surface is created with `EGL_PRESENT_OPAQUE_EXT`, the compositor
+    /// ignores the alpha channel.
+    PresentOpaque,
+    /// The alpha channel is set to 1 before presenting. Without
+    /// `EGL_EXT_present_opaque` nothing stops the compositor from blending, so
+    /// this is the only way to hide what is behind the window.
+    ClearAlpha,
+    /// The colors are multiplied by the alpha channel before presenting.
+    Premultiply,
+}
+
+impl AlphaCompositing {
+    fn new(mode: wgt::CompositeAlphaMode, transparent: bool, present_opaque: bool) -> Self {
+        match mode {
+            _ if !transparent => Self::Unchanged,
+            wgt::CompositeAlphaMode::Opaque if present_opaque => Self::PresentOpaque,
+            wgt::CompositeAlphaMode::Opaque => Self::ClearAlpha,
+            wgt::CompositeAlphaMode::PostMultiplied => Self::Premultiply,
+            _ => Self::Unchanged,
+        }
+    }
 }
 
 #[derive(Debug)]
@@ -1044,6 +1303,7 @@ pub struct Surface {
     raw_window_handle: raw_window_handle::RawWindowHandle,
     swapchain: Option<Swapchain>,
     srgb_kind: SrgbFrameBufferKind,
+    present_opaque: bool,
 }
 
 unsafe impl Send for Surface {}
@@ -1055,7 +1315,7 @@ impl Surface {
         _suf_texture: super::Texture,
         gl: &glow::Context,
     ) -> Result<(), crate::SurfaceError> {
-        let sc = self.swapchain.as_ref().unwrap();
+        let sc = self.swapchain.as_mut().unwrap();
 
         self.egl
             .instance
@@ -1070,6 +1330,12 @@ impl Surface {
                 crate::SurfaceError::Lost
             })?;
 
+        if let Some(interval) = sc.swap_interval.take() {
+            if let Err(e) = self.egl.instance.swap_interval(self.egl.display, interval) {
+                log::warn!("swap_interval({}) failed: {}", interval, e);
+            }
+        }
+
         unsafe { gl.disable(glow::SCISSOR_TEST) };
         unsafe { gl.color_mask(true, true, true, true) };
 
@@ -1094,6 +1360,33 @@ impl Surface {
         };
         unsafe { gl.bind_framebuffer(glow::READ_FRAMEBUFFER, None) };
 
+        match sc.alpha {
+            AlphaCompositing::Unchanged | AlphaCompositing::PresentOpaque => {}
+            AlphaCompositing::ClearAlpha => {
+                unsafe { gl.color_mask(false, false, false, true) };
+                unsafe { gl.clear_color(0.0, 0.0, 0.0, 1.0) };
+                unsafe { gl.clear(glow::COLOR_BUFFER_BIT) };
+                unsafe { gl.color_mask(true, true, true, true) };
+            }
+            AlphaCompositing::Premultiply => {
+                // Blending with the window contents multiplies them by their alpha,
+                // whatever the triangle outputs.
+                unsafe { gl.use_program(sc.premultiply_program) };
+                unsafe { gl.disable(glow::DEPTH_TEST) };
+                unsafe { gl.disable(glow::STENCIL_TEST) };
+                unsafe { gl.disable(glow::CULL_FACE) };
+                unsafe { gl.viewport(0, 0, sc.extent.width as i32, sc.extent.height as i32) };
+                unsafe { gl.enable(glow::BLEND) };
+                unsafe { gl.blend_equation(glow::FUNC_ADD) };
+                unsafe {
+                    gl.blend_func_separate(glow::ZERO, glow::DST_ALPHA, glow::ZERO, glow::ONE)
+                };
+                unsafe { gl.draw_arrays(glow::TRIANGLES, 0, 3) };
+                unsafe { gl.disable(glow::BLEND) };
+                unsafe { gl.use_program(None) };
+            }
+        }
+
         self.egl
             .instance
             .swap_buffers(self.egl.display, sc.surface)
@@ -1115,24 +1408,100 @@ impl Surface {
     unsafe fn unconfigure_impl(
         &mut self,
         device: &super::Device,
-    ) -> Option<(khronos_egl::Surface, Option<*mut raw::c_void>)> {
+    ) -> Option<(
+        khronos_egl::Surface,
+        Option<*mut raw::c_void>,
+        AlphaCompositing,
+    )> {
         let gl = &device.shared.context.lock();
         match self.swapchain.take() {
             Some(sc) => {
                 unsafe { gl.delete_renderbuffer(sc.renderbuffer) };
                 unsafe { gl.delete_framebuffer(sc.framebuffer) };
-                Some((sc.surface, sc.wl_window))
+                if let Some(program) = sc.premultiply_program {
+                    unsafe { gl.delete_program(program) };
+                }
+                Some((sc.surface, sc.wl_window, sc.alpha))
             }
             None => None,
         }
     }
 
+    unsafe fn destroy_window_surface(
+        &self,
+        surface: khronos_egl::Surface,
+        wl_window: Option<*mut raw::c_void>,
+    ) {
+        self.egl
+            .instance
+            .destroy_surface(self.egl.display, surface)
+            .unwrap();
+        if let Some(window) = wl_window {
+            let library = &self
+                .wsi
+                .display_owner
+                .as_ref()
+                .expect("unsupported window")
+                .library;
+            let wl_egl_window_destroy: libloading::Symbol<WlEglWindowDestroyFun> =
+                unsafe { library.get(b"wl_egl_window_destroy") }.unwrap();
+            unsafe { wl_egl_window_destroy(window) };
+        }
+    }
+
     pub fn supports_srgb(&self) -> bool {
         match self.srgb_kind {
             SrgbFrameBufferKind::None => false,
             _ => true,
         }
     }
+
+    fn config_attrib(&self, attribute: khronos_egl::Int) -> khronos_egl::Int {
+        self.egl
+            .instance
+            .get_config_attrib(self.egl.display, self.config, attribute)
+            .unwrap_or(0)
+    }
+
+    /// Returns `true` if the window is composited with the alpha channel of the surface.
+    fn is_transparent(&self) -> bool {
+        // Wayland and X11 compositors blend with premultiplied alpha,
+        // given an ARGB visual on X11.
+        matches!(self.wsi.kind, WindowKind::Wayland | WindowKind::X11)
+            && self.config_attrib(khronos_egl::ALPHA_SIZE) > 0
+    }
+
+    pub(super) fn present_modes(&self) -> Vec<wgt::PresentMode> {
+        present_modes(
+            self.wsi.kind,
+            self.config_attrib(khronos_egl::MIN_SWAP_INTERVAL),
+        )
+    }
+
+    pub(super) fn composite_alpha_modes(&self) -> Vec<wgt::CompositeAlphaMode> {
+        composite_alpha_modes(self.is_transparent())
+    }
+}
+
+fn present_modes(kind: WindowKind, min_swap_interval: khronos_egl::Int) -> Vec<wgt::PresentMode> {
+    let mut present_modes = vec![wgt::PresentMode::Fifo];
+    if min_swap_interval == 0 {
+        // Wayland compositors never tear, a zero swap interval replaces the pending frame.
+        present_modes.push(match kind {
+            WindowKind::Wayland => wgt::PresentMode::Mailbox,
+            _ => wgt::PresentMode::Immediate,
+        });
+    }
+    present_modes
+}
+
+fn composite_alpha_modes(transparent: bool) -> Vec<wgt::CompositeAlphaMode> {
+    let mut alpha_modes = vec![wgt::CompositeAlphaMode::Opaque];
+    if transparent {
+        alpha_modes.push(wgt::CompositeAlphaMode::PreMultiplied);
+        alpha_modes.push(wgt::CompositeAlphaMode::PostMultiplied);
+    }
+    alpha_modes
 }
 
 impl crate::Surface<super::Api> for Surface {
@@ -1143,9 +1512,24 @@ impl crate::Surface<super::Api> for Surface {
     ) -> Result<(), crate::SurfaceError> {
         use raw_window_handle::RawWindowHandle as Rwh;
 
+        let alpha = AlphaCompositing::new(
+            config.composite_alpha_mode,
+            self.is_transparent(),
+            self.present_opaque,
+        );
+        let present_opaque = alpha == AlphaCompositing::PresentOpaque;
+
         let (surface, wl_window) = match unsafe { self.unconfigure_impl(device) } {
-            Some(pair) => pair,
-            None => {
+            // `EGL_PRESENT_OPAQUE_EXT` can only be set when creating the surface
+            Some((surface, wl_window, old_alpha))
+                if (old_alpha == AlphaCompositing::PresentOpaque) == present_opaque =>
+            {
+                (surface, wl_window)
+            }
+            old => {
+                if let Some((surface, wl_window, _)) = old {
+                    unsafe { self.destroy_window_surface(surface, wl_window) };
+                }
                 let mut wl_window = None;
                 let (mut temp_xlib_handle, mut temp_xcb_handle);
                 #[allow(trivial_casts)]
@@ -1227,6 +1611,10 @@ impl crate::Surface<super::Api> for Surface {
                         }
                     }
                 }
+                if present_opaque {
+                    attributes.push(EGL_PRESENT_OPAQUE_EXT as i32);
+                    attributes.push(khronos_egl::TRUE as i32);
+                }
                 attributes.push(khronos_egl::ATTRIB_NONE as i32);
 
                 #[cfg(not(target_os = "emscripten"))]
@@ -1315,6 +1703,13 @@ impl crate::Surface<super::Api> for Surface {
         unsafe { gl.bind_renderbuffer(glow::RENDERBUFFER, None) };
         unsafe { gl.bind_framebuffer(glow::READ_FRAMEBUFFER, None) };
 
+        let premultiply_program = match alpha {
+            AlphaCompositing::Premultiply => {
+                Some(unsafe { super::Adapter::create_shader_clear_program(gl) }.0)
+            }
+            _ => None,
+        };
+
         self.swapchain = Some(Swapchain {
             surface,
             wl_window,
@@ -1324,28 +1719,20 @@ impl crate::Surface<super::Api> for Surface {
             format: config.format,
             format_desc,
             sample_type: wgt::TextureSampleType::Float { filterable: false },
+            swap_interval: Some(match config.present_mode {
+                wgt::PresentMode::Immediate | wgt::PresentMode::Mailbox => 0,
+                _ => 1,
+            }),
+            alpha,
+            premultiply_program,
         });
 
         Ok(())
     }
 
     unsafe fn unconfigure(&mut self, device: &super::Device) {
-        if let Some((surface, wl_window)) = unsafe { self.unconfigure_impl(device) } {
-            self.egl
-                .instance
-                .destroy_surface(self.egl.display, surface)
-                .unwrap();
-            if let Some(window) = wl_window {
-                let library = &self
-                    .wsi
-                    .display_owner
-                    .as_ref()
-                    .expect("unsupported window")
-                    .library;
-                let wl_egl_window_destroy: libloading::Symbol<WlEglWindowDestroyFun> =
-                    unsafe { library.get(b"wl_egl_window_destroy") }.unwrap();
-                unsafe { wl_egl_window_destroy(window) };
-            }
+        if let Some((surface, wl_window, _)) = unsafe { self.unconfigure_impl(device) } {
+            unsafe { self.destroy_window_surface(surface, wl_window) };
         }
     }
 
@@ -1377,3 +1764,53 @@ impl crate::Surface<super::Api> for Surface {
     }
     unsafe fn discard_texture(&mut self, _texture: super::Texture) {}
 }
+
+#[cfg(test)]
+mod tests {
+    use super::{composite_alpha_modes, present_modes, AlphaCompositing, WindowKind};
+    use wgt::{CompositeAlphaMode, PresentMode};
+
+    #[test]
+    fn test_present_modes() {
+        assert_eq!(present_modes(WindowKind::X11, 1), [PresentMode::Fifo]);
+        assert_eq!(
+            present_modes(WindowKind::X11, 0),
+            [PresentMode::Fifo, PresentMode::Immediate]
+        );
+        assert_eq!(
+            present_modes(WindowKind::Wayland, 0),
+            [PresentMode::Fifo, PresentMode::Mailbox]
+        );
+    }
+
+    #[test]
+    fn test_composite_alpha_modes() {
+        assert_eq!(composite_alpha_modes(false), [CompositeAlphaMode::Opaque]);
+        assert_eq!(
+            composite_alpha_modes(true),
+            [
+                CompositeAlphaMode::Opaque,
+                CompositeAlphaMode::PreMultiplied,
+                CompositeAlphaMode::PostMultiplied,
+            ]
+        );
+    }
+
+    #[test]
+    fn test_alpha_compositing() {
+        use AlphaCompositing as Ac;
+
+        for mode in composite_alpha_modes(false) {
+            assert_eq!(Ac::new(mode, false, true), Ac::Unchanged);
+        }
+        let expected = [
+            (CompositeAlphaMode::Opaque, true, Ac::PresentOpaque),
+            (CompositeAlphaMode::Opaque, false, Ac::ClearAlpha),
+            (CompositeAlphaMode::PreMultiplied, false, Ac::Unchanged),
+            (CompositeAlphaMode::PostMultiplied, false, Ac::Premultiply),
+        ];
+        for (mode, present_opaque, alpha) in expected {
+            assert_eq!(Ac::new(mode, true, present_opaque), alpha, "{mode:?}");
+        }
+    }
+}
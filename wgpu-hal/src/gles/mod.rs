@@ -68,6 +68,7 @@ mod adapter;
 mod command;
 mod conv;
 mod device;
+mod program_binary;
 mod queue;
 
 use crate::{CopyExtent, TextureDescriptor};
@@ -76,6 +77,7 @@ use crate::{CopyExtent, TextureDescriptor};
 pub use self::egl::{AdapterContext, AdapterContextLock};
 #[cfg(any(not(target_arch = "wasm32"), target_os = "emscripten"))]
 use self::egl::{Instance, Surface};
+use self::program_binary::{ProgramBinaryCache, ProgramBinaryFns};
 
 #[cfg(all(target_arch = "wasm32", not(target_os = "emscripten")))]
 pub use self::web::AdapterContext;
@@ -86,10 +88,10 @@ use arrayvec::ArrayVec;
 
 use glow::HasContext;
 
-use naga::FastHashMap;
+use naga::{FastHashMap, FastHashSet};
 use parking_lot::Mutex;
 use std::sync::atomic::AtomicU32;
-use std::{fmt, ops::Range, sync::Arc};
+use std::{ffi, fmt, ops::Range, sync::Arc};
 
 #[derive(Clone)]
 pub struct Api;
@@ -156,6 +158,17 @@ bitflags::bitflags! {
         const COLOR_BUFFER_FLOAT = 1 << 9;
         /// Supports linear flitering `f32` textures.
         const TEXTURE_FLOAT_LINEAR = 1 << 10;
+        /// Timestamp queries may be invalidated by a disjoint operation (`GL_EXT_disjoint_timer_query`).
+        const TIMER_QUERY_DISJOINT = 1 << 11;
+        /// Supports `base_vertex` in indexed draws natively, instead of emulating it.
+        const BASE_VERTEX = 1 << 12;
+        /// Supports issuing several indirect draws at once, natively on desktop GL 4.3+ or through
+        /// `GL_ARB_multi_draw_indirect`/`GL_EXT_multi_draw_indirect`.
+        const MULTI_DRAW_INDIRECT = 1 << 13;
+        /// Supports `glPolygonMode`, natively on desktop GL or through `GL_NV_polygon_mode`.
+        const POLYGON_MODE = 1 << 14;
+        /// Supports binding `GL_QUERY_BUFFER`, natively on desktop GL 4.4+ or through `GL_ARB_query_buffer_object`.
+        const QUERY_BUFFER = 1 << 15;
     }
 }
 
@@ -197,6 +210,76 @@ pub struct TextureFormatDesc {
     pub data_type: u32,
 }
 
+type MultiDrawArraysIndirectFn = unsafe extern "system" fn(
+    mode: u32,
+    indirect: *const ffi::c_void,
+    draw_count: i32,
+    stride: i32,
+);
+type MultiDrawElementsIndirectFn = unsafe extern "system" fn(
+    mode: u32,
+    ty: u32,
+    indirect: *const ffi::c_void,
+    draw_count: i32,
+    stride: i32,
+);
+
+/// Entry points of multi-draw-indirect, core since desktop GL 4.3 and provided by
+/// `GL_EXT_multi_draw_indirect` on ES, which glow doesn't wrap.
+#[derive(Clone, Copy)]
+pub struct MultiDrawIndirectFns {
+    arrays: MultiDrawArraysIndirectFn,
+    elements: MultiDrawElementsIndirectFn,
+}
+
+impl MultiDrawIndirectFns {
+    /// Loads the entry points, returning `None` if any of them is missing.
+    #[allow(dead_code)] // not used on the web
+    fn load(mut get_proc_address: impl FnMut(&str) -> *const ffi::c_void) -> Option<Self> {
+        let mut load = |name: &str| {
+            let function = get_proc_address(name);
+            if function.is_null() {
+                get_proc_address(&format!("{name}EXT"))
+            } else {
+                function
+            }
+        };
+        let arrays = load("glMultiDrawArraysIndirect");
+        let elements = load("glMultiDrawElementsIndirect");
+        if arrays.is_null() || elements.is_null() {
+            return None;
+        }
+        Some(unsafe {
+            Self {
+                arrays: std::mem::transmute::<*const ffi::c_void, MultiDrawArraysIndirectFn>(
+                    arrays,
+                ),
+                elements: std::mem::transmute::<*const ffi::c_void, MultiDrawElementsIndirectFn>(
+                    elements,
+                ),
+            }
+        })
+    }
+}
+
+type GetQueryObjectui64vFn = unsafe extern "system" fn(query: u32, pname: u32, params: *mut u64);
+
+/// Loads `glGetQueryObjectui64v` (or its `GL_EXT_disjoint_timer_query` alias), which glow only
+/// wraps for reading into the buffer bound to `GL_QUERY_BUFFER`.
+#[allow(dead_code)] // not used on the web
+fn load_get_query_object_u64(
+    mut get_proc_address: impl FnMut(&str) -> *const ffi::c_void,
+) -> Option<GetQueryObjectui64vFn> {
+    let mut function = get_proc_address("glGetQueryObjectui64v");
+    if function.is_null() {
+        function = get_proc_address("glGetQueryObjectui64vEXT");
+    }
+    if function.is_null() {
+        return None;
+    }
+    Some(unsafe { std::mem::transmute::<*const ffi::c_void, GetQueryObjectui64vFn>(function) })
+}
+
 struct AdapterShared {
     context: AdapterContext,
     private_caps: PrivateCapabilities,
@@ -206,6 +289,14 @@ struct AdapterShared {
     max_texture_size: u32,
     next_shader_id: AtomicU32,
     program_cache: Mutex<ProgramCache>,
+    /// Binaries of linked programs, `None` if the context can't retrieve them.
+    program_binaries: Option<ProgramBinaryCache>,
+    /// Line list indices of [`Command::DrawWireframe`], with their index count.
+    wireframe_indices: Mutex<FastHashMap<WireframeKey, (glow::Buffer, i32)>>,
+    /// Queries that were reset and not written to since, GL has no way to reset a query.
+    ///
+    /// Shared with the device, which forgets the queries of destroyed sets.
+    reset_queries: Mutex<FastHashSet<glow::Query>>,
 }
 
 pub struct Adapter {
@@ -235,6 +326,20 @@ pub struct Queue {
     temp_query_results: Vec<u64>,
     draw_buffer_count: u8,
     current_index_buffer: Option<glow::Buffer>,
+    /// Line list indices of [`Command::DrawWireframe`] that can't be kept
+    /// around, because shaders may change the index buffer.
+    wireframe_index_buffer: glow::Buffer,
+}
+
+/// The draw that the line list indices of a [`Command::DrawWireframe`]
+/// were built for.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+struct WireframeKey {
+    topology: u32,
+    /// Index buffer, type and offset, or `None` for non-indexed draws.
+    indices: Option<(glow::Buffer, u32, wgt::BufferAddress)>,
+    start_vertex: u32,
+    count: u32,
 }
 
 #[derive(Clone, Debug)]
@@ -244,6 +349,8 @@ pub struct Buffer {
     size: wgt::BufferAddress,
     map_flags: u32,
     data: Option<Arc<std::sync::Mutex<Vec<u8>>>>,
+    /// Shaders can write to the buffer through a storage binding.
+    shader_writable: bool,
 }
 
 #[cfg(all(
@@ -426,9 +533,22 @@ pub struct BindGroup {
 
 type ShaderId = u32;
 
+#[allow(clippy::large_enum_variant)]
+#[derive(Debug)]
+enum ShaderModuleSource {
+    Naga(crate::NagaShader),
+    /// GLSL passed through as-is, with the names of its resources for each
+    /// binding of the layout.
+    Glsl {
+        source: String,
+        stage: naga::ShaderStage,
+        bindings: Vec<wgt::PassthroughBinding<String>>,
+    },
+}
+
 #[derive(Debug)]
 pub struct ShaderModule {
-    naga: crate::NagaShader,
+    source: ShaderModuleSource,
     label: Option<String>,
     id: ShaderId,
 }
@@ -529,6 +649,17 @@ struct ProgramStage {
     naga_stage: naga::ShaderStage,
     shader_id: ShaderId,
     entry_point: String,
+    /// The overridden constants, sorted by key, with their values as bits.
+    constants: Box<[(String, u64)]>,
+}
+
+fn pipeline_constants_key(constants: &crate::PipelineConstants) -> Box<[(String, u64)]> {
+    let mut key = constants
+        .iter()
+        .map(|(name, value)| (name.clone(), value.to_bits()))
+        .collect::<Vec<_>>();
+    key.sort();
+    key.into_boxed_slice()
 }
 
 #[derive(PartialEq, Eq, Hash)]
@@ -584,7 +715,17 @@ unsafe impl Send for ComputePipeline {}
 #[derive(Debug)]
 pub struct QuerySet {
     queries: Box<[glow::Query]>,
-    target: BindTarget,
+    /// GL query targets of every element of the set. Pipeline statistics
+    /// need a separate GL query for each requested statistic.
+    targets: Box<[BindTarget]>,
+}
+
+impl QuerySet {
+    /// Returns the GL queries backing the given range of the set.
+    fn raw_queries(&self, range: Range<u32>) -> &[glow::Query] {
+        let count = self.targets.len();
+        &self.queries[range.start as usize * count..range.end as usize * count]
+    }
 }
 
 #[derive(Debug)]
@@ -685,6 +826,7 @@ struct PrimitiveState {
     front_face: u32,
     cull_face: u32,
     unclipped_depth: bool,
+    polygon_mode: u32,
 }
 
 type InvalidatedAttachments = ArrayVec<u32, { crate::MAX_COLOR_ATTACHMENTS + 2 }>;
@@ -705,16 +847,44 @@ enum Command {
         base_vertex: i32,
         instance_count: u32,
     },
+    /// Triangles drawn as lines without `glPolygonMode`, through a line list
+    /// index buffer that is built on the CPU.
+    DrawWireframe {
+        topology: u32,
+        /// Index type and offset into the bound index buffer, or `None` for
+        /// non-indexed draws.
+        indices: Option<(u32, wgt::BufferAddress)>,
+        /// The index buffer can be written by shaders, so the line list
+        /// indices can't be reused by later draws.
+        shader_writable: bool,
+        start_vertex: u32,
+        count: u32,
+        base_vertex: i32,
+        instance_count: u32,
+    },
+    /// [`Command::DrawWireframe`] with the draw arguments read back from the
+    /// indirect buffer.
+    DrawWireframeIndirect {
+        topology: u32,
+        /// Index type and the offset of the index buffer binding, or `None`
+        /// for non-indexed draws.
+        indices: Option<(u32, wgt::BufferAddress)>,
+        shader_writable: bool,
+        indirect_buf: glow::Buffer,
+        indirect_offset: wgt::BufferAddress,
+    },
     DrawIndirect {
         topology: u32,
         indirect_buf: glow::Buffer,
         indirect_offset: wgt::BufferAddress,
+        draw_count: u32,
     },
     DrawIndexedIndirect {
         topology: u32,
         index_type: u32,
         indirect_buf: glow::Buffer,
         indirect_offset: wgt::BufferAddress,
+        draw_count: u32,
     },
     Dispatch([u32; 3]),
     DispatchIndirect {
@@ -771,8 +941,13 @@ enum Command {
     SetIndexBuffer(glow::Buffer),
     BeginQuery(glow::Query, BindTarget),
     EndQuery(BindTarget),
+    TimestampQuery(glow::Query),
+    ResetQueries {
+        query_range: Range<u32>,
+    },
     CopyQueryResults {
         query_range: Range<u32>,
+        query_target: BindTarget,
         dst: Buffer,
         dst_target: BindTarget,
         dst_offset: wgt::BufferAddress,
@@ -891,14 +1066,13 @@ impl fmt::Debug for CommandBuffer {
     }
 }
 
-//TODO: we would have something like `Arc<typed_arena::Arena>`
-// here and in the command buffers. So that everything grows
-// inside the encoder and stays there until `reset_all`.
-
 pub struct CommandEncoder {
     cmd_buffer: CommandBuffer,
     state: command::State,
     private_caps: PrivateCapabilities,
+    /// Command buffers given back by `reset_all`, whose allocations are reused
+    /// by the next `end_encoding`.
+    free: Vec<CommandBuffer>,
 }
 
 impl fmt::Debug for CommandEncoder {
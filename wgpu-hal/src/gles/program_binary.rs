@@ -0,0 +1,469 @@
+//! Caching of linked program binaries through `glGetProgramBinary`.
+//!
+//! Binaries are keyed on a SHA-256 digest of the driver that linked the program and of its
+//! GLSL sources. Each entry starts with the layout version, the driver and the sources, which
+//! are compared on load, so that entries of other programs or drivers never reach the driver.
+//! The little-endian binary format and the binary itself follow. Integers are little-endian,
+//! strings are prefixed with their length.
+
+use glow::HasContext;
+use naga::FastHashMap;
+use parking_lot::Mutex;
+use sha2::{Digest, Sha256};
+use std::{
+    ffi, fs, iter, path,
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Arc,
+    },
+};
+
+const ENTRY_MAGIC: &[u8; 4] = b"WGPB";
+/// Version of the entry layout, to be bumped when it changes.
+const ENTRY_VERSION: u32 = 1;
+
+type GetProgramBinaryFn = unsafe extern "system" fn(
+    program: u32,
+    buf_size: i32,
+    length: *mut i32,
+    binary_format: *mut u32,
+    binary: *mut ffi::c_void,
+);
+type ProgramBinaryFn = unsafe extern "system" fn(
+    program: u32,
+    binary_format: u32,
+    binary: *const ffi::c_void,
+    length: i32,
+);
+type ProgramParameteriFn = unsafe extern "system" fn(program: u32, pname: u32, value: i32);
+type GetProgramivFn = unsafe extern "system" fn(program: u32, pname: u32, params: *mut i32);
+
+/// Entry points of `GL_OES_get_program_binary`, and the program queries it relies on,
+/// which glow doesn't wrap.
+#[derive(Clone, Copy)]
+pub struct ProgramBinaryFns {
+    get_program_binary: GetProgramBinaryFn,
+    program_binary: ProgramBinaryFn,
+    program_parameter: ProgramParameteriFn,
+    get_program_parameter: GetProgramivFn,
+}
+
+impl ProgramBinaryFns {
+    /// Loads the entry points, returning `None` if any of them is missing.
+    #[allow(dead_code)] // not used on the web
+    pub(super) fn load(
+        mut get_proc_address: impl FnMut(&str) -> *const ffi::c_void,
+    ) -> Option<Self> {
+        let mut load = |name: &str| {
+            let function = get_proc_address(name);
+            if function.is_null() {
+                get_proc_address(&format!("{name}OES"))
+            } else {
+                function
+            }
+        };
+        let get_program_binary = load("glGetProgramBinary");
+        let program_binary = load("glProgramBinary");
+        let program_parameter = get_proc_address("glProgramParameteri");
+        let get_program_parameter = get_proc_address("glGetProgramiv");
+        if get_program_binary.is_null()
+            || program_binary.is_null()
+            || program_parameter.is_null()
+            || get_program_parameter.is_null()
+        {
+            return None;
+        }
+        Some(unsafe {
+            Self {
+                get_program_binary: std::mem::transmute::<*const ffi::c_void, GetProgramBinaryFn>(
+                    get_program_binary,
+                ),
+                program_binary: std::mem::transmute::<*const ffi::c_void, ProgramBinaryFn>(
+                    program_binary,
+                ),
+                program_parameter: std::mem::transmute::<*const ffi::c_void, ProgramParameteriFn>(
+                    program_parameter,
+                ),
+                get_program_parameter: std::mem::transmute::<*const ffi::c_void, GetProgramivFn>(
+                    get_program_parameter,
+                ),
+            }
+        })
+    }
+}
+
+#[cfg(any(not(target_arch = "wasm32"), target_os = "emscripten"))]
+fn raw_program(program: glow::Program) -> u32 {
+    program.0.get()
+}
+
+#[cfg(all(target_arch = "wasm32", not(target_os = "emscripten")))]
+fn raw_program(_program: glow::Program) -> u32 {
+    unreachable!("WebGL has no program binaries")
+}
+
+/// Builds the entry of a binary linked by `driver` from `sources`.
+fn encode_entry(driver: &str, sources: &[String], format: u32, binary: &[u8]) -> Vec<u8> {
+    fn push_str(entry: &mut Vec<u8>, string: &str) {
+        entry.extend_from_slice(&(string.len() as u32).to_le_bytes());
+        entry.extend_from_slice(string.as_bytes());
+    }
+
+    let mut entry = Vec::new();
+    entry.extend_from_slice(ENTRY_MAGIC);
+    entry.extend_from_slice(&ENTRY_VERSION.to_le_bytes());
+    push_str(&mut entry, driver);
+    entry.extend_from_slice(&(sources.len() as u32).to_le_bytes());
+    for source in sources {
+        push_str(&mut entry, source);
+    }
+    entry.extend_from_slice(&format.to_le_bytes());
+    entry.extend_from_slice(binary);
+    entry
+}
+
+struct EntryReader<'a>(&'a [u8]);
+
+impl<'a> EntryReader<'a> {
+    fn bytes(&mut self, len: usize) -> Option<&'a [u8]> {
+        if self.0.len() < len {
+            return None;
+        }
+        let (bytes, rest) = self.0.split_at(len);
+        self.0 = rest;
+        Some(bytes)
+    }
+
+    fn u32(&mut self) -> Option<u32> {
+        Some(u32::from_le_bytes(self.bytes(4)?.try_into().unwrap()))
+    }
+
+    fn str(&mut self) -> Option<&'a [u8]> {
+        let len = self.u32()? as usize;
+        self.bytes(len)
+    }
+}
+
+/// Returns the binary format and the binary of `entry`, or `None` if the entry is corrupt
+/// or wasn't linked by `driver` from `sources`.
+fn decode_entry<'a>(entry: &'a [u8], driver: &str, sources: &[String]) -> Option<(u32, &'a [u8])> {
+    let mut reader = EntryReader(entry);
+    if reader.bytes(ENTRY_MAGIC.len())? != ENTRY_MAGIC
+        || reader.u32()? != ENTRY_VERSION
+        || reader.str()? != driver.as_bytes()
+        || reader.u32()? as usize != sources.len()
+    {
+        return None;
+    }
+    for source in sources {
+        if reader.str()? != source.as_bytes() {
+            return None;
+        }
+    }
+    let format = reader.u32()?;
+    match *reader.0 {
+        [] => None,
+        ref binary => Some((format, binary)),
+    }
+}
+
+/// Writes the entry `key` to `directory`.
+///
+/// The entry is written to a temporary file that is then renamed into place, so processes
+/// reading the directory concurrently never see a partially written entry.
+fn write_entry_file(directory: &path::Path, key: &str, entry: &[u8]) -> std::io::Result<()> {
+    static NEXT_TEMP_ID: AtomicU64 = AtomicU64::new(0);
+    let temp_id = NEXT_TEMP_ID.fetch_add(1, Ordering::Relaxed);
+    let temp_path = directory.join(format!("{key}.{}.{temp_id}.tmp", std::process::id()));
+    fs::write(&temp_path, entry)
+        .and_then(|()| fs::rename(&temp_path, directory.join(format!("{key}.bin"))))
+        .map_err(|e| {
+            let _ = fs::remove_file(&temp_path);
+            e
+        })
+}
+
+/// Entries of linked programs, kept in memory and in the storage chosen by the user.
+struct EntryStorage {
+    /// Identifies the driver, as binaries are only valid for the driver that produced them.
+    driver: String,
+    entries: Mutex<FastHashMap<String, Arc<[u8]>>>,
+    storage: wgt::GlesProgramCache,
+}
+
+impl EntryStorage {
+    fn new(driver: String, storage: wgt::GlesProgramCache) -> Self {
+        Self {
+            driver,
+            entries: Mutex::new(FastHashMap::default()),
+            storage,
+        }
+    }
+
+    fn key(&self, sources: &[String]) -> String {
+        let mut hasher = Sha256::new();
+        for part in iter::once(&self.driver).chain(sources) {
+            hasher.update((part.len() as u64).to_le_bytes());
+            hasher.update(part.as_bytes());
+        }
+        hasher
+            .finalize()
+            .iter()
+            .map(|byte| format!("{byte:02x}"))
+            .collect()
+    }
+
+    /// Returns the binary format and the binary of the program linked from `sources`.
+    fn lookup(&self, key: &str, sources: &[String]) -> Option<(u32, Vec<u8>)> {
+        let cached = self.entries.lock().get(key).cloned();
+        let entry: Arc<[u8]> = match cached {
+            Some(entry) => entry,
+            None => match self.storage {
+                wgt::GlesProgramCache::Memory => return None,
+                wgt::GlesProgramCache::Directory(ref directory) => {
+                    fs::read(directory.join(format!("{key}.bin"))).ok()?.into()
+                }
+                wgt::GlesProgramCache::Store(ref store) => store.load(key)?.into(),
+            },
+        };
+        let binary = match decode_entry(&entry, &self.driver, sources) {
+            Some((format, binary)) => (format, binary.to_vec()),
+            None => {
+                log::info!("\tProgram binary {} is corrupt or of another program", key);
+                return None;
+            }
+        };
+        self.entries.lock().insert(key.to_string(), entry);
+        Some(binary)
+    }
+
+    fn insert(&self, key: &str, sources: &[String], format: u32, binary: &[u8]) {
+        let entry = encode_entry(&self.driver, sources, format, binary);
+        match self.storage {
+            wgt::GlesProgramCache::Memory => {}
+            wgt::GlesProgramCache::Directory(ref directory) => {
+                let result = fs::create_dir_all(directory)
+                    .and_then(|()| write_entry_file(directory, key, &entry));
+                if let Err(e) = result {
+                    log::warn!("Unable to store program binary in {:?}: {}", directory, e);
+                }
+            }
+            wgt::GlesProgramCache::Store(ref store) => store.store(key, &entry),
+        }
+        self.entries.lock().insert(key.to_string(), entry.into());
+    }
+
+    fn remove(&self, key: &str) {
+        self.entries.lock().remove(key);
+    }
+}
+
+pub struct ProgramBinaryCache {
+    fns: ProgramBinaryFns,
+    entries: EntryStorage,
+}
+
+impl ProgramBinaryCache {
+    pub(super) fn new(
+        fns: ProgramBinaryFns,
+        driver: String,
+        storage: wgt::GlesProgramCache,
+    ) -> Self {
+        Self {
+            fns,
+            entries: EntryStorage::new(driver, storage),
+        }
+    }
+
+    /// Returns the key of the program linked from the given GLSL sources.
+    pub(super) fn key(&self, sources: &[String]) -> String {
+        self.entries.key(sources)
+    }
+
+    /// Loads the cached binary of the program linked from `sources` into `program`.
+    ///
+    /// Returns `false` if there is no binary, or if the driver rejected it, in which case the
+    /// program has to be linked from source.
+    pub(super) unsafe fn load(
+        &self,
+        gl: &glow::Context,
+        key: &str,
+        sources: &[String],
+        program: glow::Program,
+    ) -> bool {
+        let (format, binary) = match self.entries.lookup(key, sources) {
+            Some(binary) => binary,
+            None => return false,
+        };
+        unsafe {
+            (self.fns.program_binary)(
+                raw_program(program),
+                format,
+                binary.as_ptr().cast(),
+                binary.len() as i32,
+            )
+        };
+        if unsafe { gl.get_program_link_status(program) } {
+            log::debug!("\tLoaded program binary {}", key);
+            true
+        } else {
+            // The driver may have been updated without changing its version string.
+            log::info!("\tProgram binary {} was rejected by the driver", key);
+            self.entries.remove(key);
+            false
+        }
+    }
+
+    /// Asks the driver to keep the binary of `program` retrievable, before it is linked.
+    pub(super) unsafe fn prepare(&self, program: glow::Program) {
+        unsafe {
+            (self.fns.program_parameter)(
+                raw_program(program),
+                glow::PROGRAM_BINARY_RETRIEVABLE_HINT,
+                i32::from(glow::TRUE),
+            )
+        };
+    }
+
+    /// Retrieves the binary of `program`, linked from `sources`, and caches it under `key`.
+    pub(super) unsafe fn store(&self, key: &str, sources: &[String], program: glow::Program) {
+        let mut length = 0;
+        unsafe {
+            (self.fns.get_program_parameter)(
+                raw_program(program),
+                glow::PROGRAM_BINARY_LENGTH,
+                &mut length,
+            )
+        };
+        if length <= 0 {
+            return;
+        }
+        let mut binary = vec![0u8; length as usize];
+        let mut written = 0;
+        let mut format = 0;
+        unsafe {
+            (self.fns.get_program_binary)(
+                raw_program(program),
+                length,
+                &mut written,
+                &mut format,
+                binary.as_mut_ptr().cast(),
+            )
+        };
+        if written <= 0 {
+            return;
+        }
+        binary.truncate(written as usize);
+        self.entries.insert(key, sources, format, &binary);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::EntryStorage;
+    use parking_lot::Mutex;
+    use std::{collections::HashMap, fs, sync::Arc};
+
+    const DRIVER: &str = "vendor/renderer/OpenGL ES 3.2";
+
+    fn sources(fragment: &str) -> Vec<String> {
+        vec!["void main() {}".to_string(), fragment.to_string()]
+    }
+
+    /// Checks that an entry stored through `storage` is found by a new cache using the same
+    /// storage, and only for the same driver and sources.
+    fn check_round_trip(storage: wgt::GlesProgramCache, persistent: bool) {
+        let sources = sources("void main() { discard; }");
+        let cache = EntryStorage::new(DRIVER.to_string(), storage.clone());
+        let key = cache.key(&sources);
+        assert_eq!(cache.lookup(&key, &sources), None);
+        cache.insert(&key, &sources, 7, &[1, 2, 3]);
+        assert_eq!(cache.lookup(&key, &sources), Some((7, vec![1, 2, 3])));
+
+        let reloaded = EntryStorage::new(DRIVER.to_string(), storage.clone());
+        let expected = if persistent {
+            Some((7, vec![1, 2, 3]))
+        } else {
+            None
+        };
+        assert_eq!(reloaded.lookup(&key, &sources), expected);
+
+        // An entry found under the key of other sources or of another driver is not used.
+        let other_sources = super::tests::sources("void main() {}");
+        assert_eq!(cache.lookup(&key, &other_sources), None);
+        let other_driver = EntryStorage::new(format!("{DRIVER}.1"), storage);
+        assert_eq!(other_driver.lookup(&key, &sources), None);
+    }
+
+    #[derive(Debug, Default)]
+    struct Store(Mutex<HashMap<String, Vec<u8>>>);
+
+    impl wgt::GlesProgramBinaryStore for Store {
+        fn load(&self, key: &str) -> Option<Vec<u8>> {
+            self.0.lock().get(key).cloned()
+        }
+        fn store(&self, key: &str, binary: &[u8]) {
+            self.0.lock().insert(key.to_string(), binary.to_vec());
+        }
+    }
+
+    #[test]
+    fn test_key() {
+        let cache = EntryStorage::new(DRIVER.to_string(), wgt::GlesProgramCache::Memory);
+        let key = cache.key(&sources("void main() {}"));
+        assert_eq!(key.len(), 64);
+        assert_eq!(key, cache.key(&sources("void main() {}")));
+        assert_ne!(key, cache.key(&sources("void main() { discard; }")));
+        // The sources are length-prefixed, moving text between them changes the key.
+        assert_ne!(
+            cache.key(&["ab".to_string(), "c".to_string()]),
+            cache.key(&["a".to_string(), "bc".to_string()])
+        );
+    }
+
+    #[test]
+    fn test_memory_round_trip() {
+        check_round_trip(wgt::GlesProgramCache::Memory, false);
+    }
+
+    #[test]
+    fn test_directory_round_trip() {
+        let directory =
+            std::env::temp_dir().join(format!("wgpu-hal-program-binaries-{}", std::process::id()));
+        check_round_trip(wgt::GlesProgramCache::Directory(directory.clone()), true);
+
+        // Truncated files are ignored.
+        let sources = sources("void main() { discard; }");
+        let storage = wgt::GlesProgramCache::Directory(directory.clone());
+        let key = EntryStorage::new(DRIVER.to_string(), storage.clone()).key(&sources);
+        let path = directory.join(format!("{key}.bin"));
+        let entry = fs::read(&path).unwrap();
+        // Entries are renamed into place, no temporary file is left behind.
+        for file in fs::read_dir(&directory).unwrap() {
+            let name = file.unwrap().file_name();
+            assert!(!name.to_string_lossy().ends_with(".tmp"), "{name:?}");
+        }
+        for len in [0, 4, entry.len() - 3] {
+            fs::write(&path, &entry[..len]).unwrap();
+            let cache = EntryStorage::new(DRIVER.to_string(), storage.clone());
+            assert_eq!(cache.lookup(&key, &sources), None, "{len} bytes");
+        }
+        fs::remove_dir_all(directory).unwrap();
+    }
+
+    #[test]
+    fn test_store_round_trip() {
+        let store = Arc::new(Store::default());
+        check_round_trip(wgt::GlesProgramCache::Store(store.clone()), true);
+
+        // Entries with another layout are ignored.
+        let sources = sources("void main() { discard; }");
+        let storage = wgt::GlesProgramCache::Store(store.clone());
+        let key = EntryStorage::new(DRIVER.to_string(), storage.clone()).key(&sources);
+        for entry in store.0.lock().values_mut() {
+            entry[4] += 1;
+        }
+        let cache = EntryStorage::new(DRIVER.to_string(), storage);
+        assert_eq!(cache.lookup(&key, &sources), None);
+    }
+}
@@ -1,11 +1,17 @@
 use super::Command as C;
 use arrayvec::ArrayVec;
 use glow::HasContext;
-use std::{mem, slice, sync::Arc};
+use std::{mem, ops::Range, slice, sync::Arc};
 
 #[cfg(not(target_arch = "wasm32"))]
 const DEBUG_ID: u32 = 0;
 
+/// `GL_GPU_DISJOINT_EXT` from `GL_EXT_disjoint_timer_query`.
+const GPU_DISJOINT_EXT: u32 = 0x8FBB;
+
+/// Number of line list index buffers that wireframe draws keep around.
+const MAX_WIREFRAME_INDEX_BUFFERS: usize = 256;
+
 const CUBEMAP_FACES: [u32; 6] = [
     glow::TEXTURE_CUBE_MAP_POSITIVE_X,
     glow::TEXTURE_CUBE_MAP_NEGATIVE_X,
@@ -15,11 +21,50 @@ const CUBEMAP_FACES: [u32; 6] = [
     glow::TEXTURE_CUBE_MAP_NEGATIVE_Z,
 ];
 
+#[cfg(any(not(target_arch = "wasm32"), target_os = "emscripten"))]
+fn raw_query(query: glow::Query) -> u32 {
+    query.0.get()
+}
+
+#[cfg(all(target_arch = "wasm32", not(target_os = "emscripten")))]
+fn raw_query(_query: glow::Query) -> u32 {
+    unreachable!("WebGL has no 64-bit query results")
+}
+
 #[cfg(not(target_arch = "wasm32"))]
 fn extract_marker<'a>(data: &'a [u8], range: &std::ops::Range<u32>) -> &'a str {
     std::str::from_utf8(&data[range.start as usize..range.end as usize]).unwrap()
 }
 
+/// Converts the vertex indices of a triangle list or strip into the line list
+/// of the triangle edges. `u32::MAX` restarts a strip.
+fn triangles_to_lines(topology: u32, vertices: &[u32]) -> Vec<u32> {
+    let mut lines = Vec::with_capacity(vertices.len() * 2);
+    let mut push_triangle =
+        |t: &[u32]| lines.extend_from_slice(&[t[0], t[1], t[1], t[2], t[2], t[0]]);
+    match topology {
+        glow::TRIANGLE_STRIP => {
+            for strip in vertices.split(|&index| index == u32::MAX) {
+                strip.windows(3).for_each(&mut push_triangle);
+            }
+        }
+        _ => vertices.chunks_exact(3).for_each(&mut push_triangle),
+    }
+    lines
+}
+
+/// A draw of triangles as lines, see [`super::Queue::draw_wireframe`].
+struct WireframeDraw {
+    topology: u32,
+    /// Index type and offset of the bound index buffer, for indexed draws.
+    indices: Option<(u32, wgt::BufferAddress)>,
+    shader_writable: bool,
+    /// Range of the indices, or of the vertices of non-indexed draws.
+    range: Range<u32>,
+    base_vertex: i32,
+    instance_count: u32,
+}
+
 fn is_layered_target(target: super::BindTarget) -> bool {
     match target {
         glow::TEXTURE_2D_ARRAY | glow::TEXTURE_3D | glow::TEXTURE_CUBE_MAP_ARRAY => true,
@@ -61,6 +106,128 @@ impl super::Queue {
         }
     }
 
+    /// Draws triangles as lines, through line list indices that are built from
+    /// the bound index buffer, or from the vertex range of non-indexed draws.
+    ///
+    /// The line list indices are kept for later draws of the same range, unless
+    /// shaders can write to the index buffer.
+    unsafe fn draw_wireframe(&self, gl: &glow::Context, draw: WireframeDraw) {
+        let WireframeDraw {
+            topology,
+            indices,
+            shader_writable,
+            range,
+            base_vertex,
+            instance_count,
+        } = draw;
+        let key = super::WireframeKey {
+            topology,
+            indices: indices.map(|(index_type, index_offset)| {
+                (self.current_index_buffer.unwrap(), index_type, index_offset)
+            }),
+            start_vertex: range.start,
+            count: range.end - range.start,
+        };
+        let mut cache = self.shared.wireframe_indices.lock();
+        let (lines_buffer, line_count) = match cache.get(&key) {
+            Some(&cached) => cached,
+            None => {
+                let vertices = match indices {
+                    Some((index_type, index_offset)) => {
+                        let index_size = match index_type {
+                            glow::UNSIGNED_SHORT => 2,
+                            _ => 4,
+                        };
+                        let mut bytes = vec![0; key.count as usize * index_size];
+                        if let Err(error) = unsafe {
+                            self.shared.get_buffer_sub_data(
+                                gl,
+                                glow::ELEMENT_ARRAY_BUFFER,
+                                index_offset as i32,
+                                &mut bytes,
+                            )
+                        } {
+                            log::error!("Failed to read the indices of a wireframe draw: {error}");
+                            return;
+                        }
+                        match index_size {
+                            // widen the primitive restart value along with the indices
+                            2 => bytes
+                                .chunks_exact(2)
+                                .map(|c| match u16::from_ne_bytes([c[0], c[1]]) {
+                                    u16::MAX => u32::MAX,
+                                    index => index as u32,
+                                })
+                                .collect(),
+                            _ => bytes
+                                .chunks_exact(4)
+                                .map(|c| u32::from_ne_bytes([c[0], c[1], c[2], c[3]]))
+                                .collect(),
+                        }
+                    }
+                    None => range.collect::<Vec<_>>(),
+                };
+                let lines = triangles_to_lines(topology, &vertices);
+
+                let cached_buffer = if shader_writable {
+                    None
+                } else {
+                    if cache.len() >= MAX_WIREFRAME_INDEX_BUFFERS {
+                        for (_, (lines, _)) in cache.drain() {
+                            unsafe { gl.delete_buffer(lines) };
+                        }
+                    }
+                    unsafe { gl.create_buffer() }.ok()
+                };
+                let lines_buffer = cached_buffer.unwrap_or(self.wireframe_index_buffer);
+                unsafe { gl.bind_buffer(glow::ELEMENT_ARRAY_BUFFER, Some(lines_buffer)) };
+                unsafe {
+                    gl.buffer_data_u8_slice(
+                        glow::ELEMENT_ARRAY_BUFFER,
+                        slice::from_raw_parts(
+                            lines.as_ptr() as *const u8,
+                            lines.len() * mem::size_of::<u32>(),
+                        ),
+                        match cached_buffer {
+                            Some(_) => glow::STATIC_DRAW,
+                            None => glow::STREAM_DRAW,
+                        },
+                    )
+                };
+                let entry = (lines_buffer, lines.len() as i32);
+                if cached_buffer.is_some() {
+                    cache.insert(key, entry);
+                }
+                entry
+            }
+        };
+        drop(cache);
+
+        unsafe { gl.bind_buffer(glow::ELEMENT_ARRAY_BUFFER, Some(lines_buffer)) };
+        match base_vertex {
+            0 => unsafe {
+                gl.draw_elements_instanced(
+                    glow::LINES,
+                    line_count,
+                    glow::UNSIGNED_INT,
+                    0,
+                    instance_count as i32,
+                )
+            },
+            _ => unsafe {
+                gl.draw_elements_instanced_base_vertex(
+                    glow::LINES,
+                    line_count,
+                    glow::UNSIGNED_INT,
+                    0,
+                    instance_count as i32,
+                    base_vertex,
+                )
+            },
+        }
+        unsafe { gl.bind_buffer(glow::ELEMENT_ARRAY_BUFFER, self.current_index_buffer) };
+    }
+
     unsafe fn reset_state(&mut self, gl: &glow::Context) {
         unsafe { gl.use_program(None) };
         unsafe { gl.bind_framebuffer(glow::FRAMEBUFFER, None) };
@@ -74,6 +241,13 @@ impl super::Queue {
         if self.features.contains(wgt::Features::DEPTH_CLIP_CONTROL) {
             unsafe { gl.disable(glow::DEPTH_CLAMP) };
         }
+        if self
+            .shared
+            .private_caps
+            .contains(super::PrivateCapabilities::POLYGON_MODE)
+        {
+            unsafe { gl.polygon_mode(glow::FRONT_AND_BACK, glow::FILL) };
+        }
 
         unsafe { gl.bind_buffer(glow::ELEMENT_ARRAY_BUFFER, None) };
         self.current_index_buffer = None;
@@ -210,24 +384,150 @@ impl super::Queue {
                     },
                 }
             }
+            C::DrawWireframe {
+                topology,
+                indices,
+                shader_writable,
+                start_vertex,
+                count,
+                base_vertex,
+                instance_count,
+            } => unsafe {
+                self.draw_wireframe(
+                    gl,
+                    WireframeDraw {
+                        topology,
+                        indices,
+                        shader_writable,
+                        range: start_vertex..start_vertex + count,
+                        base_vertex,
+                        instance_count,
+                    },
+                )
+            },
+            C::DrawWireframeIndirect {
+                topology,
+                indices,
+                shader_writable,
+                indirect_buf,
+                indirect_offset,
+            } => {
+                let mut bytes = [0; mem::size_of::<wgt::DrawIndexedIndirectArgs>()];
+                let bytes = match indices {
+                    Some(_) => &mut bytes[..],
+                    None => &mut bytes[..mem::size_of::<wgt::DrawIndirectArgs>()],
+                };
+                unsafe { gl.bind_buffer(glow::DRAW_INDIRECT_BUFFER, Some(indirect_buf)) };
+                if let Err(error) = unsafe {
+                    self.shared.get_buffer_sub_data(
+                        gl,
+                        glow::DRAW_INDIRECT_BUFFER,
+                        indirect_offset as i32,
+                        bytes,
+                    )
+                } {
+                    log::error!("Failed to read the arguments of a wireframe draw: {error}");
+                    return;
+                }
+                let args = bytes
+                    .chunks_exact(4)
+                    .map(|c| u32::from_ne_bytes([c[0], c[1], c[2], c[3]]))
+                    .collect::<ArrayVec<_, 5>>();
+                match indices {
+                    Some((index_type, index_offset)) => {
+                        let index_size = match index_type {
+                            glow::UNSIGNED_SHORT => 2,
+                            _ => 4,
+                        };
+                        let base_vertex = args[3] as i32;
+                        if base_vertex != 0
+                            && !self
+                                .shared
+                                .private_caps
+                                .contains(super::PrivateCapabilities::BASE_VERTEX)
+                        {
+                            // `wgpu-core` rejects these draws, see
+                            // `DownlevelFlags::EMULATED_POLYGON_MODE_LINE`.
+                            log::error!("Indirect wireframe draws can't use a base vertex");
+                            return;
+                        }
+                        let index_offset = index_offset + index_size * args[2] as u64;
+                        unsafe {
+                            self.draw_wireframe(
+                                gl,
+                                WireframeDraw {
+                                    topology,
+                                    indices: Some((index_type, index_offset)),
+                                    shader_writable,
+                                    range: 0..args[0],
+                                    base_vertex,
+                                    instance_count: args[1],
+                                },
+                            )
+                        }
+                    }
+                    None => unsafe {
+                        self.draw_wireframe(
+                            gl,
+                            WireframeDraw {
+                                topology,
+                                indices: None,
+                                shader_writable: false,
+                                range: args[2]..args[2] + args[0],
+                                base_vertex: 0,
+                                instance_count: args[1],
+                            },
+                        )
+                    },
+                }
+            }
             C::DrawIndirect {
                 topology,
                 indirect_buf,
                 indirect_offset,
+                draw_count,
             } => {
                 unsafe { gl.bind_buffer(glow::DRAW_INDIRECT_BUFFER, Some(indirect_buf)) };
-                unsafe { gl.draw_arrays_indirect_offset(topology, indirect_offset as i32) };
+                match self.shared.context.multi_draw_indirect() {
+                    Some(fns) if draw_count > 1 => unsafe {
+                        (fns.arrays)(
+                            topology,
+                            indirect_offset as usize as *const _,
+                            draw_count as i32,
+                            0,
+                        )
+                    },
+                    _ => unsafe {
+                        gl.draw_arrays_indirect_offset(topology, indirect_offset as i32)
+                    },
+                }
             }
             C::DrawIndexedIndirect {
                 topology,
                 index_type,
                 indirect_buf,
                 indirect_offset,
+                draw_count,
             } => {
                 unsafe { gl.bind_buffer(glow::DRAW_INDIRECT_BUFFER, Some(indirect_buf)) };
-                unsafe {
-                    gl.draw_elements_indirect_offset(topology, index_type, indirect_offset as i32)
-                };
+                match self.shared.context.multi_draw_indirect() {
+                    Some(fns) if draw_count > 1 => unsafe {
+                        (fns.elements)(
+                            topology,
+                            index_type,
+                            indirect_offset as usize as *const _,
+                            draw_count as i32,
+                            0,
+                        )
+                    },
+                    _ => unsafe {
+                        gl.draw_elements_indirect_offset(
+                            topology,
+                            index_type,
+                            indirect_offset as i32,
+                        )
+                    },
+                }
             }
             C::Dispatch(group_counts) => {
                 unsafe { gl.dispatch_compute(group_counts[0], group_counts[1], group_counts[2]) };
@@ -245,6 +545,7 @@ impl super::Queue {
                 ref range,
             } => match dst.raw {
                 Some(buffer) => {
+                    unsafe { self.shared.invalidate_wireframe_indices(gl, buffer) };
                     // When `INDEX_BUFFER_ROLE_CHANGE` isn't available, we can't copy into the
                     // index buffer from the zero buffer. This would fail in Chrome with the
                     // following message:
@@ -297,6 +598,9 @@ impl super::Queue {
                 dst_target,
                 copy,
             } => {
+                if let Some(raw) = dst.raw {
+                    unsafe { self.shared.invalidate_wireframe_indices(gl, raw) };
+                }
                 let copy_src_target = glow::COPY_READ_BUFFER;
                 let is_index_buffer_only_element_dst = !self
                     .shared
@@ -332,14 +636,16 @@ impl super::Queue {
                             [copy.dst_offset as usize..copy.dst_offset as usize + size];
 
                         unsafe { gl.bind_buffer(copy_src_target, Some(src)) };
-                        unsafe {
+                        if let Err(error) = unsafe {
                             self.shared.get_buffer_sub_data(
                                 gl,
                                 copy_src_target,
                                 copy.src_offset as i32,
                                 dst_data,
                             )
-                        };
+                        } {
+                            log::error!("Failed to read back a buffer copy: {error}");
+                        }
                     }
                     (None, Some(dst)) => {
                         let data = src.data.as_ref().unwrap().lock().unwrap();
@@ -757,6 +1063,9 @@ impl super::Queue {
                 dst_target: _,
                 ref copy,
             } => {
+                if let Some(raw) = dst.raw {
+                    unsafe { self.shared.invalidate_wireframe_indices(gl, raw) };
+                }
                 let block_size = src_format.block_size(None).unwrap();
                 if src_format.is_compressed() {
                     log::error!("Not implemented yet: compressed texture copy to buffer");
@@ -857,21 +1166,78 @@ impl super::Queue {
                 self.current_index_buffer = Some(buffer);
             }
             C::BeginQuery(query, target) => {
+                self.shared.reset_queries.lock().remove(&query);
                 unsafe { gl.begin_query(target, query) };
             }
             C::EndQuery(target) => {
                 unsafe { gl.end_query(target) };
             }
+            C::TimestampQuery(query) => {
+                self.shared.reset_queries.lock().remove(&query);
+                unsafe { gl.query_counter(query, glow::TIMESTAMP) };
+            }
+            C::ResetQueries { ref query_range } => {
+                self.shared.reset_queries.lock().extend(
+                    queries[query_range.start as usize..query_range.end as usize]
+                        .iter()
+                        .copied(),
+                );
+            }
             C::CopyQueryResults {
                 ref query_range,
+                query_target,
                 ref dst,
                 dst_target,
                 dst_offset,
             } => {
+                if let Some(raw) = dst.raw {
+                    unsafe { self.shared.invalidate_wireframe_indices(gl, raw) };
+                }
                 self.temp_query_results.clear();
+                // Timestamps and pipeline statistics are 64-bit, occlusion
+                // queries only tell whether any sample passed.
+                let wide_results = query_target != glow::ANY_SAMPLES_PASSED;
+                if wide_results
+                    && self
+                        .shared
+                        .private_caps
+                        .contains(super::PrivateCapabilities::QUERY_BUFFER)
+                {
+                    // The 64-bit results are read to client memory, which
+                    // `glGetQueryObjectui64v` only does without a query buffer.
+                    unsafe { gl.bind_buffer(glow::QUERY_BUFFER, None) };
+                }
+                let reset_queries = self.shared.reset_queries.lock();
                 for &query in queries[query_range.start as usize..query_range.end as usize].iter() {
-                    let result = unsafe { gl.get_query_parameter_u32(query, glow::QUERY_RESULT) };
-                    self.temp_query_results.push(result as u64);
+                    let result = if reset_queries.contains(&query) {
+                        0
+                    } else if wide_results {
+                        let get_query_object_u64 =
+                            self.shared.context.get_query_object_u64().unwrap();
+                        let mut result = 0u64;
+                        unsafe {
+                            get_query_object_u64(raw_query(query), glow::QUERY_RESULT, &mut result)
+                        };
+                        result
+                    } else {
+                        unsafe { gl.get_query_parameter_u32(query, glow::QUERY_RESULT) as u64 }
+                    };
+                    self.temp_query_results.push(result);
+                }
+                drop(reset_queries);
+                if query_target == glow::TIMESTAMP
+                    && self
+                        .shared
+                        .private_caps
+                        .contains(super::PrivateCapabilities::TIMER_QUERY_DISJOINT)
+                    // Reading the flag also clears it.
+                    && unsafe { gl.get_parameter_i32(GPU_DISJOINT_EXT) } != 0
+                {
+                    // A disjoint operation (e.g. a GPU frequency change)
+                    // happened while the timestamps were taken, which makes
+                    // them meaningless.
+                    log::warn!("Timestamp queries were invalidated by a GPU disjoint operation");
+                    self.temp_query_results.fill(0);
                 }
                 let query_data = unsafe {
                     slice::from_raw_parts(
@@ -1229,6 +1595,13 @@ impl super::Queue {
                         unsafe { gl.disable(glow::DEPTH_CLAMP) };
                     }
                 }
+                if self
+                    .shared
+                    .private_caps
+                    .contains(super::PrivateCapabilities::POLYGON_MODE)
+                {
+                    unsafe { gl.polygon_mode(glow::FRONT_AND_BACK, state.polygon_mode) };
+                }
             }
             C::SetBlendConstant(c) => {
                 unsafe { gl.blend_color(c[0], c[1], c[2], c[3]) };
@@ -1521,6 +1894,7 @@ impl crate::Queue<super::Api> for super::Queue {
     }
 
     unsafe fn get_timestamp_period(&self) -> f32 {
+        // GL timestamps are always in nanoseconds
         1.0
     }
 }
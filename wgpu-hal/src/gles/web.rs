@@ -15,6 +15,23 @@ impl AdapterContext {
         false
     }
 
+    /// WebGL has no multi-draw-indirect.
+    pub(super) fn multi_draw_indirect(&self) -> Option<&super::MultiDrawIndirectFns> {
+        None
+    }
+
+    /// glow doesn't implement query counters on WebGL2.
+    pub(super) fn get_query_object_u64(&self) -> Option<super::GetQueryObjectui64vFn> {
+        None
+    }
+
+    /// WebGL has no program binaries.
+    pub(super) fn program_binary(
+        &self,
+    ) -> Option<(super::ProgramBinaryFns, wgt::GlesProgramCache)> {
+        None
+    }
+
     /// Obtain a lock to the EGL context and get handle to the [`glow::Context`] that can be used to
     /// do rendering.
     #[track_caller]
@@ -285,6 +302,15 @@ impl Surface {
         // present.frag takes care of handling srgb conversion
         true
     }
+
+    pub(super) fn present_modes(&self) -> Vec<wgt::PresentMode> {
+        // the browser always presents in sync with its compositor
+        vec![wgt::PresentMode::Fifo]
+    }
+
+    pub(super) fn composite_alpha_modes(&self) -> Vec<wgt::CompositeAlphaMode> {
+        vec![wgt::CompositeAlphaMode::Opaque]
+    }
 }
 
 impl crate::Surface<super::Api> for Surface {
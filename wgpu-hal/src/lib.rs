@@ -108,6 +108,7 @@ pub const QUERY_SIZE: wgt::BufferAddress = 8;
 pub type Label<'a> = Option<&'a str>;
 pub type MemoryRange = Range<wgt::BufferAddress>;
 pub type FenceValue = u64;
+pub type PipelineConstants = std::collections::HashMap<String, f64>;
 
 /// Drop guard to signal wgpu-hal is no longer using an externally created object.
 pub type DropGuard = Box<dyn std::any::Any + Send + Sync>;
@@ -132,6 +133,10 @@ pub enum ShaderError {
 pub enum PipelineError {
     #[error("Linkage failed for stage {0:?}: {1}")]
     Linkage(wgt::ShaderStages, String),
+    /// The shaders don't match the pipeline layout or the other parts of the
+    /// descriptor, which is caused by the user rather than the backend.
+    #[error("Shader stage {0:?} doesn't match the pipeline: {1}")]
+    Layout(wgt::ShaderStages, String),
     #[error("Entry point for stage {0:?} is invalid")]
     EntryPoint(naga::ShaderStage),
     #[error(transparent)]
@@ -222,6 +227,7 @@ pub trait Adapter<A: Api>: WasmNotSend + WasmNotSync {
         &self,
         features: wgt::Features,
         limits: &wgt::Limits,
+        allocator: &wgt::AllocatorConfig,
     ) -> Result<OpenDevice<A>, DeviceError>;
 
     /// Return the set of supported capabilities for a texture format.
@@ -299,6 +305,12 @@ pub trait Device<A: Api>: WasmNotSend + WasmNotSync {
         desc: &BindGroupDescriptor<A>,
     ) -> Result<A::BindGroup, DeviceError>;
     unsafe fn destroy_bind_group(&self, group: A::BindGroup);
+    /// Writes new resources into elements of binding arrays of a bind group.
+    ///
+    /// The layout of the group must have been created with
+    /// [`BindGroupLayoutFlags::UPDATE_AFTER_BIND`], and the written elements
+    /// must not be in use by the GPU.
+    unsafe fn update_bind_group(&self, group: &A::BindGroup, desc: &BindGroupUpdateDescriptor<A>);
 
     unsafe fn create_shader_module(
         &self,
@@ -335,6 +347,10 @@ pub trait Device<A: Api>: WasmNotSend + WasmNotSync {
 
     unsafe fn start_capture(&self) -> bool;
     unsafe fn stop_capture(&self);
+
+    /// Returns the memory heaps of the device and the bytes allocated for
+    /// buffers and textures created through this device.
+    unsafe fn memory_report(&self) -> MemoryReport;
 }
 
 pub trait Queue<A: Api>: WasmNotSend + WasmNotSync {
@@ -579,6 +595,13 @@ bitflags!(
     pub struct BindGroupLayoutFlags: u32 {
         /// Allows for bind group binding arrays to be shorter than the array in the BGL.
         const PARTIALLY_BOUND = 1 << 0;
+        /// Allows for elements of binding arrays to be written with
+        /// [`Device::update_bind_group`] after the bind group is used by
+        /// recorded command buffers.
+        ///
+        /// Requires [`wgt::Features::UPDATE_AFTER_BIND_DESCRIPTORS`]. Layouts
+        /// with this flag must not contain buffers with dynamic offsets.
+        const UPDATE_AFTER_BIND = 1 << 1;
     }
 );
 
@@ -633,6 +656,8 @@ bitflags!(
         const COLOR = 1 << 0;
         const DEPTH = 1 << 1;
         const STENCIL = 1 << 2;
+        const PLANE_0 = 1 << 3;
+        const PLANE_1 = 1 << 4;
     }
 );
 
@@ -642,6 +667,8 @@ impl FormatAspects {
             wgt::TextureAspect::All => Self::all(),
             wgt::TextureAspect::DepthOnly => Self::DEPTH,
             wgt::TextureAspect::StencilOnly => Self::STENCIL,
+            wgt::TextureAspect::Plane0 => Self::PLANE_0,
+            wgt::TextureAspect::Plane1 => Self::PLANE_1,
         };
         Self::from(format) & aspect_mask
     }
@@ -656,6 +683,8 @@ impl FormatAspects {
             Self::COLOR => wgt::TextureAspect::All,
             Self::DEPTH => wgt::TextureAspect::DepthOnly,
             Self::STENCIL => wgt::TextureAspect::StencilOnly,
+            Self::PLANE_0 => wgt::TextureAspect::Plane0,
+            Self::PLANE_1 => wgt::TextureAspect::Plane1,
             _ => unreachable!(),
         }
     }
@@ -671,6 +700,7 @@ impl From<wgt::TextureFormat> for FormatAspects {
             wgt::TextureFormat::Depth32FloatStencil8 | wgt::TextureFormat::Depth24PlusStencil8 => {
                 Self::DEPTH | Self::STENCIL
             }
+            wgt::TextureFormat::NV12 | wgt::TextureFormat::P010 => Self::PLANE_0 | Self::PLANE_1,
             _ => Self::COLOR,
         }
     }
@@ -681,6 +711,8 @@ bitflags!(
     pub struct MemoryFlags: u32 {
         const TRANSIENT = 1 << 0;
         const PREFER_COHERENT = 1 << 1;
+        /// The resource lives for a long time, prefer a dedicated allocation if it's large.
+        const LONG_LIVED = 1 << 2;
     }
 );
 
@@ -769,6 +801,11 @@ bitflags::bitflags! {
         /// Flag used by the wgpu-core texture tracker to say that the tracker does not know the state of the sub-resource.
         /// This is different from UNINITIALIZED as that says the tracker does know, but the texture has not been initialized.
         const UNKNOWN = 1 << 11;
+
+        /// The texture is only ever used as a render target whose contents are never loaded or stored,
+        /// so the backend may back it with lazily allocated or tile-only memory.
+        /// This is a creation hint and never part of a tracked state.
+        const TRANSIENT = 1 << 12;
     }
 }
 
@@ -777,6 +814,10 @@ pub struct InstanceDescriptor<'a> {
     pub name: &'a str,
     pub flags: InstanceFlags,
     pub dx12_shader_compiler: wgt::Dx12Compiler,
+    /// DRM device node of the EGL device to use, see [`wgt::InstanceDescriptor::gles_device`].
+    pub gles_device: Option<String>,
+    /// Where the GL backend caches program binaries, see [`wgt::GlesProgramCache`].
+    pub gles_program_cache: wgt::GlesProgramCache,
 }
 
 #[derive(Clone, Debug)]
@@ -795,6 +836,13 @@ pub struct Capabilities {
     pub downlevel: wgt::DownlevelCapabilities,
 }
 
+#[derive(Clone, Debug, Default)]
+pub struct MemoryReport {
+    pub heaps: Vec<wgt::MemoryHeapReport>,
+    pub buffer_bytes: u64,
+    pub texture_bytes: u64,
+}
+
 #[derive(Debug)]
 pub struct ExposedAdapter<A: Api> {
     pub adapter: A::Adapter,
@@ -1036,6 +1084,33 @@ pub struct BindGroupDescriptor<'a, A: Api> {
     pub entries: &'a [BindGroupEntry],
 }
 
+/// Elements of a binding array written by [`Device::update_bind_group`].
+#[derive(Clone, Debug)]
+pub struct BindGroupUpdateEntry {
+    pub binding: u32,
+    /// Index of the first written element of the array.
+    pub array_offset: u32,
+    pub resource_index: u32,
+    pub count: u32,
+}
+
+/// Update of a bind group.
+///
+/// Valid usage:
+///. - `layout` is the layout of the updated bind group
+///. - each entry writes elements of a binding array of the `layout`,
+///    within the bounds of the array
+///. - each entry's `BindGroupUpdateEntry::resource_index` is within range
+///    of the corresponding resource array.
+#[derive(Clone, Debug)]
+pub struct BindGroupUpdateDescriptor<'a, A: Api> {
+    pub layout: &'a A::BindGroupLayout,
+    pub buffers: &'a [BufferBinding<'a, A>],
+    pub samplers: &'a [&'a A::Sampler],
+    pub textures: &'a [TextureBinding<'a, A>],
+    pub entries: &'a [BindGroupUpdateEntry],
+}
+
 #[derive(Clone, Debug)]
 pub struct CommandEncoderDescriptor<'a, A: Api> {
     pub label: Label<'a>,
@@ -1063,6 +1138,29 @@ impl fmt::Debug for NagaShader {
 pub enum ShaderInput<'a> {
     Naga(NagaShader),
     SpirV(&'a [u32]),
+    /// Metal Shading Language source.
+    Msl {
+        source: &'a str,
+        /// Workgroup size of the compute entry points, which is not part of
+        /// their MSL declaration.
+        workgroup_size: [u32; 3],
+    },
+    /// Precompiled DXIL bytecode of a single entry point, which pipelines refer
+    /// to as `main`.
+    Dxil {
+        bytecode: &'a [u8],
+        stage: naga::ShaderStage,
+    },
+    /// HLSL source, compiled for the stage of each pipeline using it.
+    Hlsl(&'a str),
+    /// GLSL ES source of a single stage, with `main` as its entry point.
+    Glsl {
+        source: &'a str,
+        stage: naga::ShaderStage,
+        /// Names of the uniform blocks and samplers of the shader, for each
+        /// binding of the pipeline layout they are bound to.
+        bindings: &'a [wgt::PassthroughBinding<&'a str>],
+    },
 }
 
 pub struct ShaderModuleDescriptor<'a> {
@@ -1078,6 +1176,11 @@ pub struct ProgrammableStage<'a, A: Api> {
     /// The name of the entry point in the compiled shader. There must be a function with this name
     ///  in the shader.
     pub entry_point: &'a str,
+    /// Values of the pipeline-overridable constants of the module, keyed by
+    /// their name or their numeric identifier.
+    ///
+    /// The values have been validated against the overrides of the module.
+    pub constants: &'a PipelineConstants,
 }
 
 // Rust gets confused about the impl requirements for `A`
@@ -1086,6 +1189,7 @@ impl<A: Api> Clone for ProgrammableStage<'_, A> {
         Self {
             module: self.module,
             entry_point: self.entry_point,
+            constants: self.constants,
         }
     }
 }
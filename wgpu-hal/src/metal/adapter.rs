@@ -21,6 +21,7 @@ impl crate::Adapter<super::Api> for super::Adapter {
         &self,
         features: wgt::Features,
         _limits: &wgt::Limits,
+        _allocator: &wgt::AllocatorConfig,
     ) -> Result<crate::OpenDevice<super::Api>, crate::DeviceError> {
         let queue = self
             .shared
@@ -31,6 +32,7 @@ impl crate::Adapter<super::Api> for super::Adapter {
             device: super::Device {
                 shared: Arc::clone(&self.shared),
                 features,
+                counters: Default::default(),
             },
             queue: super::Queue {
                 raw: Arc::new(Mutex::new(queue)),
@@ -197,6 +199,8 @@ impl crate::Adapter<super::Api> for super::Adapter {
                 }
                 flags
             }
+            // Metal has no multi-planar pixel formats
+            Tf::NV12 | Tf::P010 => return Tfc::empty(),
             Tf::Rgb9e5Ufloat => {
                 if pc.msaa_apple3 {
                     all_caps
@@ -740,6 +744,11 @@ impl super::PrivateCapabilities {
             supports_preserve_invariance: version.at_least((11, 0), (13, 0), os_is_mac),
             // Metal 2.2 on mac, 2.3 on iOS.
             supports_shader_primitive_index: version.at_least((10, 15), (14, 0), os_is_mac),
+            // Memoryless textures live in tile memory, which only Apple GPUs have.
+            supports_memoryless_storage: family_check
+                && device.supports_family(MTLGPUFamily::Apple1),
+            // `recommendedMaxWorkingSetSize` only became available on iOS 16.
+            supports_memory_budget: version.at_least((10, 13), (16, 0), os_is_mac),
             has_unified_memory: if version.at_least((10, 15), (13, 0), os_is_mac) {
                 Some(device.has_unified_memory())
             } else {
@@ -770,7 +779,8 @@ impl super::PrivateCapabilities {
             | F::TEXTURE_FORMAT_16BIT_NORM
             | F::SHADER_F16
             | F::DEPTH32FLOAT_STENCIL8
-            | F::MULTI_DRAW_INDIRECT;
+            | F::MULTI_DRAW_INDIRECT
+            | F::SHADER_PASSTHROUGH;
 
         features.set(F::TEXTURE_COMPRESSION_ASTC, self.format_astc);
         features.set(F::TEXTURE_COMPRESSION_ASTC_HDR, self.format_astc_hdr);
@@ -936,6 +946,7 @@ impl super::PrivateCapabilities {
                 }
             }
             Tf::Rgb9e5Ufloat => RGB9E5Float,
+            Tf::NV12 | Tf::P010 => unreachable!(),
             Tf::Bc1RgbaUnorm => BC1_RGBA,
             Tf::Bc1RgbaUnormSrgb => BC1_RGBA_sRGB,
             Tf::Bc2RgbaUnorm => BC2_RGBA,
@@ -69,7 +69,23 @@ impl super::Device {
     ) -> Result<CompiledShader, crate::PipelineError> {
         let stage_bit = map_naga_stage(naga_stage);
 
-        let module = &stage.module.naga.module;
+        let naga_shader = match stage.module.source {
+            super::ShaderModuleSource::Naga(ref naga_shader) => naga_shader,
+            super::ShaderModuleSource::Msl {
+                ref source,
+                workgroup_size,
+            } => {
+                return self.load_passthrough_shader(
+                    source,
+                    workgroup_size,
+                    stage.entry_point,
+                    stage.constants,
+                    naga_stage,
+                )
+            }
+        };
+        let module = crate::auxil::apply_pipeline_constants(&naga_shader.module, stage.constants);
+        let module = &*module;
         let ep_resources = &layout.per_stage_map[naga_stage];
 
         let bounds_check_policy = if stage.module.runtime_checks {
@@ -114,13 +130,9 @@ impl super::Device {
             },
         };
 
-        let (source, info) = naga::back::msl::write_string(
-            module,
-            &stage.module.naga.info,
-            &options,
-            &pipeline_options,
-        )
-        .map_err(|e| crate::PipelineError::Linkage(stage_bit, format!("MSL: {:?}", e)))?;
+        let (source, info) =
+            naga::back::msl::write_string(module, &naga_shader.info, &options, &pipeline_options)
+                .map_err(|e| crate::PipelineError::Linkage(stage_bit, format!("MSL: {:?}", e)))?;
 
         log::debug!(
             "Naga generated shader for entry point '{}' and stage {:?}\n{}",
@@ -168,7 +180,7 @@ impl super::Device {
         })?;
 
         // collect sizes indices, immutable buffers, and work group memory sizes
-        let ep_info = &stage.module.naga.info.get_entry_point(ep_index);
+        let ep_info = &naga_shader.info.get_entry_point(ep_index);
         let mut wg_memory_sizes = Vec::new();
         let mut sized_bindings = Vec::new();
         let mut immutable_buffer_mask = 0;
@@ -225,6 +237,54 @@ impl super::Device {
         })
     }
 
+    /// Compiles an MSL passthrough shader.
+    ///
+    /// Its resources are expected at the indices assigned by the pipeline layout,
+    /// and it doesn't get the buffer sizes of runtime-sized arrays.
+    fn load_passthrough_shader(
+        &self,
+        source: &str,
+        workgroup_size: [u32; 3],
+        entry_point: &str,
+        constants: &crate::PipelineConstants,
+        naga_stage: naga::ShaderStage,
+    ) -> Result<CompiledShader, crate::PipelineError> {
+        let stage_bit = map_naga_stage(naga_stage);
+        crate::auxil::check_passthrough_constants(constants, naga_stage)?;
+
+        let options = metal::CompileOptions::new();
+        options.set_language_version(self.shared.private_caps.msl_version);
+
+        if self.shared.private_caps.supports_preserve_invariance {
+            options.set_preserve_invariance(true);
+        }
+
+        let library = self
+            .shared
+            .device
+            .lock()
+            .new_library_with_source(source, &options)
+            .map_err(|err| crate::PipelineError::Linkage(stage_bit, format!("Metal: {}", err)))?;
+
+        let function = library.get_function(entry_point, None).map_err(|e| {
+            log::error!("get_function: {:?}", e);
+            crate::PipelineError::EntryPoint(naga_stage)
+        })?;
+
+        Ok(CompiledShader {
+            library,
+            function,
+            wg_size: metal::MTLSize {
+                width: workgroup_size[0] as _,
+                height: workgroup_size[1] as _,
+                depth: workgroup_size[2] as _,
+            },
+            wg_memory_sizes: Vec::new(),
+            sized_bindings: Vec::new(),
+            immutable_buffer_mask: 0,
+        })
+    }
+
     fn set_buffers_mutability(
         buffers: &metal::PipelineBufferDescriptorArrayRef,
         mut immutable_mask: usize,
@@ -254,6 +314,7 @@ impl super::Device {
             array_layers,
             mip_levels,
             copy_size,
+            allocated_size: 0,
         }
     }
 
@@ -261,11 +322,16 @@ impl super::Device {
         super::Device {
             shared: Arc::new(super::AdapterShared::new(raw)),
             features,
+            counters: Default::default(),
         }
     }
 
     pub unsafe fn buffer_from_raw(raw: metal::Buffer, size: wgt::BufferAddress) -> super::Buffer {
-        super::Buffer { raw, size }
+        super::Buffer {
+            raw,
+            size,
+            allocated_size: 0,
+        }
     }
 
     pub fn raw_device(&self) -> &Mutex<metal::Device> {
@@ -299,13 +365,18 @@ impl crate::Device<super::Api> for super::Device {
             if let Some(label) = desc.label {
                 raw.set_label(label);
             }
+            let allocated_size = raw.allocated_size();
+            self.counters.add_buffer(allocated_size);
             Ok(super::Buffer {
                 raw,
                 size: desc.size,
+                allocated_size,
             })
         })
     }
-    unsafe fn destroy_buffer(&self, _buffer: super::Buffer) {}
+    unsafe fn destroy_buffer(&self, buffer: super::Buffer) {
+        self.counters.remove_buffer(buffer.allocated_size);
+    }
 
     unsafe fn map_buffer(
         &self,
@@ -361,8 +432,17 @@ impl crate::Device<super::Api> for super::Device {
             descriptor.set_height(desc.size.height as u64);
             descriptor.set_mipmap_level_count(desc.mip_level_count as u64);
             descriptor.set_pixel_format(mtl_format);
-            descriptor.set_usage(conv::map_texture_usage(desc.format, desc.usage));
-            descriptor.set_storage_mode(metal::MTLStorageMode::Private);
+            if desc.usage.contains(crate::TextureUses::TRANSIENT)
+                && self.shared.private_caps.supports_memoryless_storage
+            {
+                // Memoryless textures only exist in tile memory, so they can't be
+                // used for anything but rendering.
+                descriptor.set_usage(metal::MTLTextureUsage::RenderTarget);
+                descriptor.set_storage_mode(metal::MTLStorageMode::Memoryless);
+            } else {
+                descriptor.set_usage(conv::map_texture_usage(desc.format, desc.usage));
+                descriptor.set_storage_mode(metal::MTLStorageMode::Private);
+            }
 
             let raw = self.shared.device.lock().new_texture(&descriptor);
             if raw.as_ptr().is_null() {
@@ -371,6 +451,8 @@ impl crate::Device<super::Api> for super::Device {
             if let Some(label) = desc.label {
                 raw.set_label(label);
             }
+            let allocated_size = raw.allocated_size();
+            self.counters.add_texture(allocated_size);
 
             Ok(super::Texture {
                 raw,
@@ -379,11 +461,14 @@ impl crate::Device<super::Api> for super::Device {
                 mip_levels: desc.mip_level_count,
                 array_layers: desc.array_layer_count(),
                 copy_size: desc.copy_extent(),
+                allocated_size,
             })
         })
     }
 
-    unsafe fn destroy_texture(&self, _texture: super::Texture) {}
+    unsafe fn destroy_texture(&self, texture: super::Texture) {
+        self.counters.remove_texture(texture.allocated_size);
+    }
 
     unsafe fn create_texture_view(
         &self,
@@ -776,21 +861,41 @@ impl crate::Device<super::Api> for super::Device {
     }
 
     unsafe fn destroy_bind_group(&self, _group: super::BindGroup) {}
+    unsafe fn update_bind_group(
+        &self,
+        _group: &super::BindGroup,
+        _desc: &crate::BindGroupUpdateDescriptor<super::Api>,
+    ) {
+        unreachable!("UPDATE_AFTER_BIND_DESCRIPTORS is not supported by this backend")
+    }
 
     unsafe fn create_shader_module(
         &self,
         desc: &crate::ShaderModuleDescriptor,
         shader: crate::ShaderInput,
     ) -> Result<super::ShaderModule, crate::ShaderError> {
-        match shader {
-            crate::ShaderInput::Naga(naga) => Ok(super::ShaderModule {
-                naga,
-                runtime_checks: desc.runtime_checks,
-            }),
+        let source = match shader {
+            crate::ShaderInput::Naga(naga) => super::ShaderModuleSource::Naga(naga),
+            crate::ShaderInput::Msl {
+                source,
+                workgroup_size,
+            } => super::ShaderModuleSource::Msl {
+                source: source.to_string(),
+                workgroup_size,
+            },
             crate::ShaderInput::SpirV(_) => {
                 panic!("SPIRV_SHADER_PASSTHROUGH is not enabled for this backend")
             }
-        }
+            crate::ShaderInput::Dxil { .. }
+            | crate::ShaderInput::Hlsl(_)
+            | crate::ShaderInput::Glsl { .. } => {
+                panic!("Only MSL shaders can be passed through to Metal")
+            }
+        };
+        Ok(super::ShaderModule {
+            source,
+            runtime_checks: desc.runtime_checks,
+        })
     }
     unsafe fn destroy_shader_module(&self, _module: super::ShaderModule) {}
 
@@ -934,10 +1039,7 @@ impl crate::Device<super::Api> for super::Device {
                     desc.vertex_buffers.len(),
                     desc.layout.total_counters.vs.buffers
                 );
-                return Err(crate::PipelineError::Linkage(
-                    wgt::ShaderStages::VERTEX,
-                    msg,
-                ));
+                return Err(crate::PipelineError::Layout(wgt::ShaderStages::VERTEX, msg));
             }
 
             if !desc.vertex_buffers.is_empty() {
@@ -1178,4 +1280,22 @@ impl crate::Device<super::Api> for super::Device {
         }
         shared_capture_manager.stop_capture();
     }
+
+    unsafe fn memory_report(&self) -> crate::MemoryReport {
+        let heaps = if self.shared.private_caps.supports_memory_budget {
+            let device = self.shared.device.lock();
+            // Metal has a single pool of memory per device, so the working
+            // set size is the best approximation of the heap size.
+            let budget = device.recommended_max_working_set_size();
+            vec![wgt::MemoryHeapReport {
+                size: budget,
+                device_local: true,
+                usage: Some(device.current_allocated_size()),
+                budget: Some(budget),
+            }]
+        } else {
+            Vec::new()
+        };
+        self.counters.report(heaps)
+    }
 }
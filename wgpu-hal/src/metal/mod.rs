@@ -123,6 +123,7 @@ impl crate::Instance<Api> for Instance {
                         device_type: shared.private_caps.device_type(),
                         driver: String::new(),
                         driver_info: String::new(),
+                        device_node: String::new(),
                         backend: wgt::Backend::Metal,
                     },
                     features: shared.private_caps.features(),
@@ -236,6 +237,8 @@ struct PrivateCapabilities {
     supports_depth_clip_control: bool,
     supports_preserve_invariance: bool,
     supports_shader_primitive_index: bool,
+    supports_memoryless_storage: bool,
+    supports_memory_budget: bool,
     has_unified_memory: Option<bool>,
 }
 
@@ -300,6 +303,7 @@ impl Queue {
 pub struct Device {
     shared: Arc<AdapterShared>,
     features: wgt::Features,
+    counters: crate::auxil::AllocationCounters,
 }
 
 pub struct Surface {
@@ -415,6 +419,8 @@ impl crate::Queue<Api> for Queue {
 pub struct Buffer {
     raw: metal::Buffer,
     size: wgt::BufferAddress,
+    /// Bytes counted in the device's memory report, zero if wgpu didn't allocate the buffer.
+    allocated_size: u64,
 }
 
 unsafe impl Send for Buffer {}
@@ -434,6 +440,8 @@ pub struct Texture {
     array_layers: u32,
     mip_levels: u32,
     copy_size: crate::CopyExtent,
+    /// Bytes counted in the device's memory report, zero if wgpu didn't allocate the texture.
+    allocated_size: u64,
 }
 
 unsafe impl Send for Texture {}
@@ -631,9 +639,20 @@ pub struct BindGroup {
 unsafe impl Send for BindGroup {}
 unsafe impl Sync for BindGroup {}
 
+#[allow(clippy::large_enum_variant)]
+#[derive(Debug)]
+enum ShaderModuleSource {
+    Naga(crate::NagaShader),
+    /// MSL passed through as-is.
+    Msl {
+        source: String,
+        workgroup_size: [u32; 3],
+    },
+}
+
 #[derive(Debug)]
 pub struct ShaderModule {
-    naga: crate::NagaShader,
+    source: ShaderModuleSource,
     runtime_checks: bool,
 }
 
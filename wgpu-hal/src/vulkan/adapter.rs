@@ -14,6 +14,7 @@ fn indexing_features() -> wgt::Features {
     wgt::Features::SAMPLED_TEXTURE_AND_STORAGE_BUFFER_ARRAY_NON_UNIFORM_INDEXING
         | wgt::Features::UNIFORM_BUFFER_AND_STORAGE_TEXTURE_ARRAY_NON_UNIFORM_INDEXING
         | wgt::Features::PARTIALLY_BOUND_BINDING_ARRAY
+        | wgt::Features::UPDATE_AFTER_BIND_DESCRIPTORS
 }
 
 /// Aggregate of the `vk::PhysicalDevice*Features` structs used by `gfx`.
@@ -102,8 +103,11 @@ impl PhysicalDeviceFeatures {
                 | wgt::Features::STORAGE_RESOURCE_BINDING_ARRAY
                 | wgt::Features::UNIFORM_BUFFER_AND_STORAGE_TEXTURE_ARRAY_NON_UNIFORM_INDEXING,
         );
-        let needs_partially_bound =
-            requested_features.intersects(wgt::Features::PARTIALLY_BOUND_BINDING_ARRAY);
+        let needs_update_after_bind =
+            requested_features.contains(wgt::Features::UPDATE_AFTER_BIND_DESCRIPTORS);
+        let needs_partially_bound = requested_features
+            .intersects(wgt::Features::PARTIALLY_BOUND_BINDING_ARRAY)
+            || needs_update_after_bind;
 
         Self {
             // vk::PhysicalDeviceFeatures is a struct composed of Bool32's while
@@ -171,7 +175,11 @@ impl PhysicalDeviceFeatures {
                 //.shader_storage_image_array_dynamic_indexing(
                 //.shader_clip_distance(requested_features.contains(wgt::Features::SHADER_CLIP_DISTANCE))
                 //.shader_cull_distance(requested_features.contains(wgt::Features::SHADER_CULL_DISTANCE))
-                .shader_float64(requested_features.contains(wgt::Features::SHADER_F64))
+                .shader_float64(
+                    requested_features.intersects(
+                        wgt::Features::SHADER_F64 | wgt::Features::VERTEX_ATTRIBUTE_64BIT,
+                    ),
+                )
                 //.shader_int64(requested_features.contains(wgt::Features::SHADER_INT64))
                 .shader_int16(requested_features.contains(wgt::Features::SHADER_I16))
                 //.shader_resource_residency(requested_features.contains(wgt::Features::SHADER_RESOURCE_RESIDENCY))
@@ -194,6 +202,12 @@ impl PhysicalDeviceFeatures {
                             needs_storage_buffer_non_uniform,
                         )
                         .descriptor_binding_partially_bound(needs_partially_bound)
+                        .descriptor_binding_sampled_image_update_after_bind(needs_update_after_bind)
+                        .descriptor_binding_storage_image_update_after_bind(needs_update_after_bind)
+                        .descriptor_binding_storage_buffer_update_after_bind(
+                            needs_update_after_bind,
+                        )
+                        .descriptor_binding_update_unused_while_pending(needs_update_after_bind)
                         .build(),
                 )
             } else {
@@ -304,6 +318,7 @@ impl PhysicalDeviceFeatures {
         use wgt::{DownlevelFlags as Df, Features as F};
         let mut features = F::empty()
             | F::SPIRV_SHADER_PASSTHROUGH
+            | F::SHADER_PASSTHROUGH
             | F::MAPPABLE_PRIMARY_BUFFERS
             | F::PUSH_CONSTANTS
             | F::ADDRESS_MODE_CLAMP_TO_BORDER
@@ -315,6 +330,7 @@ impl PhysicalDeviceFeatures {
 
         let mut dl_flags = Df::COMPUTE_SHADERS
             | Df::BASE_VERTEX
+            | Df::NON_NEGATIVE_BASE_VERTEX
             | Df::READ_ONLY_DEPTH_STENCIL
             | Df::NON_POWER_OF_TWO_MIPMAPPED_TEXTURES
             | Df::COMPARISON_SAMPLERS
@@ -456,6 +472,16 @@ impl PhysicalDeviceFeatures {
             }
             if descriptor_indexing.descriptor_binding_partially_bound != 0 && !intel_windows {
                 features |= F::PARTIALLY_BOUND_BINDING_ARRAY;
+
+                // Replaced elements of arrays are left unbound until they are
+                // written, so partial binding is required as well.
+                if descriptor_indexing.descriptor_binding_sampled_image_update_after_bind != 0
+                    && descriptor_indexing.descriptor_binding_storage_image_update_after_bind != 0
+                    && descriptor_indexing.descriptor_binding_storage_buffer_update_after_bind != 0
+                    && descriptor_indexing.descriptor_binding_update_unused_while_pending != 0
+                {
+                    features |= F::UPDATE_AFTER_BIND_DESCRIPTORS;
+                }
             }
         }
 
@@ -521,6 +547,46 @@ impl PhysicalDeviceFeatures {
         );
         features.set(F::RG11B10UFLOAT_RENDERABLE, rg11b10ufloat_renderable);
 
+        // Multi-planar formats and views of their individual planes are core in Vulkan 1.1.
+        let supports_multi_planar_format = |format| {
+            caps.effective_api_version >= vk::API_VERSION_1_1
+                && supports_format(
+                    instance,
+                    phd,
+                    format,
+                    vk::ImageTiling::OPTIMAL,
+                    vk::FormatFeatureFlags::SAMPLED_IMAGE
+                        | vk::FormatFeatureFlags::TRANSFER_SRC
+                        | vk::FormatFeatureFlags::TRANSFER_DST,
+                )
+        };
+        features.set(
+            F::TEXTURE_FORMAT_NV12,
+            supports_multi_planar_format(vk::Format::G8_B8R8_2PLANE_420_UNORM),
+        );
+        features.set(
+            F::TEXTURE_FORMAT_P010,
+            supports_multi_planar_format(vk::Format::G10X6_B10X6R10X6_2PLANE_420_UNORM_3PACK16),
+        );
+
+        // Double-precision attributes are consumed by `f64` shader inputs.
+        let vertex_attribute_64bit = self.core.shader_float64 != 0
+            && [
+                vk::Format::R64_SFLOAT,
+                vk::Format::R64G64_SFLOAT,
+                vk::Format::R64G64B64_SFLOAT,
+                vk::Format::R64G64B64A64_SFLOAT,
+            ]
+            .iter()
+            .all(|&format| {
+                let properties =
+                    unsafe { instance.get_physical_device_format_properties(phd, format) };
+                properties
+                    .buffer_features
+                    .contains(vk::FormatFeatureFlags::VERTEX_BUFFER)
+            });
+        features.set(F::VERTEX_ATTRIBUTE_64BIT, vertex_attribute_64bit);
+
         (features, dl_flags)
     }
 
@@ -672,6 +738,11 @@ impl PhysicalDeviceCapabilities {
             extensions.push(vk::ExtRobustness2Fn::name());
         }
 
+        // Optional `VK_EXT_memory_budget`
+        if self.supports_extension(vk::ExtMemoryBudgetFn::name()) {
+            extensions.push(vk::ExtMemoryBudgetFn::name());
+        }
+
         // Require `VK_KHR_draw_indirect_count` if the associated feature was requested
         // Even though Vulkan 1.2 has promoted the extension to core, we must require the extension to avoid
         // large amounts of spaghetti involved with using PhysicalDeviceVulkan12Features.
@@ -963,6 +1034,7 @@ impl super::Instance {
                     .unwrap_or("?")
                     .to_owned()
             },
+            device_node: String::new(),
             backend: wgt::Backend::Vulkan,
         };
 
@@ -1174,6 +1246,7 @@ impl super::Adapter {
         features: wgt::Features,
         family_index: u32,
         queue_index: u32,
+        allocator: &wgt::AllocatorConfig,
     ) -> Result<crate::OpenDevice<super::Api>, crate::DeviceError> {
         let mem_properties = {
             profiling::scope!("vkGetPhysicalDeviceMemoryProperties");
@@ -1192,6 +1265,24 @@ impl super::Adapter {
                 u
             }
         });
+        let lazily_allocated_memory_types = memory_types
+            .iter()
+            .enumerate()
+            .filter(|&(i, mem)| {
+                valid_ash_memory_types & (1 << i) != 0
+                    && mem
+                        .property_flags
+                        .contains(vk::MemoryPropertyFlags::LAZILY_ALLOCATED)
+            })
+            .map(|(i, mem)| {
+                (
+                    i as u32,
+                    gpu_alloc::MemoryPropertyFlags::from_bits_truncate(
+                        mem.property_flags.as_raw() as u8
+                    ),
+                )
+            })
+            .collect();
 
         let swapchain_fn = khr::Swapchain::new(&self.instance.raw, &raw_device);
 
@@ -1340,7 +1431,17 @@ impl super::Adapter {
 
         let mem_allocator = {
             let limits = self.phd_capabilities.properties.limits;
-            let config = gpu_alloc::Config::i_am_prototyping(); //TODO
+            // Transient blocks grow from `transient_block_size` up to 16 times that.
+            // The default `AllocatorConfig` gives the values of `i_am_prototyping`.
+            let config = gpu_alloc::Config {
+                dedicated_threshold: allocator.dedicated_allocation_threshold,
+                preferred_dedicated_threshold: allocator.long_lived_dedicated_allocation_threshold,
+                transient_dedicated_threshold: allocator.transient_block_size * 16,
+                starting_free_list_chunk: allocator.transient_block_size,
+                final_free_list_chunk: allocator.transient_block_size * 16,
+                initial_buddy_dedicated_size: allocator.block_size,
+                ..gpu_alloc::Config::i_am_prototyping()
+            };
             let max_memory_allocation_size =
                 if let Some(maintenance_3) = self.phd_capabilities.maintenance_3 {
                     maintenance_3.max_memory_allocation_size
@@ -1384,6 +1485,8 @@ impl super::Adapter {
             mem_allocator: Mutex::new(mem_allocator),
             desc_allocator: Mutex::new(desc_allocator),
             valid_ash_memory_types,
+            lazily_allocated_memory_types,
+            counters: Default::default(),
             naga_options,
             #[cfg(feature = "renderdoc")]
             render_doc: Default::default(),
@@ -1398,6 +1501,7 @@ impl crate::Adapter<super::Api> for super::Adapter {
         &self,
         features: wgt::Features,
         _limits: &wgt::Limits,
+        allocator: &wgt::AllocatorConfig,
     ) -> Result<crate::OpenDevice<super::Api>, crate::DeviceError> {
         let enabled_extensions = self.required_device_extensions(features);
         let mut enabled_phd_features = self.physical_device_features(&enabled_extensions, features);
@@ -1436,6 +1540,7 @@ impl crate::Adapter<super::Api> for super::Adapter {
                 features,
                 family_info.queue_family_index,
                 0,
+                allocator,
             )
         }
     }
@@ -1496,7 +1601,10 @@ impl crate::Adapter<super::Api> for super::Adapter {
             features.intersects(vk::FormatFeatureFlags::TRANSFER_DST),
         );
         // Vulkan is very permissive about MSAA
-        flags.set(Tfc::MULTISAMPLE_RESOLVE, !format.is_compressed());
+        flags.set(
+            Tfc::MULTISAMPLE_RESOLVE,
+            !format.is_compressed() && !format.is_multi_planar_format(),
+        );
 
         // get the supported sample counts
         let format_aspect = crate::FormatAspects::from(format);
@@ -1510,6 +1618,8 @@ impl crate::Adapter<super::Api> for super::Adapter {
             limits
                 .framebuffer_stencil_sample_counts
                 .min(limits.sampled_image_stencil_sample_counts)
+        } else if format.is_multi_planar_format() {
+            vk::SampleCountFlags::TYPE_1
         } else {
             match format.sample_type(None).unwrap() {
                 wgt::TextureSampleType::Float { filterable: _ } => limits
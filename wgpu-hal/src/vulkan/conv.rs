@@ -74,6 +74,8 @@ impl super::PrivateCapabilities {
             }
             Tf::Depth16Unorm => F::D16_UNORM,
             Tf::Rgb9e5Ufloat => F::E5B9G9R9_UFLOAT_PACK32,
+            Tf::NV12 => F::G8_B8R8_2PLANE_420_UNORM,
+            Tf::P010 => F::G10X6_B10X6R10X6_2PLANE_420_UNORM_3PACK16,
             Tf::Bc1RgbaUnorm => F::BC1_RGBA_UNORM_BLOCK,
             Tf::Bc1RgbaUnormSrgb => F::BC1_RGBA_SRGB_BLOCK,
             Tf::Bc2RgbaUnorm => F::BC2_UNORM_BLOCK,
@@ -264,6 +266,9 @@ pub fn map_texture_usage(usage: crate::TextureUses) -> vk::ImageUsageFlags {
     if usage.intersects(crate::TextureUses::STORAGE_READ | crate::TextureUses::STORAGE_READ_WRITE) {
         flags |= vk::ImageUsageFlags::STORAGE;
     }
+    if usage.contains(crate::TextureUses::TRANSIENT) {
+        flags |= vk::ImageUsageFlags::TRANSIENT_ATTACHMENT;
+    }
     flags
 }
 
@@ -411,6 +416,14 @@ pub fn map_aspects(aspects: crate::FormatAspects) -> vk::ImageAspectFlags {
     if aspects.contains(crate::FormatAspects::STENCIL) {
         flags |= vk::ImageAspectFlags::STENCIL;
     }
+    // All planes of a non-disjoint multi-planar image are addressed by the color aspect.
+    if aspects.contains(crate::FormatAspects::PLANE_0 | crate::FormatAspects::PLANE_1) {
+        flags |= vk::ImageAspectFlags::COLOR;
+    } else if aspects.contains(crate::FormatAspects::PLANE_0) {
+        flags |= vk::ImageAspectFlags::PLANE_0;
+    } else if aspects.contains(crate::FormatAspects::PLANE_1) {
+        flags |= vk::ImageAspectFlags::PLANE_1;
+    }
     flags
 }
 
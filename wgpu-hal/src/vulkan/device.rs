@@ -533,9 +533,143 @@ impl
 struct CompiledStage {
     create_info: vk::PipelineShaderStageCreateInfo,
     _entry_point: CString,
+    _specialization: Option<Box<Specialization>>,
     temp_raw_module: Option<vk::ShaderModule>,
 }
 
+/// Specialization constants of a stage, kept alive while the pipeline is created.
+struct Specialization {
+    _map_entries: Vec<vk::SpecializationMapEntry>,
+    _data: Vec<u8>,
+    info: vk::SpecializationInfo,
+}
+
+impl Specialization {
+    fn new(
+        spec_constants: &[super::SpecConstant],
+        constants: &crate::PipelineConstants,
+    ) -> Option<Box<Self>> {
+        let mut map_entries = Vec::new();
+        let mut data = Vec::new();
+        for spec in spec_constants {
+            let value = match constants
+                .get(&spec.id.to_string())
+                .or_else(|| constants.get(spec.name.as_ref()?))
+            {
+                Some(&value) => value,
+                None => continue,
+            };
+            let bytes = match (spec.kind, spec.width) {
+                (naga::ScalarKind::Bool, _) => {
+                    vk::Bool32::from(value != 0.0).to_le_bytes().to_vec()
+                }
+                (naga::ScalarKind::Float, 8) => value.to_le_bytes().to_vec(),
+                (naga::ScalarKind::Float, _) => (value as f32).to_le_bytes().to_vec(),
+                (naga::ScalarKind::Sint, _) => {
+                    (value as i64).to_le_bytes()[..spec.width as usize].to_vec()
+                }
+                (naga::ScalarKind::Uint, _) => {
+                    (value as u64).to_le_bytes()[..spec.width as usize].to_vec()
+                }
+            };
+            map_entries.push(vk::SpecializationMapEntry {
+                constant_id: spec.id,
+                offset: data.len() as u32,
+                size: bytes.len(),
+            });
+            data.extend(bytes);
+        }
+        if map_entries.is_empty() {
+            return None;
+        }
+        let info = vk::SpecializationInfo::builder()
+            .map_entries(&map_entries)
+            .data(&data)
+            .build();
+        Some(Box::new(Self {
+            _map_entries: map_entries,
+            _data: data,
+            info,
+        }))
+    }
+}
+
+/// Finds the specialization constants declared by a SPIR-V module.
+fn parse_spec_constants(spv: &[u32]) -> Vec<super::SpecConstant> {
+    const OP_NAME: u32 = 5;
+    const OP_TYPE_BOOL: u32 = 20;
+    const OP_TYPE_INT: u32 = 21;
+    const OP_TYPE_FLOAT: u32 = 22;
+    const OP_SPEC_CONSTANT_TRUE: u32 = 48;
+    const OP_SPEC_CONSTANT_FALSE: u32 = 49;
+    const OP_SPEC_CONSTANT: u32 = 50;
+    const OP_DECORATE: u32 = 71;
+    const DECORATION_SPEC_ID: u32 = 1;
+    const HEADER_WORDS: usize = 5;
+
+    let mut names = BTreeMap::new();
+    let mut spec_ids = BTreeMap::new();
+    let mut types = BTreeMap::new();
+    let mut constants = Vec::new();
+
+    let mut words = spv.get(HEADER_WORDS..).unwrap_or_default();
+    while let Some(&first) = words.first() {
+        let count = (first >> 16) as usize;
+        if count == 0 || count > words.len() {
+            break;
+        }
+        let (instruction, rest) = words.split_at(count);
+        words = rest;
+        match (first & 0xFFFF, &instruction[1..]) {
+            (OP_NAME, &[target, ref name @ ..]) => {
+                let bytes = name
+                    .iter()
+                    .flat_map(|word| word.to_le_bytes())
+                    .take_while(|&byte| byte != 0)
+                    .collect::<Vec<_>>();
+                if let Ok(name) = String::from_utf8(bytes) {
+                    names.insert(target, name);
+                }
+            }
+            (OP_DECORATE, &[target, DECORATION_SPEC_ID, id]) => {
+                spec_ids.insert(target, id);
+            }
+            (OP_TYPE_BOOL, &[result]) => {
+                types.insert(result, (naga::ScalarKind::Bool, 4));
+            }
+            (OP_TYPE_INT, &[result, width, signedness]) => {
+                let kind = if signedness != 0 {
+                    naga::ScalarKind::Sint
+                } else {
+                    naga::ScalarKind::Uint
+                };
+                types.insert(result, (kind, width / 8));
+            }
+            (OP_TYPE_FLOAT, &[result, width]) => {
+                types.insert(result, (naga::ScalarKind::Float, width / 8));
+            }
+            (
+                OP_SPEC_CONSTANT_TRUE | OP_SPEC_CONSTANT_FALSE | OP_SPEC_CONSTANT,
+                &[result_type, result, ..],
+            ) => constants.push((result_type, result)),
+            _ => {}
+        }
+    }
+
+    constants
+        .into_iter()
+        .filter_map(|(result_type, result)| {
+            let &(kind, width) = types.get(&result_type)?;
+            Some(super::SpecConstant {
+                id: *spec_ids.get(&result)?,
+                name: names.remove(&result),
+                kind,
+                width,
+            })
+        })
+        .collect()
+}
+
 impl super::Device {
     pub(super) unsafe fn create_swapchain(
         &self,
@@ -713,8 +847,11 @@ impl super::Device {
         binding_map: &naga::back::spv::BindingMap,
     ) -> Result<CompiledStage, crate::PipelineError> {
         let stage_flags = crate::auxil::map_naga_stage(naga_stage);
-        let vk_module = match *stage.module {
-            super::ShaderModule::Raw(raw) => raw,
+        let (vk_module, specialization) = match *stage.module {
+            super::ShaderModule::Raw {
+                raw,
+                ref spec_constants,
+            } => (raw, Specialization::new(spec_constants, stage.constants)),
             super::ShaderModule::Intermediate {
                 ref naga_shader,
                 runtime_checks,
@@ -743,32 +880,38 @@ impl super::Device {
                 } else {
                     &self.naga_options
                 };
+                let module =
+                    crate::auxil::apply_pipeline_constants(&naga_shader.module, stage.constants);
                 let spv = {
                     profiling::scope!("naga::spv::write_vec");
                     naga::back::spv::write_vec(
-                        &naga_shader.module,
+                        &module,
                         &naga_shader.info,
                         options,
                         Some(&pipeline_options),
                     )
                 }
                 .map_err(|e| crate::PipelineError::Linkage(stage_flags, format!("{e}")))?;
-                self.create_shader_module_impl(&spv)?
+                (self.create_shader_module_impl(&spv)?, None)
             }
         };
 
         let entry_point = CString::new(stage.entry_point).unwrap();
-        let create_info = vk::PipelineShaderStageCreateInfo::builder()
+        let mut create_info = vk::PipelineShaderStageCreateInfo::builder()
             .stage(conv::map_shader_stage(stage_flags))
             .module(vk_module)
-            .name(&entry_point)
-            .build();
+            .name(&entry_point);
+        if let Some(ref specialization) = specialization {
+            create_info = create_info.specialization_info(&specialization.info);
+        }
+        let create_info = create_info.build();
 
         Ok(CompiledStage {
             create_info,
             _entry_point: entry_point,
+            _specialization: specialization,
             temp_raw_module: match *stage.module {
-                super::ShaderModule::Raw(_) => None,
+                super::ShaderModule::Raw { .. } => None,
                 super::ShaderModule::Intermediate { .. } => Some(vk_module),
             },
         })
@@ -853,16 +996,26 @@ impl crate::Device<super::Api> for super::Device {
             desc.memory_flags.contains(crate::MemoryFlags::TRANSIENT),
         );
 
-        let block = unsafe {
-            self.mem_allocator.lock().alloc(
-                &*self.shared,
-                gpu_alloc::Request {
-                    size: req.size,
-                    align_mask: req.alignment - 1,
-                    usage: alloc_usage,
-                    memory_types: req.memory_type_bits & self.valid_ash_memory_types,
-                },
-            )?
+        let request = gpu_alloc::Request {
+            size: req.size,
+            align_mask: req.alignment - 1,
+            usage: alloc_usage,
+            memory_types: req.memory_type_bits & self.valid_ash_memory_types,
+        };
+        let block = {
+            let mut mem_allocator = self.mem_allocator.lock();
+            if desc.memory_flags.contains(crate::MemoryFlags::LONG_LIVED) {
+                // Large long-lived buffers get their own memory, so they don't pin shared blocks.
+                unsafe {
+                    mem_allocator.alloc_with_dedicated(
+                        &*self.shared,
+                        request,
+                        gpu_alloc::Dedicated::Preferred,
+                    )?
+                }
+            } else {
+                unsafe { mem_allocator.alloc(&*self.shared, request)? }
+            }
         };
 
         unsafe {
@@ -878,6 +1031,8 @@ impl crate::Device<super::Api> for super::Device {
             };
         }
 
+        self.counters.add_buffer(block.size());
+
         Ok(super::Buffer {
             raw,
             block: Some(Mutex::new(block)),
@@ -886,11 +1041,9 @@ impl crate::Device<super::Api> for super::Device {
     unsafe fn destroy_buffer(&self, buffer: super::Buffer) {
         unsafe { self.shared.raw.destroy_buffer(buffer.raw, None) };
         if let Some(block) = buffer.block {
-            unsafe {
-                self.mem_allocator
-                    .lock()
-                    .dealloc(&*self.shared, block.into_inner())
-            };
+            let block = block.into_inner();
+            self.counters.remove_buffer(block.size());
+            unsafe { self.mem_allocator.lock().dealloc(&*self.shared, block) };
         }
     }
 
@@ -962,6 +1115,11 @@ impl crate::Device<super::Api> for super::Device {
             raw_flags |= vk::ImageCreateFlags::CUBE_COMPATIBLE;
         }
 
+        // Planes of multi-planar images are viewed with single-plane formats.
+        if desc.format.is_multi_planar_format() {
+            raw_flags |= vk::ImageCreateFlags::MUTABLE_FORMAT;
+        }
+
         let original_format = self.shared.private_caps.map_texture_format(desc.format);
         let mut vk_view_formats = vec![];
         let mut wgt_view_formats = vec![];
@@ -1006,16 +1164,47 @@ impl crate::Device<super::Api> for super::Device {
         let raw = unsafe { self.shared.raw.create_image(&vk_info, None)? };
         let req = unsafe { self.shared.raw.get_image_memory_requirements(raw) };
 
-        let block = unsafe {
-            self.mem_allocator.lock().alloc(
-                &*self.shared,
-                gpu_alloc::Request {
-                    size: req.size,
-                    align_mask: req.alignment - 1,
-                    usage: gpu_alloc::UsageFlags::FAST_DEVICE_ACCESS,
-                    memory_types: req.memory_type_bits & self.valid_ash_memory_types,
-                },
-            )?
+        // `gpu_alloc` never hands out lazily allocated memory, so transient
+        // attachments get a dedicated allocation from such a memory type if
+        // the device has one that fits, and regular memory otherwise.
+        let lazy_memory_type = if desc.usage.contains(crate::TextureUses::TRANSIENT) {
+            self.lazily_allocated_memory_types
+                .iter()
+                .find(|&&(index, _)| req.memory_type_bits & (1 << index) != 0)
+                .copied()
+        } else {
+            None
+        };
+
+        let block = if let Some((memory_type, props)) = lazy_memory_type {
+            let mut mem_allocator = self.mem_allocator.lock();
+            if mem_allocator.remaining_allocations() == 0 {
+                unsafe { self.shared.raw.destroy_image(raw, None) };
+                return Err(crate::DeviceError::OutOfMemory);
+            }
+            let vk_info = vk::MemoryAllocateInfo::builder()
+                .allocation_size(req.size)
+                .memory_type_index(memory_type);
+            let memory = match unsafe { self.shared.raw.allocate_memory(&vk_info, None) } {
+                Ok(memory) => memory,
+                Err(err) => {
+                    unsafe { self.shared.raw.destroy_image(raw, None) };
+                    return Err(err.into());
+                }
+            };
+            unsafe { mem_allocator.import_memory(memory, memory_type, props, 0, req.size) }
+        } else {
+            unsafe {
+                self.mem_allocator.lock().alloc(
+                    &*self.shared,
+                    gpu_alloc::Request {
+                        size: req.size,
+                        align_mask: req.alignment - 1,
+                        usage: gpu_alloc::UsageFlags::FAST_DEVICE_ACCESS,
+                        memory_types: req.memory_type_bits & self.valid_ash_memory_types,
+                    },
+                )?
+            }
         };
 
         unsafe {
@@ -1031,6 +1220,8 @@ impl crate::Device<super::Api> for super::Device {
             };
         }
 
+        self.counters.add_texture(block.size());
+
         Ok(super::Texture {
             raw,
             drop_guard: None,
@@ -1047,6 +1238,7 @@ impl crate::Device<super::Api> for super::Device {
             unsafe { self.shared.raw.destroy_image(texture.raw, None) };
         }
         if let Some(block) = texture.block {
+            self.counters.remove_texture(block.size());
             unsafe { self.mem_allocator.lock().dealloc(&*self.shared, block) };
         }
     }
@@ -1057,11 +1249,19 @@ impl crate::Device<super::Api> for super::Device {
         desc: &crate::TextureViewDescriptor,
     ) -> Result<super::TextureView, crate::DeviceError> {
         let subresource_range = conv::map_subresource_range(&desc.range, desc.format);
+        // Views of a single plane use the format of that plane.
+        let view_format = if desc.format.is_multi_planar_format() {
+            desc.format
+                .aspect_specific_format(desc.range.aspect)
+                .unwrap_or(desc.format)
+        } else {
+            desc.format
+        };
         let mut vk_info = vk::ImageViewCreateInfo::builder()
             .flags(vk::ImageViewCreateFlags::empty())
             .image(texture.raw)
             .view_type(conv::map_view_dimension(desc.dimension))
-            .format(self.shared.private_caps.map_texture_format(desc.format))
+            .format(self.shared.private_caps.map_texture_format(view_format))
             .subresource_range(subresource_range);
         let layers =
             NonZeroU32::new(subresource_range.layer_count).expect("Unexpected zero layer count");
@@ -1271,7 +1471,17 @@ impl crate::Device<super::Api> for super::Device {
             })
             .collect::<Vec<_>>();
 
-        let vk_info = vk::DescriptorSetLayoutCreateInfo::builder().bindings(&vk_bindings);
+        let update_after_bind = desc
+            .flags
+            .contains(crate::BindGroupLayoutFlags::UPDATE_AFTER_BIND);
+
+        let vk_info = vk::DescriptorSetLayoutCreateInfo::builder()
+            .bindings(&vk_bindings)
+            .flags(if update_after_bind {
+                vk::DescriptorSetLayoutCreateFlags::UPDATE_AFTER_BIND_POOL
+            } else {
+                vk::DescriptorSetLayoutCreateFlags::empty()
+            });
 
         let binding_arrays = desc
             .entries
@@ -1287,7 +1497,7 @@ impl crate::Device<super::Api> for super::Device {
             .flags
             .contains(crate::BindGroupLayoutFlags::PARTIALLY_BOUND);
 
-        let vk_info = if partially_bound {
+        let vk_info = if partially_bound || update_after_bind {
             binding_flag_vec = desc
                 .entries
                 .iter()
@@ -1298,6 +1508,16 @@ impl crate::Device<super::Api> for super::Device {
                         flags |= vk::DescriptorBindingFlags::PARTIALLY_BOUND;
                     }
 
+                    // Uniform buffers would need `descriptorBindingUniformBufferUpdateAfterBind`.
+                    if update_after_bind
+                        && entry.count.is_some()
+                        && types[entry.binding as usize].0 != vk::DescriptorType::UNIFORM_BUFFER
+                    {
+                        flags |= vk::DescriptorBindingFlags::UPDATE_AFTER_BIND
+                            | vk::DescriptorBindingFlags::UPDATE_UNUSED_WHILE_PENDING
+                            | vk::DescriptorBindingFlags::PARTIALLY_BOUND;
+                    }
+
                     flags
                 })
                 .collect::<Vec<_>>();
@@ -1328,6 +1548,7 @@ impl crate::Device<super::Api> for super::Device {
             desc_count,
             types: types.into_boxed_slice(),
             binding_arrays,
+            update_after_bind,
         })
     }
     unsafe fn destroy_bind_group_layout(&self, bg_layout: super::BindGroupLayout) {
@@ -1411,7 +1632,11 @@ impl crate::Device<super::Api> for super::Device {
             self.desc_allocator.lock().allocate(
                 &*self.shared,
                 &desc.layout.raw,
-                gpu_descriptor::DescriptorSetLayoutCreateFlags::empty(),
+                if desc.layout.update_after_bind {
+                    gpu_descriptor::DescriptorSetLayoutCreateFlags::UPDATE_AFTER_BIND
+                } else {
+                    gpu_descriptor::DescriptorSetLayoutCreateFlags::empty()
+                },
                 &desc.layout.desc_count,
                 1,
             )?
@@ -1503,6 +1728,65 @@ impl crate::Device<super::Api> for super::Device {
                 .free(&*self.shared, Some(group.set))
         };
     }
+    unsafe fn update_bind_group(
+        &self,
+        group: &super::BindGroup,
+        desc: &crate::BindGroupUpdateDescriptor<super::Api>,
+    ) {
+        let mut writes = Vec::with_capacity(desc.entries.len());
+        let mut buffer_infos = Vec::with_capacity(desc.buffers.len());
+        let mut image_infos = Vec::with_capacity(desc.samplers.len() + desc.textures.len());
+        for entry in desc.entries {
+            let (ty, _) = desc.layout.types[entry.binding as usize];
+            let start = entry.resource_index as usize;
+            let end = start + entry.count as usize;
+            let write = vk::WriteDescriptorSet::builder()
+                .dst_set(*group.set.raw())
+                .dst_binding(entry.binding)
+                .dst_array_element(entry.array_offset)
+                .descriptor_type(ty);
+            let write = match ty {
+                vk::DescriptorType::SAMPLER => {
+                    let index = image_infos.len();
+                    image_infos.extend(desc.samplers[start..end].iter().map(|binding| {
+                        vk::DescriptorImageInfo::builder()
+                            .sampler(binding.raw)
+                            .build()
+                    }));
+                    write.image_info(&image_infos[index..])
+                }
+                vk::DescriptorType::SAMPLED_IMAGE | vk::DescriptorType::STORAGE_IMAGE => {
+                    let index = image_infos.len();
+                    image_infos.extend(desc.textures[start..end].iter().map(|binding| {
+                        let layout = conv::derive_image_layout(
+                            binding.usage,
+                            binding.view.attachment.view_format,
+                        );
+                        vk::DescriptorImageInfo::builder()
+                            .image_view(binding.view.raw)
+                            .image_layout(layout)
+                            .build()
+                    }));
+                    write.image_info(&image_infos[index..])
+                }
+                vk::DescriptorType::UNIFORM_BUFFER | vk::DescriptorType::STORAGE_BUFFER => {
+                    let index = buffer_infos.len();
+                    buffer_infos.extend(desc.buffers[start..end].iter().map(|binding| {
+                        vk::DescriptorBufferInfo::builder()
+                            .buffer(binding.buffer.raw)
+                            .offset(binding.offset)
+                            .range(binding.size.map_or(vk::WHOLE_SIZE, wgt::BufferSize::get))
+                            .build()
+                    }));
+                    write.buffer_info(&buffer_infos[index..])
+                }
+                _ => unreachable!(),
+            };
+            writes.push(write.build());
+        }
+
+        unsafe { self.shared.raw.update_descriptor_sets(&writes, &[]) };
+    }
 
     unsafe fn create_shader_module(
         &self,
@@ -1511,10 +1795,18 @@ impl crate::Device<super::Api> for super::Device {
     ) -> Result<super::ShaderModule, crate::ShaderError> {
         let spv = match shader {
             crate::ShaderInput::Naga(naga_shader) => {
-                if self
-                    .shared
-                    .workarounds
-                    .contains(super::Workarounds::SEPARATE_ENTRY_POINTS)
+                // The SPIR-V backend doesn't emit specialization constants, so
+                // modules with overrides are compiled once their values are known.
+                let has_overrides = naga_shader
+                    .module
+                    .constants
+                    .iter()
+                    .any(|(_, constant)| !matches!(constant.r#override, naga::Override::None));
+                if has_overrides
+                    || self
+                        .shared
+                        .workarounds
+                        .contains(super::Workarounds::SEPARATE_ENTRY_POINTS)
                 {
                     return Ok(super::ShaderModule::Intermediate {
                         naga_shader,
@@ -1542,6 +1834,12 @@ impl crate::Device<super::Api> for super::Device {
                 )
             }
             crate::ShaderInput::SpirV(spv) => Cow::Borrowed(spv),
+            crate::ShaderInput::Msl { .. }
+            | crate::ShaderInput::Dxil { .. }
+            | crate::ShaderInput::Hlsl(_)
+            | crate::ShaderInput::Glsl { .. } => {
+                panic!("Only SPIR-V shaders can be passed through to Vulkan")
+            }
         };
 
         let raw = self.create_shader_module_impl(&spv)?;
@@ -1553,11 +1851,14 @@ impl crate::Device<super::Api> for super::Device {
             };
         }
 
-        Ok(super::ShaderModule::Raw(raw))
+        Ok(super::ShaderModule::Raw {
+            raw,
+            spec_constants: parse_spec_constants(&spv),
+        })
     }
     unsafe fn destroy_shader_module(&self, module: super::ShaderModule) {
         match module {
-            super::ShaderModule::Raw(raw) => {
+            super::ShaderModule::Raw { raw, .. } => {
                 unsafe { self.shared.raw.destroy_shader_module(raw, None) };
             }
             super::ShaderModule::Intermediate { .. } => {}
@@ -2015,6 +2316,55 @@ impl crate::Device<super::Api> for super::Device {
             }
         }
     }
+
+    unsafe fn memory_report(&self) -> crate::MemoryReport {
+        let instance = &self.shared.instance;
+        let phd = self.shared.physical_device;
+        let budget_fns = instance
+            .get_physical_device_properties
+            .as_ref()
+            .filter(|_| {
+                self.shared
+                    .enabled_extensions
+                    .contains(&vk::ExtMemoryBudgetFn::name())
+            });
+        let has_budget = budget_fns.is_some();
+
+        let mut budget_properties = vk::PhysicalDeviceMemoryBudgetPropertiesEXT::default();
+        let properties = match budget_fns {
+            Some(fns) => {
+                let mut properties2 = vk::PhysicalDeviceMemoryProperties2::builder()
+                    .push_next(&mut budget_properties)
+                    .build();
+                unsafe { fns.get_physical_device_memory_properties2(phd, &mut properties2) };
+                properties2.memory_properties
+            }
+            None => unsafe { instance.raw.get_physical_device_memory_properties(phd) },
+        };
+
+        let heaps = properties.memory_heaps[..properties.memory_heap_count as usize]
+            .iter()
+            .enumerate()
+            .map(|(index, heap)| {
+                let (usage, budget) = if has_budget {
+                    (
+                        Some(budget_properties.heap_usage[index]),
+                        Some(budget_properties.heap_budget[index]),
+                    )
+                } else {
+                    (None, None)
+                };
+                wgt::MemoryHeapReport {
+                    size: heap.size,
+                    device_local: heap.flags.contains(vk::MemoryHeapFlags::DEVICE_LOCAL),
+                    usage,
+                    budget,
+                }
+            })
+            .collect();
+
+        self.counters.report(heaps)
+    }
 }
 
 impl From<gpu_alloc::AllocationError> for crate::DeviceError {
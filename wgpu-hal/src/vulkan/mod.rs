@@ -285,6 +285,9 @@ pub struct Device {
     desc_allocator:
         Mutex<gpu_descriptor::DescriptorAllocator<vk::DescriptorPool, vk::DescriptorSet>>,
     valid_ash_memory_types: u32,
+    /// Memory types that can back transient attachments, with their properties.
+    lazily_allocated_memory_types: Vec<(u32, gpu_alloc::MemoryPropertyFlags)>,
+    counters: crate::auxil::AllocationCounters,
     naga_options: naga::back::spv::Options<'static>,
     #[cfg(feature = "renderdoc")]
     render_doc: crate::auxil::renderdoc::RenderDoc,
@@ -350,6 +353,8 @@ pub struct BindGroupLayout {
     types: Box<[(vk::DescriptorType, u32)]>,
     /// Map of binding index to size,
     binding_arrays: Vec<(u32, NonZeroU32)>,
+    /// Whether the sets are allocated from update-after-bind pools.
+    update_after_bind: bool,
 }
 
 #[derive(Debug)]
@@ -415,10 +420,25 @@ pub struct CommandBuffer {
     raw: vk::CommandBuffer,
 }
 
+/// A specialization constant of a SPIR-V module, which pipeline-overridable
+/// constants are mapped to.
+#[derive(Debug)]
+pub struct SpecConstant {
+    id: u32,
+    name: Option<String>,
+    kind: naga::ScalarKind,
+    /// Size of the constant in bytes.
+    width: u32,
+}
+
 #[derive(Debug)]
 #[allow(clippy::large_enum_variant)]
 pub enum ShaderModule {
-    Raw(vk::ShaderModule),
+    Raw {
+        raw: vk::ShaderModule,
+        /// Specialization constants declared by the SPIR-V of the module.
+        spec_constants: Vec<SpecConstant>,
+    },
     Intermediate {
         naga_shader: crate::NagaShader,
         runtime_checks: bool,
@@ -77,6 +77,7 @@ fn print_adapter(output: &mut impl io::Write, report: &AdapterReport, idx: usize
     writeln!(output, "\t      Type: {:?}", info.device_type)?;
     writeln!(output, "\t    Driver: {:?}", info.driver)?;
     writeln!(output, "\tDriverInfo: {:?}", info.driver_info)?;
+    writeln!(output, "\tDeviceNode: {:?}", info.device_node)?;
     writeln!(output, "\t Compliant: {:?}", downlevel.is_webgpu_compliant())?;
 
     //////////////
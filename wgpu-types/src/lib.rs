@@ -265,6 +265,7 @@ bitflags::bitflags! {
         /// Supported Platforms:
         /// - Vulkan
         /// - DX12
+        /// - OpenGL ES (with `GL_EXT_disjoint_timer_query` or `GL_ARB_timer_query`)
         ///
         /// This is currently unimplemented on Metal.
         ///
@@ -440,6 +441,7 @@ bitflags::bitflags! {
         /// Supported Platforms:
         /// - Vulkan
         /// - DX12
+        /// - OpenGL (with `GL_ARB_pipeline_statistics_query`)
         ///
         /// This is a native only feature with a [proposal](https://github.com/gpuweb/gpuweb/blob/0008bd30da2366af88180b511a5d0d0c1dffbc36/proposals/pipeline-statistics-query.md) for the web.
         const PIPELINE_STATISTICS_QUERY = 1 << 32;
@@ -590,6 +592,11 @@ bitflags::bitflags! {
         /// - DX12
         /// - Vulkan
         /// - Metal (Emulated on top of `draw_indirect` and `draw_indexed_indirect`)
+        /// - OpenGL (desktop GL 4.3+, or with `GL_ARB_multi_draw_indirect` or `GL_EXT_multi_draw_indirect`)
+        ///
+        /// Adapters with [`DownlevelFlags::INDIRECT_EXECUTION`] but without native support
+        /// don't expose this feature, but report [`DownlevelFlags::EMULATED_MULTI_DRAW_INDIRECT`]
+        /// and still accept it in a device request.
         ///
         /// This is a native only feature.
         ///
@@ -662,6 +669,10 @@ bitflags::bitflags! {
         /// - DX12
         /// - Vulkan
         /// - Metal
+        /// - OpenGL (desktop GL, or OpenGL ES with `GL_NV_polygon_mode`)
+        ///
+        /// Adapters that can only emulate it don't expose this feature, but report
+        /// [`DownlevelFlags::EMULATED_POLYGON_MODE_LINE`] and still accept it in a device request.
         ///
         /// This is a native only feature.
         const POLYGON_MODE_LINE = 1 << 46;
@@ -672,6 +683,7 @@ bitflags::bitflags! {
         /// Supported platforms:
         /// - DX12
         /// - Vulkan
+        /// - OpenGL (OpenGL ES with `GL_NV_polygon_mode`)
         ///
         /// This is a native only feature.
         const POLYGON_MODE_POINT = 1 << 47;
@@ -723,14 +735,61 @@ bitflags::bitflags! {
         const MULTIVIEW = 1 << 52;
         /// Enables using 64-bit types for vertex attributes.
         ///
-        /// Requires SHADER_FLOAT64.
+        /// Shaders consume these attributes as `f64` inputs, which requires [`Features::SHADER_F64`].
+        /// Other inputs can't read 64-bit attributes.
         ///
-        /// Supported Platforms: N/A
+        /// Supported platforms:
+        /// - Vulkan
         ///
         /// This is a native only feature.
         const VERTEX_ATTRIBUTE_64BIT = 1 << 53;
+        /// Allows for creation of textures of format [`TextureFormat::NV12`]
+        ///
+        /// Supported platforms:
+        /// - DX12
+        /// - Vulkan
+        ///
+        /// This is a native only feature.
+        const TEXTURE_FORMAT_NV12 = 1 << 54;
+        /// Allows for creation of textures of format [`TextureFormat::P010`]
+        ///
+        /// Supported platforms:
+        /// - DX12
+        /// - Vulkan
+        ///
+        /// This is a native only feature.
+        const TEXTURE_FORMAT_P010 = 1 << 55;
+        /// Allows for creation of shader modules from backend-native shaders with
+        /// `wgpu::Device::create_shader_module_passthrough`, bypassing naga.
+        ///
+        /// The shader is not parsed or validated, and its resources must be bound
+        /// to the pipeline layout through the binding map of the descriptor.
+        ///
+        /// Supported platforms:
+        /// - Vulkan (SPIR-V)
+        /// - DX12 (DXIL and HLSL)
+        /// - Metal (MSL)
+        /// - OpenGL ES (GLSL ES)
+        ///
+        /// This is a native only feature.
+        const SHADER_PASSTHROUGH = 1 << 56;
+        /// Allows elements of binding arrays to be replaced after their bind group
+        /// was created, with `wgpu::Queue::update_bind_group`.
+        ///
+        /// With this feature, the layouts which contain a binding array of
+        /// textures, samplers or storage buffers, and no buffer with a dynamic
+        /// offset, are created with update-after-bind bindings. Elements of their
+        /// bind groups may be replaced while the bind group is used by recorded
+        /// command buffers, as long as the replaced elements aren't read by work
+        /// in flight.
+        ///
+        /// Supported platforms:
+        /// - Vulkan 1.2+ (or VK_EXT_descriptor_indexing)'s descriptorBinding*UpdateAfterBind features
+        ///
+        /// This is a native only feature.
+        const UPDATE_AFTER_BIND_DESCRIPTORS = 1 << 57;
 
-        // 54..59 available
+        // 58..59 available
 
         // Shader:
 
@@ -1185,6 +1244,8 @@ bitflags::bitflags! {
         /// DX11 on FL10 level hardware, WebGL2, and GLES 3.0 devices do not support indirect.
         const INDIRECT_EXECUTION = 1 << 2;
         /// Supports non-zero `base_vertex` parameter to indexed draw calls.
+        ///
+        /// Implies [`DownlevelFlags::NON_NEGATIVE_BASE_VERTEX`].
         const BASE_VERTEX = 1 << 3;
         /// Supports reading from a depth/stencil buffer while using as a read-only depth/stencil
         /// attachment.
@@ -1266,6 +1327,42 @@ bitflags::bitflags! {
         ///
         /// The GLES/WebGL and Vulkan on Android doesn't support this.
         const SURFACE_VIEW_FORMATS = 1 << 21;
+
+        /// Supports positive `base_vertex` parameter to indexed draw calls, without
+        /// the full [`DownlevelFlags::BASE_VERTEX`] support for negative values.
+        ///
+        /// GLES 3.0 and WebGL2 emulate this by offsetting the vertex buffers, so the
+        /// vertex index seen by the shader does not include `base_vertex`.
+        const NON_NEGATIVE_BASE_VERTEX = 1 << 22;
+
+        /// [`Features::MULTI_DRAW_INDIRECT`] can be emulated, because the backend has no native
+        /// support for it.
+        ///
+        /// The adapter doesn't expose the feature, but devices can still be requested with it.
+        /// [`RenderPass::multi_draw_indirect`] and [`RenderPass::multi_draw_indexed_indirect`]
+        /// then issue one indirect draw per element of the indirect buffer, so they don't save
+        /// any CPU time over single indirect draws. Reported by adapters with
+        /// [`DownlevelFlags::INDIRECT_EXECUTION`] that lack native support, such as GLES without
+        /// `GL_EXT_multi_draw_indirect` and Vulkan devices without `multiDrawIndirect`.
+        ///
+        /// This is a native only flag and isn't required for WebGPU compliance.
+        ///
+        /// [`RenderPass::multi_draw_indirect`]: ../wgpu/struct.RenderPass.html#method.multi_draw_indirect
+        /// [`RenderPass::multi_draw_indexed_indirect`]: ../wgpu/struct.RenderPass.html#method.multi_draw_indexed_indirect
+        const EMULATED_MULTI_DRAW_INDIRECT = 1 << 23;
+
+        /// [`Features::POLYGON_MODE_LINE`] can be emulated, because the backend has no native
+        /// support for it.
+        ///
+        /// The adapter doesn't expose the feature, but devices can still be requested with it.
+        /// Triangle draws of pipelines with [`PolygonMode::Line`] then read their indices (and
+        /// the arguments of indirect draws) back to the CPU, and draw the triangle edges as a
+        /// line list. Without [`DownlevelFlags::BASE_VERTEX`], indexed indirect draws of such
+        /// pipelines are a validation error, as their base vertex can't be applied. Reported by
+        /// OpenGL ES adapters without `GL_NV_polygon_mode`.
+        ///
+        /// This is a native only flag and isn't required for WebGPU compliance.
+        const EMULATED_POLYGON_MODE_LINE = 1 << 24;
     }
 }
 
@@ -1276,8 +1373,13 @@ impl DownlevelFlags {
     pub const fn compliant() -> Self {
         // We use manual bit twiddling to make this a const fn as `Sub` and `.remove` aren't const
 
-        // WebGPU doesn't actually require aniso
-        Self::from_bits_truncate(Self::all().bits() & !Self::ANISOTROPIC_FILTERING.bits())
+        // WebGPU doesn't actually require aniso, multi-draw-indirect or line polygons
+        Self::from_bits_truncate(
+            Self::all().bits()
+                & !Self::ANISOTROPIC_FILTERING.bits()
+                & !Self::EMULATED_MULTI_DRAW_INDIRECT.bits()
+                & !Self::EMULATED_POLYGON_MODE_LINE.bits(),
+        )
     }
 }
 
@@ -1348,10 +1450,55 @@ pub struct AdapterInfo {
     pub driver: String,
     /// Driver info
     pub driver_info: String,
+    /// DRM device node of the adapter, e.g. `/dev/dri/renderD128`, empty if unknown
+    ///
+    /// Only reported by [`Backend::Gl`] adapters of EGL devices, and matches the node
+    /// that [`InstanceDescriptor::gles_device`] selects.
+    pub device_node: String,
     /// Backend used for device
     pub backend: Backend,
 }
 
+/// Usage and budget of a single memory heap of a device.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct MemoryHeapReport {
+    /// Total size of the heap in bytes.
+    pub size: u64,
+    /// True if the heap is local to the device (i.e. VRAM on a discrete GPU).
+    pub device_local: bool,
+    /// Bytes of the heap currently in use by this process, if the backend can tell.
+    ///
+    /// - Vulkan: requires `VK_EXT_memory_budget`.
+    /// - DX12: `DXGI_QUERY_VIDEO_MEMORY_INFO::CurrentUsage`.
+    /// - Metal: `MTLDevice.currentAllocatedSize`.
+    pub usage: Option<u64>,
+    /// Bytes of the heap this process can use before allocations are likely to
+    /// fail or degrade performance, if the backend can tell.
+    ///
+    /// - Vulkan: requires `VK_EXT_memory_budget`.
+    /// - DX12: `DXGI_QUERY_VIDEO_MEMORY_INFO::Budget`.
+    /// - Metal: `MTLDevice.recommendedMaxWorkingSetSize`.
+    pub budget: Option<u64>,
+}
+
+/// Memory usage of a device, as returned by `Device::memory_report`.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct MemoryReport {
+    /// Per-heap usage and budget. Empty if the backend can't query heaps (GL, WebGPU).
+    pub heaps: Vec<MemoryHeapReport>,
+    /// Bytes allocated for buffers by wgpu on this device. Zero on GL and WebGPU.
+    pub buffer_bytes: u64,
+    /// Bytes allocated for textures by wgpu on this device. Zero on GL and WebGPU.
+    ///
+    /// On DX12 this is an estimate based on the texture size and format.
+    pub texture_bytes: u64,
+    /// Bytes of staging buffers used by `Queue::write_buffer` and `Queue::write_texture`
+    /// that haven't been reclaimed yet. These are also counted in `buffer_bytes`.
+    pub staging_bytes: u64,
+}
+
 /// Describes a [`Device`](../wgpu/struct.Device.html).
 ///
 /// Corresponds to [WebGPU `GPUDeviceDescriptor`](
@@ -1369,6 +1516,9 @@ pub struct DeviceDescriptor<L> {
     /// Limits that the device should support. If any limit is "better" than the limit exposed by
     /// the adapter, creating a device will panic.
     pub limits: Limits,
+    /// Configuration of the allocator that backs buffers and textures created on the device.
+    #[cfg_attr(any(feature = "trace", feature = "replay"), serde(default))]
+    pub allocator: AllocatorConfig,
 }
 
 impl<L> DeviceDescriptor<L> {
@@ -1378,6 +1528,52 @@ impl<L> DeviceDescriptor<L> {
             label: fun(&self.label),
             features: self.features,
             limits: self.limits.clone(),
+            allocator: self.allocator.clone(),
+        }
+    }
+}
+
+/// Configuration of the memory allocator of a device.
+///
+/// Buffers and textures are normally sub-allocated from large blocks of device memory.
+/// This controls how big those blocks are and when a resource gets memory of its own instead.
+///
+/// - Vulkan: all fields are used.
+/// - DX12: only the dedicated allocation thresholds are used, and only when sub-allocation
+///   is enabled with the `windows_rs` feature.
+/// - Metal, GL and WebGPU: ignored, every resource is its own allocation.
+///
+/// The default configuration is the one Vulkan devices used before this was configurable.
+/// DX12 used to place every resource in a shared heap, and now gives resources above the
+/// dedicated allocation thresholds committed resources of their own.
+#[repr(C)]
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "camelCase", default))]
+pub struct AllocatorConfig {
+    /// Resources at least this large get a dedicated allocation instead of being
+    /// sub-allocated. Defaults to 32 MiB.
+    pub dedicated_allocation_threshold: u64,
+    /// Like [`dedicated_allocation_threshold`](#structfield.dedicated_allocation_threshold),
+    /// for buffers created with [`MemoryHints::LongLived`]. Defaults to 1 MiB.
+    pub long_lived_dedicated_allocation_threshold: u64,
+    /// Size of the blocks that resources with [`MemoryHints::Default`] and
+    /// [`MemoryHints::LongLived`] are sub-allocated from. Defaults to 8 MiB.
+    pub block_size: u64,
+    /// Size of the first block that buffers with [`MemoryHints::Transient`] are
+    /// sub-allocated from. These blocks are filled linearly and recycled once all of the
+    /// buffers in them are freed, which suits resources recreated every frame.
+    /// Later blocks grow up to 16 times this size. Defaults to 8 MiB.
+    pub transient_block_size: u64,
+}
+
+impl Default for AllocatorConfig {
+    fn default() -> Self {
+        Self {
+            dedicated_allocation_threshold: 32 << 20,
+            long_lived_dedicated_allocation_threshold: 1 << 20,
+            block_size: 8 << 20,
+            transient_block_size: 8 << 20,
         }
     }
 }
@@ -2062,6 +2258,36 @@ pub enum TextureFormat {
     /// [`Features::DEPTH32FLOAT_STENCIL8`] must be enabled to use this texture format.
     Depth32FloatStencil8,
 
+    /// YUV 4:2:0 chroma subsampled format.
+    ///
+    /// Contains two planes:
+    /// - 0: Single 8 bit channel luminance.
+    /// - 1: Dual 8x2 bit channel chrominance at half width and half height.
+    ///
+    /// Valid view formats for luminance are [`TextureFormat::R8Unorm`].
+    ///
+    /// Valid view formats for chrominance are [`TextureFormat::Rg8Unorm`].
+    ///
+    /// Width and height must be even.
+    ///
+    /// [`Features::TEXTURE_FORMAT_NV12`] must be enabled to use this texture format.
+    NV12,
+    /// YUV 4:2:0 chroma subsampled format with 10 bits per channel, stored in the
+    /// high bits of 16 bit words.
+    ///
+    /// Contains two planes:
+    /// - 0: Single 16 bit channel luminance.
+    /// - 1: Dual 16x2 bit channel chrominance at half width and half height.
+    ///
+    /// Valid view formats for luminance are [`TextureFormat::R16Unorm`].
+    ///
+    /// Valid view formats for chrominance are [`TextureFormat::Rg16Unorm`].
+    ///
+    /// Width and height must be even.
+    ///
+    /// [`Features::TEXTURE_FORMAT_P010`] must be enabled to use this texture format.
+    P010,
+
     // Compressed textures usable with `TEXTURE_COMPRESSION_BC` feature.
     /// 4x4 block compressed texture. 8 bytes per block (4 bit/px). 4 color + alpha pallet. 5 bit R + 6 bit G + 5 bit B + 1 bit alpha.
     /// [0, 63] ([0, 1] for alpha) converted to/from float [0, 1] in shader.
@@ -2291,6 +2517,8 @@ impl<'de> Deserialize<'de> for TextureFormat {
                     "depth24plus" => TextureFormat::Depth24Plus,
                     "depth24plus-stencil8" => TextureFormat::Depth24PlusStencil8,
                     "rgb9e5ufloat" => TextureFormat::Rgb9e5Ufloat,
+                    "nv12" => TextureFormat::NV12,
+                    "p010" => TextureFormat::P010,
                     "bc1-rgba-unorm" => TextureFormat::Bc1RgbaUnorm,
                     "bc1-rgba-unorm-srgb" => TextureFormat::Bc1RgbaUnormSrgb,
                     "bc2-rgba-unorm" => TextureFormat::Bc2RgbaUnorm,
@@ -2417,6 +2645,8 @@ impl Serialize for TextureFormat {
             TextureFormat::Depth24Plus => "depth24plus",
             TextureFormat::Depth24PlusStencil8 => "depth24plus-stencil8",
             TextureFormat::Rgb9e5Ufloat => "rgb9e5ufloat",
+            TextureFormat::NV12 => "nv12",
+            TextureFormat::P010 => "p010",
             TextureFormat::Bc1RgbaUnorm => "bc1-rgba-unorm",
             TextureFormat::Bc1RgbaUnormSrgb => "bc1-rgba-unorm-srgb",
             TextureFormat::Bc2RgbaUnorm => "bc2-rgba-unorm",
@@ -2490,6 +2720,10 @@ impl TextureFormat {
             ) => Some(Self::Stencil8),
             (Self::Depth24PlusStencil8, TextureAspect::DepthOnly) => Some(Self::Depth24Plus),
             (Self::Depth32FloatStencil8, TextureAspect::DepthOnly) => Some(Self::Depth32Float),
+            (Self::NV12, TextureAspect::Plane0) => Some(Self::R8Unorm),
+            (Self::NV12, TextureAspect::Plane1) => Some(Self::Rg8Unorm),
+            (Self::P010, TextureAspect::Plane0) => Some(Self::R16Unorm),
+            (Self::P010, TextureAspect::Plane1) => Some(Self::Rg16Unorm),
             (format, TextureAspect::All) => Some(format),
             _ => None,
         }
@@ -2530,6 +2764,30 @@ impl TextureFormat {
         }
     }
 
+    /// Returns `true` if the format is a multi-planar format
+    pub fn is_multi_planar_format(&self) -> bool {
+        self.planes().is_some()
+    }
+
+    /// Returns the number of planes a multi-planar format has.
+    pub fn planes(&self) -> Option<u32> {
+        match *self {
+            Self::NV12 | Self::P010 => Some(2),
+            _ => None,
+        }
+    }
+
+    /// Returns the subsampling factors `(width, height)` of the plane selected by `aspect`,
+    /// relative to the size of the texture.
+    ///
+    /// Returns `(1, 1)` for formats without chroma subsampling.
+    pub fn subsampling_factors(&self, aspect: Option<TextureAspect>) -> (u32, u32) {
+        match (*self, aspect) {
+            (Self::NV12 | Self::P010, Some(TextureAspect::Plane1)) => (2, 2),
+            _ => (1, 1),
+        }
+    }
+
     /// Returns `true` if the format has a color aspect
     pub fn has_color_aspect(&self) -> bool {
         !self.is_depth_stencil_format()
@@ -2605,7 +2863,9 @@ impl TextureFormat {
             | Self::Depth24Plus
             | Self::Depth24PlusStencil8
             | Self::Depth32Float
-            | Self::Depth32FloatStencil8 => (1, 1),
+            | Self::Depth32FloatStencil8
+            | Self::NV12
+            | Self::P010 => (1, 1),
 
             Self::Bc1RgbaUnorm
             | Self::Bc1RgbaUnormSrgb
@@ -2704,6 +2964,9 @@ impl TextureFormat {
 
             Self::Depth32FloatStencil8 => Features::DEPTH32FLOAT_STENCIL8,
 
+            Self::NV12 => Features::TEXTURE_FORMAT_NV12,
+            Self::P010 => Features::TEXTURE_FORMAT_P010,
+
             Self::R16Unorm
             | Self::R16Snorm
             | Self::Rg16Unorm
@@ -2756,7 +3019,8 @@ impl TextureFormat {
         // Flags
         let basic =
             TextureUsages::COPY_SRC | TextureUsages::COPY_DST | TextureUsages::TEXTURE_BINDING;
-        let attachment = basic | TextureUsages::RENDER_ATTACHMENT;
+        let attachment =
+            basic | TextureUsages::RENDER_ATTACHMENT | TextureUsages::TRANSIENT_ATTACHMENT;
         let storage = basic | TextureUsages::STORAGE_BINDING;
         let all_flags = TextureUsages::all();
         let rg11b10f = if device_features.contains(Features::RG11B10UFLOAT_RENDERABLE) {
@@ -2813,6 +3077,9 @@ impl TextureFormat {
             Self::Depth32Float =>         (        msaa, attachment),
             Self::Depth32FloatStencil8 => (        msaa, attachment),
 
+            Self::NV12 =>                 (        noaa,      basic),
+            Self::P010 =>                 (        noaa,      basic),
+
             Self::R16Unorm =>             (        msaa,    storage),
             Self::R16Snorm =>             (        msaa,    storage),
             Self::Rg16Unorm =>            (        msaa,    storage),
@@ -2917,6 +3184,12 @@ impl TextureFormat {
                 None | Some(TextureAspect::All) => None,
                 Some(TextureAspect::DepthOnly) => Some(depth),
                 Some(TextureAspect::StencilOnly) => Some(uint),
+                Some(TextureAspect::Plane0 | TextureAspect::Plane1) => None,
+            },
+
+            Self::NV12 | Self::P010 => match aspect {
+                Some(TextureAspect::Plane0 | TextureAspect::Plane1) => Some(float),
+                _ => None,
             },
 
             Self::R16Unorm
@@ -2965,6 +3238,7 @@ impl TextureFormat {
     ///  - the format is combined depth-stencil and no `aspect` was provided
     ///  - the format is `Depth24Plus`
     ///  - the format is `Depth24PlusStencil8` and `aspect` is depth.
+    ///  - the format is multi-planar and no plane `aspect` was provided
     pub fn block_size(&self, aspect: Option<TextureAspect>) -> Option<u32> {
         match *self {
             Self::R8Unorm | Self::R8Snorm | Self::R8Uint | Self::R8Sint => Some(1),
@@ -3003,14 +3277,24 @@ impl TextureFormat {
             Self::Depth32Float => Some(4),
             Self::Depth24Plus => None,
             Self::Depth24PlusStencil8 => match aspect {
-                None | Some(TextureAspect::All) => None,
-                Some(TextureAspect::DepthOnly) => None,
                 Some(TextureAspect::StencilOnly) => Some(1),
+                _ => None,
             },
             Self::Depth32FloatStencil8 => match aspect {
-                None | Some(TextureAspect::All) => None,
                 Some(TextureAspect::DepthOnly) => Some(4),
                 Some(TextureAspect::StencilOnly) => Some(1),
+                _ => None,
+            },
+
+            Self::NV12 => match aspect {
+                Some(TextureAspect::Plane0) => Some(1),
+                Some(TextureAspect::Plane1) => Some(2),
+                _ => None,
+            },
+            Self::P010 => match aspect {
+                Some(TextureAspect::Plane0) => Some(2),
+                Some(TextureAspect::Plane1) => Some(4),
+                _ => None,
             },
 
             Self::Bc1RgbaUnorm | Self::Bc1RgbaUnormSrgb | Self::Bc4RUnorm | Self::Bc4RSnorm => {
@@ -3049,7 +3333,7 @@ impl TextureFormat {
 
     /// Returns the number of components this format has taking into account the `aspect`.
     ///
-    /// The `aspect` is only relevant for combined depth-stencil formats.
+    /// The `aspect` is only relevant for combined depth-stencil and multi-planar formats.
     pub fn components_with_aspect(&self, aspect: TextureAspect) -> u8 {
         match *self {
             Self::R8Unorm
@@ -3102,6 +3386,14 @@ impl TextureFormat {
             Self::Depth24PlusStencil8 | Self::Depth32FloatStencil8 => match aspect {
                 TextureAspect::All => 2,
                 TextureAspect::DepthOnly | TextureAspect::StencilOnly => 1,
+                TextureAspect::Plane0 | TextureAspect::Plane1 => 0,
+            },
+
+            Self::NV12 | Self::P010 => match aspect {
+                TextureAspect::All => 3,
+                TextureAspect::Plane0 => 1,
+                TextureAspect::Plane1 => 2,
+                TextureAspect::DepthOnly | TextureAspect::StencilOnly => 0,
             },
 
             Self::Bc4RUnorm | Self::Bc4RSnorm => 1,
@@ -3374,6 +3666,14 @@ fn texture_format_serialize() {
         serde_json::to_string(&TextureFormat::Rgb9e5Ufloat).unwrap(),
         "\"rgb9e5ufloat\"".to_string()
     );
+    assert_eq!(
+        serde_json::to_string(&TextureFormat::NV12).unwrap(),
+        "\"nv12\"".to_string()
+    );
+    assert_eq!(
+        serde_json::to_string(&TextureFormat::P010).unwrap(),
+        "\"p010\"".to_string()
+    );
     assert_eq!(
         serde_json::to_string(&TextureFormat::Bc1RgbaUnorm).unwrap(),
         "\"bc1-rgba-unorm\"".to_string()
@@ -3666,6 +3966,14 @@ fn texture_format_deserialize() {
         serde_json::from_str::<TextureFormat>("\"rgb9e5ufloat\"").unwrap(),
         TextureFormat::Rgb9e5Ufloat
     );
+    assert_eq!(
+        serde_json::from_str::<TextureFormat>("\"nv12\"").unwrap(),
+        TextureFormat::NV12
+    );
+    assert_eq!(
+        serde_json::from_str::<TextureFormat>("\"p010\"").unwrap(),
+        TextureFormat::P010
+    );
     assert_eq!(
         serde_json::from_str::<TextureFormat>("\"bc1-rgba-unorm\"").unwrap(),
         TextureFormat::Bc1RgbaUnorm
@@ -4390,6 +4698,9 @@ pub struct BufferDescriptor<L> {
     /// If this is `true`, [`size`](#structfield.size) must be a multiple of
     /// [`COPY_BUFFER_ALIGNMENT`].
     pub mapped_at_creation: bool,
+    /// How long the buffer is expected to live, so the allocator can pick where to put it.
+    #[cfg_attr(any(feature = "trace", feature = "replay"), serde(default))]
+    pub memory_hints: MemoryHints,
 }
 
 impl<L> BufferDescriptor<L> {
@@ -4400,10 +4711,30 @@ impl<L> BufferDescriptor<L> {
             size: self.size,
             usage: self.usage,
             mapped_at_creation: self.mapped_at_creation,
+            memory_hints: self.memory_hints,
         }
     }
 }
 
+/// Hints about the lifetime of a buffer, used to pick how its memory is allocated.
+///
+/// See [`AllocatorConfig`] for what each backend does with them.
+#[repr(C)]
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum MemoryHints {
+    /// No particular expectations.
+    #[default]
+    Default,
+    /// The buffer is short-lived, e.g. recreated every frame. It is sub-allocated
+    /// linearly from blocks of [`AllocatorConfig::transient_block_size`].
+    Transient,
+    /// The buffer lives for a long time. Buffers larger than
+    /// [`AllocatorConfig::long_lived_dedicated_allocation_threshold`] get a dedicated
+    /// allocation, so they don't keep shared blocks alive.
+    LongLived,
+}
+
 /// Describes a [`CommandEncoder`](../wgpu/struct.CommandEncoder.html).
 ///
 /// Corresponds to [WebGPU `GPUCommandEncoderDescriptor`](
@@ -4566,6 +4897,32 @@ bitflags::bitflags! {
         const STORAGE_BINDING = 1 << 3;
         /// Allows a texture to be an output attachment of a render pass.
         const RENDER_ATTACHMENT = 1 << 4;
+
+        //
+        // ---- Restart Numbering for Native Usages ---
+        //
+        // Native Usages:
+        //
+
+        /// Hints that the texture is a transient render attachment whose contents never
+        /// outlive a single render pass, such as a depth buffer or a multisampled color
+        /// target that is resolved and then thrown away.
+        ///
+        /// Backends may back such textures with lazily allocated or tile-only memory, which
+        /// can avoid committing device memory for them entirely on tile-based GPUs.
+        ///
+        /// The only other usage allowed alongside this one is [`TextureUsages::RENDER_ATTACHMENT`],
+        /// and every render pass using the texture must clear it on load and discard it on store.
+        ///
+        /// Supported platforms:
+        /// - Vulkan (lazily allocated memory, if the device exposes it)
+        /// - Metal (memoryless storage on Apple GPUs)
+        /// - OpenGL (renderbuffers)
+        ///
+        /// On other platforms the texture is allocated like any other render attachment.
+        ///
+        /// This is a native only usage.
+        const TRANSIENT_ATTACHMENT = 1 << 16;
     }
 }
 
@@ -5197,6 +5554,10 @@ pub enum TextureAspect {
     StencilOnly,
     /// Depth.
     DepthOnly,
+    /// Plane 0.
+    Plane0,
+    /// Plane 1.
+    Plane1,
 }
 
 /// How edges should be handled in texture addressing.
@@ -5788,6 +6149,101 @@ pub struct BindGroupLayoutEntry {
     pub count: Option<NonZeroU32>,
 }
 
+/// The interface of a shader module, as seen by the pipelines using it.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct ShaderModuleReflection {
+    /// The entry points of the module.
+    pub entry_points: Vec<EntryPointReflection>,
+}
+
+/// The interface of a single entry point of a shader module.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct EntryPointReflection {
+    /// Name of the entry point.
+    pub name: String,
+    /// The stage this entry point runs in, with a single bit set.
+    pub stage: ShaderStages,
+    /// Size of the workgroups of a compute entry point, `[0; 3]` otherwise.
+    pub workgroup_size: [u32; 3],
+    /// The resources statically used by the entry point.
+    pub bindings: Vec<BindingReflection>,
+    /// The vertex attributes read by a vertex entry point, sorted by location.
+    pub vertex_inputs: Vec<VertexInputReflection>,
+    /// The color outputs of a fragment entry point, sorted by location.
+    pub fragment_outputs: Vec<FragmentOutputReflection>,
+}
+
+/// A resource used by an entry point.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct BindingReflection {
+    /// Name of the resource in the shader, if any.
+    pub name: Option<String>,
+    /// Index of the bind group the resource belongs to.
+    pub group: u32,
+    /// Binding index of the resource in its bind group.
+    pub binding: u32,
+    /// The binding type a layout derived from this entry point would have.
+    ///
+    /// Buffers get their minimum size from the shader, and storage buffers and
+    /// textures are read-only when the entry point only reads them.
+    pub ty: BindingType,
+}
+
+/// A vertex attribute read by a vertex entry point.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct VertexInputReflection {
+    /// Location of the attribute.
+    pub location: ShaderLocation,
+    /// The 32 or 64-bit format matching the type of the attribute in the shader.
+    ///
+    /// Normalized and narrower formats with the same number of components can
+    /// be used as well.
+    pub format: VertexFormat,
+}
+
+/// A color output of a fragment entry point.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct FragmentOutputReflection {
+    /// Location of the output, which is the index of its color target.
+    pub location: ShaderLocation,
+    /// The sample type of the target formats this output can be written to.
+    pub sample_type: TextureSampleType,
+    /// Number of components of the output.
+    ///
+    /// The target format must not have more components than this.
+    pub components: u32,
+}
+
+/// Binds a resource declared by a passthrough shader to an entry of the
+/// pipeline layout.
+///
+/// Corresponds to the binding map of `wgpu::ShaderModuleDescriptorPassthrough`.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "trace", derive(Serialize))]
+#[cfg_attr(feature = "replay", derive(Deserialize))]
+pub struct PassthroughBinding<S> {
+    /// Index of the bind group the resource belongs to.
+    pub group: u32,
+    /// Binding index of the entry in the bind group layout.
+    pub binding: u32,
+    /// Name of the resource in the shader source.
+    ///
+    /// A GLSL combined image sampler is bound by mapping its name to both the
+    /// texture entry and the sampler entry.
+    pub name: S,
+}
+
+impl<S> PassthroughBinding<S> {
+    /// Takes a closure and maps the name of the binding into another.
+    pub fn map_name<K>(&self, fun: impl FnOnce(&S) -> K) -> PassthroughBinding<K> {
+        PassthroughBinding {
+            group: self.group,
+            binding: self.binding,
+            name: fun(&self.name),
+        }
+    }
+}
+
 /// View of a buffer which can be used to copy to/from a texture.
 ///
 /// Corresponds to [WebGPU `GPUImageCopyBuffer`](
@@ -6316,12 +6772,50 @@ pub enum Dx12Compiler {
     },
 }
 
+/// Persistent storage for the program binaries of the GL backend.
+///
+/// Keys are hex SHA-256 digests of the generated GLSL and the driver version. The stored
+/// entries hold both as well, so a binary is only ever given back to the driver that produced
+/// it, for the program it was linked from.
+pub trait GlesProgramBinaryStore: std::fmt::Debug + Send + Sync {
+    /// Returns the binary previously stored under `key`, if any.
+    fn load(&self, key: &str) -> Option<Vec<u8>>;
+    /// Stores `binary` under `key`, replacing any previous binary.
+    fn store(&self, key: &str, binary: &[u8]);
+}
+
+/// Where the GL backend caches the binaries of linked programs.
+///
+/// Programs are always reused across identical pipelines of a device. Persisting their
+/// binaries (through `glGetProgramBinary`) skips the GLSL compilation on later runs.
+#[derive(Clone, Debug, Default)]
+pub enum GlesProgramCache {
+    /// Binaries are only kept in memory.
+    #[default]
+    Memory,
+    /// Binaries are also stored as files in the given directory, which is created if needed.
+    Directory(PathBuf),
+    /// Binaries are also stored through the given store.
+    Store(std::sync::Arc<dyn GlesProgramBinaryStore>),
+}
+
 /// Options for creating an instance.
 pub struct InstanceDescriptor {
     /// Which `Backends` to enable.
     pub backends: Backends,
     /// Which DX12 shader compiler to use.
     pub dx12_shader_compiler: Dx12Compiler,
+    /// DRM device node (e.g. `/dev/dri/renderD129`) of the EGL device the GL backend uses.
+    ///
+    /// When `None`, the GL backend uses the display of the window system, or exposes every
+    /// EGL device as its own adapter on headless systems. The device node of these adapters
+    /// is reported in [`AdapterInfo::device_node`].
+    ///
+    /// `wgpu::util::gles_device_from_env` can be used to set it from the
+    /// `WGPU_GLES_DEVICE` environment variable.
+    pub gles_device: Option<String>,
+    /// Where the GL backend caches the binaries of linked programs.
+    pub gles_program_cache: GlesProgramCache,
 }
 
 impl Default for InstanceDescriptor {
@@ -6329,6 +6823,8 @@ impl Default for InstanceDescriptor {
         Self {
             backends: Backends::all(),
             dx12_shader_compiler: Dx12Compiler::default(),
+            gles_device: None,
+            gles_program_cache: GlesProgramCache::default(),
         }
     }
 }
@@ -1,11 +1,13 @@
 use crate::{
     context::{ObjectId, Unused},
     AdapterInfo, BindGroupDescriptor, BindGroupLayoutDescriptor, BindingResource, BufferBinding,
-    BufferDescriptor, CommandEncoderDescriptor, ComputePassDescriptor, ComputePipelineDescriptor,
+    BufferDescriptor, CommandEncoderDescriptor, CompilationInfo, CompilationMessage,
+    CompilationMessageType, ComputePassDescriptor, ComputePipelineDescriptor,
     DownlevelCapabilities, Features, Label, Limits, LoadOp, MapMode, Operations,
     PipelineLayoutDescriptor, RenderBundleEncoderDescriptor, RenderPipelineDescriptor,
-    SamplerDescriptor, ShaderModuleDescriptor, ShaderModuleDescriptorSpirV, ShaderSource,
-    SurfaceStatus, TextureDescriptor, TextureViewDescriptor, UncapturedErrorHandler,
+    SamplerDescriptor, ShaderModuleDescriptor, ShaderModuleDescriptorPassthrough,
+    ShaderModuleDescriptorSpirV, ShaderSource, SourceLocation, SurfaceStatus, TextureDescriptor,
+    TextureViewDescriptor, UncapturedErrorHandler,
 };
 
 use arrayvec::ArrayVec;
@@ -16,10 +18,12 @@ use std::{
     borrow::Cow::{Borrowed, Owned},
     error::Error,
     fmt,
-    future::{ready, Ready},
+    future::{ready, Future, Ready},
     ops::Range,
+    pin::Pin,
     slice,
     sync::Arc,
+    task::{self, Poll, Waker},
 };
 use wgc::command::{bundle_ffi::*, compute_ffi::*, render_ffi::*};
 use wgc::id::TypedId;
@@ -27,7 +31,10 @@ use wgt::{WasmNotSend, WasmNotSync};
 
 const LABEL: &str = "label";
 
-pub struct Context(wgc::global::Global<wgc::identity::IdentityManagerFactory>);
+type Global = wgc::global::Global<wgc::identity::IdentityManagerFactory>;
+
+// Shared with the worker threads creating pipelines asynchronously.
+pub struct Context(Arc<Global>);
 
 impl Drop for Context {
     fn drop(&mut self) {
@@ -43,13 +50,13 @@ impl fmt::Debug for Context {
 
 impl Context {
     pub unsafe fn from_hal_instance<A: wgc::hal_api::HalApi>(hal_instance: A::Instance) -> Self {
-        Self(unsafe {
+        Self(Arc::new(unsafe {
             wgc::global::Global::from_hal_instance::<A>(
                 "wgpu",
                 wgc::identity::IdentityManagerFactory,
                 hal_instance,
             )
-        })
+        }))
     }
 
     /// # Safety
@@ -60,12 +67,12 @@ impl Context {
     }
 
     pub unsafe fn from_core_instance(core_instance: wgc::instance::Instance) -> Self {
-        Self(unsafe {
+        Self(Arc::new(unsafe {
             wgc::global::Global::from_instance(wgc::identity::IdentityManagerFactory, core_instance)
-        })
+        }))
     }
 
-    pub(crate) fn global(&self) -> &wgc::global::Global<wgc::identity::IdentityManagerFactory> {
+    pub(crate) fn global(&self) -> &Global {
         &self.0
     }
 
@@ -301,30 +308,8 @@ impl Context {
         label: Label,
         string: &'static str,
     ) {
-        let error = wgc::error::ContextError {
-            string,
-            cause: Box::new(cause),
-            label: label.unwrap_or_default().to_string(),
-            label_key,
-        };
-        let mut sink = sink_mutex.lock();
-        let mut source_opt: Option<&(dyn Error + 'static)> = Some(&error);
-        while let Some(source) = source_opt {
-            if let Some(wgc::device::DeviceError::OutOfMemory) =
-                source.downcast_ref::<wgc::device::DeviceError>()
-            {
-                return sink.handle_error(crate::Error::OutOfMemory {
-                    source: Box::new(error),
-                });
-            }
-            source_opt = source.source();
-        }
-
-        // Otherwise, it is a validation error
-        sink.handle_error(crate::Error::Validation {
-            description: self.format_error(&error),
-            source: Box::new(error),
-        });
+        let error = make_error(self.global(), cause, label_key, label, string);
+        sink_mutex.lock().handle_error(error);
     }
 
     fn handle_error_nolabel(
@@ -336,6 +321,25 @@ impl Context {
         self.handle_error(sink_mutex, cause, "", None, string)
     }
 
+    /// Reports the error of a shader module creation, if any, and returns the compilation
+    /// info of the module.
+    fn handle_shader_module_error(
+        &self,
+        sink_mutex: &Mutex<ErrorSinkRaw>,
+        error: Option<wgc::pipeline::CreateShaderModuleError>,
+        label: Label,
+        string: &'static str,
+    ) -> CompilationInfo {
+        match error {
+            Some(cause) => {
+                let compilation_info = CompilationInfo::from(&cause);
+                self.handle_error(sink_mutex, cause, LABEL, label, string);
+                compilation_info
+            }
+            None => CompilationInfo::default(),
+        }
+    }
+
     #[track_caller]
     fn handle_error_fatal(
         &self,
@@ -346,23 +350,59 @@ impl Context {
     }
 
     fn format_error(&self, err: &(impl Error + 'static)) -> String {
-        let global = self.global();
-        let mut err_descs = vec![];
+        format_error(self.global(), err)
+    }
+}
 
-        let mut err_str = String::new();
-        wgc::error::format_pretty_any(&mut err_str, global, err);
-        err_descs.push(err_str);
+fn make_error(
+    global: &Global,
+    cause: impl Error + WasmNotSend + WasmNotSync + 'static,
+    label_key: &'static str,
+    label: Label,
+    string: &'static str,
+) -> crate::Error {
+    let error = wgc::error::ContextError {
+        string,
+        cause: Box::new(cause),
+        label: label.unwrap_or_default().to_string(),
+        label_key,
+    };
+    match wgc::error::error_type(&error) {
+        wgc::error::ErrorType::OutOfMemory => crate::Error::OutOfMemory {
+            source: Box::new(error),
+        },
+        wgc::error::ErrorType::Internal => crate::Error::Internal {
+            description: format_error(global, &error),
+            source: Box::new(error),
+        },
+        wgc::error::ErrorType::Validation => crate::Error::Validation {
+            description: format_error(global, &error),
+            source: Box::new(error),
+        },
+    }
+}
 
-        let mut source_opt = err.source();
-        while let Some(source) = source_opt {
-            let mut source_str = String::new();
-            wgc::error::format_pretty_any(&mut source_str, global, source);
-            err_descs.push(source_str);
-            source_opt = source.source();
-        }
+fn format_error(global: &Global, err: &(impl Error + 'static)) -> String {
+    let mut err_descs = vec![];
+
+    let mut err_str = String::new();
+    wgc::error::format_pretty_any(&mut err_str, global, err);
+    err_descs.push(err_str);
 
-        format!("Validation Error\n\nCaused by:\n{}", err_descs.join(""))
+    let mut source_opt = err.source();
+    while let Some(source) = source_opt {
+        let mut source_str = String::new();
+        wgc::error::format_pretty_any(&mut source_str, global, source);
+        err_descs.push(source_str);
+        source_opt = source.source();
     }
+
+    let kind = match wgc::error::error_type(err) {
+        wgc::error::ErrorType::Validation => "Validation Error",
+        wgc::error::ErrorType::OutOfMemory => "Out of Memory",
+        wgc::error::ErrorType::Internal => "Internal Error",
+    };
+    format!("{kind}\n\nCaused by:\n{}", err_descs.join(""))
 }
 
 fn map_buffer_copy_view(view: crate::ImageCopyBuffer) -> wgc::command::ImageCopyBuffer {
@@ -437,6 +477,114 @@ fn map_pass_channel<V: Copy + Default>(
     }
 }
 
+fn map_render_pipeline_descriptor(
+    desc: &RenderPipelineDescriptor,
+) -> wgc::pipeline::RenderPipelineDescriptor<'static> {
+    use wgc::pipeline as pipe;
+
+    pipe::RenderPipelineDescriptor {
+        label: desc.label.map(|label| Owned(label.to_string())),
+        layout: desc.layout.map(|l| l.id.into()),
+        vertex: pipe::VertexState {
+            stage: pipe::ProgrammableStageDescriptor {
+                module: desc.vertex.module.id.into(),
+                entry_point: Owned(desc.vertex.entry_point.to_string()),
+                constants: Owned(desc.vertex.constants.clone()),
+            },
+            buffers: desc
+                .vertex
+                .buffers
+                .iter()
+                .map(|vbuf| pipe::VertexBufferLayout {
+                    array_stride: vbuf.array_stride,
+                    step_mode: vbuf.step_mode,
+                    attributes: Owned(vbuf.attributes.to_vec()),
+                })
+                .collect(),
+        },
+        primitive: desc.primitive,
+        depth_stencil: desc.depth_stencil.clone(),
+        multisample: desc.multisample,
+        fragment: desc.fragment.as_ref().map(|frag| pipe::FragmentState {
+            stage: pipe::ProgrammableStageDescriptor {
+                module: frag.module.id.into(),
+                entry_point: Owned(frag.entry_point.to_string()),
+                constants: Owned(frag.constants.clone()),
+            },
+            targets: Owned(frag.targets.to_vec()),
+        }),
+        multiview: desc.multiview,
+    }
+}
+
+fn map_compute_pipeline_descriptor(
+    desc: &ComputePipelineDescriptor,
+) -> wgc::pipeline::ComputePipelineDescriptor<'static> {
+    use wgc::pipeline as pipe;
+
+    pipe::ComputePipelineDescriptor {
+        label: desc.label.map(|label| Owned(label.to_string())),
+        layout: desc.layout.map(|l| l.id.into()),
+        stage: pipe::ProgrammableStageDescriptor {
+            module: desc.module.id.into(),
+            entry_point: Owned(desc.entry_point.to_string()),
+            constants: Owned(desc.constants.clone()),
+        },
+    }
+}
+
+/// A future resolved by a `wgpu-core` callback, which may be called from
+/// another thread.
+pub struct CallbackFuture<T> {
+    state: Arc<Mutex<CallbackFutureState<T>>>,
+}
+
+struct CallbackFutureState<T> {
+    result: Option<T>,
+    waker: Option<Waker>,
+}
+
+impl<T> CallbackFuture<T> {
+    fn new() -> Self {
+        Self {
+            state: Arc::new(Mutex::new(CallbackFutureState {
+                result: None,
+                waker: None,
+            })),
+        }
+    }
+
+    /// Returns the function resolving this future with its result.
+    fn resolver(&self) -> impl FnOnce(T) {
+        let state = Arc::clone(&self.state);
+        move |result| {
+            let waker = {
+                let mut state = state.lock();
+                state.result = Some(result);
+                state.waker.take()
+            };
+            if let Some(waker) = waker {
+                waker.wake();
+            }
+        }
+    }
+}
+
+impl<T> Future for CallbackFuture<T> {
+    type Output = T;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut task::Context<'_>) -> Poll<T> {
+        let mut state = self.state.lock();
+        match state.result.take() {
+            Some(result) => Poll::Ready(result),
+            None => {
+                state.waker = Some(cx.waker().clone());
+                Poll::Pending
+            }
+        }
+    }
+}
+
 #[derive(Debug)]
 pub struct Surface {
     id: wgc::id::SurfaceId,
@@ -473,6 +621,11 @@ pub struct Buffer {
     error_sink: ErrorSink,
 }
 
+#[derive(Debug)]
+pub struct ShaderModule {
+    compilation_info: CompilationInfo,
+}
+
 #[derive(Debug)]
 pub struct Texture {
     id: wgc::id::TextureId,
@@ -515,7 +668,7 @@ impl crate::Context for Context {
     type QueueId = wgc::id::QueueId;
     type QueueData = Queue;
     type ShaderModuleId = wgc::id::ShaderModuleId;
-    type ShaderModuleData = ();
+    type ShaderModuleData = ShaderModule;
     type BindGroupLayoutId = wgc::id::BindGroupLayoutId;
     type BindGroupLayoutData = ();
     type BindGroupId = wgc::id::BindGroupId;
@@ -571,13 +724,18 @@ impl crate::Context for Context {
     >;
 
     type PopErrorScopeFuture = Ready<Option<crate::Error>>;
+    type CompilationInfoFuture = Ready<CompilationInfo>;
+    type CreateRenderPipelineFuture =
+        CallbackFuture<Result<(Self::RenderPipelineId, Self::RenderPipelineData), crate::Error>>;
+    type CreateComputePipelineFuture =
+        CallbackFuture<Result<(Self::ComputePipelineId, Self::ComputePipelineData), crate::Error>>;
 
     fn init(instance_desc: wgt::InstanceDescriptor) -> Self {
-        Self(wgc::global::Global::new(
+        Self(Arc::new(wgc::global::Global::new(
             "wgpu",
             wgc::identity::IdentityManagerFactory,
             instance_desc,
-        ))
+        )))
     }
 
     fn instance_create_surface(
@@ -868,6 +1026,18 @@ impl crate::Context for Context {
         }
     }
 
+    fn device_memory_report(
+        &self,
+        device: &Self::DeviceId,
+        _device_data: &Self::DeviceData,
+    ) -> wgt::MemoryReport {
+        let global = &self.0;
+        match wgc::gfx_select!(device => global.device_memory_report(*device)) {
+            Ok(report) => report,
+            Err(err) => self.handle_error_fatal(err, "Device::memory_report"),
+        }
+    }
+
     #[cfg_attr(
         not(any(
             feature = "spirv",
@@ -892,15 +1062,12 @@ impl crate::Context for Context {
         let source = match desc.source {
             #[cfg(feature = "spirv")]
             ShaderSource::SpirV(ref spv) => {
-                // Parse the given shader code and store its representation.
                 let options = naga::front::spv::Options {
                     adjust_coordinate_space: false, // we require NDC_Y_UP feature
                     strict_capabilities: true,
                     block_ctx_dump_prefix: None,
                 };
-                let parser = naga::front::spv::Frontend::new(spv.iter().cloned(), &options);
-                let module = parser.parse().unwrap();
-                wgc::pipeline::ShaderModuleSource::Naga(Owned(module))
+                wgc::pipeline::ShaderModuleSource::SpirV(Borrowed(spv), options)
             }
             #[cfg(feature = "glsl")]
             ShaderSource::Glsl {
@@ -908,15 +1075,11 @@ impl crate::Context for Context {
                 stage,
                 ref defines,
             } => {
-                // Parse the given shader code and store its representation.
                 let options = naga::front::glsl::Options {
                     stage,
                     defines: defines.clone(),
                 };
-                let mut parser = naga::front::glsl::Frontend::default();
-                let module = parser.parse(&options, shader).unwrap();
-
-                wgc::pipeline::ShaderModuleSource::Naga(Owned(module))
+                wgc::pipeline::ShaderModuleSource::Glsl(Borrowed(shader), options)
             }
             #[cfg(feature = "wgsl")]
             ShaderSource::Wgsl(ref code) => wgc::pipeline::ShaderModuleSource::Wgsl(Borrowed(code)),
@@ -927,16 +1090,13 @@ impl crate::Context for Context {
         let (id, error) = wgc::gfx_select!(
             device => global.device_create_shader_module(*device, &descriptor, source, ())
         );
-        if let Some(cause) = error {
-            self.handle_error(
-                &device_data.error_sink,
-                cause,
-                LABEL,
-                desc.label,
-                "Device::create_shader_module",
-            );
-        }
-        (id, ())
+        let compilation_info = self.handle_shader_module_error(
+            &device_data.error_sink,
+            error,
+            desc.label,
+            "Device::create_shader_module",
+        );
+        (id, ShaderModule { compilation_info })
     }
 
     unsafe fn device_create_shader_module_spirv(
@@ -955,16 +1115,47 @@ impl crate::Context for Context {
         let (id, error) = wgc::gfx_select!(
             device => global.device_create_shader_module_spirv(*device, &descriptor, Borrowed(&desc.source), ())
         );
-        if let Some(cause) = error {
-            self.handle_error(
-                &device_data.error_sink,
-                cause,
-                LABEL,
-                desc.label,
-                "Device::create_shader_module_spirv",
-            );
-        }
-        (id, ())
+        let compilation_info = self.handle_shader_module_error(
+            &device_data.error_sink,
+            error,
+            desc.label,
+            "Device::create_shader_module_spirv",
+        );
+        (id, ShaderModule { compilation_info })
+    }
+
+    unsafe fn device_create_shader_module_passthrough(
+        &self,
+        device: &Self::DeviceId,
+        device_data: &Self::DeviceData,
+        desc: &ShaderModuleDescriptorPassthrough,
+    ) -> (Self::ShaderModuleId, Self::ShaderModuleData) {
+        let global = &self.0;
+        let descriptor = wgc::pipeline::ShaderModuleDescriptorPassthrough {
+            label: desc.label.map(Borrowed),
+            stage: desc.stage,
+            workgroup_size: desc.workgroup_size,
+            spirv: desc.spirv.as_deref().map(Borrowed),
+            msl: desc.msl.as_deref().map(Borrowed),
+            dxil: desc.dxil.as_deref().map(Borrowed),
+            hlsl: desc.hlsl.as_deref().map(Borrowed),
+            glsl: desc.glsl.as_deref().map(Borrowed),
+            bindings: desc
+                .bindings
+                .iter()
+                .map(|binding| binding.map_name(|&name| Borrowed(name)))
+                .collect(),
+        };
+        let (id, error) = wgc::gfx_select!(
+            device => global.device_create_shader_module_passthrough(*device, &descriptor, ())
+        );
+        let compilation_info = self.handle_shader_module_error(
+            &device_data.error_sink,
+            error,
+            desc.label,
+            "Device::create_shader_module_passthrough",
+        );
+        (id, ShaderModule { compilation_info })
     }
 
     fn device_create_bind_group_layout(
@@ -1183,6 +1374,7 @@ impl crate::Context for Context {
                 stage: pipe::ProgrammableStageDescriptor {
                     module: desc.vertex.module.id.into(),
                     entry_point: Borrowed(desc.vertex.entry_point),
+                    constants: Borrowed(desc.vertex.constants),
                 },
                 buffers: Borrowed(&vertex_buffers),
             },
@@ -1193,6 +1385,7 @@ impl crate::Context for Context {
                 stage: pipe::ProgrammableStageDescriptor {
                     module: frag.module.id.into(),
                     entry_point: Borrowed(frag.entry_point),
+                    constants: Borrowed(frag.constants),
                 },
                 targets: Borrowed(frag.targets),
             }),
@@ -1221,6 +1414,68 @@ impl crate::Context for Context {
         }
         (id, ())
     }
+    fn device_create_render_pipeline_async(
+        &self,
+        device: &Self::DeviceId,
+        _device_data: &Self::DeviceData,
+        desc: &RenderPipelineDescriptor,
+    ) -> Self::CreateRenderPipelineFuture {
+        let implicit_pipeline_ids = match desc.layout {
+            Some(_) => None,
+            None => Some(wgc::device::ImplicitPipelineIds {
+                root_id: (),
+                group_ids: &[(); wgc::MAX_BIND_GROUPS],
+            }),
+        };
+        let descriptor = map_render_pipeline_descriptor(desc);
+
+        let future = CallbackFuture::new();
+        let resolve = future.resolver();
+        let label = desc.label.map(str::to_string);
+        let global = Arc::clone(&self.0);
+        let callback = Box::new(
+            move |id: wgc::id::RenderPipelineId,
+                  error: Option<wgc::pipeline::CreateRenderPipelineError>| {
+                let result = match error {
+                    Some(cause) => {
+                        if let wgc::pipeline::CreateRenderPipelineError::Internal {
+                            stage,
+                            ref error,
+                        } = cause
+                        {
+                            log::error!(
+                                "Shader translation error for stage {:?}: {}",
+                                stage,
+                                error
+                            );
+                            log::error!("Please report it to https://github.com/gfx-rs/naga");
+                        }
+                        let error = make_error(
+                            &global,
+                            cause,
+                            LABEL,
+                            label.as_deref(),
+                            "Device::create_render_pipeline_async",
+                        );
+                        wgc::gfx_select!(id => global.render_pipeline_drop(id));
+                        Err(error)
+                    }
+                    None => Ok((id, ())),
+                };
+                resolve(result);
+            },
+        );
+
+        let global = &self.0;
+        wgc::gfx_select!(device => global.device_create_render_pipeline_async(
+            *device,
+            descriptor,
+            (),
+            implicit_pipeline_ids,
+            callback
+        ));
+        future
+    }
     fn device_create_compute_pipeline(
         &self,
         device: &Self::DeviceId,
@@ -1242,6 +1497,7 @@ impl crate::Context for Context {
             stage: pipe::ProgrammableStageDescriptor {
                 module: desc.module.id.into(),
                 entry_point: Borrowed(desc.entry_point),
+                constants: Borrowed(desc.constants),
             },
         };
 
@@ -1271,6 +1527,66 @@ impl crate::Context for Context {
         }
         (id, ())
     }
+    fn device_create_compute_pipeline_async(
+        &self,
+        device: &Self::DeviceId,
+        _device_data: &Self::DeviceData,
+        desc: &ComputePipelineDescriptor,
+    ) -> Self::CreateComputePipelineFuture {
+        let implicit_pipeline_ids = match desc.layout {
+            Some(_) => None,
+            None => Some(wgc::device::ImplicitPipelineIds {
+                root_id: (),
+                group_ids: &[(); wgc::MAX_BIND_GROUPS],
+            }),
+        };
+        let descriptor = map_compute_pipeline_descriptor(desc);
+
+        let future = CallbackFuture::new();
+        let resolve = future.resolver();
+        let label = desc.label.map(str::to_string);
+        let global = Arc::clone(&self.0);
+        let callback = Box::new(
+            move |id: wgc::id::ComputePipelineId,
+                  error: Option<wgc::pipeline::CreateComputePipelineError>| {
+                let result = match error {
+                    Some(cause) => {
+                        if let wgc::pipeline::CreateComputePipelineError::Internal(ref error) =
+                            cause
+                        {
+                            log::warn!(
+                                "Shader translation error for stage {:?}: {}",
+                                wgt::ShaderStages::COMPUTE,
+                                error
+                            );
+                            log::warn!("Please report it to https://github.com/gfx-rs/naga");
+                        }
+                        let error = make_error(
+                            &global,
+                            cause,
+                            LABEL,
+                            label.as_deref(),
+                            "Device::create_compute_pipeline_async",
+                        );
+                        wgc::gfx_select!(id => global.compute_pipeline_drop(id));
+                        Err(error)
+                    }
+                    None => Ok((id, ())),
+                };
+                resolve(result);
+            },
+        );
+
+        let global = &self.0;
+        wgc::gfx_select!(device => global.device_create_compute_pipeline_async(
+            *device,
+            descriptor,
+            (),
+            implicit_pipeline_ids,
+            callback
+        ));
+        future
+    }
     fn device_create_buffer(
         &self,
         device: &Self::DeviceId,
@@ -1670,6 +1986,21 @@ impl crate::Context for Context {
         let global = &self.0;
         wgc::gfx_select!(*shader_module => global.shader_module_drop(*shader_module))
     }
+    fn shader_get_compilation_info(
+        &self,
+        _shader_module: &Self::ShaderModuleId,
+        shader_module_data: &Self::ShaderModuleData,
+    ) -> Self::CompilationInfoFuture {
+        ready(shader_module_data.compilation_info.clone())
+    }
+    fn shader_module_reflect(
+        &self,
+        shader_module: &Self::ShaderModuleId,
+        _shader_module_data: &Self::ShaderModuleData,
+    ) -> Option<wgt::ShaderModuleReflection> {
+        let global = &self.0;
+        wgc::gfx_select!(shader_module => global.shader_module_reflect(*shader_module))
+    }
     fn command_encoder_drop(
         &self,
         command_encoder: &Self::CommandEncoderId,
@@ -1745,6 +2076,25 @@ impl crate::Context for Context {
         (id, ())
     }
 
+    fn bind_group_layout_entries(
+        &self,
+        bind_group_layout: &Self::BindGroupLayoutId,
+        _bind_group_layout_data: &Self::BindGroupLayoutData,
+    ) -> Option<Vec<wgt::BindGroupLayoutEntry>> {
+        let global = &self.0;
+        wgc::gfx_select!(*bind_group_layout => global.bind_group_layout_entries(*bind_group_layout))
+    }
+
+    fn pipeline_layout_get_bind_group_layouts(
+        &self,
+        pipeline_layout: &Self::PipelineLayoutId,
+        _pipeline_layout_data: &Self::PipelineLayoutData,
+    ) -> Option<Vec<(Self::BindGroupLayoutId, Self::BindGroupLayoutData)>> {
+        let global = &self.0;
+        let ids = wgc::gfx_select!(*pipeline_layout => global.pipeline_layout_get_bind_group_layouts(*pipeline_layout))?;
+        Some(ids.into_iter().map(|id| (id, ())).collect())
+    }
+
     fn command_encoder_copy_buffer_to_buffer(
         &self,
         encoder: &Self::CommandEncoderId,
@@ -2272,6 +2622,67 @@ impl crate::Context for Context {
         }
     }
 
+    fn queue_update_bind_group(
+        &self,
+        queue: &Self::QueueId,
+        queue_data: &Self::QueueData,
+        bind_group: &Self::BindGroupId,
+        _bind_group_data: &Self::BindGroupData,
+        entries: &[crate::BindGroupUpdateEntry],
+    ) {
+        use wgc::binding_model as bm;
+
+        let entries = entries
+            .iter()
+            .map(|entry| bm::BindGroupUpdateEntry {
+                binding: entry.binding,
+                array_offset: entry.array_offset,
+                resource: match entry.resource {
+                    BindingResource::Buffer(BufferBinding {
+                        buffer,
+                        offset,
+                        size,
+                    }) => bm::BindingResource::Buffer(bm::BufferBinding {
+                        buffer_id: buffer.id.into(),
+                        offset,
+                        size,
+                    }),
+                    BindingResource::BufferArray(array) => bm::BindingResource::BufferArray(
+                        array
+                            .iter()
+                            .map(|binding| bm::BufferBinding {
+                                buffer_id: binding.buffer.id.into(),
+                                offset: binding.offset,
+                                size: binding.size,
+                            })
+                            .collect(),
+                    ),
+                    BindingResource::Sampler(sampler) => {
+                        bm::BindingResource::Sampler(sampler.id.into())
+                    }
+                    BindingResource::SamplerArray(array) => bm::BindingResource::SamplerArray(
+                        array.iter().map(|sampler| sampler.id.into()).collect(),
+                    ),
+                    BindingResource::TextureView(texture_view) => {
+                        bm::BindingResource::TextureView(texture_view.id.into())
+                    }
+                    BindingResource::TextureViewArray(array) => {
+                        bm::BindingResource::TextureViewArray(
+                            array.iter().map(|view| view.id.into()).collect(),
+                        )
+                    }
+                },
+            })
+            .collect::<Vec<_>>();
+
+        let global = &self.0;
+        if let Err(cause) = wgc::gfx_select!(
+            *queue => global.queue_update_bind_group(*queue, *bind_group, &entries)
+        ) {
+            self.handle_error_nolabel(&queue_data.error_sink, cause, "Queue::update_bind_group");
+        }
+    }
+
     fn queue_submit<I: Iterator<Item = (Self::CommandBufferId, Self::CommandBufferData)>>(
         &self,
         queue: &Self::QueueId,
@@ -3026,6 +3437,7 @@ impl ErrorSinkRaw {
         let filter = match err {
             crate::Error::OutOfMemory { .. } => crate::ErrorFilter::OutOfMemory,
             crate::Error::Validation { .. } => crate::ErrorFilter::Validation,
+            crate::Error::Internal { .. } => crate::ErrorFilter::Internal,
         };
         match self
             .scopes
@@ -3051,6 +3463,26 @@ impl fmt::Debug for ErrorSinkRaw {
     }
 }
 
+impl From<&wgc::pipeline::CreateShaderModuleError> for CompilationInfo {
+    fn from(value: &wgc::pipeline::CreateShaderModuleError) -> Self {
+        let messages = value
+            .diagnostics()
+            .into_iter()
+            .map(|(message, location)| CompilationMessage {
+                message,
+                message_type: CompilationMessageType::Error,
+                location: location.map(|location| SourceLocation {
+                    line_number: location.line_number,
+                    line_position: location.line_position,
+                    offset: location.offset,
+                    length: location.length,
+                }),
+            })
+            .collect();
+        CompilationInfo { messages }
+    }
+}
+
 fn default_error_handler(err: crate::Error) {
     log::error!("Handling wgpu errors as fatal by default");
     panic!("wgpu error: {err}\n");
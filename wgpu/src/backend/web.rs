@@ -120,6 +120,11 @@ impl crate::Error {
             }
         } else if js_error.has_type::<web_sys::GpuOutOfMemoryError>() {
             crate::Error::OutOfMemory { source }
+        } else if let Some(js_error) = js_error.dyn_ref::<web_sys::GpuInternalError>() {
+            crate::Error::Internal {
+                source,
+                description: js_error.message(),
+            }
         } else {
             panic!("Unexpected error");
         }
@@ -497,6 +502,21 @@ fn map_vertex_step_mode(mode: wgt::VertexStepMode) -> web_sys::GpuVertexStepMode
     }
 }
 
+/// Sets the `constants` member of a programmable stage, which `web_sys` doesn't expose yet.
+fn map_pipeline_constants(
+    stage: &js_sys::Object,
+    constants: &std::collections::HashMap<String, f64>,
+) {
+    if constants.is_empty() {
+        return;
+    }
+    let mapped = js_sys::Object::new();
+    for (key, &value) in constants {
+        js_sys::Reflect::set(&mapped, &JsValue::from_str(key), &JsValue::from_f64(value)).unwrap();
+    }
+    js_sys::Reflect::set(stage, &JsValue::from_str("constants"), &mapped).unwrap();
+}
+
 fn map_extent_3d(extent: wgt::Extent3d) -> web_sys::GpuExtent3dDict {
     let mut mapped = web_sys::GpuExtent3dDict::new(extent.width);
     mapped.height(extent.height);
@@ -554,27 +574,44 @@ fn map_buffer_copy_view(view: crate::ImageCopyBuffer) -> web_sys::GpuImageCopyBu
     mapped
 }
 
-fn map_texture_copy_view(view: crate::ImageCopyTexture) -> web_sys::GpuImageCopyTexture {
+/// Returns `None` after reporting a validation error if the view uses a plane aspect.
+fn map_texture_copy_view(
+    view: crate::ImageCopyTexture,
+    operation: &str,
+) -> Option<web_sys::GpuImageCopyTexture> {
     let texture: &<Context as crate::Context>::TextureData =
         downcast_ref(view.texture.data.as_ref());
+    if map_texture_aspect(view.aspect).is_none() {
+        plane_aspect_error(&texture.0, view.aspect, operation);
+        return None;
+    }
     let mut mapped = web_sys::GpuImageCopyTexture::new(&texture.0);
     mapped.mip_level(view.mip_level);
     mapped.origin(&map_origin_3d(view.origin));
-    mapped
+    Some(mapped)
 }
 
+/// Returns `None` after reporting a validation error if the view uses a plane aspect.
 fn map_tagged_texture_copy_view(
     view: crate::ImageCopyTextureTagged,
-) -> web_sys::GpuImageCopyTextureTagged {
+    operation: &str,
+) -> Option<web_sys::GpuImageCopyTextureTagged> {
     let texture: &<Context as crate::Context>::TextureData =
         downcast_ref(view.texture.data.as_ref());
+    let aspect = match map_texture_aspect(view.aspect) {
+        Some(aspect) => aspect,
+        None => {
+            plane_aspect_error(&texture.0, view.aspect, operation);
+            return None;
+        }
+    };
     let mut mapped = web_sys::GpuImageCopyTextureTagged::new(&texture.0);
     mapped.mip_level(view.mip_level);
     mapped.origin(&map_origin_3d(view.origin));
-    mapped.aspect(map_texture_aspect(view.aspect));
+    mapped.aspect(aspect);
     // mapped.color_space(map_color_space(view.color_space));
     mapped.premultiplied_alpha(view.premultiplied_alpha);
-    mapped
+    Some(mapped)
 }
 
 fn map_external_texture_copy_view(
@@ -586,14 +623,49 @@ fn map_external_texture_copy_view(
     mapped
 }
 
-fn map_texture_aspect(aspect: wgt::TextureAspect) -> web_sys::GpuTextureAspect {
+/// Returns `None` for the plane aspects, which WebGPU doesn't have.
+///
+/// Multi-planar formats aren't supported on the web, so a plane aspect never refers to an
+/// aspect of the texture. Callers report it with [`plane_aspect_error`] instead of using it.
+fn map_texture_aspect(aspect: wgt::TextureAspect) -> Option<web_sys::GpuTextureAspect> {
     match aspect {
-        wgt::TextureAspect::All => web_sys::GpuTextureAspect::All,
-        wgt::TextureAspect::StencilOnly => web_sys::GpuTextureAspect::StencilOnly,
-        wgt::TextureAspect::DepthOnly => web_sys::GpuTextureAspect::DepthOnly,
+        wgt::TextureAspect::All => Some(web_sys::GpuTextureAspect::All),
+        wgt::TextureAspect::StencilOnly => Some(web_sys::GpuTextureAspect::StencilOnly),
+        wgt::TextureAspect::DepthOnly => Some(web_sys::GpuTextureAspect::DepthOnly),
+        wgt::TextureAspect::Plane0 | wgt::TextureAspect::Plane1 => None,
     }
 }
 
+/// Makes the browser report a validation error naming the plane `aspect` used by
+/// `operation`, on the device of `texture`.
+///
+/// WebGPU can't be asked to raise an error, so this creates a separate view of `texture`
+/// without any mip level, which is invalid, labeled with the description of the error. The
+/// error then goes through the error scopes and the uncaptured error handler of the device
+/// like the errors of the browser. The returned view is invalid.
+fn plane_aspect_error(
+    texture: &web_sys::GpuTexture,
+    aspect: wgt::TextureAspect,
+    operation: &str,
+) -> web_sys::GpuTextureView {
+    let mut mapped = web_sys::GpuTextureViewDescriptor::new();
+    mapped.mip_level_count(0);
+    mapped.label(&format!(
+        "{operation} uses the {aspect:?} aspect, which isn't supported on WebGPU"
+    ));
+    texture.create_view_with_descriptor(&mapped)
+}
+
+/// Makes the browser report a validation error with the given description on `device`.
+///
+/// Like [`plane_aspect_error`], this creates an invalid object, a buffer without any usage,
+/// labeled with the description of the error.
+fn device_validation_error(device: &web_sys::GpuDevice, description: &str) {
+    let mut mapped = web_sys::GpuBufferDescriptor::new(0.0, 0);
+    mapped.label(description);
+    device.create_buffer(&mapped);
+}
+
 fn map_filter_mode(mode: wgt::FilterMode) -> web_sys::GpuFilterMode {
     match mode {
         wgt::FilterMode::Nearest => web_sys::GpuFilterMode::Nearest,
@@ -707,15 +779,16 @@ fn future_request_device(
     (
         Identified<web_sys::GpuDevice>,
         Sendable<web_sys::GpuDevice>,
-        Identified<web_sys::GpuQueue>,
-        Sendable<web_sys::GpuQueue>,
+        Identified<(web_sys::GpuQueue, web_sys::GpuDevice)>,
+        Sendable<(web_sys::GpuQueue, web_sys::GpuDevice)>,
     ),
     crate::RequestDeviceError,
 > {
     result
         .map(|js_value| {
             let (device_id, device_data) = create_identified(web_sys::GpuDevice::from(js_value));
-            let (queue_id, queue_data) = create_identified(device_data.0.queue());
+            let (queue_id, queue_data) =
+                create_identified((device_data.0.queue(), device_data.0.clone()));
 
             (device_id, device_data, queue_id, queue_data)
         })
@@ -732,6 +805,177 @@ fn future_pop_error_scope(result: JsFutureResult) -> Option<crate::Error> {
     }
 }
 
+fn future_compilation_info(result: JsFutureResult) -> crate::CompilationInfo {
+    let info: web_sys::GpuCompilationInfo = match result {
+        Ok(js_value) => js_value.unchecked_into(),
+        Err(_) => return crate::CompilationInfo::default(),
+    };
+    let messages = info
+        .messages()
+        .iter()
+        .map(|js_message| {
+            let message: web_sys::GpuCompilationMessage = js_message.unchecked_into();
+            let message_type = match message.type_() {
+                web_sys::GpuCompilationMessageType::Error => crate::CompilationMessageType::Error,
+                web_sys::GpuCompilationMessageType::Warning => {
+                    crate::CompilationMessageType::Warning
+                }
+                _ => crate::CompilationMessageType::Info,
+            };
+            // Messages without a position in the source report a line number of 0.
+            let location = (message.line_num() > 0.0).then(|| crate::SourceLocation {
+                line_number: message.line_num() as u32,
+                line_position: message.line_pos() as u32,
+                offset: message.offset() as u32,
+                length: message.length() as u32,
+            });
+            crate::CompilationMessage {
+                message: message.message(),
+                message_type,
+                location,
+            }
+        })
+        .collect();
+    crate::CompilationInfo { messages }
+}
+
+fn future_create_pipeline<T: JsCast>(
+    result: JsFutureResult,
+) -> Result<(Identified<T>, Sendable<T>), crate::Error> {
+    match result {
+        Ok(js_value) => Ok(create_identified(js_value.unchecked_into())),
+        // The promise is rejected with a `GPUPipelineError`.
+        Err(js_error) => Err(crate::Error::Validation {
+            source: Box::<dyn std::error::Error + Send + Sync>::from("<WebGPU Error>"),
+            description: js_sys::Reflect::get(&js_error, &JsValue::from_str("message"))
+                .ok()
+                .and_then(|message| message.as_string())
+                .unwrap_or_default(),
+        }),
+    }
+}
+
+fn map_render_pipeline_descriptor(
+    desc: &crate::RenderPipelineDescriptor,
+) -> web_sys::GpuRenderPipelineDescriptor {
+    let module: &<Context as crate::Context>::ShaderModuleData =
+        downcast_ref(desc.vertex.module.data.as_ref());
+    let mut mapped_vertex_state = web_sys::GpuVertexState::new(desc.vertex.entry_point, &module.0);
+    map_pipeline_constants(&mapped_vertex_state, desc.vertex.constants);
+
+    let buffers = desc
+        .vertex
+        .buffers
+        .iter()
+        .map(|vbuf| {
+            let mapped_attributes = vbuf
+                .attributes
+                .iter()
+                .map(|attr| {
+                    web_sys::GpuVertexAttribute::new(
+                        map_vertex_format(attr.format),
+                        attr.offset as f64,
+                        attr.shader_location,
+                    )
+                })
+                .collect::<js_sys::Array>();
+
+            let mut mapped_vbuf =
+                web_sys::GpuVertexBufferLayout::new(vbuf.array_stride as f64, &mapped_attributes);
+            mapped_vbuf.step_mode(map_vertex_step_mode(vbuf.step_mode));
+            mapped_vbuf
+        })
+        .collect::<js_sys::Array>();
+
+    mapped_vertex_state.buffers(&buffers);
+
+    let auto_layout = wasm_bindgen::JsValue::from(web_sys::GpuAutoLayoutMode::Auto);
+    let mut mapped_desc = web_sys::GpuRenderPipelineDescriptor::new(
+        &match desc.layout {
+            Some(layout) => {
+                let layout: &<Context as crate::Context>::PipelineLayoutData =
+                    downcast_ref(layout.data.as_ref());
+                JsValue::from(&layout.0)
+            }
+            None => auto_layout,
+        },
+        &mapped_vertex_state,
+    );
+
+    if let Some(label) = desc.label {
+        mapped_desc.label(label);
+    }
+
+    if let Some(ref depth_stencil) = desc.depth_stencil {
+        mapped_desc.depth_stencil(&map_depth_stencil_state(depth_stencil));
+    }
+
+    if let Some(ref frag) = desc.fragment {
+        let targets = frag
+            .targets
+            .iter()
+            .map(|target| match target {
+                Some(target) => {
+                    let mapped_format = map_texture_format(target.format);
+                    let mut mapped_color_state = web_sys::GpuColorTargetState::new(mapped_format);
+                    if let Some(ref bs) = target.blend {
+                        let alpha = map_blend_component(&bs.alpha);
+                        let color = map_blend_component(&bs.color);
+                        let mapped_blend_state = web_sys::GpuBlendState::new(&alpha, &color);
+                        mapped_color_state.blend(&mapped_blend_state);
+                    }
+                    mapped_color_state.write_mask(target.write_mask.bits());
+                    wasm_bindgen::JsValue::from(mapped_color_state)
+                }
+                None => wasm_bindgen::JsValue::null(),
+            })
+            .collect::<js_sys::Array>();
+        let module: &<Context as crate::Context>::ShaderModuleData =
+            downcast_ref(frag.module.data.as_ref());
+        let mapped_fragment_desc =
+            web_sys::GpuFragmentState::new(frag.entry_point, &module.0, &targets);
+        map_pipeline_constants(&mapped_fragment_desc, frag.constants);
+        mapped_desc.fragment(&mapped_fragment_desc);
+    }
+
+    let mut mapped_multisample = web_sys::GpuMultisampleState::new();
+    mapped_multisample.count(desc.multisample.count);
+    mapped_multisample.mask(desc.multisample.mask as u32);
+    mapped_multisample.alpha_to_coverage_enabled(desc.multisample.alpha_to_coverage_enabled);
+    mapped_desc.multisample(&mapped_multisample);
+
+    let mapped_primitive = map_primitive_state(&desc.primitive);
+    mapped_desc.primitive(&mapped_primitive);
+
+    mapped_desc
+}
+
+fn map_compute_pipeline_descriptor(
+    desc: &crate::ComputePipelineDescriptor,
+) -> web_sys::GpuComputePipelineDescriptor {
+    let shader_module: &<Context as crate::Context>::ShaderModuleData =
+        downcast_ref(desc.module.data.as_ref());
+    let mapped_compute_stage =
+        web_sys::GpuProgrammableStage::new(desc.entry_point, &shader_module.0);
+    map_pipeline_constants(&mapped_compute_stage, desc.constants);
+    let auto_layout = wasm_bindgen::JsValue::from(web_sys::GpuAutoLayoutMode::Auto);
+    let mut mapped_desc = web_sys::GpuComputePipelineDescriptor::new(
+        &match desc.layout {
+            Some(layout) => {
+                let layout: &<Context as crate::Context>::PipelineLayoutData =
+                    downcast_ref(layout.data.as_ref());
+                JsValue::from(&layout.0)
+            }
+            None => auto_layout,
+        },
+        &mapped_compute_stage,
+    );
+    if let Some(label) = desc.label {
+        mapped_desc.label(label);
+    }
+    mapped_desc
+}
+
 /// Calls `callback(success_value)` when the promise completes successfully, calls `callback(failure_value)`
 /// when the promise completes unsuccessfully.
 fn register_then_closures<F, T>(promise: &Promise, callback: F, success_value: T, failure_value: T)
@@ -873,8 +1117,9 @@ impl crate::context::Context for Context {
     type AdapterData = Sendable<web_sys::GpuAdapter>;
     type DeviceId = Identified<web_sys::GpuDevice>;
     type DeviceData = Sendable<web_sys::GpuDevice>;
-    type QueueId = Identified<web_sys::GpuQueue>;
-    type QueueData = Sendable<web_sys::GpuQueue>;
+    /// The device is kept along the queue to report the errors of queue operations.
+    type QueueId = Identified<(web_sys::GpuQueue, web_sys::GpuDevice)>;
+    type QueueData = Sendable<(web_sys::GpuQueue, web_sys::GpuDevice)>;
     type ShaderModuleId = Identified<web_sys::GpuShaderModule>;
     type ShaderModuleData = Sendable<web_sys::GpuShaderModule>;
     type BindGroupLayoutId = Identified<web_sys::GpuBindGroupLayout>;
@@ -936,6 +1181,22 @@ impl crate::context::Context for Context {
     >;
     type PopErrorScopeFuture =
         MakeSendFuture<wasm_bindgen_futures::JsFuture, fn(JsFutureResult) -> Option<crate::Error>>;
+    type CompilationInfoFuture = MakeSendFuture<
+        wasm_bindgen_futures::JsFuture,
+        fn(JsFutureResult) -> crate::CompilationInfo,
+    >;
+    type CreateRenderPipelineFuture = MakeSendFuture<
+        wasm_bindgen_futures::JsFuture,
+        fn(
+            JsFutureResult,
+        ) -> Result<(Self::RenderPipelineId, Self::RenderPipelineData), crate::Error>,
+    >;
+    type CreateComputePipelineFuture = MakeSendFuture<
+        wasm_bindgen_futures::JsFuture,
+        fn(
+            JsFutureResult,
+        ) -> Result<(Self::ComputePipelineId, Self::ComputePipelineData), crate::Error>,
+    >;
 
     fn init(_instance_desc: wgt::InstanceDescriptor) -> Self {
         let global: Global = js_sys::global().unchecked_into();
@@ -1118,6 +1379,7 @@ impl crate::context::Context for Context {
             device_type: wgt::DeviceType::Other,
             driver: String::new(),
             driver_info: String::new(),
+            device_node: String::new(),
             backend: wgt::Backend::BrowserWebGpu,
         }
     }
@@ -1271,6 +1533,15 @@ impl crate::context::Context for Context {
         wgt::DownlevelCapabilities::default()
     }
 
+    fn device_memory_report(
+        &self,
+        _device: &Self::DeviceId,
+        _device_data: &Self::DeviceData,
+    ) -> wgt::MemoryReport {
+        // WebGPU doesn't expose memory usage
+        wgt::MemoryReport::default()
+    }
+
     #[cfg_attr(
         not(any(
             feature = "spirv",
@@ -1375,6 +1646,15 @@ impl crate::context::Context for Context {
         unreachable!("SPIRV_SHADER_PASSTHROUGH is not enabled for this backend")
     }
 
+    unsafe fn device_create_shader_module_passthrough(
+        &self,
+        _device: &Self::DeviceId,
+        _device_data: &Self::DeviceData,
+        _desc: &crate::ShaderModuleDescriptorPassthrough,
+    ) -> (Self::ShaderModuleId, Self::ShaderModuleData) {
+        unreachable!("SHADER_PASSTHROUGH is not enabled for this backend")
+    }
+
     fn device_create_bind_group_layout(
         &self,
         _device: &Self::DeviceId,
@@ -1561,128 +1841,48 @@ impl crate::context::Context for Context {
         device_data: &Self::DeviceData,
         desc: &crate::RenderPipelineDescriptor,
     ) -> (Self::RenderPipelineId, Self::RenderPipelineData) {
-        let module: &<Context as crate::Context>::ShaderModuleData =
-            downcast_ref(desc.vertex.module.data.as_ref());
-        let mut mapped_vertex_state =
-            web_sys::GpuVertexState::new(desc.vertex.entry_point, &module.0);
-
-        let buffers = desc
-            .vertex
-            .buffers
-            .iter()
-            .map(|vbuf| {
-                let mapped_attributes = vbuf
-                    .attributes
-                    .iter()
-                    .map(|attr| {
-                        web_sys::GpuVertexAttribute::new(
-                            map_vertex_format(attr.format),
-                            attr.offset as f64,
-                            attr.shader_location,
-                        )
-                    })
-                    .collect::<js_sys::Array>();
-
-                let mut mapped_vbuf = web_sys::GpuVertexBufferLayout::new(
-                    vbuf.array_stride as f64,
-                    &mapped_attributes,
-                );
-                mapped_vbuf.step_mode(map_vertex_step_mode(vbuf.step_mode));
-                mapped_vbuf
-            })
-            .collect::<js_sys::Array>();
-
-        mapped_vertex_state.buffers(&buffers);
-
-        let auto_layout = wasm_bindgen::JsValue::from(web_sys::GpuAutoLayoutMode::Auto);
-        let mut mapped_desc = web_sys::GpuRenderPipelineDescriptor::new(
-            &match desc.layout {
-                Some(layout) => {
-                    let layout: &<Context as crate::Context>::PipelineLayoutData =
-                        downcast_ref(layout.data.as_ref());
-                    JsValue::from(&layout.0)
-                }
-                None => auto_layout,
-            },
-            &mapped_vertex_state,
-        );
-
-        if let Some(label) = desc.label {
-            mapped_desc.label(label);
-        }
-
-        if let Some(ref depth_stencil) = desc.depth_stencil {
-            mapped_desc.depth_stencil(&map_depth_stencil_state(depth_stencil));
-        }
-
-        if let Some(ref frag) = desc.fragment {
-            let targets = frag
-                .targets
-                .iter()
-                .map(|target| match target {
-                    Some(target) => {
-                        let mapped_format = map_texture_format(target.format);
-                        let mut mapped_color_state =
-                            web_sys::GpuColorTargetState::new(mapped_format);
-                        if let Some(ref bs) = target.blend {
-                            let alpha = map_blend_component(&bs.alpha);
-                            let color = map_blend_component(&bs.color);
-                            let mapped_blend_state = web_sys::GpuBlendState::new(&alpha, &color);
-                            mapped_color_state.blend(&mapped_blend_state);
-                        }
-                        mapped_color_state.write_mask(target.write_mask.bits());
-                        wasm_bindgen::JsValue::from(mapped_color_state)
-                    }
-                    None => wasm_bindgen::JsValue::null(),
-                })
-                .collect::<js_sys::Array>();
-            let module: &<Context as crate::Context>::ShaderModuleData =
-                downcast_ref(frag.module.data.as_ref());
-            let mapped_fragment_desc =
-                web_sys::GpuFragmentState::new(frag.entry_point, &module.0, &targets);
-            mapped_desc.fragment(&mapped_fragment_desc);
-        }
-
-        let mut mapped_multisample = web_sys::GpuMultisampleState::new();
-        mapped_multisample.count(desc.multisample.count);
-        mapped_multisample.mask(desc.multisample.mask as u32);
-        mapped_multisample.alpha_to_coverage_enabled(desc.multisample.alpha_to_coverage_enabled);
-        mapped_desc.multisample(&mapped_multisample);
-
-        let mapped_primitive = map_primitive_state(&desc.primitive);
-        mapped_desc.primitive(&mapped_primitive);
-
+        let mapped_desc = map_render_pipeline_descriptor(desc);
         create_identified(device_data.0.create_render_pipeline(&mapped_desc))
     }
 
+    fn device_create_render_pipeline_async(
+        &self,
+        _device: &Self::DeviceId,
+        device_data: &Self::DeviceData,
+        desc: &crate::RenderPipelineDescriptor,
+    ) -> Self::CreateRenderPipelineFuture {
+        let mapped_desc = map_render_pipeline_descriptor(desc);
+        let pipeline_promise = device_data.0.create_render_pipeline_async(&mapped_desc);
+        MakeSendFuture::new(
+            wasm_bindgen_futures::JsFuture::from(pipeline_promise),
+            future_create_pipeline,
+        )
+    }
+
     fn device_create_compute_pipeline(
         &self,
         _device: &Self::DeviceId,
         device_data: &Self::DeviceData,
         desc: &crate::ComputePipelineDescriptor,
     ) -> (Self::ComputePipelineId, Self::ComputePipelineData) {
-        let shader_module: &<Context as crate::Context>::ShaderModuleData =
-            downcast_ref(desc.module.data.as_ref());
-        let mapped_compute_stage =
-            web_sys::GpuProgrammableStage::new(desc.entry_point, &shader_module.0);
-        let auto_layout = wasm_bindgen::JsValue::from(web_sys::GpuAutoLayoutMode::Auto);
-        let mut mapped_desc = web_sys::GpuComputePipelineDescriptor::new(
-            &match desc.layout {
-                Some(layout) => {
-                    let layout: &<Context as crate::Context>::PipelineLayoutData =
-                        downcast_ref(layout.data.as_ref());
-                    JsValue::from(&layout.0)
-                }
-                None => auto_layout,
-            },
-            &mapped_compute_stage,
-        );
-        if let Some(label) = desc.label {
-            mapped_desc.label(label);
-        }
+        let mapped_desc = map_compute_pipeline_descriptor(desc);
         create_identified(device_data.0.create_compute_pipeline(&mapped_desc))
     }
 
+    fn device_create_compute_pipeline_async(
+        &self,
+        _device: &Self::DeviceId,
+        device_data: &Self::DeviceData,
+        desc: &crate::ComputePipelineDescriptor,
+    ) -> Self::CreateComputePipelineFuture {
+        let mapped_desc = map_compute_pipeline_descriptor(desc);
+        let pipeline_promise = device_data.0.create_compute_pipeline_async(&mapped_desc);
+        MakeSendFuture::new(
+            wasm_bindgen_futures::JsFuture::from(pipeline_promise),
+            future_create_pipeline,
+        )
+    }
+
     fn device_create_buffer(
         &self,
         _device: &Self::DeviceId,
@@ -1704,10 +1904,12 @@ impl crate::context::Context for Context {
         device_data: &Self::DeviceData,
         desc: &crate::TextureDescriptor,
     ) -> (Self::TextureId, Self::TextureData) {
+        // Transient attachments are a native-only memory hint, WebGPU has no equivalent.
+        let usage = desc.usage - wgt::TextureUsages::TRANSIENT_ATTACHMENT;
         let mut mapped_desc = web_sys::GpuTextureDescriptor::new(
             map_texture_format(desc.format),
             &map_extent_3d(desc.size),
-            desc.usage.bits(),
+            usage.bits(),
         );
         if let Some(label) = desc.label {
             mapped_desc.label(label);
@@ -1852,6 +2054,7 @@ impl crate::context::Context for Context {
         device_data.0.push_error_scope(match filter {
             crate::ErrorFilter::OutOfMemory => web_sys::GpuErrorFilter::OutOfMemory,
             crate::ErrorFilter::Validation => web_sys::GpuErrorFilter::Validation,
+            crate::ErrorFilter::Internal => web_sys::GpuErrorFilter::Internal,
         });
     }
 
@@ -1919,7 +2122,6 @@ impl crate::context::Context for Context {
         if let Some(format) = desc.format {
             mapped.format(map_texture_format(format));
         }
-        mapped.aspect(map_texture_aspect(desc.aspect));
         mapped.base_array_layer(desc.base_array_layer);
         if let Some(count) = desc.array_layer_count {
             mapped.array_layer_count(count);
@@ -1928,6 +2130,16 @@ impl crate::context::Context for Context {
         if let Some(count) = desc.mip_level_count {
             mapped.mip_level_count(count);
         }
+        match map_texture_aspect(desc.aspect) {
+            Some(aspect) => mapped.aspect(aspect),
+            None => {
+                return create_identified(plane_aspect_error(
+                    &texture_data.0,
+                    desc.aspect,
+                    "Creating a texture view",
+                ))
+            }
+        };
         if let Some(label) = desc.label {
             mapped.label(label);
         }
@@ -2006,6 +2218,37 @@ impl crate::context::Context for Context {
         // Dropped automatically
     }
 
+    fn shader_get_compilation_info(
+        &self,
+        _shader_module: &Self::ShaderModuleId,
+        shader_module_data: &Self::ShaderModuleData,
+    ) -> Self::CompilationInfoFuture {
+        // Older browsers only implement the deprecated `compilationInfo` name.
+        let module = &shader_module_data.0;
+        let method = ["getCompilationInfo", "compilationInfo"]
+            .iter()
+            .find_map(|name| {
+                js_sys::Reflect::get(module, &JsValue::from_str(name))
+                    .ok()
+                    .and_then(|value| value.dyn_into::<js_sys::Function>().ok())
+            })
+            .expect("GPUShaderModule has no `getCompilationInfo` method");
+        let promise: Promise = method.call0(module).unwrap().unchecked_into();
+        MakeSendFuture::new(
+            wasm_bindgen_futures::JsFuture::from(promise),
+            future_compilation_info,
+        )
+    }
+
+    fn shader_module_reflect(
+        &self,
+        _shader_module: &Self::ShaderModuleId,
+        _shader_module_data: &Self::ShaderModuleData,
+    ) -> Option<wgt::ShaderModuleReflection> {
+        // WebGPU doesn't expose the interface of shader modules.
+        None
+    }
+
     fn command_encoder_drop(
         &self,
         _command_encoder: &Self::CommandEncoderId,
@@ -2064,6 +2307,24 @@ impl crate::context::Context for Context {
         create_identified(pipeline_data.0.get_bind_group_layout(index))
     }
 
+    fn bind_group_layout_entries(
+        &self,
+        _bind_group_layout: &Self::BindGroupLayoutId,
+        _bind_group_layout_data: &Self::BindGroupLayoutData,
+    ) -> Option<Vec<wgt::BindGroupLayoutEntry>> {
+        // WebGPU doesn't expose the entries of bind group layouts.
+        None
+    }
+
+    fn pipeline_layout_get_bind_group_layouts(
+        &self,
+        _pipeline_layout: &Self::PipelineLayoutId,
+        _pipeline_layout_data: &Self::PipelineLayoutData,
+    ) -> Option<Vec<(Self::BindGroupLayoutId, Self::BindGroupLayoutData)>> {
+        // WebGPU doesn't expose the bind group layouts of pipeline layouts.
+        None
+    }
+
     fn command_encoder_copy_buffer_to_buffer(
         &self,
         _encoder: &Self::CommandEncoderId,
@@ -2095,11 +2356,16 @@ impl crate::context::Context for Context {
         destination: crate::ImageCopyTexture,
         copy_size: wgt::Extent3d,
     ) {
+        let destination =
+            match map_texture_copy_view(destination, "Copying from a buffer to a texture") {
+                Some(destination) => destination,
+                None => return,
+            };
         encoder_data
             .0
             .copy_buffer_to_texture_with_gpu_extent_3d_dict(
                 &map_buffer_copy_view(source),
-                &map_texture_copy_view(destination),
+                &destination,
                 &map_extent_3d(copy_size),
             )
     }
@@ -2112,10 +2378,14 @@ impl crate::context::Context for Context {
         destination: crate::ImageCopyBuffer,
         copy_size: wgt::Extent3d,
     ) {
+        let source = match map_texture_copy_view(source, "Copying from a texture to a buffer") {
+            Some(source) => source,
+            None => return,
+        };
         encoder_data
             .0
             .copy_texture_to_buffer_with_gpu_extent_3d_dict(
-                &map_texture_copy_view(source),
+                &source,
                 &map_buffer_copy_view(destination),
                 &map_extent_3d(copy_size),
             )
@@ -2129,11 +2399,19 @@ impl crate::context::Context for Context {
         destination: crate::ImageCopyTexture,
         copy_size: wgt::Extent3d,
     ) {
+        let operation = "Copying from a texture to a texture";
+        let (source, destination) = match (
+            map_texture_copy_view(source, operation),
+            map_texture_copy_view(destination, operation),
+        ) {
+            (Some(source), Some(destination)) => (source, destination),
+            _ => return,
+        };
         encoder_data
             .0
             .copy_texture_to_texture_with_gpu_extent_3d_dict(
-                &map_texture_copy_view(source),
-                &map_texture_copy_view(destination),
+                &source,
+                &destination,
                 &map_extent_3d(copy_size),
             )
     }
@@ -2395,7 +2673,7 @@ impl crate::context::Context for Context {
         data: &[u8],
     ) {
         /* Skip the copy once gecko allows BufferSource instead of ArrayBuffer
-        queue_data.0.write_buffer_with_f64_and_u8_array_and_f64_and_f64(
+        queue_data.0 .0.write_buffer_with_f64_and_u8_array_and_f64_and_f64(
             &buffer_data.0,
             offset as f64,
             data,
@@ -2403,15 +2681,14 @@ impl crate::context::Context for Context {
             data.len() as f64,
         );
         */
-        queue_data
-            .0
-            .write_buffer_with_f64_and_buffer_source_and_f64_and_f64(
-                &buffer_data.0,
-                offset as f64,
-                &js_sys::Uint8Array::from(data).buffer(),
-                0f64,
-                data.len() as f64,
-            );
+        let (ref queue, _) = queue_data.0;
+        queue.write_buffer_with_f64_and_buffer_source_and_f64_and_f64(
+            &buffer_data.0,
+            offset as f64,
+            &js_sys::Uint8Array::from(data).buffer(),
+            0f64,
+            data.len() as f64,
+        );
     }
 
     fn queue_validate_write_buffer(
@@ -2503,23 +2780,26 @@ impl crate::context::Context for Context {
             mapped_data_layout.rows_per_image(rows_per_image);
         }
         mapped_data_layout.offset(data_layout.offset as f64);
+        let texture = match map_texture_copy_view(texture, "Writing to a texture") {
+            Some(texture) => texture,
+            None => return,
+        };
 
         /* Skip the copy once gecko allows BufferSource instead of ArrayBuffer
-        queue_data.0.write_texture_with_u8_array_and_gpu_extent_3d_dict(
-            &map_texture_copy_view(texture),
+        queue_data.0 .0.write_texture_with_u8_array_and_gpu_extent_3d_dict(
+            &texture,
             data,
             &mapped_data_layout,
             &map_extent_3d(size),
         );
         */
-        queue_data
-            .0
-            .write_texture_with_buffer_source_and_gpu_extent_3d_dict(
-                &map_texture_copy_view(texture),
-                &js_sys::Uint8Array::from(data).buffer(),
-                &mapped_data_layout,
-                &map_extent_3d(size),
-            );
+        let (ref queue, _) = queue_data.0;
+        queue.write_texture_with_buffer_source_and_gpu_extent_3d_dict(
+            &texture,
+            &js_sys::Uint8Array::from(data).buffer(),
+            &mapped_data_layout,
+            &map_extent_3d(size),
+        );
     }
 
     fn queue_copy_external_image_to_texture(
@@ -2530,13 +2810,32 @@ impl crate::context::Context for Context {
         dest: crate::ImageCopyTextureTagged,
         size: wgt::Extent3d,
     ) {
-        queue_data
-            .0
-            .copy_external_image_to_texture_with_gpu_extent_3d_dict(
-                &map_external_texture_copy_view(source),
-                &map_tagged_texture_copy_view(dest),
-                &map_extent_3d(size),
-            );
+        let dest =
+            match map_tagged_texture_copy_view(dest, "Copying from an external image to a texture")
+            {
+                Some(dest) => dest,
+                None => return,
+            };
+        let (ref queue, _) = queue_data.0;
+        queue.copy_external_image_to_texture_with_gpu_extent_3d_dict(
+            &map_external_texture_copy_view(source),
+            &dest,
+            &map_extent_3d(size),
+        );
+    }
+
+    fn queue_update_bind_group(
+        &self,
+        _queue: &Self::QueueId,
+        queue_data: &Self::QueueData,
+        _bind_group: &Self::BindGroupId,
+        _bind_group_data: &Self::BindGroupData,
+        _entries: &[crate::BindGroupUpdateEntry],
+    ) {
+        device_validation_error(
+            &queue_data.0 .1,
+            "Updating a bind group requires UPDATE_AFTER_BIND_DESCRIPTORS, which isn't supported on WebGPU",
+        );
     }
 
     fn queue_submit<I: Iterator<Item = (Self::CommandBufferId, Self::CommandBufferData)>>(
@@ -2549,7 +2848,7 @@ impl crate::context::Context for Context {
             .map(|(_, data)| data.0)
             .collect::<js_sys::Array>();
 
-        queue_data.0.submit(&temp_command_buffers);
+        queue_data.0 .0.submit(&temp_command_buffers);
 
         (Unused, ())
     }
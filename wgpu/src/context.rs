@@ -3,19 +3,19 @@ use std::{any::Any, fmt::Debug, future::Future, num::NonZeroU64, ops::Range, pin
 use wgt::{
     strict_assert, strict_assert_eq, AdapterInfo, BufferAddress, BufferSize, Color,
     DownlevelCapabilities, DynamicOffset, Extent3d, Features, ImageDataLayout,
-    ImageSubresourceRange, IndexFormat, Limits, ShaderStages, SurfaceStatus, TextureFormat,
-    TextureFormatFeatures, WasmNotSend, WasmNotSync,
+    ImageSubresourceRange, IndexFormat, Limits, MemoryReport, ShaderModuleReflection, ShaderStages,
+    SurfaceStatus, TextureFormat, TextureFormatFeatures, WasmNotSend, WasmNotSync,
 };
 
 use crate::{
     AnyWasmNotSendSync, BindGroupDescriptor, BindGroupLayoutDescriptor, Buffer, BufferAsyncError,
-    BufferDescriptor, CommandEncoderDescriptor, ComputePassDescriptor, ComputePipelineDescriptor,
-    DeviceDescriptor, Error, ErrorFilter, ImageCopyBuffer, ImageCopyTexture, Maintain, MapMode,
-    PipelineLayoutDescriptor, QuerySetDescriptor, RenderBundleDescriptor,
-    RenderBundleEncoderDescriptor, RenderPassDescriptor, RenderPipelineDescriptor,
-    RequestAdapterOptions, RequestDeviceError, SamplerDescriptor, ShaderModuleDescriptor,
-    ShaderModuleDescriptorSpirV, Texture, TextureDescriptor, TextureViewDescriptor,
-    UncapturedErrorHandler,
+    BufferDescriptor, CommandEncoderDescriptor, CompilationInfo, ComputePassDescriptor,
+    ComputePipelineDescriptor, DeviceDescriptor, Error, ErrorFilter, ImageCopyBuffer,
+    ImageCopyTexture, Maintain, MapMode, PipelineLayoutDescriptor, QuerySetDescriptor,
+    RenderBundleDescriptor, RenderBundleEncoderDescriptor, RenderPassDescriptor,
+    RenderPipelineDescriptor, RequestAdapterOptions, RequestDeviceError, SamplerDescriptor,
+    ShaderModuleDescriptor, ShaderModuleDescriptorPassthrough, ShaderModuleDescriptorSpirV,
+    Texture, TextureDescriptor, TextureViewDescriptor, UncapturedErrorHandler,
 };
 
 /// Meta trait for an id tracked by a context.
@@ -94,6 +94,13 @@ pub trait Context: Debug + WasmNotSend + WasmNotSync + Sized {
         > + WasmNotSend
         + 'static;
     type PopErrorScopeFuture: Future<Output = Option<Error>> + WasmNotSend + 'static;
+    type CompilationInfoFuture: Future<Output = CompilationInfo> + WasmNotSend + 'static;
+    type CreateRenderPipelineFuture: Future<Output = Result<(Self::RenderPipelineId, Self::RenderPipelineData), Error>>
+        + WasmNotSend
+        + 'static;
+    type CreateComputePipelineFuture: Future<Output = Result<(Self::ComputePipelineId, Self::ComputePipelineData), Error>>
+        + WasmNotSend
+        + 'static;
 
     fn init(instance_desc: wgt::InstanceDescriptor) -> Self;
     fn instance_create_surface(
@@ -189,6 +196,11 @@ pub trait Context: Debug + WasmNotSend + WasmNotSync + Sized {
         device: &Self::DeviceId,
         device_data: &Self::DeviceData,
     ) -> DownlevelCapabilities;
+    fn device_memory_report(
+        &self,
+        device: &Self::DeviceId,
+        device_data: &Self::DeviceData,
+    ) -> MemoryReport;
     fn device_create_shader_module(
         &self,
         device: &Self::DeviceId,
@@ -202,6 +214,12 @@ pub trait Context: Debug + WasmNotSend + WasmNotSync + Sized {
         device_data: &Self::DeviceData,
         desc: &ShaderModuleDescriptorSpirV,
     ) -> (Self::ShaderModuleId, Self::ShaderModuleData);
+    unsafe fn device_create_shader_module_passthrough(
+        &self,
+        device: &Self::DeviceId,
+        device_data: &Self::DeviceData,
+        desc: &ShaderModuleDescriptorPassthrough,
+    ) -> (Self::ShaderModuleId, Self::ShaderModuleData);
     fn device_create_bind_group_layout(
         &self,
         device: &Self::DeviceId,
@@ -226,12 +244,24 @@ pub trait Context: Debug + WasmNotSend + WasmNotSync + Sized {
         device_data: &Self::DeviceData,
         desc: &RenderPipelineDescriptor,
     ) -> (Self::RenderPipelineId, Self::RenderPipelineData);
+    fn device_create_render_pipeline_async(
+        &self,
+        device: &Self::DeviceId,
+        device_data: &Self::DeviceData,
+        desc: &RenderPipelineDescriptor,
+    ) -> Self::CreateRenderPipelineFuture;
     fn device_create_compute_pipeline(
         &self,
         device: &Self::DeviceId,
         device_data: &Self::DeviceData,
         desc: &ComputePipelineDescriptor,
     ) -> (Self::ComputePipelineId, Self::ComputePipelineData);
+    fn device_create_compute_pipeline_async(
+        &self,
+        device: &Self::DeviceId,
+        device_data: &Self::DeviceData,
+        desc: &ComputePipelineDescriptor,
+    ) -> Self::CreateComputePipelineFuture;
     fn device_create_buffer(
         &self,
         device: &Self::DeviceId,
@@ -348,6 +378,16 @@ pub trait Context: Debug + WasmNotSend + WasmNotSync + Sized {
         shader_module: &Self::ShaderModuleId,
         shader_module_data: &Self::ShaderModuleData,
     );
+    fn shader_get_compilation_info(
+        &self,
+        shader_module: &Self::ShaderModuleId,
+        shader_module_data: &Self::ShaderModuleData,
+    ) -> Self::CompilationInfoFuture;
+    fn shader_module_reflect(
+        &self,
+        shader_module: &Self::ShaderModuleId,
+        shader_module_data: &Self::ShaderModuleData,
+    ) -> Option<ShaderModuleReflection>;
     fn command_encoder_drop(
         &self,
         command_encoder: &Self::CommandEncoderId,
@@ -386,6 +426,16 @@ pub trait Context: Debug + WasmNotSend + WasmNotSync + Sized {
         pipeline_data: &Self::RenderPipelineData,
         index: u32,
     ) -> (Self::BindGroupLayoutId, Self::BindGroupLayoutData);
+    fn bind_group_layout_entries(
+        &self,
+        bind_group_layout: &Self::BindGroupLayoutId,
+        bind_group_layout_data: &Self::BindGroupLayoutData,
+    ) -> Option<Vec<wgt::BindGroupLayoutEntry>>;
+    fn pipeline_layout_get_bind_group_layouts(
+        &self,
+        pipeline_layout: &Self::PipelineLayoutId,
+        pipeline_layout_data: &Self::PipelineLayoutData,
+    ) -> Option<Vec<(Self::BindGroupLayoutId, Self::BindGroupLayoutData)>>;
 
     #[allow(clippy::too_many_arguments)]
     fn command_encoder_copy_buffer_to_buffer(
@@ -570,6 +620,14 @@ pub trait Context: Debug + WasmNotSend + WasmNotSync + Sized {
         dest: crate::ImageCopyTextureTagged,
         size: wgt::Extent3d,
     );
+    fn queue_update_bind_group(
+        &self,
+        queue: &Self::QueueId,
+        queue_data: &Self::QueueData,
+        bind_group: &Self::BindGroupId,
+        bind_group_data: &Self::BindGroupData,
+        entries: &[crate::BindGroupUpdateEntry],
+    );
     fn queue_submit<I: Iterator<Item = (Self::CommandBufferId, Self::CommandBufferData)>>(
         &self,
         queue: &Self::QueueId,
@@ -1160,6 +1218,42 @@ pub type DevicePopErrorFuture = Box<dyn Future<Output = Option<Error>> + Send>;
 )))]
 pub type DevicePopErrorFuture = Box<dyn Future<Output = Option<Error>>>;
 
+#[cfg(any(
+    not(target_arch = "wasm32"),
+    all(
+        feature = "fragile-send-sync-non-atomic-wasm",
+        not(target_feature = "atomics")
+    )
+))]
+pub type ShaderCompilationInfoFuture = Box<dyn Future<Output = CompilationInfo> + Send>;
+#[cfg(not(any(
+    not(target_arch = "wasm32"),
+    all(
+        feature = "fragile-send-sync-non-atomic-wasm",
+        not(target_feature = "atomics")
+    )
+)))]
+pub type ShaderCompilationInfoFuture = Box<dyn Future<Output = CompilationInfo>>;
+
+#[cfg(any(
+    not(target_arch = "wasm32"),
+    all(
+        feature = "fragile-send-sync-non-atomic-wasm",
+        not(target_feature = "atomics")
+    )
+))]
+pub type DeviceCreatePipelineFuture =
+    Box<dyn Future<Output = Result<(ObjectId, Box<crate::Data>), Error>> + Send>;
+#[cfg(not(any(
+    not(target_arch = "wasm32"),
+    all(
+        feature = "fragile-send-sync-non-atomic-wasm",
+        not(target_feature = "atomics")
+    )
+)))]
+pub type DeviceCreatePipelineFuture =
+    Box<dyn Future<Output = Result<(ObjectId, Box<crate::Data>), Error>>>;
+
 #[cfg(any(
     not(target_arch = "wasm32"),
     all(
@@ -1262,6 +1356,7 @@ pub(crate) trait DynContext: Debug + WasmNotSend + WasmNotSync {
         device: &ObjectId,
         device_data: &crate::Data,
     ) -> DownlevelCapabilities;
+    fn device_memory_report(&self, device: &ObjectId, device_data: &crate::Data) -> MemoryReport;
     fn device_create_shader_module(
         &self,
         device: &ObjectId,
@@ -1275,6 +1370,12 @@ pub(crate) trait DynContext: Debug + WasmNotSend + WasmNotSync {
         device_data: &crate::Data,
         desc: &ShaderModuleDescriptorSpirV,
     ) -> (ObjectId, Box<crate::Data>);
+    unsafe fn device_create_shader_module_passthrough(
+        &self,
+        device: &ObjectId,
+        device_data: &crate::Data,
+        desc: &ShaderModuleDescriptorPassthrough,
+    ) -> (ObjectId, Box<crate::Data>);
     fn device_create_bind_group_layout(
         &self,
         device: &ObjectId,
@@ -1299,12 +1400,24 @@ pub(crate) trait DynContext: Debug + WasmNotSend + WasmNotSync {
         device_data: &crate::Data,
         desc: &RenderPipelineDescriptor,
     ) -> (ObjectId, Box<crate::Data>);
+    fn device_create_render_pipeline_async(
+        &self,
+        device: &ObjectId,
+        device_data: &crate::Data,
+        desc: &RenderPipelineDescriptor,
+    ) -> Pin<DeviceCreatePipelineFuture>;
     fn device_create_compute_pipeline(
         &self,
         device: &ObjectId,
         device_data: &crate::Data,
         desc: &ComputePipelineDescriptor,
     ) -> (ObjectId, Box<crate::Data>);
+    fn device_create_compute_pipeline_async(
+        &self,
+        device: &ObjectId,
+        device_data: &crate::Data,
+        desc: &ComputePipelineDescriptor,
+    ) -> Pin<DeviceCreatePipelineFuture>;
     fn device_create_buffer(
         &self,
         device: &ObjectId,
@@ -1400,6 +1513,16 @@ pub(crate) trait DynContext: Debug + WasmNotSend + WasmNotSync {
     );
     fn pipeline_layout_drop(&self, pipeline_layout: &ObjectId, pipeline_layout_data: &crate::Data);
     fn shader_module_drop(&self, shader_module: &ObjectId, shader_module_data: &crate::Data);
+    fn shader_get_compilation_info(
+        &self,
+        shader_module: &ObjectId,
+        shader_module_data: &crate::Data,
+    ) -> Pin<ShaderCompilationInfoFuture>;
+    fn shader_module_reflect(
+        &self,
+        shader_module: &ObjectId,
+        shader_module_data: &crate::Data,
+    ) -> Option<ShaderModuleReflection>;
     fn command_encoder_drop(&self, command_encoder: &ObjectId, command_encoder_data: &crate::Data);
     fn command_buffer_drop(&self, command_buffer: &ObjectId, command_buffer_data: &crate::Data);
     fn render_bundle_drop(&self, render_bundle: &ObjectId, render_bundle_data: &crate::Data);
@@ -1418,6 +1541,16 @@ pub(crate) trait DynContext: Debug + WasmNotSend + WasmNotSync {
         pipeline_data: &crate::Data,
         index: u32,
     ) -> (ObjectId, Box<crate::Data>);
+    fn bind_group_layout_entries(
+        &self,
+        bind_group_layout: &ObjectId,
+        bind_group_layout_data: &crate::Data,
+    ) -> Option<Vec<wgt::BindGroupLayoutEntry>>;
+    fn pipeline_layout_get_bind_group_layouts(
+        &self,
+        pipeline_layout: &ObjectId,
+        pipeline_layout_data: &crate::Data,
+    ) -> Option<Vec<(ObjectId, Box<crate::Data>)>>;
 
     #[allow(clippy::too_many_arguments)]
     fn command_encoder_copy_buffer_to_buffer(
@@ -1598,6 +1731,14 @@ pub(crate) trait DynContext: Debug + WasmNotSend + WasmNotSync {
         dest: crate::ImageCopyTextureTagged,
         size: wgt::Extent3d,
     );
+    fn queue_update_bind_group(
+        &self,
+        queue: &ObjectId,
+        queue_data: &crate::Data,
+        bind_group: &ObjectId,
+        bind_group_data: &crate::Data,
+        entries: &[crate::BindGroupUpdateEntry],
+    );
     fn queue_submit<'a>(
         &self,
         queue: &ObjectId,
@@ -2209,6 +2350,12 @@ where
         Context::device_downlevel_properties(self, &device, device_data)
     }
 
+    fn device_memory_report(&self, device: &ObjectId, device_data: &crate::Data) -> MemoryReport {
+        let device = <T::DeviceId>::from(*device);
+        let device_data = downcast_ref(device_data);
+        Context::device_memory_report(self, &device, device_data)
+    }
+
     fn device_create_shader_module(
         &self,
         device: &ObjectId,
@@ -2241,6 +2388,20 @@ where
         (shader_module.into(), Box::new(data) as _)
     }
 
+    unsafe fn device_create_shader_module_passthrough(
+        &self,
+        device: &ObjectId,
+        device_data: &crate::Data,
+        desc: &ShaderModuleDescriptorPassthrough,
+    ) -> (ObjectId, Box<crate::Data>) {
+        let device = <T::DeviceId>::from(*device);
+        let device_data = downcast_ref(device_data);
+        let (shader_module, data) = unsafe {
+            Context::device_create_shader_module_passthrough(self, &device, device_data, desc)
+        };
+        (shader_module.into(), Box::new(data) as _)
+    }
+
     fn device_create_bind_group_layout(
         &self,
         device: &ObjectId,
@@ -2293,6 +2454,21 @@ where
         (render_pipeline.into(), Box::new(data) as _)
     }
 
+    fn device_create_render_pipeline_async(
+        &self,
+        device: &ObjectId,
+        device_data: &crate::Data,
+        desc: &RenderPipelineDescriptor,
+    ) -> Pin<DeviceCreatePipelineFuture> {
+        let device = <T::DeviceId>::from(*device);
+        let device_data = downcast_ref(device_data);
+        let future = Context::device_create_render_pipeline_async(self, &device, device_data, desc);
+        Box::pin(async move {
+            let (render_pipeline, data) = future.await?;
+            Ok((render_pipeline.into(), Box::new(data) as _))
+        })
+    }
+
     fn device_create_compute_pipeline(
         &self,
         device: &ObjectId,
@@ -2306,6 +2482,22 @@ where
         (compute_pipeline.into(), Box::new(data) as _)
     }
 
+    fn device_create_compute_pipeline_async(
+        &self,
+        device: &ObjectId,
+        device_data: &crate::Data,
+        desc: &ComputePipelineDescriptor,
+    ) -> Pin<DeviceCreatePipelineFuture> {
+        let device = <T::DeviceId>::from(*device);
+        let device_data = downcast_ref(device_data);
+        let future =
+            Context::device_create_compute_pipeline_async(self, &device, device_data, desc);
+        Box::pin(async move {
+            let (compute_pipeline, data) = future.await?;
+            Ok((compute_pipeline.into(), Box::new(data) as _))
+        })
+    }
+
     fn device_create_buffer(
         &self,
         device: &ObjectId,
@@ -2553,6 +2745,30 @@ where
         Context::shader_module_drop(self, &shader_module, shader_module_data)
     }
 
+    fn shader_get_compilation_info(
+        &self,
+        shader_module: &ObjectId,
+        shader_module_data: &crate::Data,
+    ) -> Pin<ShaderCompilationInfoFuture> {
+        let shader_module = <T::ShaderModuleId>::from(*shader_module);
+        let shader_module_data = downcast_ref(shader_module_data);
+        Box::pin(Context::shader_get_compilation_info(
+            self,
+            &shader_module,
+            shader_module_data,
+        ))
+    }
+
+    fn shader_module_reflect(
+        &self,
+        shader_module: &ObjectId,
+        shader_module_data: &crate::Data,
+    ) -> Option<ShaderModuleReflection> {
+        let shader_module = <T::ShaderModuleId>::from(*shader_module);
+        let shader_module_data = downcast_ref(shader_module_data);
+        Context::shader_module_reflect(self, &shader_module, shader_module_data)
+    }
+
     fn command_encoder_drop(&self, command_encoder: &ObjectId, command_encoder_data: &crate::Data) {
         let command_encoder = <T::CommandEncoderId>::from(*command_encoder);
         let command_encoder_data = downcast_ref(command_encoder_data);
@@ -2609,6 +2825,36 @@ where
         (bind_group_layout.into(), Box::new(data) as _)
     }
 
+    fn bind_group_layout_entries(
+        &self,
+        bind_group_layout: &ObjectId,
+        bind_group_layout_data: &crate::Data,
+    ) -> Option<Vec<wgt::BindGroupLayoutEntry>> {
+        let bind_group_layout = <T::BindGroupLayoutId>::from(*bind_group_layout);
+        let bind_group_layout_data = downcast_ref(bind_group_layout_data);
+        Context::bind_group_layout_entries(self, &bind_group_layout, bind_group_layout_data)
+    }
+
+    fn pipeline_layout_get_bind_group_layouts(
+        &self,
+        pipeline_layout: &ObjectId,
+        pipeline_layout_data: &crate::Data,
+    ) -> Option<Vec<(ObjectId, Box<crate::Data>)>> {
+        let pipeline_layout = <T::PipelineLayoutId>::from(*pipeline_layout);
+        let pipeline_layout_data = downcast_ref(pipeline_layout_data);
+        let layouts = Context::pipeline_layout_get_bind_group_layouts(
+            self,
+            &pipeline_layout,
+            pipeline_layout_data,
+        )?;
+        Some(
+            layouts
+                .into_iter()
+                .map(|(bind_group_layout, data)| (bind_group_layout.into(), Box::new(data) as _))
+                .collect(),
+        )
+    }
+
     fn command_encoder_copy_buffer_to_buffer(
         &self,
         encoder: &ObjectId,
@@ -2999,6 +3245,28 @@ where
         Context::queue_copy_external_image_to_texture(self, &queue, queue_data, source, dest, size)
     }
 
+    fn queue_update_bind_group(
+        &self,
+        queue: &ObjectId,
+        queue_data: &crate::Data,
+        bind_group: &ObjectId,
+        bind_group_data: &crate::Data,
+        entries: &[crate::BindGroupUpdateEntry],
+    ) {
+        let queue = <T::QueueId>::from(*queue);
+        let queue_data = downcast_ref(queue_data);
+        let bind_group = <T::BindGroupId>::from(*bind_group);
+        let bind_group_data = downcast_ref(bind_group_data);
+        Context::queue_update_bind_group(
+            self,
+            &queue,
+            queue_data,
+            &bind_group,
+            bind_group_data,
+            entries,
+        )
+    }
+
     fn queue_submit<'a>(
         &self,
         queue: &ObjectId,
@@ -15,6 +15,7 @@ mod macros;
 use std::{
     any::Any,
     borrow::Cow,
+    collections::HashMap,
     error,
     fmt::{Debug, Display},
     future::Future,
@@ -29,23 +30,24 @@ use context::{Context, DeviceRequest, DynContext, ObjectId};
 use parking_lot::Mutex;
 
 pub use wgt::{
-    AdapterInfo, AddressMode, AstcBlock, AstcChannel, Backend, Backends, BindGroupLayoutEntry,
-    BindingType, BlendComponent, BlendFactor, BlendOperation, BlendState, BufferAddress,
-    BufferBindingType, BufferSize, BufferUsages, Color, ColorTargetState, ColorWrites,
-    CommandBufferDescriptor, CompareFunction, CompositeAlphaMode, DepthBiasState,
-    DepthStencilState, DeviceType, DownlevelCapabilities, DownlevelFlags, Dx12Compiler,
-    DynamicOffset, Extent3d, Face, Features, FilterMode, FrontFace, ImageDataLayout,
-    ImageSubresourceRange, IndexFormat, InstanceDescriptor, Limits, MultisampleState, Origin2d,
-    Origin3d, PipelineStatisticsTypes, PolygonMode, PowerPreference, PredefinedColorSpace,
-    PresentMode, PresentationTimestamp, PrimitiveState, PrimitiveTopology, PushConstantRange,
-    QueryType, RenderBundleDepthStencil, SamplerBindingType, SamplerBorderColor, ShaderLocation,
-    ShaderModel, ShaderStages, StencilFaceState, StencilOperation, StencilState,
-    StorageTextureAccess, SurfaceCapabilities, SurfaceStatus, TextureAspect, TextureDimension,
-    TextureFormat, TextureFormatFeatureFlags, TextureFormatFeatures, TextureSampleType,
-    TextureUsages, TextureViewDimension, VertexAttribute, VertexFormat, VertexStepMode,
-    WasmNotSend, WasmNotSync, COPY_BUFFER_ALIGNMENT, COPY_BYTES_PER_ROW_ALIGNMENT, MAP_ALIGNMENT,
-    PUSH_CONSTANT_ALIGNMENT, QUERY_RESOLVE_BUFFER_ALIGNMENT, QUERY_SET_MAX_QUERIES, QUERY_SIZE,
-    VERTEX_STRIDE_ALIGNMENT,
+    AdapterInfo, AddressMode, AllocatorConfig, AstcBlock, AstcChannel, Backend, Backends,
+    BindGroupLayoutEntry, BindingReflection, BindingType, BlendComponent, BlendFactor,
+    BlendOperation, BlendState, BufferAddress, BufferBindingType, BufferSize, BufferUsages, Color,
+    ColorTargetState, ColorWrites, CommandBufferDescriptor, CompareFunction, CompositeAlphaMode,
+    DepthBiasState, DepthStencilState, DeviceType, DownlevelCapabilities, DownlevelFlags,
+    Dx12Compiler, DynamicOffset, EntryPointReflection, Extent3d, Face, Features, FilterMode,
+    FragmentOutputReflection, FrontFace, GlesProgramBinaryStore, GlesProgramCache, ImageDataLayout,
+    ImageSubresourceRange, IndexFormat, InstanceDescriptor, Limits, MemoryHeapReport, MemoryHints,
+    MemoryReport, MultisampleState, Origin2d, Origin3d, PipelineStatisticsTypes, PolygonMode,
+    PowerPreference, PredefinedColorSpace, PresentMode, PresentationTimestamp, PrimitiveState,
+    PrimitiveTopology, PushConstantRange, QueryType, RenderBundleDepthStencil, SamplerBindingType,
+    SamplerBorderColor, ShaderLocation, ShaderModel, ShaderModuleReflection, ShaderStages,
+    StencilFaceState, StencilOperation, StencilState, StorageTextureAccess, SurfaceCapabilities,
+    SurfaceStatus, TextureAspect, TextureDimension, TextureFormat, TextureFormatFeatureFlags,
+    TextureFormatFeatures, TextureSampleType, TextureUsages, TextureViewDimension, VertexAttribute,
+    VertexFormat, VertexInputReflection, VertexStepMode, WasmNotSend, WasmNotSync,
+    COPY_BUFFER_ALIGNMENT, COPY_BYTES_PER_ROW_ALIGNMENT, MAP_ALIGNMENT, PUSH_CONSTANT_ALIGNMENT,
+    QUERY_RESOLVE_BUFFER_ALIGNMENT, QUERY_SET_MAX_QUERIES, QUERY_SIZE, VERTEX_STRIDE_ALIGNMENT,
 };
 
 #[cfg(any(
@@ -75,6 +77,9 @@ pub enum ErrorFilter {
     OutOfMemory,
     /// Catch only validation errors.
     Validation,
+    /// Catch only internal errors, raised when the implementation fails for a
+    /// reason unrelated to the use of the API.
+    Internal,
 }
 static_assertions::assert_impl_all!(ErrorFilter: Send, Sync);
 
@@ -451,6 +456,31 @@ impl Drop for BindGroupLayout {
     }
 }
 
+impl BindGroupLayout {
+    /// Returns the entries of the layout, sorted by binding.
+    ///
+    /// Returns `None` for an invalid layout, and on the WebGPU backend, which
+    /// doesn't expose the entries of layouts.
+    pub fn entries(&self) -> Option<Vec<BindGroupLayoutEntry>> {
+        DynContext::bind_group_layout_entries(&*self.context, &self.id, self.data.as_ref())
+    }
+}
+
+/// Layouts are equal if bind groups created with one of them can be used
+/// where the other is expected.
+///
+/// `wgpu-core` deduplicates the layouts of a device by their entries, so
+/// layouts created from the same entries, and the layouts returned by
+/// `get_bind_group_layout` on pipelines with compatible layouts, are equal.
+/// On the WebGPU backend, a layout is only equal to itself.
+impl PartialEq for BindGroupLayout {
+    fn eq(&self, other: &Self) -> bool {
+        self.id == other.id
+    }
+}
+
+impl Eq for BindGroupLayout {}
+
 /// Handle to a binding group.
 ///
 /// A `BindGroup` represents the set of resources bound to the bindings described by a
@@ -514,6 +544,81 @@ impl Drop for ShaderModule {
     }
 }
 
+impl ShaderModule {
+    /// Get the compilation info for the shader module.
+    ///
+    /// This contains the errors of a module that failed to be created, and is
+    /// empty for a valid one, as naga doesn't report warnings.
+    pub fn get_compilation_info(&self) -> impl Future<Output = CompilationInfo> + WasmNotSend {
+        self.context
+            .shader_get_compilation_info(&self.id, self.data.as_ref())
+    }
+
+    /// Describes the entry points of the shader module, with the bindings,
+    /// vertex inputs and fragment outputs they use.
+    ///
+    /// This can be used to build bind group layouts and vertex buffer layouts
+    /// matching the shader. Returns `None` for an invalid module, a module
+    /// created from SPIR-V passed through to the backend, and on the WebGPU
+    /// backend, which doesn't expose the interface of modules.
+    pub fn reflect(&self) -> Option<ShaderModuleReflection> {
+        DynContext::shader_module_reflect(&*self.context, &self.id, self.data.as_ref())
+    }
+}
+
+/// Compilation information for a shader module.
+///
+/// Corresponds to [WebGPU `GPUCompilationInfo`](
+/// https://gpuweb.github.io/gpuweb/#gpucompilationinfo).
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct CompilationInfo {
+    /// The messages from the shader compilation process.
+    pub messages: Vec<CompilationMessage>,
+}
+
+/// A single message from the shader compilation process.
+///
+/// Corresponds to [WebGPU `GPUCompilationMessage`](
+/// https://gpuweb.github.io/gpuweb/#gpucompilationmessage).
+#[derive(Clone, Debug, PartialEq)]
+pub struct CompilationMessage {
+    /// The text of the message.
+    pub message: String,
+    /// The type of the message.
+    pub message_type: CompilationMessageType,
+    /// Where in the source code the message points at, if anywhere.
+    pub location: Option<SourceLocation>,
+}
+
+/// The type of a compilation message.
+///
+/// Corresponds to [WebGPU `GPUCompilationMessageType`](
+/// https://gpuweb.github.io/gpuweb/#enumdef-gpucompilationmessagetype).
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum CompilationMessageType {
+    /// An error message.
+    Error,
+    /// A warning message.
+    Warning,
+    /// An informational message.
+    Info,
+}
+
+/// A span of the shader source a compilation message points at.
+///
+/// On the web, positions are measured in UTF-16 code units, as reported by the browser.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct SourceLocation {
+    /// 1-based line number.
+    pub line_number: u32,
+    /// 1-based column of the start of the span, in characters.
+    pub line_position: u32,
+    /// 0-based offset of the start of the span, in bytes.
+    pub offset: u32,
+    /// Length of the span, in bytes.
+    pub length: u32,
+}
+
 /// Source of a shader module.
 ///
 /// The source will be parsed and validated.
@@ -583,6 +688,86 @@ pub struct ShaderModuleDescriptorSpirV<'a> {
 }
 static_assertions::assert_impl_all!(ShaderModuleDescriptorSpirV: Send, Sync);
 
+/// Binds a resource of a passthrough shader to an entry of the pipeline layout.
+///
+/// For use with [`ShaderModuleDescriptorPassthrough`].
+pub type PassthroughBinding<'a> = wgt::PassthroughBinding<&'a str>;
+
+/// Descriptor for a shader module given by backend-native shaders, for use with
+/// [`Device::create_shader_module_passthrough`].
+///
+/// Only the shader for the backend of the device is used, the others can be left
+/// to `None`.
+///
+/// Except on GL, where [`bindings`](Self::bindings) maps them by name, the
+/// resources of the shader must be declared where the backend places the
+/// entries of the pipeline layout:
+///
+/// - Vulkan: the descriptor set is the index of the bind group, and the binding
+///   is the binding of the entry.
+/// - Metal: the buffer, texture and sampler indices are counted separately for
+///   each stage, through the bind groups in order and their entries visible to
+///   the stage by increasing binding. Push constants take buffer 0 when the
+///   stage has any, and vertex buffers take the highest buffer indices.
+/// - DX12: the `b`, `t`, `u` and `s` registers of `space0` are counted
+///   separately, through the bind groups in order and, in each of them, the
+///   entries without a dynamic offset by increasing binding, then the samplers,
+///   then the buffers with a dynamic offset. Push constants take `b0`, moving
+///   the other constant buffers to `space1`.
+///
+/// Pipeline-overridable constants can only be set for SPIR-V shaders.
+///
+/// This type is unique to the Rust API of `wgpu`. In the WebGPU specification,
+/// only WGSL source code strings are accepted.
+#[derive(Clone, Debug)]
+pub struct ShaderModuleDescriptorPassthrough<'a> {
+    /// Debug label of the shader module. This will show up in graphics debuggers for easy identification.
+    pub label: Label<'a>,
+    /// Stage of the GLSL shader or DXIL bytecode, whose entry point is `main`.
+    pub stage: ShaderStages,
+    /// Workgroup size of the compute entry points of the MSL shader, which is
+    /// not part of their declaration.
+    pub workgroup_size: [u32; 3],
+    /// Binary SPIR-V data in 4-byte words, used on Vulkan.
+    pub spirv: Option<Cow<'a, [u32]>>,
+    /// Metal Shading Language source, used on Metal.
+    pub msl: Option<Cow<'a, str>>,
+    /// DXIL bytecode of a single entry point of [`stage`](Self::stage), used on
+    /// DX12 in place of `hlsl`.
+    pub dxil: Option<Cow<'a, [u8]>>,
+    /// HLSL source, used on DX12 and compiled for each pipeline stage using it.
+    pub hlsl: Option<Cow<'a, str>>,
+    /// GLSL ES source, used on GL.
+    pub glsl: Option<Cow<'a, str>>,
+    /// Maps the resources declared by the GLSL shader to the bindings of the
+    /// pipeline layout.
+    ///
+    /// Uniform blocks and textures are bound by name to the slots of the layout.
+    /// Storage buffers and storage textures can't be rebound, so they must be
+    /// declared at the slots GL assigns to the layout: storage buffer bindings
+    /// and image units are counted separately, through the bind groups in order
+    /// and their entries by increasing binding. Creating the module fails on
+    /// other backends if the map isn't empty.
+    pub bindings: &'a [PassthroughBinding<'a>],
+}
+static_assertions::assert_impl_all!(ShaderModuleDescriptorPassthrough: Send, Sync);
+
+impl Default for ShaderModuleDescriptorPassthrough<'_> {
+    fn default() -> Self {
+        Self {
+            label: None,
+            stage: ShaderStages::NONE,
+            workgroup_size: [1; 3],
+            spirv: None,
+            msl: None,
+            dxil: None,
+            hlsl: None,
+            glsl: None,
+            bindings: &[],
+        }
+    }
+}
+
 /// Handle to a pipeline layout.
 ///
 /// A `PipelineLayout` object describes the available binding groups of a pipeline.
@@ -613,6 +798,30 @@ impl Drop for PipelineLayout {
     }
 }
 
+impl PipelineLayout {
+    /// Returns the bind group layouts of the pipeline layout, in group order.
+    ///
+    /// Returns `None` for an invalid layout, and on the WebGPU backend, which
+    /// doesn't expose the bind group layouts of pipeline layouts.
+    pub fn bind_group_layouts(&self) -> Option<Vec<BindGroupLayout>> {
+        let layouts = DynContext::pipeline_layout_get_bind_group_layouts(
+            &*self.context,
+            &self.id,
+            self.data.as_ref(),
+        )?;
+        Some(
+            layouts
+                .into_iter()
+                .map(|(id, data)| BindGroupLayout {
+                    context: Arc::clone(&self.context),
+                    id,
+                    data,
+                })
+                .collect(),
+        )
+    }
+}
+
 /// Handle to a rendering (graphics) pipeline.
 ///
 /// A `RenderPipeline` object represents a graphics pipeline and its stages, bindings, vertex
@@ -1319,6 +1528,29 @@ pub struct BindGroupDescriptor<'a> {
 ))]
 static_assertions::assert_impl_all!(BindGroupDescriptor: Send, Sync);
 
+/// Elements of a binding array to replace in a bind group.
+///
+/// For use with [`Queue::update_bind_group`].
+#[derive(Clone, Debug)]
+pub struct BindGroupUpdateEntry<'a> {
+    /// Slot of the binding array. Corresponds to an entry of the same binding
+    /// index in the [`BindGroupLayoutDescriptor`].
+    pub binding: u32,
+    /// Index of the first replaced element of the array.
+    pub array_offset: u32,
+    /// New resources of the elements, either a single resource or an array of
+    /// them.
+    pub resource: BindingResource<'a>,
+}
+#[cfg(any(
+    not(target_arch = "wasm32"),
+    all(
+        feature = "fragile-send-sync-non-atomic-wasm",
+        not(target_feature = "atomics")
+    )
+))]
+static_assertions::assert_impl_all!(BindGroupUpdateEntry: Send, Sync);
+
 /// Describes the attachments of a render pass.
 ///
 /// For use with [`CommandEncoder::begin_render_pass`].
@@ -1376,6 +1608,9 @@ pub struct VertexState<'a> {
     /// The name of the entry point in the compiled shader. There must be a function with this name
     /// in the shader.
     pub entry_point: &'a str,
+    /// Values of the pipeline-overridable constants of the module, keyed by their name
+    /// or their numeric identifier. Overrides that aren't given keep their default value.
+    pub constants: &'a HashMap<String, f64>,
     /// The format of any vertex buffers used with this pipeline.
     pub buffers: &'a [VertexBufferLayout<'a>],
 }
@@ -1401,6 +1636,9 @@ pub struct FragmentState<'a> {
     /// The name of the entry point in the compiled shader. There must be a function with this name
     /// in the shader.
     pub entry_point: &'a str,
+    /// Values of the pipeline-overridable constants of the module, keyed by their name
+    /// or their numeric identifier. Overrides that aren't given keep their default value.
+    pub constants: &'a HashMap<String, f64>,
     /// The color state of the render targets.
     pub targets: &'a [Option<ColorTargetState>],
 }
@@ -1478,6 +1716,9 @@ pub struct ComputePipelineDescriptor<'a> {
     /// The name of the entry point in the compiled shader. There must be a function with this name
     /// and no return value in the shader.
     pub entry_point: &'a str,
+    /// Values of the pipeline-overridable constants of the module, keyed by their name
+    /// or their numeric identifier. Overrides that aren't given keep their default value.
+    pub constants: &'a HashMap<String, f64>,
 }
 #[cfg(any(
     not(target_arch = "wasm32"),
@@ -2246,6 +2487,14 @@ impl Device {
         DynContext::device_limits(&*self.context, &self.id, self.data.as_ref())
     }
 
+    /// Get the memory heaps of this device and how much of them wgpu is using.
+    ///
+    /// Heap usage and budget are only reported on Vulkan (with `VK_EXT_memory_budget`),
+    /// DX12 and Metal. Buffer and texture totals are not tracked on GL and the web.
+    pub fn memory_report(&self) -> MemoryReport {
+        DynContext::device_memory_report(&*self.context, &self.id, self.data.as_ref())
+    }
+
     /// Creates a shader module from either SPIR-V or WGSL source code.
     pub fn create_shader_module(&self, desc: ShaderModuleDescriptor) -> ShaderModule {
         let (id, data) = DynContext::device_create_shader_module(
@@ -2317,6 +2566,34 @@ impl Device {
         }
     }
 
+    /// Creates a shader module from backend-native shaders directly.
+    ///
+    /// Requires [`Features::SHADER_PASSTHROUGH`].
+    ///
+    /// # Safety
+    ///
+    /// This function passes the shader for the backend of the device as-is and can
+    /// potentially result in a driver crash or bogus behaviour. The shader is not
+    /// validated against the pipelines using it.
+    pub unsafe fn create_shader_module_passthrough(
+        &self,
+        desc: &ShaderModuleDescriptorPassthrough,
+    ) -> ShaderModule {
+        let (id, data) = unsafe {
+            DynContext::device_create_shader_module_passthrough(
+                &*self.context,
+                &self.id,
+                self.data.as_ref(),
+                desc,
+            )
+        };
+        ShaderModule {
+            context: Arc::clone(&self.context),
+            id,
+            data,
+        }
+    }
+
     /// Creates an empty [`CommandEncoder`].
     pub fn create_command_encoder(&self, desc: &CommandEncoderDescriptor) -> CommandEncoder {
         let (id, data) = DynContext::device_create_command_encoder(
@@ -2412,6 +2689,31 @@ impl Device {
         }
     }
 
+    /// Creates a [`RenderPipeline`] without blocking on the compilation of
+    /// its shaders.
+    ///
+    /// Unlike [`Device::create_render_pipeline`], an invalid descriptor is
+    /// reported by the returned future, instead of the error scopes.
+    ///
+    /// On native, the backend pipeline is compiled on a separate thread, so
+    /// the future may resolve without the device being polled.
+    pub fn create_render_pipeline_async(
+        &self,
+        desc: &RenderPipelineDescriptor,
+    ) -> impl Future<Output = Result<RenderPipeline, Error>> + WasmNotSend {
+        let context = Arc::clone(&self.context);
+        let future = DynContext::device_create_render_pipeline_async(
+            &*self.context,
+            &self.id,
+            self.data.as_ref(),
+            desc,
+        );
+        async move {
+            let (id, data) = future.await?;
+            Ok(RenderPipeline { context, id, data })
+        }
+    }
+
     /// Creates a [`ComputePipeline`].
     pub fn create_compute_pipeline(&self, desc: &ComputePipelineDescriptor) -> ComputePipeline {
         let (id, data) = DynContext::device_create_compute_pipeline(
@@ -2427,6 +2729,27 @@ impl Device {
         }
     }
 
+    /// Creates a [`ComputePipeline`] without blocking on the compilation of
+    /// its shader.
+    ///
+    /// See [`Device::create_render_pipeline_async`].
+    pub fn create_compute_pipeline_async(
+        &self,
+        desc: &ComputePipelineDescriptor,
+    ) -> impl Future<Output = Result<ComputePipeline, Error>> + WasmNotSend {
+        let context = Arc::clone(&self.context);
+        let future = DynContext::device_create_compute_pipeline_async(
+            &*self.context,
+            &self.id,
+            self.data.as_ref(),
+            desc,
+        );
+        async move {
+            let (id, data) = future.await?;
+            Ok(ComputePipeline { context, id, data })
+        }
+    }
+
     /// Creates a [`Buffer`].
     pub fn create_buffer(&self, desc: &BufferDescriptor) -> Buffer {
         let mut map_context = MapContext::new(desc.size);
@@ -3601,6 +3924,8 @@ impl<'a> RenderPass<'a> {
 }
 
 /// [`Features::MULTI_DRAW_INDIRECT`] must be enabled on the device in order to call these functions.
+/// Adapters reporting [`DownlevelFlags::EMULATED_MULTI_DRAW_INDIRECT`] don't expose the feature,
+/// but accept it in [`Adapter::request_device`], and the draws are then issued one at a time.
 impl<'a> RenderPass<'a> {
     /// Dispatches multiple draw calls from the active vertex buffer(s) based on the contents of the `indirect_buffer`.
     /// `count` draw calls are issued.
@@ -4432,6 +4757,28 @@ impl Queue {
         )
     }
 
+    /// Replaces elements of binding arrays of `bind_group`.
+    ///
+    /// Requires [`Features::UPDATE_AFTER_BIND_DESCRIPTORS`]. Only the binding
+    /// arrays of textures, samplers and storage buffers of layouts without
+    /// dynamic offsets can be updated.
+    ///
+    /// The update happens immediately, which avoids recreating large bindless
+    /// bind groups when one of their resources changes. It is a validation
+    /// error to update a bind group which is used by a submission that hasn't
+    /// completed yet, or by a command buffer or render bundle that was
+    /// recorded but not submitted yet.
+    pub fn update_bind_group(&self, bind_group: &BindGroup, entries: &[BindGroupUpdateEntry]) {
+        DynContext::queue_update_bind_group(
+            &*self.context,
+            &self.id,
+            self.data.as_ref(),
+            &bind_group.id,
+            bind_group.data.as_ref(),
+            entries,
+        )
+    }
+
     /// Submits a series of finished command buffers for execution.
     pub fn submit<I: IntoIterator<Item = CommandBuffer>>(
         &self,
@@ -4961,6 +5308,31 @@ pub enum Error {
         /// Description of the validation error.
         description: String,
     },
+    /// Internal error, signifying a failure of the implementation which isn't
+    /// caused by invalid use of the API, like a shader that can't be translated
+    /// for the backend or a lost device
+    Internal {
+        /// Lower level source of the error.
+        #[cfg(any(
+            not(target_arch = "wasm32"),
+            all(
+                feature = "fragile-send-sync-non-atomic-wasm",
+                not(target_feature = "atomics")
+            )
+        ))]
+        source: Box<dyn error::Error + Send + 'static>,
+        /// Lower level source of the error.
+        #[cfg(not(any(
+            not(target_arch = "wasm32"),
+            all(
+                feature = "fragile-send-sync-non-atomic-wasm",
+                not(target_feature = "atomics")
+            )
+        )))]
+        source: Box<dyn error::Error + 'static>,
+        /// Description of the internal error.
+        description: String,
+    },
 }
 #[cfg(any(
     not(target_arch = "wasm32"),
@@ -4976,6 +5348,7 @@ impl error::Error for Error {
         match self {
             Error::OutOfMemory { source } => Some(source.as_ref()),
             Error::Validation { source, .. } => Some(source.as_ref()),
+            Error::Internal { source, .. } => Some(source.as_ref()),
         }
     }
 }
@@ -4985,6 +5358,7 @@ impl Display for Error {
         match self {
             Error::OutOfMemory { .. } => f.write_str("Out of Memory"),
             Error::Validation { description, .. } => f.write_str(description),
+            Error::Internal { description, .. } => f.write_str(description),
         }
     }
 }
@@ -105,6 +105,7 @@ impl StagingBelt {
                         size,
                         usage: BufferUsages::MAP_WRITE | BufferUsages::COPY_SRC,
                         mapped_at_creation: true,
+                        memory_hints: crate::MemoryHints::Default,
                     })),
                     size,
                     offset: 0,
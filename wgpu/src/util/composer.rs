@@ -0,0 +1,613 @@
+use crate::{ShaderSource, SourceLocation};
+use std::{
+    borrow::Cow,
+    collections::{HashMap, HashSet},
+    error, fmt,
+};
+
+/// Builds WGSL shaders from a set of modules, with C-like preprocessor
+/// directives.
+///
+/// Directives are lines starting with `#`:
+/// - `#include "name"` inserts the module registered as `name`, and
+///   `#import name` does the same unless the module was already inserted.
+/// - `#define NAME value` and `#undef NAME` change the defines for the rest of
+///   the composition.
+/// - `#ifdef NAME`, `#ifndef NAME`, `#if condition`, `#elif condition`, `#else`
+///   and `#endif` keep or discard the lines they enclose.
+///
+/// A condition is made of comparisons (`==`, `!=`, `<`, `<=`, `>`, `>=`),
+/// `defined(NAME)` and single values, combined with `!`, `&&` and `||`. A name
+/// stands for its value when it is defined, and values are compared as
+/// integers when both sides are integers. A single value is true if it is
+/// defined to anything other than an empty string, `0` or `false`.
+///
+/// Defines are not substituted in the source, so the lines of the composed
+/// shader are exactly those of its modules, and [`ComposedWgsl::locate`] can
+/// map the locations of compilation messages back to them.
+#[derive(Clone, Debug, Default)]
+pub struct WgslComposer {
+    modules: HashMap<String, String>,
+    defines: HashMap<String, String>,
+}
+
+impl WgslComposer {
+    /// Creates a composer without any module or define.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Registers a module which can be included under `name`.
+    pub fn add_module(&mut self, name: impl Into<String>, source: impl Into<String>) {
+        self.modules.insert(name.into(), source.into());
+    }
+
+    /// Sets the value of a define for the shaders composed afterwards.
+    pub fn define(&mut self, name: impl Into<String>, value: impl Into<String>) {
+        self.defines.insert(name.into(), value.into());
+    }
+
+    /// Composes the shader `source`, using `name` to refer to it in locations
+    /// and errors.
+    pub fn compose(&self, name: &str, source: &str) -> Result<ComposedWgsl, ComposeError> {
+        let mut state = ComposeState {
+            composer: self,
+            defines: self.defines.clone(),
+            imported: HashSet::new(),
+            stack: Vec::new(),
+            output: ComposedWgsl {
+                source: String::new(),
+                files: Vec::new(),
+                lines: Vec::new(),
+            },
+        };
+        state.compose(name, source)?;
+        Ok(state.output)
+    }
+}
+
+/// A shader built by [`WgslComposer::compose`].
+#[derive(Clone, Debug)]
+pub struct ComposedWgsl {
+    source: String,
+    files: Vec<String>,
+    lines: Vec<ComposedLine>,
+}
+
+/// Origin of a line of a composed shader.
+#[derive(Clone, Copy, Debug)]
+struct ComposedLine {
+    file: usize,
+    line_number: u32,
+    /// Offset of the line in its module.
+    offset: u32,
+    /// Offset of the line in the composed source.
+    composed_offset: u32,
+}
+
+/// Location in one of the modules of a [`ComposedWgsl`].
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct ComposedLocation<'a> {
+    /// Name of the module.
+    pub file: &'a str,
+    /// 1-based line number in the module.
+    pub line_number: u32,
+    /// 1-based column of the start of the span, in characters.
+    pub line_position: u32,
+    /// 0-based offset of the start of the span in the module, in bytes.
+    pub offset: u32,
+    /// Length of the span, in bytes.
+    pub length: u32,
+}
+
+impl ComposedWgsl {
+    /// Returns the composed WGSL source.
+    pub fn source(&self) -> &str {
+        &self.source
+    }
+
+    /// Returns the composed source, for a [`crate::ShaderModuleDescriptor`].
+    pub fn shader_source(&self) -> ShaderSource<'_> {
+        ShaderSource::Wgsl(Cow::Borrowed(&self.source))
+    }
+
+    /// Maps a location in the composed source, such as the one of a
+    /// [`crate::CompilationMessage`], to the module it comes from.
+    pub fn locate(&self, location: SourceLocation) -> Option<ComposedLocation<'_>> {
+        let line = self
+            .lines
+            .get((location.line_number as usize).checked_sub(1)?)?;
+        let offset = location.offset.checked_sub(line.composed_offset)?;
+        Some(ComposedLocation {
+            file: &self.files[line.file],
+            line_number: line.line_number,
+            line_position: location.line_position,
+            offset: line.offset + offset,
+            length: location.length,
+        })
+    }
+}
+
+/// Error when composing a WGSL shader.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ComposeError {
+    /// Name of the module containing the error.
+    pub file: String,
+    /// 1-based line number of the error in the module.
+    pub line_number: u32,
+    /// What went wrong.
+    pub kind: ComposeErrorKind,
+}
+
+/// Kind of a [`ComposeError`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum ComposeErrorKind {
+    /// No module is registered with this name.
+    UnknownModule(String),
+    /// The module includes itself, directly or not.
+    RecursiveInclude(String),
+    /// The directive isn't supported.
+    UnknownDirective(String),
+    /// The directive is missing its argument, or has an invalid one.
+    InvalidDirective(String),
+    /// The condition of an `#if` or `#elif` couldn't be evaluated.
+    InvalidCondition(String),
+    /// An `#elif`, `#else` or `#endif` doesn't match any `#if`.
+    UnmatchedDirective(String),
+    /// An `#if` isn't closed by an `#endif` before the end of its module.
+    UnterminatedConditional,
+}
+
+impl fmt::Display for ComposeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}:{}: ", self.file, self.line_number)?;
+        match self.kind {
+            ComposeErrorKind::UnknownModule(ref name) => write!(f, "Unknown module {name:?}"),
+            ComposeErrorKind::RecursiveInclude(ref name) => {
+                write!(f, "Module {name:?} includes itself")
+            }
+            ComposeErrorKind::UnknownDirective(ref directive) => {
+                write!(f, "Unknown directive `#{directive}`")
+            }
+            ComposeErrorKind::InvalidDirective(ref line) => write!(f, "Invalid directive `{line}`"),
+            ComposeErrorKind::InvalidCondition(ref condition) => {
+                write!(f, "Invalid condition `{condition}`")
+            }
+            ComposeErrorKind::UnmatchedDirective(ref directive) => {
+                write!(f, "`#{directive}` without a matching `#if`")
+            }
+            ComposeErrorKind::UnterminatedConditional => write!(f, "`#if` without an `#endif`"),
+        }
+    }
+}
+
+impl error::Error for ComposeError {}
+
+/// State of an `#if` block.
+struct Conditional {
+    /// Whether the lines of the current branch are kept.
+    active: bool,
+    /// Whether one of the branches was already kept.
+    taken: bool,
+    /// Whether the block itself is in kept lines.
+    parent_active: bool,
+    /// Whether the `#else` was reached.
+    has_else: bool,
+}
+
+struct ComposeState<'a> {
+    composer: &'a WgslComposer,
+    defines: HashMap<String, String>,
+    imported: HashSet<String>,
+    /// Modules being composed, for detecting recursive includes.
+    stack: Vec<String>,
+    output: ComposedWgsl,
+}
+
+impl ComposeState<'_> {
+    fn compose(&mut self, name: &str, source: &str) -> Result<(), ComposeError> {
+        let file = match self.output.files.iter().position(|file| file == name) {
+            Some(file) => file,
+            None => {
+                self.output.files.push(name.to_string());
+                self.output.files.len() - 1
+            }
+        };
+        self.stack.push(name.to_string());
+        self.imported.insert(name.to_string());
+
+        let mut conditionals = Vec::<Conditional>::new();
+        let mut offset = 0;
+        let mut line_number = 0;
+        let mut lines = source.split('\n').peekable();
+        while let Some(line) = lines.next() {
+            // Skip the empty piece after a final newline.
+            if line.is_empty() && lines.peek().is_none() {
+                break;
+            }
+            let line_offset = offset;
+            offset += line.len() + 1;
+            line_number += 1;
+
+            let error = |kind| ComposeError {
+                file: name.to_string(),
+                line_number,
+                kind,
+            };
+            let active = !matches!(conditionals.last(), Some(Conditional { active: false, .. }));
+
+            let directive = match line.trim().strip_prefix('#') {
+                Some(directive) => directive.trim_start(),
+                None => {
+                    if active {
+                        self.output.lines.push(ComposedLine {
+                            file,
+                            line_number,
+                            offset: line_offset as u32,
+                            composed_offset: self.output.source.len() as u32,
+                        });
+                        self.output.source.push_str(line);
+                        self.output.source.push('\n');
+                    }
+                    continue;
+                }
+            };
+            let (keyword, argument) = match directive.split_once(char::is_whitespace) {
+                Some((keyword, argument)) => (keyword, argument.trim()),
+                None => (directive, ""),
+            };
+            let invalid = || error(ComposeErrorKind::InvalidDirective(line.trim().to_string()));
+
+            match keyword {
+                "if" | "ifdef" | "ifndef" => {
+                    let condition = if !active {
+                        false
+                    } else if keyword == "if" {
+                        evaluate(&self.defines, argument).map_err(error)?
+                    } else {
+                        let name = identifier(argument).ok_or_else(invalid)?;
+                        self.defines.contains_key(name) == (keyword == "ifdef")
+                    };
+                    conditionals.push(Conditional {
+                        active: condition,
+                        taken: condition,
+                        parent_active: active,
+                        has_else: false,
+                    });
+                }
+                "elif" | "else" | "endif" => {
+                    let conditional = match conditionals.last_mut() {
+                        Some(conditional) if keyword == "endif" || !conditional.has_else => {
+                            conditional
+                        }
+                        _ => {
+                            return Err(error(ComposeErrorKind::UnmatchedDirective(
+                                keyword.to_string(),
+                            )))
+                        }
+                    };
+                    match keyword {
+                        "elif" => {
+                            conditional.active = conditional.parent_active
+                                && !conditional.taken
+                                && evaluate(&self.defines, argument).map_err(error)?;
+                            conditional.taken |= conditional.active;
+                        }
+                        "else" => {
+                            conditional.active = conditional.parent_active && !conditional.taken;
+                            conditional.has_else = true;
+                        }
+                        _ => {
+                            conditionals.pop();
+                        }
+                    }
+                }
+                _ if !active => {}
+                "define" => {
+                    let (name, value) = match argument.split_once(char::is_whitespace) {
+                        Some((name, value)) => (name, value.trim()),
+                        None => (argument, ""),
+                    };
+                    let name = identifier(name).ok_or_else(invalid)?;
+                    self.defines.insert(name.to_string(), value.to_string());
+                }
+                "undef" => {
+                    let name = identifier(argument).ok_or_else(invalid)?;
+                    self.defines.remove(name);
+                }
+                "include" | "import" => {
+                    let module = argument
+                        .strip_prefix('"')
+                        .and_then(|name| name.strip_suffix('"'))
+                        .or_else(|| {
+                            argument
+                                .strip_prefix('<')
+                                .and_then(|name| name.strip_suffix('>'))
+                        })
+                        .unwrap_or(argument);
+                    if module.is_empty() {
+                        return Err(invalid());
+                    }
+                    if self.stack.iter().any(|name| name == module) {
+                        return Err(error(ComposeErrorKind::RecursiveInclude(
+                            module.to_string(),
+                        )));
+                    }
+                    if keyword == "import" && self.imported.contains(module) {
+                        continue;
+                    }
+                    let composer = self.composer;
+                    let source = composer.modules.get(module).ok_or_else(|| {
+                        error(ComposeErrorKind::UnknownModule(module.to_string()))
+                    })?;
+                    self.compose(module, source)?;
+                }
+                _ => {
+                    return Err(error(ComposeErrorKind::UnknownDirective(
+                        keyword.to_string(),
+                    )))
+                }
+            }
+        }
+
+        if !conditionals.is_empty() {
+            return Err(ComposeError {
+                file: name.to_string(),
+                line_number,
+                kind: ComposeErrorKind::UnterminatedConditional,
+            });
+        }
+        self.stack.pop();
+        Ok(())
+    }
+}
+
+fn identifier(name: &str) -> Option<&str> {
+    let mut chars = name.chars();
+    let first = chars.next()?;
+    if (first.is_alphabetic() || first == '_') && chars.all(|c| c.is_alphanumeric() || c == '_') {
+        Some(name)
+    } else {
+        None
+    }
+}
+
+/// Evaluates the condition of an `#if` or `#elif`.
+fn evaluate(defines: &HashMap<String, String>, condition: &str) -> Result<bool, ComposeErrorKind> {
+    let invalid = || ComposeErrorKind::InvalidCondition(condition.to_string());
+    if condition.is_empty() {
+        return Err(invalid());
+    }
+    let value = |token: &str| -> Option<String> {
+        let token = token.trim();
+        if token.is_empty() {
+            None
+        } else if let Some(value) = defines.get(token) {
+            Some(value.clone())
+        } else {
+            Some(token.to_string())
+        }
+    };
+
+    let mut any = false;
+    for alternative in condition.split("||") {
+        let mut all = true;
+        for term in alternative.split("&&") {
+            let mut term = term.trim();
+            let mut negated = false;
+            while let Some(rest) = term.strip_prefix('!') {
+                if rest.starts_with('=') {
+                    break;
+                }
+                negated = !negated;
+                term = rest.trim_start();
+            }
+
+            let result = if let Some(name) = term
+                .strip_prefix("defined")
+                .map(str::trim_start)
+                .and_then(|rest| rest.strip_prefix('('))
+                .and_then(|rest| rest.strip_suffix(')'))
+            {
+                let name = identifier(name.trim()).ok_or_else(invalid)?;
+                defines.contains_key(name)
+            } else if let Some((left, operator, right)) = ["==", "!=", "<=", ">=", "<", ">"]
+                .iter()
+                .find_map(|&operator| {
+                    let (left, right) = term.split_once(operator)?;
+                    Some((left, operator, right))
+                })
+            {
+                let left = value(left).ok_or_else(invalid)?;
+                let right = value(right).ok_or_else(invalid)?;
+                match (left.parse::<i64>(), right.parse::<i64>()) {
+                    (Ok(left), Ok(right)) => match operator {
+                        "==" => left == right,
+                        "!=" => left != right,
+                        "<=" => left <= right,
+                        ">=" => left >= right,
+                        "<" => left < right,
+                        _ => left > right,
+                    },
+                    _ => match operator {
+                        "==" => left == right,
+                        "!=" => left != right,
+                        _ => return Err(invalid()),
+                    },
+                }
+            } else {
+                if term.is_empty() {
+                    return Err(invalid());
+                }
+                match defines.get(term) {
+                    Some(value) => !matches!(value.trim(), "" | "0" | "false"),
+                    None => match term.parse::<i64>() {
+                        Ok(value) => value != 0,
+                        Err(_) => term == "true",
+                    },
+                }
+            };
+            all &= result != negated;
+        }
+        any |= all;
+    }
+    Ok(any)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn composer() -> WgslComposer {
+        let mut composer = WgslComposer::new();
+        composer.add_module("common", "const PI: f32 = 3.14;\n");
+        composer.add_module(
+            "lighting",
+            "#import common\n#ifdef SHADOWS\nfn shadow() {}\n#endif\nfn light() {}\n",
+        );
+        composer
+    }
+
+    #[test]
+    fn include_and_import() {
+        let composer = composer();
+        let composed = composer
+            .compose("main", "#import common\n#import lighting\nfn main() {}")
+            .unwrap();
+        assert_eq!(
+            composed.source(),
+            "const PI: f32 = 3.14;\nfn light() {}\nfn main() {}\n"
+        );
+
+        let composed = composer
+            .compose("main", "#include \"common\"\n#include <common>\n")
+            .unwrap();
+        assert_eq!(
+            composed.source(),
+            "const PI: f32 = 3.14;\nconst PI: f32 = 3.14;\n"
+        );
+    }
+
+    #[test]
+    fn conditionals() {
+        let mut composer = composer();
+        composer.define("SHADOWS", "1");
+        composer.define("QUALITY", "2");
+        composer.define("MODE", "fast");
+        let source = "\
+#import lighting
+#if QUALITY >= 2 && MODE == fast
+a
+#elif defined(SHADOWS)
+b
+#else
+c
+#endif
+#if !SHADOWS || QUALITY < 1
+d
+#elif MODE != fast
+e
+#else
+#define LATE 1
+#endif
+#ifndef LATE
+f
+#endif
+#if LATE
+g
+#endif
+";
+        let composed = composer.compose("main", source).unwrap();
+        assert_eq!(
+            composed.source(),
+            "const PI: f32 = 3.14;\nfn shadow() {}\nfn light() {}\na\ng\n"
+        );
+    }
+
+    #[test]
+    fn locations() {
+        let composer = composer();
+        let composed = composer
+            .compose("main", "#import lighting\n\nfn main() { x }\n")
+            .unwrap();
+        // `light` on the second line of the composed source.
+        let offset = composed.source().find("light").unwrap() as u32;
+        let location = composed
+            .locate(SourceLocation {
+                line_number: 2,
+                line_position: 4,
+                offset,
+                length: 5,
+            })
+            .unwrap();
+        assert_eq!(
+            location,
+            ComposedLocation {
+                file: "lighting",
+                line_number: 5,
+                line_position: 4,
+                offset: 55,
+                length: 5,
+            }
+        );
+
+        let offset = composed.source().find('x').unwrap() as u32;
+        let location = composed
+            .locate(SourceLocation {
+                line_number: 4,
+                line_position: 13,
+                offset,
+                length: 1,
+            })
+            .unwrap();
+        assert_eq!(location.file, "main");
+        assert_eq!(location.line_number, 3);
+        assert_eq!(location.offset, 30);
+    }
+
+    #[test]
+    fn errors() {
+        let mut composer = composer();
+        composer.add_module("a", "#include b\n");
+        composer.add_module("b", "\n#include a\n");
+        let error = |source| composer.compose("main", source).unwrap_err();
+
+        assert_eq!(
+            error("\n#include missing"),
+            ComposeError {
+                file: "main".to_string(),
+                line_number: 2,
+                kind: ComposeErrorKind::UnknownModule("missing".to_string()),
+            }
+        );
+        assert_eq!(
+            error("#include a"),
+            ComposeError {
+                file: "b".to_string(),
+                line_number: 2,
+                kind: ComposeErrorKind::RecursiveInclude("a".to_string()),
+            }
+        );
+        assert_eq!(
+            error("#pragma once").kind,
+            ComposeErrorKind::UnknownDirective("pragma".to_string())
+        );
+        assert_eq!(
+            error("#if X <").kind,
+            ComposeErrorKind::InvalidCondition("X <".to_string())
+        );
+        assert_eq!(
+            error("#ifdef X\n#else\n#else\n#endif").kind,
+            ComposeErrorKind::UnmatchedDirective("else".to_string())
+        );
+        assert_eq!(
+            error("#ifdef X\n").kind,
+            ComposeErrorKind::UnterminatedConditional
+        );
+        // Directives in discarded lines aren't looked at, except conditionals.
+        assert!(composer
+            .compose("main", "#if 0\n#include missing\n#if X <\n#endif\n#endif")
+            .is_ok());
+    }
+}
@@ -44,6 +44,7 @@ impl DeviceExt for crate::Device {
                 size: 0,
                 usage: descriptor.usage,
                 mapped_at_creation: false,
+                memory_hints: crate::MemoryHints::Default,
             };
 
             self.create_buffer(&wgt_descriptor)
@@ -62,6 +63,7 @@ impl DeviceExt for crate::Device {
                 size: padded_size,
                 usage: descriptor.usage,
                 mapped_at_creation: true,
+                memory_hints: crate::MemoryHints::Default,
             };
 
             let buffer = self.create_buffer(&wgt_descriptor);
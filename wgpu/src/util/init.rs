@@ -36,6 +36,9 @@ pub fn power_preference_from_env() -> Option<PowerPreference> {
 }
 
 /// Initialize the adapter obeying the WGPU_ADAPTER_NAME environment variable.
+///
+/// The variable is matched against the adapter names, or the device node path that GL
+/// adapters of EGL devices report in [`AdapterInfo::device_node`](wgt::AdapterInfo::device_node).
 #[cfg(not(target_arch = "wasm32"))]
 pub fn initialize_adapter_from_env(
     instance: &Instance,
@@ -58,7 +61,9 @@ pub fn initialize_adapter_from_env(
             }
         }
 
-        if info.name.to_lowercase().contains(&desired_adapter_name) {
+        if info.name.to_lowercase().contains(&desired_adapter_name)
+            || info.device_node.to_lowercase() == desired_adapter_name
+        {
             chosen_adapter = Some(adapter);
             break;
         }
@@ -95,6 +100,12 @@ pub async fn initialize_adapter_from_env_or_default(
     }
 }
 
+/// Get the DRM device node of the EGL device for the GL backend from the environment
+/// variable `WGPU_GLES_DEVICE`, e.g. `/dev/dri/renderD129`.
+pub fn gles_device_from_env() -> Option<String> {
+    std::env::var("WGPU_GLES_DEVICE").ok()
+}
+
 /// Choose which DX12 shader compiler to use from the environment variable `WGPU_DX12_COMPILER`.
 ///
 /// Possible values are `dxc` and `fxc`. Case insensitive.
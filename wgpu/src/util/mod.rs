@@ -4,10 +4,14 @@
 //! they are unique to the `wgpu` library.
 
 mod belt;
+#[cfg(feature = "wgsl")]
+mod composer;
 mod device;
 mod encoder;
 mod indirect;
 mod init;
+#[cfg(all(feature = "wgsl", not(target_arch = "wasm32")))]
+mod watcher;
 
 use std::sync::Arc;
 use std::{
@@ -17,10 +21,17 @@ use std::{
 };
 
 pub use belt::StagingBelt;
+#[cfg(feature = "wgsl")]
+pub use composer::{ComposeError, ComposeErrorKind, ComposedLocation, ComposedWgsl, WgslComposer};
 pub use device::{BufferInitDescriptor, DeviceExt};
 pub use encoder::RenderEncoder;
 pub use indirect::*;
 pub use init::*;
+#[cfg(all(feature = "wgsl", not(target_arch = "wasm32")))]
+pub use watcher::{
+    ShaderReload, ShaderWatcher, ShaderWatcherError, WatchedComputePipeline, WatchedRenderPipeline,
+    WatchedShader,
+};
 pub use wgt::math::*;
 
 /// Treat the given byte slice as a SPIR-V module.
@@ -106,6 +117,7 @@ impl DownloadBuffer {
             size,
             usage: super::BufferUsages::COPY_DST | super::BufferUsages::MAP_READ,
             mapped_at_creation: false,
+            memory_hints: super::MemoryHints::Transient,
             label: None,
         }));
 
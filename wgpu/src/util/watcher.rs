@@ -0,0 +1,376 @@
+use crate::{
+    CompilationInfo, ComputePipeline, Device, ErrorFilter, RenderPipeline, ShaderModule,
+    ShaderModuleDescriptor, ShaderSource,
+};
+use std::{
+    collections::hash_map::DefaultHasher,
+    error, fmt, fs,
+    hash::{Hash, Hasher},
+    io,
+    path::{Path, PathBuf},
+};
+
+/// A WGSL file watched by a [`ShaderWatcher`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct WatchedShader(usize);
+
+/// A render pipeline rebuilt by a [`ShaderWatcher`] when its shaders change.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct WatchedRenderPipeline(usize);
+
+/// A compute pipeline rebuilt by a [`ShaderWatcher`] when its shaders change.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct WatchedComputePipeline(usize);
+
+/// Error when building a shader module or pipeline of a [`ShaderWatcher`].
+#[derive(Debug)]
+pub enum ShaderWatcherError {
+    /// The shader source could not be read.
+    Io(io::Error),
+    /// The shader failed to compile.
+    Compilation(CompilationInfo),
+    /// The shader module failed to build for a reason other than its source,
+    /// like running out of memory or the backend failing to translate it.
+    Module(crate::Error),
+    /// A pipeline failed to build from the shader.
+    Pipeline(crate::Error),
+}
+
+impl fmt::Display for ShaderWatcherError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match *self {
+            Self::Io(ref error) => write!(f, "Failed to read the shader: {error}"),
+            Self::Compilation(ref info) => {
+                write!(f, "Failed to compile the shader")?;
+                for message in info.messages.iter() {
+                    match message.location {
+                        Some(location) => write!(
+                            f,
+                            "\n{}:{}: {}",
+                            location.line_number, location.line_position, message.message
+                        )?,
+                        None => write!(f, "\n{}", message.message)?,
+                    }
+                }
+                Ok(())
+            }
+            Self::Module(ref error) => write!(f, "Failed to build the shader: {error}"),
+            Self::Pipeline(ref error) => write!(f, "Failed to build a pipeline: {error}"),
+        }
+    }
+}
+
+impl error::Error for ShaderWatcherError {
+    fn source(&self) -> Option<&(dyn error::Error + 'static)> {
+        match *self {
+            Self::Io(ref error) => Some(error),
+            Self::Compilation(_) => None,
+            Self::Module(ref error) | Self::Pipeline(ref error) => Some(error),
+        }
+    }
+}
+
+/// Outcome of reloading a changed shader, returned by [`ShaderWatcher::poll`].
+#[derive(Debug)]
+pub struct ShaderReload {
+    /// The shader which changed.
+    pub shader: WatchedShader,
+    /// Path of the shader source.
+    pub path: PathBuf,
+    /// Whether the shader and the pipelines using it were rebuilt.
+    ///
+    /// On error, the previous module and pipelines are kept.
+    pub result: Result<(), ShaderWatcherError>,
+}
+
+struct Shader {
+    path: PathBuf,
+    /// Hash of the source the module was last built from, if it could be read.
+    source_hash: Option<u64>,
+    module: ShaderModule,
+}
+
+type PipelineBuilder<P> = Box<dyn Fn(&Device, &[&ShaderModule]) -> P>;
+
+struct Pipeline<P> {
+    shaders: Vec<WatchedShader>,
+    build: PipelineBuilder<P>,
+    pipeline: P,
+}
+
+/// Reloads WGSL shaders when their source file changes, and rebuilds the
+/// pipelines using them.
+///
+/// Shaders are added with [`ShaderWatcher::add_shader`], and pipelines with a
+/// closure building them from the modules of their shaders. Calling
+/// [`ShaderWatcher::poll`], typically once per frame, reads the shader files
+/// and recompiles the shaders whose content changed, then rebuilds their
+/// pipelines. Changes are detected by content rather than modification time,
+/// so a file rewritten within the time resolution of the file system is still
+/// reloaded, and a file saved without changes isn't.
+///
+/// Modules and pipelines are built inside validation, out-of-memory and
+/// internal error scopes. If a shader fails to compile, or any of its pipelines
+/// fails to build, the previous module and pipelines are kept, and the error is
+/// reported until the file changes again.
+///
+/// The futures returned by the watcher resolve immediately on native backends.
+#[derive(Default)]
+pub struct ShaderWatcher {
+    shaders: Vec<Shader>,
+    render_pipelines: Vec<Pipeline<RenderPipeline>>,
+    compute_pipelines: Vec<Pipeline<ComputePipeline>>,
+}
+
+impl fmt::Debug for ShaderWatcher {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ShaderWatcher")
+            .field(
+                "shaders",
+                &self.shaders.iter().map(|s| &s.path).collect::<Vec<_>>(),
+            )
+            .field("render_pipelines", &self.render_pipelines.len())
+            .field("compute_pipelines", &self.compute_pipelines.len())
+            .finish()
+    }
+}
+
+impl ShaderWatcher {
+    /// Creates a watcher without any shader.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Compiles the WGSL file at `path` and watches it for changes.
+    pub async fn add_shader(
+        &mut self,
+        device: &Device,
+        path: impl Into<PathBuf>,
+    ) -> Result<WatchedShader, ShaderWatcherError> {
+        let path = path.into();
+        let source = fs::read_to_string(&path).map_err(ShaderWatcherError::Io)?;
+        let source_hash = Some(hash_source(&source));
+        let module = create_module(device, &path, source).await?;
+        self.shaders.push(Shader {
+            path,
+            source_hash,
+            module,
+        });
+        Ok(WatchedShader(self.shaders.len() - 1))
+    }
+
+    /// Returns the current module of a shader.
+    pub fn shader(&self, shader: WatchedShader) -> &ShaderModule {
+        &self.shaders[shader.0].module
+    }
+
+    /// Builds a render pipeline with `build`, and rebuilds it whenever one of
+    /// `shaders` changes.
+    ///
+    /// `build` gets the current modules of `shaders`, in the same order.
+    pub async fn add_render_pipeline(
+        &mut self,
+        device: &Device,
+        shaders: &[WatchedShader],
+        build: impl Fn(&Device, &[&ShaderModule]) -> RenderPipeline + 'static,
+    ) -> Result<WatchedRenderPipeline, ShaderWatcherError> {
+        let pipeline = self.add_pipeline(device, shaders, Box::new(build)).await?;
+        self.render_pipelines.push(pipeline);
+        Ok(WatchedRenderPipeline(self.render_pipelines.len() - 1))
+    }
+
+    /// Returns the current version of a render pipeline.
+    pub fn render_pipeline(&self, pipeline: WatchedRenderPipeline) -> &RenderPipeline {
+        &self.render_pipelines[pipeline.0].pipeline
+    }
+
+    /// Builds a compute pipeline with `build`, and rebuilds it whenever one of
+    /// `shaders` changes.
+    ///
+    /// `build` gets the current modules of `shaders`, in the same order.
+    pub async fn add_compute_pipeline(
+        &mut self,
+        device: &Device,
+        shaders: &[WatchedShader],
+        build: impl Fn(&Device, &[&ShaderModule]) -> ComputePipeline + 'static,
+    ) -> Result<WatchedComputePipeline, ShaderWatcherError> {
+        let pipeline = self.add_pipeline(device, shaders, Box::new(build)).await?;
+        self.compute_pipelines.push(pipeline);
+        Ok(WatchedComputePipeline(self.compute_pipelines.len() - 1))
+    }
+
+    /// Returns the current version of a compute pipeline.
+    pub fn compute_pipeline(&self, pipeline: WatchedComputePipeline) -> &ComputePipeline {
+        &self.compute_pipelines[pipeline.0].pipeline
+    }
+
+    /// Reloads the shaders whose file content changed since they were last
+    /// loaded, and rebuilds their pipelines.
+    ///
+    /// Returns the outcome for each changed shader.
+    pub async fn poll(&mut self, device: &Device) -> Vec<ShaderReload> {
+        let mut reloads = Vec::new();
+        for index in 0..self.shaders.len() {
+            let source = fs::read_to_string(&self.shaders[index].path);
+            let source_hash = source.as_deref().ok().map(hash_source);
+            if source_hash == self.shaders[index].source_hash {
+                continue;
+            }
+            self.shaders[index].source_hash = source_hash;
+
+            let shader = WatchedShader(index);
+            let result = match source {
+                Ok(source) => self.reload(device, shader, source).await,
+                Err(error) => Err(ShaderWatcherError::Io(error)),
+            };
+            if let Err(ref error) = result {
+                log::warn!("{:?}: {}", self.shaders[index].path, error);
+            }
+            reloads.push(ShaderReload {
+                shader,
+                path: self.shaders[index].path.clone(),
+                result,
+            });
+        }
+        reloads
+    }
+
+    async fn add_pipeline<P>(
+        &self,
+        device: &Device,
+        shaders: &[WatchedShader],
+        build: PipelineBuilder<P>,
+    ) -> Result<Pipeline<P>, ShaderWatcherError> {
+        let modules = shaders
+            .iter()
+            .map(|shader| &self.shaders[shader.0].module)
+            .collect::<Vec<_>>();
+        let pipeline = build_pipeline(device, &build, &modules).await?;
+        Ok(Pipeline {
+            shaders: shaders.to_vec(),
+            build,
+            pipeline,
+        })
+    }
+
+    /// Rebuilds a shader and its pipelines, only replacing them if all of
+    /// them were built successfully.
+    async fn reload(
+        &mut self,
+        device: &Device,
+        shader: WatchedShader,
+        source: String,
+    ) -> Result<(), ShaderWatcherError> {
+        let module = create_module(device, &self.shaders[shader.0].path, source).await?;
+        let render_pipelines = rebuild_pipelines(
+            device,
+            &self.shaders,
+            &self.render_pipelines,
+            shader,
+            &module,
+        )
+        .await?;
+        let compute_pipelines = rebuild_pipelines(
+            device,
+            &self.shaders,
+            &self.compute_pipelines,
+            shader,
+            &module,
+        )
+        .await?;
+
+        self.shaders[shader.0].module = module;
+        for (index, pipeline) in render_pipelines {
+            self.render_pipelines[index].pipeline = pipeline;
+        }
+        for (index, pipeline) in compute_pipelines {
+            self.compute_pipelines[index].pipeline = pipeline;
+        }
+        Ok(())
+    }
+}
+
+fn hash_source(source: &str) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    source.hash(&mut hasher);
+    hasher.finish()
+}
+
+/// Calls `f` inside validation, out-of-memory and internal error scopes,
+/// returning the error caught by any of them.
+async fn catch_errors<T>(device: &Device, f: impl FnOnce() -> T) -> (T, Option<crate::Error>) {
+    device.push_error_scope(ErrorFilter::Internal);
+    device.push_error_scope(ErrorFilter::OutOfMemory);
+    device.push_error_scope(ErrorFilter::Validation);
+    let value = f();
+    let validation = device.pop_error_scope().await;
+    let out_of_memory = device.pop_error_scope().await;
+    let internal = device.pop_error_scope().await;
+    (value, validation.or(out_of_memory).or(internal))
+}
+
+async fn create_module(
+    device: &Device,
+    path: &Path,
+    source: String,
+) -> Result<ShaderModule, ShaderWatcherError> {
+    let (module, error) = catch_errors(device, || {
+        device.create_shader_module(ShaderModuleDescriptor {
+            label: path.to_str(),
+            source: ShaderSource::Wgsl(source.into()),
+        })
+    })
+    .await;
+    match error {
+        Some(crate::Error::Validation { .. }) => Err(ShaderWatcherError::Compilation(
+            module.get_compilation_info().await,
+        )),
+        Some(error) => Err(ShaderWatcherError::Module(error)),
+        None => Ok(module),
+    }
+}
+
+async fn build_pipeline<P>(
+    device: &Device,
+    build: &PipelineBuilder<P>,
+    modules: &[&ShaderModule],
+) -> Result<P, ShaderWatcherError> {
+    let (pipeline, error) = catch_errors(device, || build(device, modules)).await;
+    match error {
+        Some(error) => Err(ShaderWatcherError::Pipeline(error)),
+        None => Ok(pipeline),
+    }
+}
+
+/// Builds the pipelines using `shader` with its new `module`, returning them
+/// with their index.
+async fn rebuild_pipelines<P>(
+    device: &Device,
+    shaders: &[Shader],
+    pipelines: &[Pipeline<P>],
+    shader: WatchedShader,
+    module: &ShaderModule,
+) -> Result<Vec<(usize, P)>, ShaderWatcherError> {
+    let mut rebuilt = Vec::new();
+    for (index, pipeline) in pipelines.iter().enumerate() {
+        if !pipeline.shaders.contains(&shader) {
+            continue;
+        }
+        let modules = pipeline
+            .shaders
+            .iter()
+            .map(|&other| {
+                if other == shader {
+                    module
+                } else {
+                    &shaders[other.0].module
+                }
+            })
+            .collect::<Vec<_>>();
+        rebuilt.push((
+            index,
+            build_pipeline(device, &pipeline.build, &modules).await?,
+        ));
+    }
+    Ok(rebuilt)
+}